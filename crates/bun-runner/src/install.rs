@@ -0,0 +1,357 @@
+//! Provisioning bun without the network: the `bun` npm package's platform
+//! binaries in `node_modules`, and local release archives.
+
+use crate::runner::BunError;
+use camino::{Utf8Path, Utf8PathBuf};
+use fs2::FileExt;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Environment variable naming a local bun archive to install from: a release
+/// `.zip` (e.g. `bun-linux-x64.zip`), an npm tarball (`.tgz`) of a platform
+/// package, or a bare bun binary.
+pub const BUN_ARCHIVE_ENV: &str = "SVELTE_CHECK_RS_BUN_ARCHIVE";
+
+/// Environment variable holding the expected SHA-256 of [`BUN_ARCHIVE_ENV`].
+/// Without it the checksum is looked up in a `SHASUMS256.txt` next to the
+/// archive.
+pub const BUN_SHA256_ENV: &str = "SVELTE_CHECK_RS_BUN_SHA256";
+
+const BUN_EXE: &str = if cfg!(windows) { "bun.exe" } else { "bun" };
+
+/// The `@oven/*` packages the `bun` npm package installs binaries from, best
+/// match for this platform first.
+fn npm_platform_packages() -> &'static [&'static str] {
+    if cfg!(all(
+        target_os = "linux",
+        target_arch = "x86_64",
+        target_env = "musl"
+    )) {
+        &["bun-linux-x64-musl", "bun-linux-x64-musl-baseline"]
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        &["bun-linux-x64", "bun-linux-x64-baseline"]
+    } else if cfg!(all(
+        target_os = "linux",
+        target_arch = "aarch64",
+        target_env = "musl"
+    )) {
+        &["bun-linux-aarch64-musl"]
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        &["bun-linux-aarch64"]
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        &["bun-darwin-aarch64"]
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        &["bun-darwin-x64", "bun-darwin-x64-baseline"]
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        &["bun-windows-x64", "bun-windows-x64-baseline"]
+    } else {
+        &[]
+    }
+}
+
+/// A place [`BunRunner::find_bun`](crate::BunRunner::find_bun) looks for bun.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BunLocation {
+    /// A `bin` directory holding a `bun` executable or shim.
+    Bin(Utf8PathBuf),
+    /// A `node_modules` directory holding the `bun` npm package's platform
+    /// binaries under `@oven/`.
+    NpmPackages(Utf8PathBuf),
+    /// The `PATH` environment variable.
+    Path,
+}
+
+impl BunLocation {
+    /// Returns the bun executable at this location, if there is one.
+    pub(crate) fn resolve(&self) -> Option<Utf8PathBuf> {
+        match self {
+            BunLocation::Bin(bin) => crate::runner::find_bun_in_bin(bin),
+            BunLocation::NpmPackages(node_modules) => npm_platform_packages()
+                .iter()
+                .map(|package| {
+                    node_modules
+                        .join("@oven")
+                        .join(package)
+                        .join("bin")
+                        .join(BUN_EXE)
+                })
+                .find(|path| path.is_file()),
+            BunLocation::Path => which::which("bun")
+                .ok()
+                .and_then(|path| Utf8PathBuf::try_from(path).ok()),
+        }
+    }
+}
+
+impl std::fmt::Display for BunLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BunLocation::Bin(bin) => write!(f, "{bin}"),
+            BunLocation::NpmPackages(node_modules) => write!(
+                f,
+                "{node_modules}/@oven/{{{}}}/bin/{BUN_EXE}",
+                npm_platform_packages().join(",")
+            ),
+            BunLocation::Path => write!(f, "PATH"),
+        }
+    }
+}
+
+/// Returns the `node_modules` directories of `workspace_root` and its
+/// parents, where a hoisted `bun` npm package may have put its binaries.
+pub(crate) fn npm_package_locations(workspace_root: &Utf8Path) -> Vec<BunLocation> {
+    workspace_root
+        .ancestors()
+        .map(|dir| BunLocation::NpmPackages(dir.join("node_modules")))
+        .collect()
+}
+
+/// Describes where bun was looked for, and how to provide it offline.
+pub(crate) fn describe_search(locations: &[BunLocation]) -> String {
+    let mut message = String::from("bun was not found in any of:");
+    for location in locations {
+        message.push_str("\n  - ");
+        message.push_str(&location.to_string());
+    }
+    message.push_str(&format!(
+        "\nTo install without network access, install the `bun` npm package or set {BUN_ARCHIVE_ENV} to a local bun archive."
+    ));
+    message
+}
+
+/// Installs bun from a local archive into `install_dir/bin`, after checking
+/// the archive against `expected_sha256` or the `SHASUMS256.txt` next to it.
+pub(crate) async fn install_from_archive(
+    archive: &Utf8Path,
+    expected_sha256: Option<&str>,
+    install_dir: &Utf8Path,
+) -> Result<Utf8PathBuf, BunError> {
+    if !archive.is_file() {
+        return Err(BunError::InstallFailed(format!(
+            "{BUN_ARCHIVE_ENV} points to {archive}, which is not a file"
+        )));
+    }
+    let expected = match expected_sha256 {
+        Some(checksum) => checksum.trim().to_ascii_lowercase(),
+        None => checksum_from_shasums(archive)?,
+    };
+    let actual = sha256_file(archive)?;
+    if actual != expected {
+        return Err(BunError::InstallFailed(format!(
+            "checksum mismatch for {archive}: expected sha256 {expected}, got {actual}"
+        )));
+    }
+
+    let bin = install_dir.join("bin");
+    fs::create_dir_all(&bin)
+        .map_err(|e| BunError::InstallFailed(format!("failed to create {bin}: {e}")))?;
+    let lock_path = install_dir.join(".install.lock");
+    let lock_file = File::create(&lock_path)
+        .map_err(|e| BunError::InstallFailed(format!("failed to create lock file: {e}")))?;
+    lock_file
+        .lock_exclusive()
+        .map_err(|e| BunError::InstallFailed(format!("failed to acquire lock: {e}")))?;
+
+    let staging = install_dir.join(format!("staging-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)
+        .map_err(|e| BunError::InstallFailed(format!("failed to create {staging}: {e}")))?;
+    let result = unpack(archive, &staging).await.and_then(|()| {
+        let binary = find_file(&staging, BUN_EXE).ok_or_else(|| {
+            BunError::InstallFailed(format!("{archive} does not contain a {BUN_EXE} binary"))
+        })?;
+        let dest = bin.join(BUN_EXE);
+        fs::copy(&binary, &dest)
+            .map_err(|e| BunError::InstallFailed(format!("failed to copy bun to {dest}: {e}")))?;
+        make_executable(&dest)?;
+        Ok(dest)
+    });
+    let _ = fs::remove_dir_all(&staging);
+
+    if let Ok(path) = &result {
+        eprintln!("bun installed from {archive} at {path}");
+    }
+    result
+}
+
+/// Reads the archive's checksum from `SHASUMS256.txt` in its directory, the
+/// format bun releases publish (`<sha256>  <file name>`).
+fn checksum_from_shasums(archive: &Utf8Path) -> Result<String, BunError> {
+    let shasums = archive.with_file_name("SHASUMS256.txt");
+    let name = archive.file_name().unwrap_or_default();
+    let missing = || {
+        BunError::InstallFailed(format!(
+            "no checksum for {archive}: set {BUN_SHA256_ENV} or add a SHASUMS256.txt listing {name} next to it (looked in {shasums})"
+        ))
+    };
+    let contents = fs::read_to_string(&shasums).map_err(|_| missing())?;
+    contents
+        .lines()
+        .find_map(|line| {
+            let (checksum, file) = line.split_once(char::is_whitespace)?;
+            (file.trim().trim_start_matches('*') == name).then(|| checksum.to_ascii_lowercase())
+        })
+        .ok_or_else(missing)
+}
+
+fn sha256_file(path: &Utf8Path) -> Result<String, BunError> {
+    let bytes = fs::read(path)
+        .map_err(|e| BunError::InstallFailed(format!("failed to read {path}: {e}")))?;
+    Ok(Sha256::digest(&bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Unpacks `archive` into `dest`; anything that is not a `.zip` or tarball is
+/// taken to be the bun binary itself.
+async fn unpack(archive: &Utf8Path, dest: &Utf8Path) -> Result<(), BunError> {
+    let name = archive.as_str().to_ascii_lowercase();
+    let mut command = if name.ends_with(".zip") {
+        if cfg!(windows) {
+            // bsdtar, shipped with Windows 10 and later, reads zip files.
+            let mut command = Command::new("tar");
+            command.args(["-xf", archive.as_str(), "-C", dest.as_str()]);
+            command
+        } else {
+            let mut command = Command::new("unzip");
+            command.args(["-q", "-o", archive.as_str(), "-d", dest.as_str()]);
+            command
+        }
+    } else if name.ends_with(".tgz") || name.ends_with(".tar.gz") {
+        let mut command = Command::new("tar");
+        command.args(["-xzf", archive.as_str(), "-C", dest.as_str()]);
+        command
+    } else {
+        fs::copy(archive, dest.join(BUN_EXE))
+            .map_err(|e| BunError::InstallFailed(format!("failed to copy {archive}: {e}")))?;
+        return Ok(());
+    };
+
+    let output = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|e| BunError::InstallFailed(format!("failed to unpack {archive}: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BunError::InstallFailed(format!(
+            "failed to unpack {archive}: {stderr}"
+        )));
+    }
+    Ok(())
+}
+
+/// Finds a file named `name` under `dir`, at any depth.
+fn find_file(dir: &Utf8Path, name: &str) -> Option<Utf8PathBuf> {
+    let mut subdirs = Vec::new();
+    for entry in dir.read_dir_utf8().ok()?.flatten() {
+        let path = entry.into_path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else if path.file_name() == Some(name) {
+            return Some(path);
+        }
+    }
+    subdirs.iter().find_map(|subdir| find_file(subdir, name))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Utf8Path) -> Result<(), BunError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| BunError::InstallFailed(format!("failed to make {path} executable: {e}")))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Utf8Path) -> Result<(), BunError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root() -> (tempfile::TempDir, Utf8PathBuf) {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        (temp, root)
+    }
+
+    #[test]
+    fn npm_packages_are_found_in_parent_node_modules() {
+        let Some(package) = npm_platform_packages().first() else {
+            return;
+        };
+        let (_temp, root) = temp_root();
+        let bin = root.join("node_modules/@oven").join(package).join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join(BUN_EXE), "").unwrap();
+        let app = root.join("apps/web");
+
+        let found = npm_package_locations(&app)
+            .iter()
+            .find_map(BunLocation::resolve);
+        assert_eq!(found, Some(bin.join(BUN_EXE)));
+    }
+
+    #[test]
+    fn search_description_lists_every_location() {
+        let locations = vec![
+            BunLocation::Bin(Utf8PathBuf::from("/app/node_modules/.bin")),
+            BunLocation::Path,
+        ];
+        let message = describe_search(&locations);
+        assert!(message.contains("\n  - /app/node_modules/.bin\n  - PATH\n"));
+        assert!(message.contains(BUN_ARCHIVE_ENV));
+    }
+
+    #[tokio::test]
+    async fn install_from_archive_checks_the_checksum() {
+        let (_temp, root) = temp_root();
+        let archive = root.join("bun");
+        fs::write(&archive, "#!/bin/sh\necho 1.2.0\n").unwrap();
+        let install_dir = root.join("cache");
+
+        let err = install_from_archive(&archive, Some("00"), &install_dir)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+
+        let err = install_from_archive(&archive, None, &install_dir)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("SHASUMS256.txt"), "{err}");
+
+        let checksum = sha256_file(&archive).unwrap();
+        fs::write(root.join("SHASUMS256.txt"), format!("{checksum}  bun\n")).unwrap();
+        let installed = install_from_archive(&archive, None, &install_dir)
+            .await
+            .unwrap();
+        assert_eq!(installed, install_dir.join("bin").join(BUN_EXE));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn install_from_archive_unpacks_npm_tarballs() {
+        let (_temp, root) = temp_root();
+        let package = root.join("package/bin");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("bun"), "#!/bin/sh\n").unwrap();
+        let archive = root.join("bun-linux-x64-1.2.0.tgz");
+        let status = std::process::Command::new("tar")
+            .args(["-czf", archive.as_str(), "-C", root.as_str(), "package"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let checksum = sha256_file(&archive).unwrap();
+
+        let installed = install_from_archive(&archive, Some(&checksum), &root.join("cache"))
+            .await
+            .unwrap();
+        assert_eq!(fs::read_to_string(&installed).unwrap(), "#!/bin/sh\n");
+    }
+}
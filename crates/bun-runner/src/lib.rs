@@ -1,8 +1,14 @@
-//! bun process runner for Svelte compiler diagnostics.
+//! bun (or node) process runner for Svelte compiler diagnostics.
 
+mod install;
+mod preprocessed;
 mod runner;
 
+pub use install::{BUN_ARCHIVE_ENV, BUN_SHA256_ENV};
+pub use preprocessed::PreprocessedSource;
 pub use runner::{
-    BunCompileOptions, BunDiagnostic, BunDiagnosticSeverity, BunError, BunExperimentalOptions,
-    BunInput, BunPosition, BunRunner,
+    BridgeCompiler, BridgeInfo, BridgeRuntime, BunCompileOptions, BunDiagnostic,
+    BunDiagnosticSeverity, BunError, BunExperimentalOptions, BunInput, BunInputKind, BunPosition,
+    BunPreprocessed, BunRunner, JsRuntime, JsRuntimeKind, JsRuntimePreference,
+    BRIDGE_PROTOCOL_VERSION,
 };
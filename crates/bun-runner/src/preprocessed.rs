@@ -0,0 +1,134 @@
+//! Preprocessor output for files with custom extensions.
+
+/// Svelte markup produced by running the project's preprocessors over a file
+/// with a custom extension (such as mdsvex's `.svx`), with enough of the
+/// preprocessor's source map to put positions back into the original file.
+#[derive(Debug, Clone)]
+pub struct PreprocessedSource {
+    /// The file as written.
+    pub source: String,
+    /// Svelte markup produced by the preprocessors.
+    pub code: String,
+    /// Decoded mappings per generated line: `[generated column, original
+    /// line, original column]`, 0-based, columns in UTF-16 code units.
+    lines: Vec<Vec<[u32; 3]>>,
+    code_line_starts: Vec<usize>,
+    source_line_starts: Vec<usize>,
+}
+
+impl PreprocessedSource {
+    pub(crate) fn new(source: String, code: String, lines: Vec<Vec<[u32; 3]>>) -> Self {
+        Self {
+            code_line_starts: line_starts(&code),
+            source_line_starts: line_starts(&source),
+            source,
+            code,
+            lines,
+        }
+    }
+
+    /// Maps a byte offset in [`code`](Self::code) to the byte offset of the
+    /// same position in [`source`](Self::source).
+    ///
+    /// The compiler bridge maps every generated line, building a line map for
+    /// preprocessors that return none; positions on lines past the mappings
+    /// keep their line and column.
+    pub fn original_offset(&self, offset: u32) -> u32 {
+        let offset = (offset as usize).min(self.code.len());
+        let line = self
+            .code_line_starts
+            .partition_point(|&start| start <= offset)
+            - 1;
+        let line_start = self.code_line_starts[line];
+        let column = utf16_len(self.code.get(line_start..offset).unwrap_or_default());
+
+        let (line, column) = match self.lines.get(line).filter(|s| !s.is_empty()) {
+            Some(segments) => {
+                let mut matched = segments[0];
+                for segment in segments {
+                    if segment[0] > column {
+                        break;
+                    }
+                    matched = *segment;
+                }
+                (
+                    matched[1] as usize,
+                    matched[2] + column.saturating_sub(matched[0]),
+                )
+            }
+            None => (line, column),
+        };
+
+        let Some(&start) = self.source_line_starts.get(line) else {
+            return self.source.len() as u32;
+        };
+        let end = self
+            .source_line_starts
+            .get(line + 1)
+            .map_or(self.source.len(), |&next| next - 1);
+        let mut units = 0;
+        for (index, ch) in self.source[start..end].char_indices() {
+            if units >= column {
+                return (start + index) as u32;
+            }
+            units += ch.len_utf16() as u32;
+        }
+        end as u32
+    }
+}
+
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.chars().map(|ch| ch.len_utf16() as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn original_offset_follows_segments() {
+        // `# Title\n\n<p>{count}</p>` -> `<h1>Title</h1>\n<p>{count}</p>`
+        let source = "# Title\n\n<p>{count}</p>\n".to_string();
+        let code = "<h1>Title</h1>\n<p>{count}</p>\n".to_string();
+        let lines = vec![vec![[0, 0, 0], [4, 0, 2], [9, 0, 7]], vec![[0, 2, 0]]];
+        let preprocessed = PreprocessedSource::new(source, code, lines);
+
+        // `Title` in the heading.
+        assert_eq!(preprocessed.original_offset(4), 2);
+        // `count` on the second generated line, third original line.
+        let count = preprocessed.code.find("count").unwrap() as u32;
+        assert_eq!(
+            preprocessed.original_offset(count),
+            preprocessed.source.find("count").unwrap() as u32
+        );
+    }
+
+    #[test]
+    fn original_offset_counts_utf16_columns() {
+        let source = "é {x}\n".to_string();
+        let code = "<p>é {x}</p>\n".to_string();
+        let lines = vec![vec![[0, 0, 0], [3, 0, 0]]];
+        let preprocessed = PreprocessedSource::new(source, code, lines);
+
+        let x = preprocessed.code.find('x').unwrap() as u32;
+        assert_eq!(
+            preprocessed.original_offset(x),
+            preprocessed.source.find('x').unwrap() as u32
+        );
+    }
+
+    #[test]
+    fn unmapped_lines_keep_their_position() {
+        let source = "a\nbc\n".to_string();
+        let code = "a\nbc\n".to_string();
+        let preprocessed = PreprocessedSource::new(source, code, Vec::new());
+        assert_eq!(preprocessed.original_offset(3), 3);
+        assert_eq!(preprocessed.original_offset(99), 5);
+    }
+}
@@ -1,36 +1,298 @@
 //! bun process runner.
 
+use crate::install::{
+    describe_search, install_from_archive, npm_package_locations, BunLocation, BUN_ARCHIVE_ENV,
+    BUN_SHA256_ENV,
+};
+use crate::preprocessed::PreprocessedSource;
 use blake3::Hasher;
 use camino::{Utf8Path, Utf8PathBuf};
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, File};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror::Error;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 use tokio::task::JoinHandle;
 
-const BUN_SCRIPT_FILENAME: &str = "bun-svelte-compiler.mjs";
-const BUN_SCRIPT_SOURCE: &str = r#"import { createInterface } from 'node:readline';
+/// Default per-file compile timeout; see [`BunRunner::with_file_timeout`].
+const DEFAULT_FILE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Expands to the bridge protocol version as a literal, so the Rust constant
+/// and the script's `PROTOCOL_VERSION` come from one place.
+macro_rules! bridge_protocol_version {
+    () => {
+        1
+    };
+}
+
+/// Version of the line-delimited JSON protocol the bridge script speaks.
+/// Bump it whenever requests, responses or the ready handshake change shape.
+pub const BRIDGE_PROTOCOL_VERSION: u32 = bridge_protocol_version!();
+
+const BUN_SCRIPT_SOURCE: &str = concat!(
+    "const PROTOCOL_VERSION = ",
+    bridge_protocol_version!(),
+    ";\n",
+    "const SCRIPT_VERSION = '",
+    env!("CARGO_PKG_VERSION"),
+    "';\n",
+    r#"import { createInterface } from 'node:readline';
 import { stdin, stdout } from 'node:process';
 import { createRequire } from 'node:module';
 import { pathToFileURL } from 'node:url';
 
-let compile = null;
+const require = createRequire(pathToFileURL(process.cwd() + '/'));
+
+// stdout carries the protocol. svelte.config, preprocessors and the compiler
+// run in this process and may log, so everything except protocol frames goes
+// to stderr.
+const writeFrame = stdout.write.bind(stdout);
+stdout.write = process.stderr.write.bind(process.stderr);
+for (const method of ['log', 'info', 'debug', 'warn', 'dir', 'table']) {
+  console[method] = console.error;
+}
+
+let compilerPath = null;
+let compiler = {};
 try {
-  const require = createRequire(pathToFileURL(process.cwd() + '/'));
-  const compilerPath = require.resolve('svelte/compiler');
-  const mod = await import(pathToFileURL(compilerPath).href);
-  compile = mod.compile;
+  compilerPath = require.resolve('svelte/compiler');
+  compiler = await import(pathToFileURL(compilerPath).href);
 } catch (err) {
   const message = err && err.message ? err.message : String(err);
-  console.error(`svelte-check-rs bun runner failed to load svelte/compiler: ${message}`);
+  console.error(
+    `svelte-check-rs compiler bridge failed to load svelte/compiler from ${process.cwd()}: ${message}\nInstall svelte in the workspace (e.g. npm install -D svelte).`
+  );
   process.exit(2);
 }
+const { compile, compileModule, preprocess } = compiler;
+
+// The project's svelte.config (path passed by svelte-check-rs) supplies the
+// preprocessors run on components before compile(), its compilerOptions, and
+// the warningFilter / onwarn functions. A config that fails to load is
+// reported once; compiles then only use the statically parsed options.
+const readyWarnings = [];
+let preprocessors = null;
+let configCompilerOptions = {};
+let warningFilter = null;
+let onwarn = null;
+const configPath = process.argv[2];
+if (configPath) {
+  try {
+    const mod = await import(pathToFileURL(configPath).href);
+    const config = mod.default || mod;
+    if (config && config.preprocess) preprocessors = config.preprocess;
+    if (config && config.compilerOptions && typeof config.compilerOptions === 'object') {
+      // The filter is applied below so suppressed warnings can be reported.
+      const { warningFilter: filter, ...rest } = config.compilerOptions;
+      configCompilerOptions = rest;
+      if (typeof filter === 'function') warningFilter = filter;
+    }
+    if (config && typeof config.onwarn === 'function') onwarn = config.onwarn;
+  } catch (err) {
+    const message = err && err.message ? err.message : String(err);
+    readyWarnings.push(`failed to load ${configPath}; preprocessors and warning filters will not run: ${message}`);
+  }
+}
 
-stdout.write(JSON.stringify({ ready: true }) + '\n');
+// Mirrors vite-plugin-svelte: warningFilter returning false drops a warning,
+// and onwarn keeps it only if it calls the default handler.
+function isSuppressed(warning) {
+  try {
+    if (warningFilter && !warningFilter(warning)) return true;
+    if (onwarn) {
+      let emitted = false;
+      onwarn(warning, () => { emitted = true; });
+      return !emitted;
+    }
+  } catch {
+    // A throwing filter keeps the warning.
+  }
+  return false;
+}
+
+// compileModule only parses JavaScript. Prefer node's position-preserving
+// type stripping; fall back to the workspace's TypeScript and map positions
+// back through its source map.
+let nodeStripTypes;
+let typescript;
+
+async function stripTypes(source, filename) {
+  if (nodeStripTypes === undefined) {
+    try {
+      const nodeModule = await import('node:module');
+      nodeStripTypes = typeof nodeModule.stripTypeScriptTypes === 'function'
+        ? nodeModule.stripTypeScriptTypes
+        : null;
+    } catch {
+      nodeStripTypes = null;
+    }
+  }
+  if (nodeStripTypes) {
+    try {
+      return { code: nodeStripTypes(source, { mode: 'strip' }), lines: null };
+    } catch {
+      // Non-erasable syntax (enums, namespaces); let TypeScript handle it.
+    }
+  }
+
+  if (typescript === undefined) {
+    try {
+      const mod = await import(pathToFileURL(require.resolve('typescript')).href);
+      typescript = mod.default || mod;
+    } catch {
+      typescript = null;
+    }
+  }
+  if (!typescript) return null;
+
+  const output = typescript.transpileModule(source, {
+    fileName: filename,
+    compilerOptions: {
+      target: typescript.ScriptTarget.ESNext,
+      module: typescript.ModuleKind.ESNext,
+      sourceMap: true
+    }
+  });
+  const map = JSON.parse(output.sourceMapText);
+  return { code: output.outputText, lines: decodeMappings(map.mappings) };
+}
+
+const BASE64 = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+
+function decodeMappings(mappings) {
+  const lines = [];
+  let srcLine = 0;
+  let srcCol = 0;
+  for (const line of mappings.split(';')) {
+    const segments = [];
+    let genCol = 0;
+    for (const segment of line.split(',')) {
+      if (!segment) continue;
+      const values = [];
+      let value = 0;
+      let shift = 0;
+      for (const char of segment) {
+        const digit = BASE64.indexOf(char);
+        value += (digit & 31) << shift;
+        if (digit & 32) {
+          shift += 5;
+        } else {
+          values.push(value & 1 ? -(value >>> 1) : value >>> 1);
+          value = 0;
+          shift = 0;
+        }
+      }
+      genCol += values[0];
+      if (values.length >= 4) {
+        srcLine += values[2];
+        srcCol += values[3];
+        segments.push([genCol, srcLine, srcCol]);
+      }
+    }
+    lines.push(segments);
+  }
+  return lines;
+}
+
+// Decoded mappings for preprocessor output, covering every generated line.
+// Preprocessors that return no usable map (mdsvex returns `map: ''` in some
+// versions) get one built from the lines they passed through unchanged.
+// Lines the map doesn't cover follow the nearest mapped line above them.
+function preprocessedLines(processed, source) {
+  let map = processed.map;
+  if (typeof map === 'string') {
+    try {
+      map = map ? JSON.parse(map) : null;
+    } catch {
+      map = null;
+    }
+  }
+  const lines = map && typeof map.mappings === 'string'
+    ? decodeMappings(map.mappings)
+    : matchLines(processed.code, source);
+  const lastSourceLine = source.split('\n').length - 1;
+  let mappedLine = 0;
+  let mappedSourceLine = 0;
+  const count = processed.code.split('\n').length;
+  for (let line = 0; line < count; line++) {
+    const segments = lines[line];
+    if (segments && segments.length > 0) {
+      mappedLine = line;
+      mappedSourceLine = segments[segments.length - 1][1];
+    } else {
+      lines[line] = [[0, Math.min(mappedSourceLine + line - mappedLine, lastSourceLine), 0]];
+    }
+  }
+  return lines;
+}
+
+// Pairs generated lines with identical original lines, in order.
+function matchLines(code, source) {
+  const sourceLines = source.split('\n');
+  const byText = new Map();
+  sourceLines.forEach((line, index) => {
+    const text = line.trim();
+    if (!text) return;
+    if (!byText.has(text)) byText.set(text, []);
+    byText.get(text).push(index);
+  });
+  const indent = (line) => line.length - line.trimStart().length;
+  let next = 0;
+  return code.split('\n').map((line) => {
+    const candidates = byText.get(line.trim());
+    if (!candidates) return [];
+    const index = candidates.find((candidate) => candidate >= next) ?? candidates[candidates.length - 1];
+    next = index + 1;
+    return [[indent(line), index, indent(sourceLines[index])]];
+  });
+}
+
+function originalPosition(lines, pos) {
+  const segments = lines[pos.line - 1];
+  if (!segments || segments.length === 0) return pos;
+  let match = segments[0];
+  for (const segment of segments) {
+    if (segment[0] > pos.column) break;
+    match = segment;
+  }
+  return { line: match[1] + 1, column: match[2] + Math.max(0, pos.column - match[0]) };
+}
+
+function errorDiagnostic(err, stage, position) {
+  const start = position(err && err.start ? err.start : { line: 1, column: 0 });
+  const end = err && err.end ? position(err.end) : start;
+  const code = err && err.code ? err.code : `${stage}_error`;
+  const message = err && err.message ? err.message : String(err);
+  return { code, message, start, end, severity: 'error' };
+}
+
+// The handshake: svelte-check-rs checks the protocol version and only sends
+// request kinds whose compiler function is listed in `capabilities`.
+writeFrame(
+  JSON.stringify({
+    ready: true,
+    protocol: PROTOCOL_VERSION,
+    script: SCRIPT_VERSION,
+    runtime:
+      typeof Bun !== 'undefined'
+        ? { name: 'bun', version: Bun.version }
+        : { name: 'node', version: process.versions.node },
+    compiler: {
+      path: compilerPath,
+      version: typeof compiler.VERSION === 'string' ? compiler.VERSION : null
+    },
+    capabilities: ['compile', 'compileModule', 'preprocess'].filter(
+      (name) => typeof compiler[name] === 'function'
+    ),
+    warnings: readyWarnings
+  }) + '\n'
+);
 
 const rl = createInterface({ input: stdin, crlfDelay: Infinity });
 
@@ -42,83 +304,189 @@ for await (const line of rl) {
     req = JSON.parse(line);
   } catch (err) {
     const message = err && err.message ? err.message : String(err);
-    stdout.write(JSON.stringify({ id: null, error: `invalid json: ${message}` }) + '\n');
+    writeFrame(JSON.stringify({ id: null, error: `invalid json: ${message}` }) + '\n');
     continue;
   }
 
   const id = req.id;
+  const kind = req.kind || 'component';
   const filename = req.filename;
   const source = req.source;
   const options = req.options || {};
 
+  if (kind !== 'component' && kind !== 'module' && kind !== 'preprocess') {
+    writeFrame(JSON.stringify({ id, error: `unknown request kind: ${kind}` }) + '\n');
+    continue;
+  }
+
+  if (kind === 'preprocess') {
+    // Files with custom extensions (mdsvex's .svx): hand back the Svelte
+    // markup so svelte-check-rs can check it. No code means no preprocessor
+    // handled the file.
+    const response = { id, diagnostics: [], dependencies: [] };
+    if (preprocessors) {
+      try {
+        const processed = await preprocess(source, preprocessors, { filename });
+        response.dependencies = processed.dependencies || [];
+        if (processed.code !== source) {
+          response.code = processed.code;
+          response.mappings = preprocessedLines(processed, source);
+        }
+      } catch (err) {
+        response.diagnostics = [errorDiagnostic(err, 'preprocess', (pos) => pos)];
+      }
+    }
+    writeFrame(JSON.stringify(response) + '\n');
+    continue;
+  }
+
+  // Options svelte-check-rs sends win over the evaluated config's, which win
+  // over the defaults.
   const compileOptions = {
-    filename,
-    generate: options.generate || 'client',
-    dev: options.dev === undefined ? true : options.dev,
-    runes: options.runes
+    generate: 'client',
+    dev: true,
+    ...configCompilerOptions,
+    filename
   };
+  for (const key of ['generate', 'dev', 'runes']) {
+    if (options[key] !== undefined) compileOptions[key] = options[key];
+  }
 
   if (options.experimental != null && typeof options.experimental === 'object') {
-    compileOptions.experimental = options.experimental;
+    compileOptions.experimental = { ...compileOptions.experimental, ...options.experimental };
   }
 
   let diagnostics = [];
+  let dependencies = [];
+  let lines = null;
+  let stage = 'compile';
+  const position = (pos) => (lines ? originalPosition(lines, pos) : pos);
 
   try {
-    const result = compile(source, compileOptions);
+    let result;
+    if (kind === 'module') {
+      const moduleOptions = {
+        filename,
+        generate: compileOptions.generate,
+        dev: compileOptions.dev
+      };
+      if (compileOptions.experimental) {
+        moduleOptions.experimental = compileOptions.experimental;
+      }
+
+      let code = source;
+      if (/\.[cm]?ts$/.test(filename)) {
+        const stripped = await stripTypes(source, filename);
+        if (!stripped) {
+          // No way to strip types; skip rather than report bogus parse errors.
+          writeFrame(JSON.stringify({ id, diagnostics }) + '\n');
+          continue;
+        }
+        code = stripped.code;
+        lines = stripped.lines;
+      }
+      result = compileModule(code, moduleOptions);
+    } else {
+      let code = source;
+      if (preprocessors) {
+        stage = 'preprocess';
+        const processed = await preprocess(source, preprocessors, { filename });
+        stage = 'compile';
+        code = processed.code;
+        dependencies = processed.dependencies || [];
+        if (code !== source) lines = preprocessedLines(processed, source);
+      }
+      result = compile(code, compileOptions);
+    }
     if (result && Array.isArray(result.warnings)) {
       diagnostics = result.warnings.map((warning) => ({
         code: warning.code || 'warning',
         message: warning.message || '',
-        start: warning.start || { line: 1, column: 0 },
-        end: warning.end || warning.start || { line: 1, column: 0 },
-        severity: 'warning'
+        start: position(warning.start || { line: 1, column: 0 }),
+        end: position(warning.end || warning.start || { line: 1, column: 0 }),
+        severity: 'warning',
+        suppressed: isSuppressed(warning)
       }));
     }
   } catch (err) {
-    const start = err && err.start ? err.start : { line: 1, column: 0 };
-    const end = err && err.end ? err.end : start;
-    const code = err && err.code ? err.code : 'compile_error';
-    const message = err && err.message ? err.message : String(err);
-    diagnostics = [{
-      code,
-      message,
-      start,
-      end,
-      severity: 'error'
-    }];
+    diagnostics = [errorDiagnostic(err, stage, position)];
   }
 
-  stdout.write(JSON.stringify({ id, diagnostics }) + '\n');
+  writeFrame(JSON.stringify({ id, diagnostics, dependencies }) + '\n');
 }
-"#;
+"#
+);
 
 /// Error types for bun runner.
 #[derive(Debug, Error)]
 pub enum BunError {
-    /// Failed to spawn bun process.
-    #[error("failed to spawn bun: {0}")]
+    /// Failed to spawn the JavaScript runtime (bun or node).
+    #[error("failed to spawn JavaScript runtime: {0}")]
     SpawnFailed(#[from] std::io::Error),
 
-    /// bun process exited with error.
-    #[error("bun exited with code {code}: {stderr}")]
+    /// The JavaScript runtime exited with an error.
+    #[error("JavaScript runtime exited with code {code}: {stderr}")]
     ProcessFailed { code: i32, stderr: String },
 
     /// bun binary not found.
     #[error("bun binary not found at: {0}")]
     NotFound(Utf8PathBuf),
 
+    /// No JavaScript runtime satisfying the requested preference was found.
+    #[error("{0}")]
+    RuntimeNotFound(String),
+
     /// Failed to install bun.
     #[error("failed to install bun: {0}")]
     InstallFailed(String),
 
-    /// bun runner protocol error.
-    #[error("bun runner protocol error: {0}")]
+    /// Compiler bridge protocol error.
+    #[error("compiler bridge protocol error: {0}")]
     ProtocolError(String),
 
-    /// Failed to parse bun response.
-    #[error("failed to parse bun response: {0}")]
+    /// Failed to parse a compiler bridge response.
+    #[error("failed to parse compiler bridge response: {0}")]
     ParseError(String),
+
+    /// The bridge script speaks a different protocol version.
+    #[error("incompatible compiler bridge: {0}")]
+    IncompatibleBridge(String),
+}
+
+/// A JavaScript runtime capable of hosting the compiler bridge script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsRuntimeKind {
+    Bun,
+    Node,
+}
+
+impl JsRuntimeKind {
+    /// Returns the binary name of the runtime.
+    pub fn name(self) -> &'static str {
+        match self {
+            JsRuntimeKind::Bun => "bun",
+            JsRuntimeKind::Node => "node",
+        }
+    }
+}
+
+/// Which runtime to use for the compiler bridge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsRuntimePreference {
+    /// Prefer bun, fall back to node, and install bun if neither is found.
+    #[default]
+    Auto,
+    /// Always use bun, installing it if necessary.
+    Bun,
+    /// Always use node; never installs anything.
+    Node,
+}
+
+/// A resolved JavaScript runtime binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsRuntime {
+    pub kind: JsRuntimeKind,
+    pub path: Utf8PathBuf,
 }
 
 /// Subset of Svelte `compile()` options under `experimental`.
@@ -141,10 +509,38 @@ pub struct BunCompileOptions {
     pub experimental: Option<BunExperimentalOptions>,
 }
 
+/// How the compiler bridge compiles a [`BunInput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BunInputKind {
+    /// A `.svelte` component, passed to `compile()`.
+    #[default]
+    Component,
+    /// A `.svelte.ts` / `.svelte.js` rune module, passed to `compileModule()`
+    /// after TypeScript stripping.
+    Module,
+    /// A file with a custom extension (such as `.svx`), only run through the
+    /// `svelte.config` preprocessors. Used by [`BunRunner::preprocess_files`].
+    Preprocess,
+}
+
+impl BunInputKind {
+    /// Returns the `svelte/compiler` export the bridge needs for this kind,
+    /// as listed in [`BridgeInfo::capabilities`].
+    pub fn capability(self) -> &'static str {
+        match self {
+            BunInputKind::Component => "compile",
+            BunInputKind::Module => "compileModule",
+            BunInputKind::Preprocess => "preprocess",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BunInput {
     pub filename: Utf8PathBuf,
     pub source: String,
+    pub kind: BunInputKind,
     pub options: BunCompileOptions,
 }
 
@@ -168,11 +564,16 @@ pub struct BunDiagnostic {
     pub severity: BunDiagnosticSeverity,
     pub start: BunPosition,
     pub end: BunPosition,
+    /// Dropped by the project's `warningFilter` / `onwarn`. Returned so callers
+    /// can drop matching native diagnostics; not meant to be reported.
+    #[serde(default)]
+    pub suppressed: bool,
 }
 
 #[derive(Debug, Serialize)]
 struct BunRequest {
     id: u64,
+    kind: BunInputKind,
     filename: String,
     source: String,
     options: BunCompileOptions,
@@ -182,12 +583,89 @@ struct BunRequest {
 struct BunResponse {
     id: Option<u64>,
     diagnostics: Option<Vec<BunJsDiagnostic>>,
+    /// Files the preprocessors read while processing the input.
+    #[serde(default)]
+    dependencies: Vec<Utf8PathBuf>,
+    /// Preprocessor output for `preprocess` requests.
+    code: Option<String>,
+    /// Decoded source map of `code`, per generated line.
+    #[serde(default)]
+    mappings: Vec<Vec<[u32; 3]>>,
     error: Option<String>,
 }
 
+/// A custom-extension file run through the project's preprocessors.
+#[derive(Debug, Clone)]
+pub struct BunPreprocessed {
+    pub filename: Utf8PathBuf,
+    /// `None` when no preprocessor handled the file or preprocessing failed.
+    pub output: Option<PreprocessedSource>,
+    /// Preprocessor errors and worker failures, positioned in the original file.
+    pub diagnostics: Vec<BunDiagnostic>,
+}
+
 #[derive(Debug, Deserialize)]
 struct BunReady {
     ready: bool,
+    /// Missing from scripts written before the protocol was versioned.
+    protocol: Option<u32>,
+    #[serde(flatten)]
+    info: Option<BridgeInfo>,
+    #[serde(default)]
+    warnings: Vec<String>,
+}
+
+/// What a compiler bridge worker reports in its start-up handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BridgeInfo {
+    /// The svelte-check-rs version that wrote the bridge script.
+    pub script: String,
+    /// The runtime hosting the script.
+    pub runtime: BridgeRuntime,
+    /// The `svelte/compiler` the script resolved from the workspace.
+    pub compiler: BridgeCompiler,
+    /// The compiler functions available, out of `compile`, `compileModule`,
+    /// `preprocess` and `parse`.
+    pub capabilities: Vec<String>,
+}
+
+/// The runtime reported in a [`BridgeInfo`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BridgeRuntime {
+    /// `bun` or `node`.
+    pub name: String,
+    pub version: String,
+}
+
+/// The `svelte/compiler` reported in a [`BridgeInfo`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BridgeCompiler {
+    pub path: Utf8PathBuf,
+    /// The compiler's `VERSION` export, if it has one.
+    pub version: Option<String>,
+}
+
+impl BridgeInfo {
+    /// Returns whether the bridge can handle requests of `kind`.
+    pub fn supports(&self, kind: BunInputKind) -> bool {
+        self.capabilities
+            .iter()
+            .any(|capability| capability == kind.capability())
+    }
+
+    /// Returns an actionable message for a request of `kind` the installed
+    /// compiler can't handle.
+    fn unsupported_message(&self, kind: BunInputKind) -> String {
+        format!(
+            "svelte/compiler {} at {} does not export {}(); upgrade svelte to check this file",
+            self.compiler
+                .version
+                .as_deref()
+                .unwrap_or("(unknown version)"),
+            self.compiler.path,
+            kind.capability()
+        )
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -197,6 +675,8 @@ struct BunJsDiagnostic {
     start: BunJsPosition,
     end: BunJsPosition,
     severity: String,
+    #[serde(default)]
+    suppressed: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -205,69 +685,160 @@ struct BunJsPosition {
     column: u32,
 }
 
-/// The bun runner.
+/// The compiler bridge runner.
+///
+/// Despite the name, the bridge script only uses `node:` APIs and runs under
+/// either bun or node (see [`JsRuntimePreference`]).
+///
+/// Workers are long-lived: they are started on demand, returned to a shared
+/// pool after each check, and reused by later checks on this runner or any of
+/// its clones.
 #[derive(Debug, Clone)]
 pub struct BunRunner {
-    bun_path: Utf8PathBuf,
+    runtime: JsRuntime,
     workspace_root: Utf8PathBuf,
     script_path: Utf8PathBuf,
     worker_count: usize,
+    file_timeout: Duration,
+    svelte_config: Option<Utf8PathBuf>,
+    pool: Arc<WorkerPool>,
 }
 
 impl BunRunner {
-    /// Creates a new bun runner.
+    /// Creates a new runner hosting the compiler bridge on `runtime`.
     pub fn new(
-        bun_path: Utf8PathBuf,
+        runtime: JsRuntime,
         workspace_root: Utf8PathBuf,
         worker_count: usize,
     ) -> Result<Self, BunError> {
         let script_path = ensure_script()?;
         let worker_count = worker_count.max(1);
+        let svelte_config = find_svelte_config(&workspace_root);
         Ok(Self {
-            bun_path,
+            runtime,
             workspace_root,
             script_path,
             worker_count,
+            file_timeout: DEFAULT_FILE_TIMEOUT,
+            svelte_config,
+            pool: Arc::new(WorkerPool::default()),
         })
     }
 
+    /// Sets how long a single file may compile before its worker is
+    /// restarted and the file reported as timed out.
+    pub fn with_file_timeout(mut self, timeout: Duration) -> Self {
+        self.file_timeout = timeout;
+        self
+    }
+
+    /// Returns the runtime hosting the compiler bridge.
+    pub fn runtime(&self) -> &JsRuntime {
+        &self.runtime
+    }
+
+    /// Starts a worker (or reuses an idle one) and returns its handshake.
+    ///
+    /// Fails when the bridge can't start, e.g. because `svelte/compiler` is
+    /// missing, or speaks a different protocol version.
+    pub async fn handshake(&self) -> Result<BridgeInfo, BunError> {
+        let config_hash = self.svelte_config_hash();
+        let worker = match self.pool.checkout(config_hash) {
+            Some(worker) => worker,
+            None => {
+                self.pool
+                    .spawn(
+                        self.runtime.path.clone(),
+                        self.workspace_root.clone(),
+                        self.script_path.clone(),
+                        self.svelte_config.clone(),
+                    )
+                    .await?
+            }
+        };
+        let info = worker.info.clone();
+        self.pool.checkin(worker);
+        Ok(info)
+    }
+
     /// Attempts to find bun in workspace, PATH, home directory, or cache.
     /// 1. Workspace node_modules/.bin/bun (if workspace_root provided)
-    /// 2. PATH
-    /// 3. ~/.bun/bin/bun (default install location)
-    /// 4. Cache directory
+    /// 2. The `bun` npm package's platform binaries in node_modules of the
+    ///    workspace and its parents
+    /// 3. PATH
+    /// 4. ~/.bun/bin/bun (default install location)
+    /// 5. Cache directory (bun installed from a local archive, then
+    ///    node_modules/.bin)
     pub fn find_bun(workspace_root: Option<&Utf8Path>) -> Option<Utf8PathBuf> {
+        bun_locations(workspace_root)
+            .iter()
+            .find_map(BunLocation::resolve)
+    }
+
+    /// Attempts to find node in the workspace or on PATH.
+    /// 1. Workspace node_modules/.bin/node (if workspace_root provided)
+    /// 2. PATH
+    pub fn find_node(workspace_root: Option<&Utf8Path>) -> Option<Utf8PathBuf> {
         if let Some(workspace) = workspace_root {
             let bin = workspace.join("node_modules/.bin");
-            if let Some(path) = find_bun_in_bin(&bin) {
+            if let Some(path) = find_node_in_bin(&bin) {
                 return Some(path);
             }
         }
 
-        if let Ok(path) = which::which("bun") {
-            if let Ok(utf8_path) = Utf8PathBuf::try_from(path) {
-                return Some(utf8_path);
-            }
+        which::which("node")
+            .ok()
+            .and_then(|path| Utf8PathBuf::try_from(path).ok())
+    }
+
+    /// Finds an installed runtime matching `preference` without installing
+    /// anything. `Auto` prefers bun and falls back to node.
+    pub fn find_runtime(
+        workspace_root: Option<&Utf8Path>,
+        preference: JsRuntimePreference,
+    ) -> Option<JsRuntime> {
+        let bun = || {
+            Self::find_bun(workspace_root).map(|path| JsRuntime {
+                kind: JsRuntimeKind::Bun,
+                path,
+            })
+        };
+        let node = || {
+            Self::find_node(workspace_root).map(|path| JsRuntime {
+                kind: JsRuntimeKind::Node,
+                path,
+            })
+        };
+
+        match preference {
+            JsRuntimePreference::Auto => bun().or_else(node),
+            JsRuntimePreference::Bun => bun(),
+            JsRuntimePreference::Node => node(),
         }
+    }
 
-        // Check ~/.bun/bin/bun (default install location from bun.sh/install)
-        if let Some(home) = dirs::home_dir() {
-            if let Ok(home) = Utf8PathBuf::try_from(home) {
-                let bun_home = home.join(".bun/bin");
-                if let Some(path) = find_bun_in_bin(&bun_home) {
-                    return Some(path);
-                }
-            }
+    /// Finds a runtime matching `preference`, installing bun when bun is
+    /// acceptable and nothing is found (see [`BunRunner::ensure_bun`]).
+    pub async fn ensure_runtime(
+        workspace_root: Option<&Utf8Path>,
+        preference: JsRuntimePreference,
+    ) -> Result<JsRuntime, BunError> {
+        if let Some(runtime) = Self::find_runtime(workspace_root, preference) {
+            return Ok(runtime);
         }
 
-        if let Some(cache_dir) = Self::get_cache_dir() {
-            let bin = cache_dir.join("node_modules/.bin");
-            if let Some(path) = find_bun_in_bin(&bin) {
-                return Some(path);
+        match preference {
+            JsRuntimePreference::Node => Err(BunError::RuntimeNotFound(
+                "node not found on PATH or in node_modules/.bin".into(),
+            )),
+            JsRuntimePreference::Auto | JsRuntimePreference::Bun => {
+                let path = Self::provision_bun(workspace_root).await?;
+                Ok(JsRuntime {
+                    kind: JsRuntimeKind::Bun,
+                    path,
+                })
             }
         }
-
-        None
     }
 
     /// Gets the cache directory for svelte-check-rs.
@@ -283,42 +854,77 @@ impl BunRunner {
             BunError::InstallFailed("bun not found - run with --bun-update to install".into())
         })?;
 
-        let output = Command::new(&bun_path)
-            .arg("--version")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(BunError::SpawnFailed)?;
+        let version = runtime_version(&bun_path).await?;
+        Ok((version, bun_path))
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(BunError::ProcessFailed {
-                code: output.status.code().unwrap_or(-1),
-                stderr: stderr.to_string(),
-            });
-        }
+    /// Gets the version of the runtime that `preference` resolves to.
+    pub async fn get_runtime_version(
+        workspace_root: Option<&Utf8Path>,
+        preference: JsRuntimePreference,
+    ) -> Result<(String, JsRuntime), BunError> {
+        let runtime =
+            Self::find_runtime(workspace_root, preference).ok_or_else(|| match preference {
+                JsRuntimePreference::Node => BunError::RuntimeNotFound(
+                    "node not found on PATH or in node_modules/.bin".into(),
+                ),
+                JsRuntimePreference::Auto | JsRuntimePreference::Bun => BunError::InstallFailed(
+                    "bun not found - run with --bun-update to install".into(),
+                ),
+            })?;
 
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok((version, bun_path))
+        let version = runtime_version(&runtime.path).await?;
+        Ok((version, runtime))
     }
 
     /// Updates bun to the specified version or latest if None.
     ///
-    /// Installs bun using the official install script from bun.sh.
+    /// Installs bun from the archive in `SVELTE_CHECK_RS_BUN_ARCHIVE` when it
+    /// is set (the version is then whatever the archive holds), otherwise
+    /// using the official install script from bun.sh.
     pub async fn update_bun(version: Option<&str>) -> Result<Utf8PathBuf, BunError> {
-        Self::install_bun_via_script(version).await
+        match std::env::var(BUN_ARCHIVE_ENV) {
+            Ok(archive) if !archive.is_empty() => Self::install_bun_from_archive(&archive).await,
+            _ => Self::install_bun_via_script(version).await,
+        }
     }
 
     /// Finds bun or installs it if not found.
     ///
-    /// Installs bun using the official install script from bun.sh.
+    /// Installs bun from the archive in `SVELTE_CHECK_RS_BUN_ARCHIVE` when it
+    /// is set, otherwise using the official install script from bun.sh. If
+    /// that fails, the error lists every location that was searched.
     pub async fn ensure_bun(workspace_root: Option<&Utf8Path>) -> Result<Utf8PathBuf, BunError> {
         if let Some(path) = Self::find_bun(workspace_root) {
             return Ok(path);
         }
 
-        Self::install_bun_via_script(None).await
+        Self::provision_bun(workspace_root).await
+    }
+
+    /// Installs bun after [`BunRunner::find_bun`] came up empty.
+    async fn provision_bun(workspace_root: Option<&Utf8Path>) -> Result<Utf8PathBuf, BunError> {
+        Self::update_bun(None).await.map_err(|err| {
+            let reason = match err {
+                BunError::InstallFailed(reason) => reason,
+                other => other.to_string(),
+            };
+            BunError::InstallFailed(format!(
+                "{reason}\n{}",
+                describe_search(&bun_locations(workspace_root))
+            ))
+        })
+    }
+
+    /// Installs bun from a local archive into the cache directory, verifying
+    /// its checksum first.
+    async fn install_bun_from_archive(archive: &str) -> Result<Utf8PathBuf, BunError> {
+        let install_dir = bun_install_dir()
+            .ok_or_else(|| BunError::InstallFailed("could not determine cache directory".into()))?;
+        let expected = std::env::var(BUN_SHA256_ENV)
+            .ok()
+            .filter(|checksum| !checksum.trim().is_empty());
+        install_from_archive(Utf8Path::new(archive), expected.as_deref(), &install_dir).await
     }
 
     /// Installs bun using the official install script from bun.sh.
@@ -426,14 +1032,21 @@ impl BunRunner {
             return Ok(Vec::new());
         }
 
-        let svelte_version = self.resolve_svelte_version();
+        let svelte_version = Self::detect_svelte_version(&self.workspace_root);
+        // Preprocessors come from the config, so its contents are part of the key.
+        let svelte_config = self
+            .svelte_config
+            .as_ref()
+            .and_then(|path| fs::read(path).ok());
+        let config_hash = svelte_config.as_deref().map(blake3::hash);
         let cache_dir = self.compiler_cache_dir();
         let mut diagnostics_by_key: HashMap<String, Vec<BunDiagnostic>> = HashMap::new();
         let mut misses = Vec::new();
         let mut ordered_keys = Vec::with_capacity(inputs.len());
 
         for input in inputs {
-            let key = compiler_cache_key(&input, svelte_version.as_deref())?;
+            let key =
+                compiler_cache_key(&input, svelte_version.as_deref(), svelte_config.as_deref())?;
             ordered_keys.push(key.clone());
             if let Some(cached) = cache_dir
                 .as_ref()
@@ -445,59 +1058,27 @@ impl BunRunner {
             }
         }
 
-        if !misses.is_empty() {
-            let worker_count = self.worker_count.min(misses.len()).max(1);
-            let mut chunks: Vec<Vec<(String, BunInput)>> = vec![Vec::new(); worker_count];
-            for (idx, entry) in misses.into_iter().enumerate() {
-                chunks[idx % worker_count].push(entry);
-            }
-
-            let mut handles = Vec::new();
-            for chunk in chunks.into_iter().filter(|c| !c.is_empty()) {
-                let bun_path = self.bun_path.clone();
-                let workspace_root = self.workspace_root.clone();
-                let script_path = self.script_path.clone();
-                handles.push(tokio::spawn(async move {
-                    let mut key_by_file = HashMap::new();
-                    let inputs: Vec<BunInput> = chunk
-                        .into_iter()
-                        .map(|(key, input)| {
-                            key_by_file.insert(input.filename.clone(), key);
-                            input
-                        })
-                        .collect();
-                    let mut worker =
-                        BunWorker::spawn(bun_path, workspace_root, script_path).await?;
-                    let diagnostics = worker.check_batch(inputs).await?;
-                    Ok::<_, BunError>((key_by_file, diagnostics))
-                }));
-            }
-
-            for handle in handles {
-                let (key_by_file, chunk_diags) = handle
-                    .await
-                    .map_err(|e| BunError::ProtocolError(format!("join error: {e}")))??;
-                for key in key_by_file.values() {
-                    diagnostics_by_key.entry(key.clone()).or_default();
-                }
-                for diag in chunk_diags {
-                    if let Some(key) = key_by_file.get(&diag.file) {
-                        diagnostics_by_key
-                            .entry(key.clone())
-                            .or_default()
-                            .push(diag);
+        for (key, outcome) in self.run_queue(misses, config_hash).await? {
+            let diagnostics = match outcome {
+                CompileOutcome::Compiled(output) => {
+                    // A worker started after the config changed mid-check ran
+                    // a different config than the key describes.
+                    if let Some(dir) = cache_dir
+                        .as_ref()
+                        .filter(|_| output.config_hash == config_hash)
+                    {
+                        let _ = write_cached_diagnostics(
+                            &dir.join(format!("{key}.json")),
+                            &output.diagnostics,
+                            &output.dependencies,
+                        );
                     }
+                    output.diagnostics
                 }
-            }
-
-            if let Some(dir) = &cache_dir {
-                for key in &ordered_keys {
-                    if let Some(diagnostics) = diagnostics_by_key.get(key) {
-                        let _ =
-                            write_cached_diagnostics(&dir.join(format!("{key}.json")), diagnostics);
-                    }
-                }
-            }
+                // Not cached: the next check should try the file again.
+                CompileOutcome::Failed(diagnostic) => vec![diagnostic],
+            };
+            diagnostics_by_key.insert(key, diagnostics);
         }
 
         let mut diagnostics = Vec::new();
@@ -510,6 +1091,188 @@ impl BunRunner {
         Ok(diagnostics)
     }
 
+    /// Runs files with custom extensions (such as mdsvex's `.svx`) through the
+    /// `svelte.config` preprocessors, returning the Svelte markup they produce.
+    ///
+    /// Results are not cached: callers check the output natively every run.
+    pub async fn preprocess_files(
+        &self,
+        files: Vec<(Utf8PathBuf, String)>,
+    ) -> Result<Vec<BunPreprocessed>, BunError> {
+        let jobs = files
+            .iter()
+            .enumerate()
+            .map(|(index, (filename, source))| {
+                let input = BunInput {
+                    filename: filename.clone(),
+                    source: source.clone(),
+                    kind: BunInputKind::Preprocess,
+                    options: BunCompileOptions::default(),
+                };
+                (index.to_string(), input)
+            })
+            .collect();
+        let mut files: Vec<Option<(Utf8PathBuf, String)>> = files.into_iter().map(Some).collect();
+
+        let mut results: Vec<Option<BunPreprocessed>> = vec![None; files.len()];
+        let config_hash = self.svelte_config_hash();
+        for (key, outcome) in self.run_queue(jobs, config_hash).await? {
+            let Some(index) = key.parse::<usize>().ok() else {
+                continue;
+            };
+            let Some((filename, source)) = files.get_mut(index).and_then(Option::take) else {
+                continue;
+            };
+            results[index] = Some(match outcome {
+                CompileOutcome::Compiled(output) => BunPreprocessed {
+                    filename,
+                    output: output
+                        .preprocessed
+                        .map(|(code, mappings)| PreprocessedSource::new(source, code, mappings)),
+                    diagnostics: output.diagnostics,
+                },
+                CompileOutcome::Failed(diagnostic) => BunPreprocessed {
+                    filename,
+                    output: None,
+                    diagnostics: vec![diagnostic],
+                },
+            });
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Runs keyed inputs on pooled workers and returns the outcome for each key.
+    ///
+    /// Idle workers that loaded a different `svelte.config` than
+    /// `config_hash` are stopped rather than reused.
+    async fn run_queue(
+        &self,
+        jobs: Vec<(String, BunInput)>,
+        config_hash: Option<blake3::Hash>,
+    ) -> Result<Vec<(String, CompileOutcome)>, BunError> {
+        if jobs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Idle workers pull the next file from a shared queue, so one slow
+        // component never holds back files queued behind it.
+        let worker_count = self.worker_count.min(jobs.len()).max(1);
+        let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
+
+        let mut handles = Vec::with_capacity(worker_count);
+        for _ in 0..worker_count {
+            let runner = self.clone();
+            let queue = Arc::clone(&queue);
+            handles.push(tokio::spawn(async move {
+                runner.drain_queue(&queue, config_hash).await
+            }));
+        }
+
+        let mut outcomes = Vec::new();
+        for handle in handles {
+            outcomes.extend(
+                handle
+                    .await
+                    .map_err(|e| BunError::ProtocolError(format!("join error: {e}")))??,
+            );
+        }
+        Ok(outcomes)
+    }
+
+    /// Compiles queued inputs on one pooled worker until the queue is empty,
+    /// then returns the worker to the pool.
+    async fn drain_queue(
+        &self,
+        queue: &Mutex<VecDeque<(String, BunInput)>>,
+        config_hash: Option<blake3::Hash>,
+    ) -> Result<Vec<(String, CompileOutcome)>, BunError> {
+        let mut worker = self.pool.checkout(config_hash);
+        let mut outcomes = Vec::new();
+
+        loop {
+            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+            let Some((key, input)) = next else {
+                break;
+            };
+            let outcome = self.compile_on(&mut worker, &input).await?;
+            outcomes.push((key, outcome));
+        }
+
+        if let Some(worker) = worker {
+            self.pool.checkin(worker);
+        }
+        Ok(outcomes)
+    }
+
+    /// Compiles one input, restarting the worker if it crashes or hangs.
+    ///
+    /// A crashed worker is restarted and the file retried once; a timeout is
+    /// not retried. Either way the file gets a diagnostic instead of failing
+    /// the batch. Only failing to start a worker at all is an error.
+    async fn compile_on(
+        &self,
+        slot: &mut Option<BunWorker>,
+        input: &BunInput,
+    ) -> Result<CompileOutcome, BunError> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let worker = match slot {
+                Some(worker) => worker,
+                None => slot.insert(
+                    self.pool
+                        .spawn(
+                            self.runtime.path.clone(),
+                            self.workspace_root.clone(),
+                            self.script_path.clone(),
+                            self.svelte_config.clone(),
+                        )
+                        .await?,
+                ),
+            };
+
+            if !worker.info.supports(input.kind) {
+                return Ok(CompileOutcome::Failed(worker_failure_diagnostic(
+                    input,
+                    "compiler_unsupported",
+                    worker.info.unsupported_message(input.kind),
+                )));
+            }
+
+            match tokio::time::timeout(self.file_timeout, worker.compile(input)).await {
+                Ok(Ok(output)) => return Ok(CompileOutcome::Compiled(output)),
+                Ok(Err(err)) => {
+                    // Dropping the worker kills the process.
+                    *slot = None;
+                    if attempts >= 2 {
+                        return Ok(CompileOutcome::Failed(worker_failure_diagnostic(
+                            input,
+                            "compiler_crashed",
+                            format!("Svelte compiler worker failed on this file: {err}"),
+                        )));
+                    }
+                }
+                Err(_) => {
+                    *slot = None;
+                    return Ok(CompileOutcome::Failed(worker_failure_diagnostic(
+                        input,
+                        "compiler_timeout",
+                        format!(
+                            "Svelte compiler did not finish within {}s; compiler diagnostics for this file were skipped",
+                            self.file_timeout.as_secs_f64()
+                        ),
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Hashes the current contents of the project's `svelte.config`.
+    fn svelte_config_hash(&self) -> Option<blake3::Hash> {
+        svelte_config_hash(self.svelte_config.as_deref())
+    }
+
     fn compiler_cache_dir(&self) -> Option<Utf8PathBuf> {
         let cache_base = self
             .workspace_node_modules_dir()
@@ -534,8 +1297,10 @@ impl BunRunner {
         None
     }
 
-    fn resolve_svelte_version(&self) -> Option<String> {
-        let mut current = Some(self.workspace_root.as_path());
+    /// Returns the version of the `svelte` package installed for
+    /// `workspace_root`, looking in `node_modules` of it and its parents.
+    pub fn detect_svelte_version(workspace_root: &Utf8Path) -> Option<String> {
+        let mut current = Some(workspace_root);
         while let Some(dir) = current {
             let package_json = dir.join("node_modules/svelte/package.json");
             if let Ok(contents) = fs::read_to_string(&package_json) {
@@ -551,31 +1316,76 @@ impl BunRunner {
     }
 }
 
-fn compiler_cache_key(input: &BunInput, svelte_version: Option<&str>) -> Result<String, BunError> {
+fn svelte_config_hash(svelte_config: Option<&Utf8Path>) -> Option<blake3::Hash> {
+    svelte_config
+        .and_then(|path| fs::read(path).ok())
+        .map(|contents| blake3::hash(&contents))
+}
+
+fn compiler_cache_key(
+    input: &BunInput,
+    svelte_version: Option<&str>,
+    svelte_config: Option<&[u8]>,
+) -> Result<String, BunError> {
     let options = serde_json::to_vec(&input.options)
         .map_err(|e| BunError::ProtocolError(format!("failed to serialize options: {e}")))?;
     let mut hasher = Hasher::new();
-    hasher.update(b"compiler-diagnostics-v1");
+    hasher.update(b"compiler-diagnostics-v2");
     hasher.update(BUN_SCRIPT_SOURCE.as_bytes());
     hasher.update(svelte_version.unwrap_or("unknown").as_bytes());
+    hasher.update(&[0]);
+    hasher.update(svelte_config.unwrap_or_default());
     hasher.update(input.filename.as_str().as_bytes());
     hasher.update(&[0]);
+    hasher.update(&[input.kind as u8]);
     hasher.update(input.source.as_bytes());
     hasher.update(&[0]);
     hasher.update(&options);
     Ok(hasher.finalize().to_hex().to_string())
 }
 
+/// A cached compile result. `dependencies` pairs each file the preprocessors
+/// read with its content hash; any change invalidates the entry.
+#[derive(Serialize, Deserialize)]
+struct CachedDiagnostics {
+    diagnostics: Vec<BunDiagnostic>,
+    #[serde(default)]
+    dependencies: Vec<(Utf8PathBuf, String)>,
+}
+
 fn read_cached_diagnostics(path: &Utf8Path) -> Option<Vec<BunDiagnostic>> {
     let contents = fs::read(path).ok()?;
-    serde_json::from_slice(&contents).ok()
+    let cached: CachedDiagnostics = serde_json::from_slice(&contents).ok()?;
+    let fresh = cached
+        .dependencies
+        .iter()
+        .all(|(dependency, hash)| file_hash(dependency).as_deref() == Some(hash.as_str()));
+    fresh.then_some(cached.diagnostics)
+}
+
+fn file_hash(path: &Utf8Path) -> Option<String> {
+    let contents = fs::read(path).ok()?;
+    Some(blake3::hash(&contents).to_hex().to_string())
 }
 
 fn write_cached_diagnostics(
     path: &Utf8Path,
     diagnostics: &[BunDiagnostic],
+    dependencies: &[Utf8PathBuf],
 ) -> Result<(), BunError> {
-    let contents = serde_json::to_vec(diagnostics)
+    let mut hashed = Vec::with_capacity(dependencies.len());
+    for dependency in dependencies {
+        // An unreadable dependency can't be validated later; skip caching.
+        let hash = file_hash(dependency).ok_or_else(|| {
+            BunError::ProtocolError(format!("failed to hash dependency {dependency}"))
+        })?;
+        hashed.push((dependency.clone(), hash));
+    }
+    let cached = CachedDiagnostics {
+        diagnostics: diagnostics.to_vec(),
+        dependencies: hashed,
+    };
+    let contents = serde_json::to_vec(&cached)
         .map_err(|e| BunError::ProtocolError(format!("failed to serialize cache: {e}")))?;
     // Write through a sibling tmp file and rename so concurrent svelte-check-rs
     // processes can never observe a partially-written cache entry.
@@ -639,15 +1449,67 @@ fn project_cache_namespace(project_root: &Utf8Path) -> String {
         .to_string()
 }
 
-fn find_bun_in_bin(bin: &Utf8Path) -> Option<Utf8PathBuf> {
-    // The extensionless `bun` shim co-installed on Windows by npm/pnpm/yarn
-    // is a Unix shell script. Picking it would have `CreateProcess` reject
-    // it with `%1 is not a valid Win32 application` (os error 193); probe
-    // only the executable shims on Windows.
-    let candidates: &[&str] = if cfg!(windows) {
-        &["bun.exe", "bun.cmd", "bun.bat"]
+/// Finds the project's `svelte.config`, probing extensions in the same order
+/// as svelte-check-rs's own config loader.
+fn find_svelte_config(workspace_root: &Utf8Path) -> Option<Utf8PathBuf> {
+    [
+        "svelte.config.js",
+        "svelte.config.ts",
+        "svelte.config.cjs",
+        "svelte.config.mjs",
+        "svelte.config.mts",
+    ]
+    .iter()
+    .map(|name| workspace_root.join(name))
+    .find(|path| path.is_file())
+}
+
+/// Returns where [`BunRunner::find_bun`] looks for bun, in order.
+fn bun_locations(workspace_root: Option<&Utf8Path>) -> Vec<BunLocation> {
+    let mut locations = Vec::new();
+    if let Some(workspace) = workspace_root {
+        locations.push(BunLocation::Bin(workspace.join("node_modules/.bin")));
+        locations.extend(npm_package_locations(workspace));
+    }
+    locations.push(BunLocation::Path);
+    // Default install location from bun.sh/install
+    if let Some(home) = dirs::home_dir().and_then(|home| Utf8PathBuf::try_from(home).ok()) {
+        locations.push(BunLocation::Bin(home.join(".bun/bin")));
+    }
+    if let Some(install_dir) = bun_install_dir() {
+        locations.push(BunLocation::Bin(install_dir.join("bin")));
+    }
+    if let Some(cache_dir) = BunRunner::get_cache_dir() {
+        locations.push(BunLocation::Bin(cache_dir.join("node_modules/.bin")));
+    }
+    locations
+}
+
+/// Returns where bun installed from a local archive lives.
+fn bun_install_dir() -> Option<Utf8PathBuf> {
+    BunRunner::get_cache_dir().map(|dir| dir.join("bun"))
+}
+
+pub(crate) fn find_bun_in_bin(bin: &Utf8Path) -> Option<Utf8PathBuf> {
+    find_executable_in_bin(bin, "bun")
+}
+
+fn find_node_in_bin(bin: &Utf8Path) -> Option<Utf8PathBuf> {
+    find_executable_in_bin(bin, "node")
+}
+
+fn find_executable_in_bin(bin: &Utf8Path, name: &str) -> Option<Utf8PathBuf> {
+    // The extensionless shim co-installed on Windows by npm/pnpm/yarn is a
+    // Unix shell script. Picking it would have `CreateProcess` reject it with
+    // `%1 is not a valid Win32 application` (os error 193); probe only the
+    // executable shims on Windows.
+    let candidates: Vec<String> = if cfg!(windows) {
+        ["exe", "cmd", "bat"]
+            .iter()
+            .map(|ext| format!("{name}.{ext}"))
+            .collect()
     } else {
-        &["bun"]
+        vec![name.to_string()]
     };
 
     for candidate in candidates.iter() {
@@ -660,16 +1522,44 @@ fn find_bun_in_bin(bin: &Utf8Path) -> Option<Utf8PathBuf> {
     None
 }
 
+/// Runs `<runtime> --version`, dropping node's leading `v` so both runtimes
+/// report a bare semver.
+async fn runtime_version(path: &Utf8Path) -> Result<String, BunError> {
+    let output = Command::new(path)
+        .arg("--version")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(BunError::SpawnFailed)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BunError::ProcessFailed {
+            code: output.status.code().unwrap_or(-1),
+            stderr: stderr.to_string(),
+        });
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(version.strip_prefix('v').unwrap_or(&version).to_string())
+}
+
 fn ensure_script() -> Result<Utf8PathBuf, BunError> {
     let cache_dir = BunRunner::get_cache_dir()
         .ok_or_else(|| BunError::InstallFailed("could not determine cache directory".into()))?;
     fs::create_dir_all(&cache_dir)
         .map_err(|e| BunError::InstallFailed(format!("failed to create cache dir: {e}")))?;
 
-    let script_path = cache_dir.join(BUN_SCRIPT_FILENAME);
     let mut hasher = Hasher::new();
     hasher.update(BUN_SCRIPT_SOURCE.as_bytes());
     let expected_hash = hasher.finalize();
+    // Different svelte-check-rs versions share the cache directory, so each
+    // script gets its own file rather than overwriting the others'.
+    let script_path = cache_dir.join(format!(
+        "bun-svelte-compiler-{}.mjs",
+        &expected_hash.to_hex()[..16]
+    ));
 
     if let Ok(existing) = fs::read(&script_path) {
         let mut hasher = Hasher::new();
@@ -685,54 +1575,166 @@ fn ensure_script() -> Result<Utf8PathBuf, BunError> {
     Ok(script_path)
 }
 
+/// Idle compiler workers shared by a [`BunRunner`] and its clones.
+#[derive(Default)]
+struct WorkerPool {
+    idle: Mutex<Vec<BunWorker>>,
+    /// Set once the worker start-up warnings have been printed.
+    warned: AtomicBool,
+}
+
+impl WorkerPool {
+    /// Starts a worker, printing its start-up warnings (such as a
+    /// `svelte.config` that failed to load) only for the first one.
+    async fn spawn(
+        &self,
+        runtime_path: Utf8PathBuf,
+        workspace_root: Utf8PathBuf,
+        script_path: Utf8PathBuf,
+        svelte_config: Option<Utf8PathBuf>,
+    ) -> Result<BunWorker, BunError> {
+        let (worker, warnings) =
+            BunWorker::spawn(runtime_path, workspace_root, script_path, svelte_config).await?;
+        if !warnings.is_empty() && !self.warned.swap(true, Ordering::Relaxed) {
+            for warning in warnings {
+                eprintln!("Warning: {warning}");
+            }
+        }
+        Ok(worker)
+    }
+
+    /// Takes an idle worker that loaded the config hashing to `config_hash`.
+    /// Idle workers running an older config are dropped, which stops them.
+    fn checkout(&self, config_hash: Option<blake3::Hash>) -> Option<BunWorker> {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        idle.retain(|worker| worker.config_hash == config_hash);
+        idle.pop()
+    }
+
+    fn checkin(&self, worker: BunWorker) {
+        self.idle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(worker);
+    }
+}
+
+impl std::fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let idle = self.idle.lock().map(|idle| idle.len()).unwrap_or(0);
+        f.debug_struct("WorkerPool").field("idle", &idle).finish()
+    }
+}
+
+/// What a worker returned for one input.
+struct CompileOutput {
+    diagnostics: Vec<BunDiagnostic>,
+    /// Hash of the `svelte.config` the worker loaded.
+    config_hash: Option<blake3::Hash>,
+    /// Files the preprocessors read while processing the input.
+    dependencies: Vec<Utf8PathBuf>,
+    /// Preprocessor output and its decoded mappings for
+    /// [`BunInputKind::Preprocess`] inputs that a preprocessor handled.
+    preprocessed: Option<(String, Vec<Vec<[u32; 3]>>)>,
+}
+
+enum CompileOutcome {
+    /// The compiler ran; the diagnostics are safe to cache as long as the
+    /// preprocessor dependencies are unchanged.
+    Compiled(CompileOutput),
+    /// The worker crashed or timed out on this file.
+    Failed(BunDiagnostic),
+}
+
+fn worker_failure_diagnostic(input: &BunInput, code: &str, message: String) -> BunDiagnostic {
+    BunDiagnostic {
+        file: input.filename.clone(),
+        code: code.to_string(),
+        message,
+        severity: BunDiagnosticSeverity::Error,
+        start: BunPosition { line: 1, column: 1 },
+        end: BunPosition { line: 1, column: 1 },
+        suppressed: false,
+    }
+}
+
+/// How much of a worker's stderr is kept for error messages. Pooled workers
+/// live for a whole watch session, so only the most recent output is kept.
+const STDERR_TAIL_BYTES: usize = 64 * 1024;
+
+/// Reads a worker's stderr until it closes, keeping the last
+/// [`STDERR_TAIL_BYTES`] bytes.
+async fn read_stderr_tail(stderr: impl tokio::io::AsyncRead + Unpin) -> String {
+    let mut reader = BufReader::new(stderr);
+    let mut tail: VecDeque<u8> = VecDeque::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                tail.extend(&chunk[..read]);
+                let excess = tail.len().saturating_sub(STDERR_TAIL_BYTES);
+                tail.drain(..excess);
+            }
+        }
+    }
+    String::from_utf8_lossy(tail.make_contiguous()).into_owned()
+}
+
 struct BunWorker {
+    next_id: u64,
     child: Child,
     stdin: ChildStdin,
     stdout: tokio::io::Lines<BufReader<ChildStdout>>,
     stderr_task: Option<JoinHandle<String>>,
+    info: BridgeInfo,
+    /// Hash of the `svelte.config` read just before the worker imported it.
+    config_hash: Option<blake3::Hash>,
 }
 
 impl BunWorker {
     async fn spawn(
-        bun_path: Utf8PathBuf,
+        runtime_path: Utf8PathBuf,
         workspace_root: Utf8PathBuf,
         script_path: Utf8PathBuf,
-    ) -> Result<Self, BunError> {
-        let mut child = Command::new(&bun_path)
-            .arg(&script_path)
+        svelte_config: Option<Utf8PathBuf>,
+    ) -> Result<(Self, Vec<String>), BunError> {
+        let config_hash = svelte_config_hash(svelte_config.as_deref());
+        let mut command = Command::new(&runtime_path);
+        command.arg(&script_path);
+        if let Some(config) = &svelte_config {
+            command.arg(config);
+        }
+        let mut child = command
             .current_dir(&workspace_root)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .spawn()
             .map_err(BunError::SpawnFailed)?;
 
         let stdin = child
             .stdin
             .take()
-            .ok_or_else(|| BunError::ProtocolError("failed to open bun stdin".to_string()))?;
+            .ok_or_else(|| BunError::ProtocolError("failed to open runtime stdin".to_string()))?;
         let stdout = child
             .stdout
             .take()
-            .ok_or_else(|| BunError::ProtocolError("failed to open bun stdout".to_string()))?;
+            .ok_or_else(|| BunError::ProtocolError("failed to open runtime stdout".to_string()))?;
         let stderr = child
             .stderr
             .take()
-            .ok_or_else(|| BunError::ProtocolError("failed to open bun stderr".to_string()))?;
+            .ok_or_else(|| BunError::ProtocolError("failed to open runtime stderr".to_string()))?;
 
-        let stderr_task = tokio::spawn(async move {
-            let mut reader = BufReader::new(stderr);
-            let mut buffer = String::new();
-            let _ = reader.read_to_string(&mut buffer).await;
-            buffer
-        });
+        let stderr_task = tokio::spawn(read_stderr_tail(stderr));
 
         let mut stdout_reader = BufReader::new(stdout).lines();
 
         let ready_line = stdout_reader
             .next_line()
             .await
-            .map_err(|e| BunError::ProtocolError(format!("failed to read bun ready: {e}")))?;
+            .map_err(|e| BunError::ProtocolError(format!("failed to read bridge ready: {e}")))?;
 
         let Some(ready_line) = ready_line else {
             let stderr = stderr_task.await.unwrap_or_default();
@@ -747,105 +1749,122 @@ impl BunWorker {
             .map_err(|e| BunError::ParseError(format!("invalid ready response: {e}")))?;
         if !ready.ready {
             return Err(BunError::ProtocolError(format!(
-                "unexpected bun ready response: {}",
+                "unexpected bridge ready response: {}",
                 ready_line
             )));
         }
+        let info = match (ready.protocol, ready.info) {
+            (Some(BRIDGE_PROTOCOL_VERSION), Some(info)) => info,
+            (protocol, _) => {
+                let reported = protocol.map_or("no protocol version".to_string(), |version| {
+                    format!("protocol v{version}")
+                });
+                return Err(BunError::IncompatibleBridge(format!(
+                    "{script_path} reported {reported}, but svelte-check-rs expects protocol v{BRIDGE_PROTOCOL_VERSION} and a full handshake; delete the script so it is rewritten on the next run"
+                )));
+            }
+        };
 
-        Ok(Self {
-            child,
-            stdin,
-            stdout: stdout_reader,
-            stderr_task: Some(stderr_task),
-        })
+        Ok((
+            Self {
+                next_id: 1,
+                child,
+                stdin,
+                stdout: stdout_reader,
+                stderr_task: Some(stderr_task),
+                info,
+                config_hash,
+            },
+            ready.warnings,
+        ))
     }
 
-    async fn check_batch(&mut self, inputs: Vec<BunInput>) -> Result<Vec<BunDiagnostic>, BunError> {
-        let mut pending = HashMap::new();
-
-        for (id, input) in (1u64..).zip(inputs.iter()) {
-            let request = BunRequest {
-                id,
-                filename: input.filename.to_string(),
-                source: input.source.clone(),
-                options: input.options.clone(),
-            };
-
-            let line = serde_json::to_string(&request).map_err(|e| {
-                BunError::ProtocolError(format!("failed to serialize request: {e}"))
-            })?;
-            self.stdin.write_all(line.as_bytes()).await.map_err(|e| {
-                BunError::ProtocolError(format!("failed to write to bun stdin: {e}"))
-            })?;
-            self.stdin
-                .write_all(b"\n")
-                .await
-                .map_err(|e| BunError::ProtocolError(format!("failed to write newline: {e}")))?;
-
-            pending.insert(id, input.filename.clone());
-        }
+    /// Compiles one input and waits for its diagnostics and the files its
+    /// preprocessors depended on.
+    async fn compile(&mut self, input: &BunInput) -> Result<CompileOutput, BunError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = BunRequest {
+            id,
+            kind: input.kind,
+            filename: input.filename.to_string(),
+            source: input.source.clone(),
+            options: input.options.clone(),
+        };
 
+        let mut line = serde_json::to_string(&request)
+            .map_err(|e| BunError::ProtocolError(format!("failed to serialize request: {e}")))?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await.map_err(|e| {
+            BunError::ProtocolError(format!("failed to write to runtime stdin: {e}"))
+        })?;
         self.stdin
             .flush()
             .await
-            .map_err(|e| BunError::ProtocolError(format!("failed to flush bun stdin: {e}")))?;
-
-        let mut diagnostics = Vec::new();
+            .map_err(|e| BunError::ProtocolError(format!("failed to flush runtime stdin: {e}")))?;
 
-        while !pending.is_empty() {
-            let line = self.stdout.next_line().await.map_err(|e| {
-                BunError::ProtocolError(format!("failed to read bun response: {e}"))
+        let line =
+            self.stdout.next_line().await.map_err(|e| {
+                BunError::ProtocolError(format!("failed to read bridge response: {e}"))
             })?;
 
-            let Some(line) = line else {
-                let stderr = match self.stderr_task.take() {
-                    Some(handle) => handle.await.unwrap_or_default(),
-                    None => String::new(),
-                };
-                let status = self.child.wait().await.map_err(BunError::SpawnFailed)?;
-                return Err(BunError::ProcessFailed {
-                    code: status.code().unwrap_or(-1),
-                    stderr,
-                });
+        let Some(line) = line else {
+            let stderr = match self.stderr_task.take() {
+                Some(handle) => handle.await.unwrap_or_default(),
+                None => String::new(),
             };
+            let status = self.child.wait().await.map_err(BunError::SpawnFailed)?;
+            return Err(BunError::ProcessFailed {
+                code: status.code().unwrap_or(-1),
+                stderr,
+            });
+        };
 
-            let response: BunResponse = serde_json::from_str(&line)
-                .map_err(|e| BunError::ParseError(format!("invalid response: {e} ({line})")))?;
+        let response: BunResponse = serde_json::from_str(&line)
+            .map_err(|e| BunError::ParseError(format!("invalid response: {e} ({line})")))?;
 
-            if let Some(error) = response.error {
-                return Err(BunError::ProtocolError(error));
-            }
+        if let Some(error) = response.error {
+            return Err(BunError::ProtocolError(error));
+        }
 
-            let id = response
-                .id
-                .ok_or_else(|| BunError::ProtocolError(format!("missing response id: {line}")))?;
-
-            let file = pending
-                .remove(&id)
-                .ok_or_else(|| BunError::ProtocolError(format!("unexpected response id {id}")))?;
-
-            if let Some(diags) = response.diagnostics {
-                diagnostics.extend(diags.into_iter().map(|diag| BunDiagnostic {
-                    file: file.clone(),
-                    code: diag.code,
-                    message: diag.message,
-                    severity: match diag.severity.as_str() {
-                        "error" => BunDiagnosticSeverity::Error,
-                        _ => BunDiagnosticSeverity::Warning,
-                    },
-                    start: BunPosition {
-                        line: diag.start.line.max(1),
-                        column: diag.start.column + 1,
-                    },
-                    end: BunPosition {
-                        line: diag.end.line.max(1),
-                        column: diag.end.column + 1,
-                    },
-                }));
-            }
+        if response.id != Some(id) {
+            return Err(BunError::ProtocolError(format!(
+                "unexpected response id {:?} (expected {id})",
+                response.id
+            )));
         }
 
-        Ok(diagnostics)
+        let diagnostics = response
+            .diagnostics
+            .unwrap_or_default()
+            .into_iter()
+            .map(|diag| BunDiagnostic {
+                file: input.filename.clone(),
+                code: diag.code,
+                message: diag.message,
+                severity: match diag.severity.as_str() {
+                    "error" => BunDiagnosticSeverity::Error,
+                    _ => BunDiagnosticSeverity::Warning,
+                },
+                start: BunPosition {
+                    line: diag.start.line.max(1),
+                    column: diag.start.column + 1,
+                },
+                end: BunPosition {
+                    line: diag.end.line.max(1),
+                    column: diag.end.column + 1,
+                },
+                suppressed: diag.suppressed,
+            })
+            .collect();
+
+        Ok(CompileOutput {
+            diagnostics,
+            config_hash: self.config_hash,
+            dependencies: response.dependencies,
+            preprocessed: response.code.map(|code| (code, response.mappings)),
+        })
     }
 }
 
@@ -864,6 +1883,7 @@ mod tests {
         BunInput {
             filename: Utf8PathBuf::from(filename),
             source: source.to_string(),
+            kind: BunInputKind::Component,
             options: BunCompileOptions::default(),
         }
     }
@@ -872,8 +1892,8 @@ mod tests {
     fn compiler_cache_key_is_stable_for_identical_inputs() {
         let a = make_input("App.svelte", "<h1>hi</h1>");
         let b = make_input("App.svelte", "<h1>hi</h1>");
-        let key_a = compiler_cache_key(&a, Some("5.0.0")).unwrap();
-        let key_b = compiler_cache_key(&b, Some("5.0.0")).unwrap();
+        let key_a = compiler_cache_key(&a, Some("5.0.0"), None).unwrap();
+        let key_b = compiler_cache_key(&b, Some("5.0.0"), None).unwrap();
         assert_eq!(key_a, key_b);
     }
 
@@ -882,8 +1902,8 @@ mod tests {
         let a = make_input("App.svelte", "<h1>hi</h1>");
         let b = make_input("App.svelte", "<h1>bye</h1>");
         assert_ne!(
-            compiler_cache_key(&a, Some("5.0.0")).unwrap(),
-            compiler_cache_key(&b, Some("5.0.0")).unwrap()
+            compiler_cache_key(&a, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&b, Some("5.0.0"), None).unwrap()
         );
     }
 
@@ -893,8 +1913,8 @@ mod tests {
         let mut b = make_input("App.svelte", "<h1>hi</h1>");
         b.options.runes = Some(true);
         assert_ne!(
-            compiler_cache_key(&a, Some("5.0.0")).unwrap(),
-            compiler_cache_key(&b, Some("5.0.0")).unwrap()
+            compiler_cache_key(&a, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&b, Some("5.0.0"), None).unwrap()
         );
     }
 
@@ -902,8 +1922,24 @@ mod tests {
     fn compiler_cache_key_changes_with_svelte_version() {
         let input = make_input("App.svelte", "<h1>hi</h1>");
         assert_ne!(
-            compiler_cache_key(&input, Some("5.0.0")).unwrap(),
-            compiler_cache_key(&input, Some("5.1.0")).unwrap()
+            compiler_cache_key(&input, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&input, Some("5.1.0"), None).unwrap()
+        );
+    }
+
+    #[test]
+    fn detect_svelte_version_searches_parent_node_modules() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        let package = root.join("node_modules/svelte");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("package.json"), r#"{"version": "5.36.2"}"#).unwrap();
+        let app = root.join("apps/web");
+        fs::create_dir_all(&app).unwrap();
+
+        assert_eq!(
+            BunRunner::detect_svelte_version(&app).as_deref(),
+            Some("5.36.2")
         );
     }
 
@@ -911,18 +1947,347 @@ mod tests {
     fn compiler_cache_key_distinguishes_missing_version() {
         let input = make_input("App.svelte", "<h1>hi</h1>");
         assert_ne!(
-            compiler_cache_key(&input, None).unwrap(),
-            compiler_cache_key(&input, Some("5.0.0")).unwrap()
+            compiler_cache_key(&input, None, None).unwrap(),
+            compiler_cache_key(&input, Some("5.0.0"), None).unwrap()
         );
     }
 
+    #[test]
+    fn compiler_cache_key_changes_with_kind() {
+        let a = make_input("state.svelte.js", "export const x = $state(0);");
+        let mut b = make_input("state.svelte.js", "export const x = $state(0);");
+        b.kind = BunInputKind::Module;
+        assert_ne!(
+            compiler_cache_key(&a, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&b, Some("5.0.0"), None).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn stderr_tail_keeps_only_recent_output() {
+        let mut output = "old\n".repeat(STDERR_TAIL_BYTES);
+        output.push_str("latest error");
+        let tail = read_stderr_tail(output.as_bytes()).await;
+        assert_eq!(tail.len(), STDERR_TAIL_BYTES);
+        assert!(tail.ends_with("\nlatest error"));
+    }
+
+    #[test]
+    fn bun_request_serializes_kind() {
+        let request = BunRequest {
+            id: 1,
+            kind: BunInputKind::Module,
+            filename: "state.svelte.ts".into(),
+            source: String::new(),
+            options: BunCompileOptions::default(),
+        };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["kind"], "module");
+    }
+
     #[test]
     fn compiler_cache_key_changes_with_filename() {
         let a = make_input("a.svelte", "<h1>hi</h1>");
         let b = make_input("b.svelte", "<h1>hi</h1>");
         assert_ne!(
-            compiler_cache_key(&a, Some("5.0.0")).unwrap(),
-            compiler_cache_key(&b, Some("5.0.0")).unwrap()
+            compiler_cache_key(&a, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&b, Some("5.0.0"), None).unwrap()
+        );
+    }
+
+    /// Workspace with a stub `svelte/compiler` whose `compile()` hangs on
+    /// `hang`, exits on `crash`, and otherwise warns on the last line with
+    /// `"<pid> <compile count>"` (or the compile options for `options`). Its
+    /// `preprocess()` prepends a line and depends on `dep.scss`. Returns
+    /// `None` when node is unavailable.
+    fn stub_compiler_runner(
+        worker_count: usize,
+        svelte_config: Option<&str>,
+    ) -> Option<(tempfile::TempDir, BunRunner)> {
+        let node = BunRunner::find_node(None)?;
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let workspace =
+            Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).expect("utf8 temp path");
+        if let Some(config) = svelte_config {
+            std::fs::write(workspace.join("package.json"), r#"{"type":"module"}"#)
+                .expect("write package.json");
+            std::fs::write(workspace.join("svelte.config.js"), config).expect("write config");
+        }
+        let svelte = workspace.join("node_modules/svelte");
+        std::fs::create_dir_all(&svelte).expect("svelte dir");
+        std::fs::write(
+            svelte.join("package.json"),
+            r#"{"name":"svelte","version":"0.0.0-stub","type":"module","exports":{"./compiler":"./compiler.js"}}"#,
+        )
+        .expect("write package.json");
+        std::fs::write(
+            svelte.join("compiler.js"),
+            r#"let compiled = 0;
+export function compile(source, options) {
+  if (source === 'options') return { warnings: [{ code: 'stub', message: JSON.stringify(options) }] };
+  if (source === 'hang') for (;;) {}
+  if (source === 'crash') process.exit(1);
+  const line = source.split('\n').length;
+  return { warnings: [{ code: 'stub', message: `${process.pid} ${++compiled}`, start: { line, column: 0 } }] };
+}
+export function compileModule(source) { return compile(source); }
+export const VERSION = '5.0.0-stub';
+export async function preprocess(source, preprocessors, { filename }) {
+  if (filename.endsWith('.md')) return { code: source };
+  if (source === 'throw') throw new Error('bad markdown');
+  return { code: '\n' + source, map: { mappings: ';AAAA' }, dependencies: [process.cwd() + '/dep.scss'] };
+}
+"#,
+        )
+        .expect("write compiler.js");
+
+        let runtime = JsRuntime {
+            kind: JsRuntimeKind::Node,
+            path: node,
+        };
+        let runner = BunRunner::new(runtime, workspace, worker_count)
+            .expect("runner")
+            .with_file_timeout(Duration::from_millis(1500));
+        Some((temp_dir, runner))
+    }
+
+    #[test]
+    fn bridge_script_declares_protocol_version() {
+        assert!(BUN_SCRIPT_SOURCE.starts_with(&format!(
+            "const PROTOCOL_VERSION = {BRIDGE_PROTOCOL_VERSION};\n"
+        )));
+    }
+
+    #[test]
+    fn unversioned_ready_line_parses_without_handshake() {
+        let ready: BunReady = serde_json::from_str(r#"{"ready":true,"warnings":[]}"#).unwrap();
+        assert_eq!(ready.protocol, None);
+        assert!(ready.info.is_none());
+    }
+
+    #[tokio::test]
+    async fn handshake_reports_runtime_compiler_and_capabilities() {
+        let Some((_dir, runner)) = stub_compiler_runner(1, None) else {
+            return;
+        };
+
+        let info = runner.handshake().await.expect("handshake");
+        assert_eq!(info.script, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.runtime.name, "node");
+        assert!(info
+            .compiler
+            .path
+            .ends_with("node_modules/svelte/compiler.js"));
+        assert_eq!(info.compiler.version.as_deref(), Some("5.0.0-stub"));
+        assert_eq!(
+            info.capabilities,
+            vec!["compile", "compileModule", "preprocess"]
+        );
+    }
+
+    #[tokio::test]
+    async fn mismatched_protocol_is_an_actionable_error() {
+        let Some((dir, mut runner)) = stub_compiler_runner(1, None) else {
+            return;
+        };
+        let script = Utf8PathBuf::try_from(dir.path().join("old-bridge.mjs")).unwrap();
+        std::fs::write(
+            &script,
+            "console.log(JSON.stringify({ ready: true, protocol: 0 }));\n",
+        )
+        .expect("write script");
+        runner.script_path = script.clone();
+
+        let err = runner.handshake().await.unwrap_err();
+        assert!(matches!(err, BunError::IncompatibleBridge(_)), "{err}");
+        let message = err.to_string();
+        assert!(message.contains(script.as_str()), "{message}");
+        assert!(message.contains("protocol v0"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn missing_capability_is_reported_per_file() {
+        let Some((dir, runner)) = stub_compiler_runner(1, None) else {
+            return;
+        };
+        std::fs::write(
+            dir.path().join("node_modules/svelte/compiler.js"),
+            "export function compile() { return { warnings: [] }; }\n",
+        )
+        .expect("write compiler.js");
+
+        let mut input = make_input("/state.svelte.js", "export const x = 1;");
+        input.kind = BunInputKind::Module;
+        let diagnostics = runner.check_files(vec![input]).await.expect("check");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "compiler_unsupported");
+        assert!(
+            diagnostics[0]
+                .message
+                .contains("does not export compileModule()"),
+            "{}",
+            diagnostics[0].message
+        );
+    }
+
+    #[tokio::test]
+    async fn worker_pool_reuses_workers_across_checks() {
+        let Some((_dir, runner)) = stub_compiler_runner(1, None) else {
+            return;
+        };
+
+        let first = runner
+            .check_files(vec![make_input("/a.svelte", "a")])
+            .await
+            .expect("first check");
+        let second = runner
+            .clone()
+            .check_files(vec![make_input("/b.svelte", "b")])
+            .await
+            .expect("second check");
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 1);
+        let pid = |diags: &[BunDiagnostic]| diags[0].message.split(' ').next().map(String::from);
+        assert_eq!(pid(&first), pid(&second), "same worker pid");
+    }
+
+    #[tokio::test]
+    async fn worker_pool_reports_timeouts_and_crashes_per_file() {
+        let Some((_dir, runner)) = stub_compiler_runner(2, None) else {
+            return;
+        };
+
+        let diagnostics = runner
+            .check_files(vec![
+                make_input("/hang.svelte", "hang"),
+                make_input("/crash.svelte", "crash"),
+                make_input("/a.svelte", "a"),
+                make_input("/b.svelte", "b"),
+            ])
+            .await
+            .expect("check survives failing files");
+
+        let code_for = |file: &str| {
+            diagnostics
+                .iter()
+                .find(|d| d.file == file)
+                .map(|d| d.code.as_str())
+        };
+        assert_eq!(code_for("/hang.svelte"), Some("compiler_timeout"));
+        assert_eq!(code_for("/crash.svelte"), Some("compiler_crashed"));
+        assert_eq!(code_for("/a.svelte"), Some("stub"));
+        assert_eq!(code_for("/b.svelte"), Some("stub"));
+
+        // Failures are not cached, and the pool recovers for the next check.
+        let retried = runner
+            .check_files(vec![make_input("/crash.svelte", "crash")])
+            .await
+            .expect("second check");
+        assert_eq!(retried[0].code, "compiler_crashed");
+    }
+
+    #[tokio::test]
+    async fn preprocessed_positions_map_back_and_dependencies_invalidate_cache() {
+        let config = "export default { preprocess: [] };\n";
+        let Some((dir, runner)) = stub_compiler_runner(1, Some(config)) else {
+            return;
+        };
+        let dep = dir.path().join("dep.scss");
+        std::fs::write(&dep, "$color: red;").expect("write dep");
+        let check = || runner.check_files(vec![make_input("/a.svelte", "a")]);
+
+        let first = check().await.expect("first check");
+        // The stub warns on the preprocessed line 2, which maps back to line 1.
+        assert_eq!(first[0].start.line, 1);
+        assert!(first[0].message.ends_with(" 1"));
+
+        let cached = check().await.expect("cached check");
+        assert_eq!(cached[0].message, first[0].message);
+
+        std::fs::write(&dep, "$color: blue;").expect("update dep");
+        let recompiled = check().await.expect("recompiled check");
+        assert!(recompiled[0].message.ends_with(" 2"));
+    }
+
+    #[tokio::test]
+    async fn preprocess_files_returns_mapped_markup_for_handled_files() {
+        let config = "export default { preprocess: [] };\n";
+        let Some((_dir, runner)) = stub_compiler_runner(2, Some(config)) else {
+            return;
+        };
+
+        let results = runner
+            .preprocess_files(vec![
+                ("/page.svx".into(), "# hi".into()),
+                ("/notes.md".into(), "plain".into()),
+                ("/broken.svx".into(), "throw".into()),
+            ])
+            .await
+            .expect("preprocess");
+
+        assert_eq!(results.len(), 3);
+        let page = results[0].output.as_ref().expect("page output");
+        assert_eq!(page.code, "\n# hi");
+        // The stub's map puts generated line 2 on original line 1.
+        assert_eq!(page.original_offset(3), 2);
+        assert!(results[1].output.is_none());
+        assert!(results[1].diagnostics.is_empty());
+        assert!(results[2].output.is_none());
+        assert_eq!(results[2].diagnostics[0].code, "preprocess_error");
+        assert_eq!(results[2].diagnostics[0].message, "bad markdown");
+    }
+
+    #[tokio::test]
+    async fn evaluated_config_supplies_compiler_options_and_warning_filter() {
+        let config = "export default {\n  compilerOptions: { namespace: 'svg', runes: false, warningFilter: (w) => w.code !== 'stub' }\n};\n";
+        let Some((_dir, runner)) = stub_compiler_runner(1, Some(config)) else {
+            return;
+        };
+        let mut input = make_input("/a.svelte", "options");
+        input.options.runes = Some(true);
+
+        let diagnostics = runner.check_files(vec![input]).await.expect("check");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].suppressed);
+        let options: serde_json::Value =
+            serde_json::from_str(&diagnostics[0].message).expect("options json");
+        assert_eq!(options["namespace"], "svg");
+        // Options sent by svelte-check-rs win over the evaluated config.
+        assert_eq!(options["runes"], true);
+        assert_eq!(options["filename"], "/a.svelte");
+        assert!(options.get("warningFilter").is_none());
+    }
+
+    #[tokio::test]
+    async fn config_edit_restarts_idle_workers() {
+        let config = "export default {\n  compilerOptions: { warningFilter: (w) => w.code !== 'stub' }\n};\n";
+        let Some((dir, runner)) = stub_compiler_runner(1, Some(config)) else {
+            return;
+        };
+        let check = || runner.check_files(vec![make_input("/a.svelte", "a")]);
+
+        let filtered = check().await.expect("first check");
+        assert!(filtered[0].suppressed);
+
+        std::fs::write(dir.path().join("svelte.config.js"), "export default {};\n")
+            .expect("edit config");
+        let unfiltered = check().await.expect("second check");
+        assert!(
+            !unfiltered[0].suppressed,
+            "worker kept the old warningFilter"
+        );
+        let pid = |diags: &[BunDiagnostic]| diags[0].message.split(' ').next().map(String::from);
+        assert_ne!(pid(&filtered), pid(&unfiltered), "worker restarted");
+    }
+
+    #[test]
+    fn compiler_cache_key_changes_with_svelte_config() {
+        let input = make_input("App.svelte", "<h1>hi</h1>");
+        assert_ne!(
+            compiler_cache_key(&input, Some("5.0.0"), None).unwrap(),
+            compiler_cache_key(&input, Some("5.0.0"), Some(b"export default {}")).unwrap()
         );
     }
 
@@ -953,6 +2318,45 @@ mod tests {
         assert_eq!(find_bun_in_bin(&bin), Some(cmd));
     }
 
+    #[test]
+    fn find_node_in_bin_finds_platform_shim() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let bin = Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).expect("utf8 temp path");
+
+        let shim_name = if cfg!(windows) { "node.exe" } else { "node" };
+        let shim = bin.join(shim_name);
+        std::fs::write(&shim, b"\0").expect("write shim");
+
+        assert_eq!(find_node_in_bin(&bin), Some(shim));
+        assert_eq!(find_bun_in_bin(&bin), None);
+    }
+
+    #[test]
+    fn find_runtime_prefers_workspace_bun_over_node() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let workspace =
+            Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).expect("utf8 temp path");
+        let bin = workspace.join("node_modules/.bin");
+        std::fs::create_dir_all(&bin).expect("bin dir");
+        let (bun, node) = if cfg!(windows) {
+            ("bun.exe", "node.exe")
+        } else {
+            ("bun", "node")
+        };
+        std::fs::write(bin.join(bun), b"\0").expect("write bun");
+        std::fs::write(bin.join(node), b"\0").expect("write node");
+
+        let auto =
+            BunRunner::find_runtime(Some(&workspace), JsRuntimePreference::Auto).expect("runtime");
+        assert_eq!(auto.kind, JsRuntimeKind::Bun);
+        assert_eq!(auto.path, bin.join(bun));
+
+        let forced =
+            BunRunner::find_runtime(Some(&workspace), JsRuntimePreference::Node).expect("runtime");
+        assert_eq!(forced.kind, JsRuntimeKind::Node);
+        assert_eq!(forced.path, bin.join(node));
+    }
+
     #[test]
     fn find_bun_in_bin_ignores_directory_with_shim_name() {
         // is_file() guard: a directory whose name matches a candidate must
@@ -169,6 +169,16 @@ impl SourceMap {
         self.mappings.sort_by_key(|m| m.generated.start);
     }
 
+    /// Rewrites the original span of every mapping, e.g. to point them at the
+    /// file a preprocessor produced the original source from.
+    ///
+    /// Generated spans are untouched, so lookups stay sorted.
+    pub fn map_original(&mut self, mut f: impl FnMut(Span) -> Span) {
+        for mapping in &mut self.mappings {
+            mapping.original = f(mapping.original);
+        }
+    }
+
     /// Finds the mapping that contains the given generated position.
     fn find_mapping_for_generated(&self, generated: ByteOffset) -> Option<&Mapping> {
         // Binary search for the first mapping where generated.start <= position
@@ -434,6 +444,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_map_original_rewrites_original_spans() {
+        let mut builder = SourceMapBuilder::new();
+        builder.add_source(TextSize::from(10), "hello");
+        let mut map = builder.build();
+        map.map_original(|span| Span::new(span.start + TextSize::from(5), span.end));
+        assert_eq!(
+            map.original_position(TextSize::from(2)),
+            Some(TextSize::from(17))
+        );
+    }
+
     #[test]
     fn test_reverse_lookup() {
         let mut builder = SourceMapBuilder::new();
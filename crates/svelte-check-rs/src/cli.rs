@@ -1,5 +1,6 @@
 //! CLI argument parsing.
 
+use bun_runner::JsRuntimePreference;
 use camino::Utf8PathBuf;
 use clap::{Parser, ValueEnum};
 
@@ -60,6 +61,20 @@ pub struct Args {
     #[arg(long = "bun-update")]
     pub bun_update: Option<Option<String>>,
 
+    /// JavaScript runtime for Svelte compiler diagnostics
+    #[arg(long = "js-runtime", value_enum, default_value = "auto")]
+    pub js_runtime: JsRuntimeArg,
+
+    /// Also compile components with `generate: "server"` to report SSR-only
+    /// compiler warnings (auto: SvelteKit projects)
+    #[arg(long, value_enum, default_value = "auto")]
+    pub ssr: SsrArg,
+
+    /// Skip project-wide checks (unused components, import cycles, context
+    /// keys), which read every .ts/.js file in the workspace for imports
+    #[arg(long = "skip-project-checks")]
+    pub skip_project_checks: bool,
+
     /// Print timing breakdowns
     #[arg(long)]
     pub timings: bool,
@@ -130,6 +145,40 @@ pub enum Threshold {
     Warning,
 }
 
+/// JavaScript runtime selection for the compiler bridge.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
+pub enum JsRuntimeArg {
+    /// Prefer bun, fall back to node (default)
+    #[default]
+    Auto,
+    /// Always use bun (installed if missing)
+    Bun,
+    /// Always use node
+    Node,
+}
+
+impl From<JsRuntimeArg> for JsRuntimePreference {
+    fn from(arg: JsRuntimeArg) -> Self {
+        match arg {
+            JsRuntimeArg::Auto => JsRuntimePreference::Auto,
+            JsRuntimeArg::Bun => JsRuntimePreference::Bun,
+            JsRuntimeArg::Node => JsRuntimePreference::Node,
+        }
+    }
+}
+
+/// Whether to run the server-side compile pass.
+#[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
+pub enum SsrArg {
+    /// Run it for SvelteKit projects (default)
+    #[default]
+    Auto,
+    /// Always run it
+    On,
+    /// Never run it
+    Off,
+}
+
 /// Timing output format.
 #[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
 pub enum TimingFormat {
@@ -175,6 +224,42 @@ mod tests {
         assert!(matches!(args.output, OutputFormat::Machine));
     }
 
+    #[test]
+    fn test_ssr() {
+        let args = Args::parse_from(["svelte-check-rs"]);
+        assert_eq!(args.ssr, SsrArg::Auto);
+
+        let args = Args::parse_from(["svelte-check-rs", "--ssr", "off"]);
+        assert_eq!(args.ssr, SsrArg::Off);
+    }
+
+    #[test]
+    fn test_skip_project_checks() {
+        let args = Args::parse_from(["svelte-check-rs"]);
+        assert!(!args.skip_project_checks);
+
+        let args = Args::parse_from(["svelte-check-rs", "--skip-project-checks"]);
+        assert!(args.skip_project_checks);
+    }
+
+    #[test]
+    fn test_js_runtime() {
+        let args = Args::parse_from(["svelte-check-rs"]);
+        assert_eq!(args.js_runtime, JsRuntimeArg::Auto);
+
+        let args = Args::parse_from(["svelte-check-rs", "--js-runtime", "node"]);
+        assert_eq!(
+            JsRuntimePreference::from(args.js_runtime),
+            JsRuntimePreference::Node
+        );
+
+        let args = Args::parse_from(["svelte-check-rs", "--js-runtime", "bun"]);
+        assert_eq!(
+            JsRuntimePreference::from(args.js_runtime),
+            JsRuntimePreference::Bun
+        );
+    }
+
     #[test]
     fn test_debug_flags() {
         // Test --list-files
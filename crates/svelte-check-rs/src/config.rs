@@ -8,8 +8,8 @@ use std::sync::Arc;
 use swc_common::SourceMap;
 use swc_ecma_ast::{
     ArrayLit, AssignExpr, AssignOp, AssignTarget, CallExpr, Callee, Decl, ExportDefaultExpr, Expr,
-    ExprStmt, KeyValueProp, Lit, MemberExpr, MemberProp, ModuleDecl, ModuleItem, ObjectLit, Pat,
-    Prop, PropName, PropOrSpread, SimpleAssignTarget, Stmt, VarDeclKind,
+    ExprStmt, KeyValueProp, Lit, MemberExpr, MemberProp, MethodProp, ModuleDecl, ModuleItem,
+    ObjectLit, Pat, Prop, PropName, PropOrSpread, SimpleAssignTarget, Stmt, VarDeclKind,
 };
 use swc_ecma_parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
 
@@ -74,6 +74,10 @@ pub struct SvelteConfig {
 
     /// Compiler options.
     pub compiler_options: SvelteCompilerOptions,
+
+    /// Whether `preprocess` is set. Files with custom extensions (e.g. `.svx`)
+    /// are then checked through the compiler bridge's preprocessing pass.
+    pub has_preprocessors: bool,
 }
 
 /// SvelteKit-specific configuration.
@@ -81,6 +85,16 @@ pub struct SvelteConfig {
 pub struct KitConfig {
     /// Path aliases (e.g., `$lib` -> `./src/lib`).
     pub alias: HashMap<String, String>,
+
+    /// Routes directory from `kit.files.routes`, when set.
+    pub routes: Option<String>,
+}
+
+impl KitConfig {
+    /// Returns the routes directory, defaulting to `src/routes`.
+    pub fn routes_dir(&self) -> &str {
+        self.routes.as_deref().unwrap_or("src/routes")
+    }
 }
 
 /// Svelte compiler options.
@@ -582,6 +596,17 @@ impl SvelteConfig {
         }
     }
 
+    /// Gets the key of a property, including methods and shorthands
+    /// (`{ preprocess }`).
+    fn prop_key(prop: &Prop) -> Option<&str> {
+        match prop {
+            Prop::KeyValue(KeyValueProp { key, .. }) => Self::prop_name_str(key),
+            Prop::Method(MethodProp { key, .. }) => Self::prop_name_str(key),
+            Prop::Shorthand(ident) => Some(ident.sym.as_str()),
+            _ => None,
+        }
+    }
+
     /// Gets a string value from a Str literal.
     fn str_value(s: &swc_ecma_ast::Str) -> Option<&str> {
         s.value.as_str()
@@ -591,6 +616,9 @@ impl SvelteConfig {
     fn extract_config_from_object(obj: &ObjectLit, config: &mut SvelteConfig) {
         for prop in &obj.props {
             if let PropOrSpread::Prop(prop) = prop {
+                if Self::prop_key(prop) == Some("preprocess") {
+                    config.has_preprocessors = true;
+                }
                 if let Prop::KeyValue(KeyValueProp { key, value }) = prop.as_ref() {
                     let Some(key_name) = Self::prop_name_str(key) else {
                         continue;
@@ -638,6 +666,26 @@ impl SvelteConfig {
                         if let Expr::Object(alias_obj) = value.as_ref() {
                             Self::extract_aliases(alias_obj, config);
                         }
+                    } else if key_name == "files" {
+                        if let Expr::Object(files_obj) = value.as_ref() {
+                            Self::extract_kit_files(files_obj, config);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Extracts the routes directory from `kit.files`.
+    fn extract_kit_files(obj: &ObjectLit, config: &mut SvelteConfig) {
+        for prop in &obj.props {
+            if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(KeyValueProp { key, value }) = prop.as_ref() {
+                    if Self::prop_name_str(key) != Some("routes") {
+                        continue;
+                    }
+                    if let Expr::Lit(Lit::Str(s)) = value.as_ref() {
+                        config.kit.routes = Self::str_value(s).map(str::to_string);
                     }
                 }
             }
@@ -714,9 +762,10 @@ impl SvelteConfig {
     /// - `.svelte.js` - JavaScript module files with runes
     ///
     /// Any extra extensions declared in `svelte.config.js` (e.g. `.svx` from
-    /// mdsvex) are appended so they are still discovered and reported, but the
-    /// orchestrator filters out the unrecognized ones with a user-facing
-    /// warning rather than feeding them into the type-checker.
+    /// mdsvex) are appended so they are still discovered. With preprocessors
+    /// configured the orchestrator checks their preprocessed output; otherwise
+    /// it filters them out with a user-facing warning rather than feeding them
+    /// into the type-checker.
     ///
     /// Order matters: longer suffixes must come before `.svelte` so that
     /// `.svelte.ts` matches before `.svelte`.
@@ -733,7 +782,7 @@ impl SvelteConfig {
 
     /// Returns extensions declared in `svelte.config.js` that we don't
     /// natively support. Files with these extensions are discovered (so we can
-    /// report them) but skipped from the rest of the pipeline.
+    /// report them) and only checked if a preprocessor turns them into Svelte.
     pub fn unsupported_extensions(&self) -> Vec<&str> {
         self.extensions
             .iter()
@@ -995,6 +1044,7 @@ mod tests {
                     ("$lib".to_string(), "./src/lib".to_string()),
                     ("$components".to_string(), "./src/components".to_string()),
                 ]),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1033,6 +1083,9 @@ mod tests {
             Some(&"./src/components".to_string())
         );
 
+        // Without kit.files.routes, SvelteKit's default applies
+        assert_eq!(config.kit.routes_dir(), "src/routes");
+
         // Verify compilerOptions.runes was extracted
         assert_eq!(config.compiler_options.runes, Some(true));
 
@@ -1049,6 +1102,9 @@ mod tests {
                     alias: {
                         '$lib': './src/lib',
                         '$utils': './src/utils'
+                    },
+                    files: {
+                        routes: 'src/pages'
                     }
                 },
                 compilerOptions: {
@@ -1073,6 +1129,7 @@ mod tests {
             config.kit.alias.get("$utils"),
             Some(&"./src/utils".to_string())
         );
+        assert_eq!(config.kit.routes_dir(), "src/pages");
         assert_eq!(config.compiler_options.runes, Some(true));
         assert_eq!(config.compiler_options.experimental_async, Some(true));
 
@@ -1080,6 +1137,30 @@ mod tests {
         std::fs::remove_file(config_path).ok();
     }
 
+    #[test]
+    fn test_parse_svelte_config_detects_preprocessors() {
+        let temp_dir = std::env::temp_dir().join("svelte_check_rs_preprocess_test");
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let config_path = temp_dir.join("svelte.config.js");
+        let utf8_path = Utf8PathBuf::try_from(temp_dir.clone()).unwrap();
+
+        std::fs::write(
+            &config_path,
+            "export default { extensions: ['.svelte', '.svx'] };",
+        )
+        .unwrap();
+        assert!(!SvelteConfig::load(&utf8_path).has_preprocessors);
+
+        std::fs::write(
+            &config_path,
+            "import { mdsvex } from 'mdsvex';\nexport default { extensions: ['.svelte', '.svx'], preprocess: [mdsvex()] };",
+        )
+        .unwrap();
+        assert!(SvelteConfig::load(&utf8_path).has_preprocessors);
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
+
     #[test]
     fn test_parse_svelte_config_ts() {
         // Issue #3009: a `svelte.config.ts` must be probed and parsed with the
@@ -5,8 +5,10 @@ mod config;
 mod orchestrator;
 mod output;
 
-use bun_runner::BunRunner;
-use camino::Utf8Path;
+use bun_runner::{
+    BunInputKind, BunRunner, JsRuntimePreference, BRIDGE_PROTOCOL_VERSION, BUN_ARCHIVE_ENV,
+};
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
 use cli::Args;
 use miette::Result;
@@ -21,14 +23,7 @@ async fn main() -> Result<()> {
 
     // Handle tsgo version command
     if args.tsgo_version {
-        let workspace = if args.workspace.as_str() == "." {
-            std::env::current_dir()
-                .ok()
-                .and_then(|p| camino::Utf8PathBuf::try_from(p).ok())
-                .unwrap_or_else(|| args.workspace.to_owned())
-        } else {
-            args.workspace.to_owned()
-        };
+        let workspace = resolve_workspace(&args.workspace);
 
         match TsgoRunner::get_tsgo_version(&workspace).await {
             Ok((version, path)) => {
@@ -50,12 +45,13 @@ async fn main() -> Result<()> {
         println!();
     }
 
-    // Handle bun version command
+    // Handle bun version command (reports node when that is the selected runtime)
     if args.bun_version {
-        match BunRunner::get_bun_version().await {
-            Ok((version, path)) => {
-                println!("bun {}", version);
-                println!("path: {}", path);
+        let workspace = resolve_workspace(&args.workspace);
+        match BunRunner::get_runtime_version(Some(&workspace), args.js_runtime.into()).await {
+            Ok((version, runtime)) => {
+                println!("{} {}", runtime.kind.name(), version);
+                println!("path: {}", runtime.path);
                 if let Some(cache_dir) = BunRunner::get_cache_dir() {
                     println!("cache: {}", cache_dir);
                 }
@@ -88,7 +84,7 @@ async fn main() -> Result<()> {
 
     // Handle debug-paths command
     if args.debug_paths {
-        print_debug_paths(&args.workspace);
+        print_debug_paths(&args.workspace, args.js_runtime.into()).await;
         return Ok(());
     }
 
@@ -108,16 +104,21 @@ async fn main() -> Result<()> {
     }
 }
 
-/// Prints debug information about resolved paths and package manager.
-fn print_debug_paths(workspace: &Utf8Path) {
-    let workspace = if workspace.as_str() == "." {
+/// Resolves `.` to the current directory so reported paths are absolute.
+fn resolve_workspace(workspace: &Utf8Path) -> Utf8PathBuf {
+    if workspace.as_str() == "." {
         std::env::current_dir()
             .ok()
-            .and_then(|p| camino::Utf8PathBuf::try_from(p).ok())
+            .and_then(|p| Utf8PathBuf::try_from(p).ok())
             .unwrap_or_else(|| workspace.to_owned())
     } else {
         workspace.to_owned()
-    };
+    }
+}
+
+/// Prints debug information about resolved paths and package manager.
+async fn print_debug_paths(workspace: &Utf8Path, js_runtime: JsRuntimePreference) {
+    let workspace = resolve_workspace(workspace);
 
     println!("Workspace: {}", workspace);
     println!();
@@ -152,5 +153,82 @@ fn print_debug_paths(workspace: &Utf8Path) {
         if let Some(cache_dir) = BunRunner::get_cache_dir() {
             println!("  cache:    {}/node_modules/.bin/bun", cache_dir);
         }
+        if let Ok(archive) = std::env::var(BUN_ARCHIVE_ENV) {
+            println!("  archive:  {} (from {})", archive, BUN_ARCHIVE_ENV);
+        }
+    }
+
+    println!();
+
+    // node binary
+    println!("node:");
+    match BunRunner::find_node(Some(&workspace)) {
+        Some(path) => println!("  resolved: {}", path),
+        None => println!("  resolved: (not found)"),
+    }
+
+    println!();
+
+    // Runtime the compiler bridge would run on
+    println!("compiler runtime:");
+    let runtime = BunRunner::find_runtime(Some(&workspace), js_runtime);
+    match &runtime {
+        Some(runtime) => println!("  selected: {} ({})", runtime.kind.name(), runtime.path),
+        None if js_runtime == JsRuntimePreference::Node => {
+            println!("  selected: (none - node not found)");
+        }
+        None => println!("  selected: (none - bun will be auto-installed on first run)"),
+    }
+
+    // Start the compiler bridge once to show what it negotiated
+    let Some(runtime) = runtime else {
+        return;
+    };
+    println!();
+    println!("compiler bridge:");
+    let handshake = match BunRunner::new(runtime, workspace.clone(), 1) {
+        Ok(runner) => runner.handshake().await,
+        Err(e) => Err(e),
+    };
+    match handshake {
+        Ok(info) => {
+            println!(
+                "  protocol: v{} (script {})",
+                BRIDGE_PROTOCOL_VERSION, info.script
+            );
+            println!("  runtime:  {} {}", info.runtime.name, info.runtime.version);
+            println!(
+                "  svelte:   {} ({})",
+                info.compiler
+                    .version
+                    .as_deref()
+                    .unwrap_or("unknown version"),
+                info.compiler.path
+            );
+            println!("  capabilities: {}", info.capabilities.join(", "));
+            for kind in [
+                BunInputKind::Component,
+                BunInputKind::Module,
+                BunInputKind::Preprocess,
+            ] {
+                if !info.supports(kind) {
+                    println!(
+                        "  missing:  {}() - {} files will not get compiler diagnostics; upgrade svelte",
+                        kind.capability(),
+                        match kind {
+                            BunInputKind::Component => "component",
+                            BunInputKind::Module => ".svelte.js/.svelte.ts",
+                            BunInputKind::Preprocess => "custom-extension",
+                        }
+                    );
+                }
+            }
+        }
+        Err(e) => {
+            for (index, line) in e.to_string().lines().enumerate() {
+                let label = if index == 0 { "error:" } else { "" };
+                println!("  {:<7}{}", label, line);
+            }
+        }
     }
 }
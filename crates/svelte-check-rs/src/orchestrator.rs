@@ -1,26 +1,31 @@
 //! Main orchestration logic.
 
-use crate::cli::{Args, TimingFormat};
+use crate::cli::{Args, SsrArg, TimingFormat};
 use crate::config::{SvelteConfig, SvelteFileKind, TsConfig};
 use crate::output::{CheckSummary, FormattedDiagnostic, Formatter, Position};
 use bun_runner::{
     BunCompileOptions, BunDiagnostic, BunDiagnosticSeverity, BunExperimentalOptions, BunInput,
-    BunRunner,
+    BunInputKind, BunPreprocessed, BunRunner, JsRuntimePreference, PreprocessedSource,
 };
 use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 use globset::{Glob, GlobSetBuilder};
 use rayon::prelude::*;
-use source_map::LineIndex;
+use source_map::{LineIndex, Span};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
-use svelte_diagnostics::{check as check_svelte, DiagnosticOptions, Severity};
-use svelte_parser::parse;
+use svelte_diagnostics::ignore::{TsPragma, TsPragmaKind};
+use svelte_diagnostics::{
+    check as check_svelte, DiagnosticCode, DiagnosticOptions, Ignores, Project, ProjectFile,
+    RuleLevel, Severity, SvelteFeature, SvelteVersion,
+};
+use svelte_parser::{parse, ScriptLang, SvelteDocument};
 use svelte_transformer::{transform, transform_module, TransformOptions};
 use thiserror::Error;
 use tsgo_runner::{
-    TransformedFile, TransformedFiles, TsgoCheckOutput, TsgoCheckStats, TsgoDiagnostic, TsgoRunner,
+    DiagnosticPosition, TransformedFile, TransformedFiles, TsgoCheckOutput, TsgoCheckStats,
+    TsgoDiagnostic, TsgoRunner,
 };
 use walkdir::WalkDir;
 
@@ -54,6 +59,21 @@ fn unsupported_extension_label(file_name: &str, user_extensions: &[&str]) -> Str
 /// Builds the per-extension "N files with unregistered extension (.X) skipped"
 /// warning lines for files we discovered but can't process.
 fn format_unsupported_warnings(files: &[Utf8PathBuf], user_extensions: &[&str]) -> Vec<String> {
+    format_skipped_warnings(files, user_extensions, "unregistered extension")
+}
+
+/// Builds the per-extension "N files with unpreprocessed extension (.X)
+/// skipped" warning lines for custom-extension files that no configured
+/// preprocessor turned into Svelte.
+fn format_unpreprocessed_warnings(files: &[Utf8PathBuf], user_extensions: &[&str]) -> Vec<String> {
+    format_skipped_warnings(files, user_extensions, "unpreprocessed extension")
+}
+
+fn format_skipped_warnings(
+    files: &[Utf8PathBuf],
+    user_extensions: &[&str],
+    reason: &str,
+) -> Vec<String> {
     if files.is_empty() {
         return Vec::new();
     }
@@ -67,8 +87,8 @@ fn format_unsupported_warnings(files: &[Utf8PathBuf], user_extensions: &[&str])
         .map(|(ext, count)| {
             let plural = if count == 1 { "file" } else { "files" };
             format!(
-                "warning: {} {} with unregistered extension ({}) skipped",
-                count, plural, ext
+                "warning: {} {} with {} ({}) skipped",
+                count, plural, reason, ext
             )
         })
         .collect()
@@ -222,6 +242,34 @@ fn normalize_tsconfig_pattern(pattern: &str) -> String {
     }
 }
 
+/// Walks the workspace for files, skipping ignored directories and files.
+fn walk_workspace(workspace: &Utf8Path, ignore_set: &globset::GlobSet) -> Vec<Utf8PathBuf> {
+    WalkDir::new(workspace)
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            if !entry.file_type().is_dir() {
+                return true;
+            }
+            let path = match Utf8Path::from_path(entry.path()) {
+                Some(path) => path,
+                None => return true,
+            };
+            let relative = path.strip_prefix(workspace).unwrap_or(path);
+            !is_ignored_dir(ignore_set, relative)
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| Utf8PathBuf::try_from(e.into_path()).ok())
+        .filter(|p| {
+            let relative = p.strip_prefix(workspace).unwrap_or(p);
+            !ignore_set.is_match(to_forward_slash(relative).as_str())
+        })
+        .collect()
+}
+
 fn is_ignored_dir(ignore_set: &globset::GlobSet, relative: &Utf8Path) -> bool {
     // globset patterns use '/' as the segment separator regardless of OS.
     // `relative` comes from `WalkDir` and carries native separators on
@@ -257,13 +305,19 @@ pub enum OrchestratorError {
     #[error("tsgo error: {0}")]
     TsgoError(String),
 
-    /// bun error.
-    #[error("bun error: {0}")]
+    /// Compiler bridge error, from bun or node.
+    #[error("compiler bridge error: {0}")]
     BunError(String),
 
     /// Compiler warnings config error.
     #[error("compiler warnings config error: {0}")]
     CompilerConfigError(String),
+
+    /// The installed Svelte is older than Svelte 5.
+    #[error(
+        "Svelte {0} is not supported; svelte-check-rs requires Svelte 5 or later (use the official svelte-check for older projects)"
+    )]
+    UnsupportedSvelteVersion(SvelteVersion),
 }
 
 /// Lexically normalizes a path: drops `.` components and resolves `..` against
@@ -333,16 +387,27 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
     // Load configuration
     let svelte_config = SvelteConfig::load(&workspace);
     let extra_paths = svelte_alias_paths(&svelte_config);
+    // Version-dependent checks assume the latest Svelte unless one is found.
+    let svelte_version = BunRunner::detect_svelte_version(&workspace)
+        .and_then(|version| SvelteVersion::parse(&version));
+    let mut experimental_async = svelte_config.compiler_options.experimental_async;
+    if let Some(version) = svelte_version.filter(|version| {
+        experimental_async == Some(true) && !version.supports(SvelteFeature::ExperimentalAsync)
+    }) {
+        eprintln!(
+            "Warning: compilerOptions.experimental.async requires Svelte {} or later, but {} is installed; checking without it",
+            SvelteFeature::ExperimentalAsync.introduced_in(),
+            version
+        );
+        experimental_async = None;
+    }
     let compiler_bun_options = BunCompileOptions {
         runes: svelte_config.compiler_options.runes,
         dev: None,
         generate: None,
-        experimental: svelte_config
-            .compiler_options
-            .experimental_async
-            .map(|enabled| BunExperimentalOptions {
-                async_: Some(enabled),
-            }),
+        experimental: experimental_async.map(|enabled| BunExperimentalOptions {
+            async_: Some(enabled),
+        }),
     };
 
     // Load tsconfig to detect module resolution strategy
@@ -361,10 +426,15 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
     if args.show_config {
         eprintln!("=== svelte-check-rs configuration ===");
         eprintln!("workspace: {}", workspace);
+        match svelte_version {
+            Some(version) => eprintln!("svelte: {}", version),
+            None => eprintln!("svelte: (not found)"),
+        }
         eprintln!();
         eprintln!("=== svelte.config.js ===");
         eprintln!("file_extensions: {:?}", svelte_config.file_extensions());
         eprintln!("kit.alias: {:?}", svelte_config.kit.alias);
+        eprintln!("kit.files.routes: {}", svelte_config.kit.routes_dir());
         eprintln!();
         eprintln!("=== tsconfig.json ===");
         if let Some(ref path) = ts_config_path {
@@ -399,6 +469,10 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
         });
     }
 
+    if let Some(version) = svelte_version.filter(|v| *v < SvelteVersion::MIN_SUPPORTED) {
+        return Err(OrchestratorError::UnsupportedSvelteVersion(version));
+    }
+
     let timings_enabled = args.timings
         || args.timings_format == TimingFormat::Json
         || read_env_bool("SVELTE_CHECK_RS_TIMINGS").unwrap_or(false);
@@ -444,34 +518,25 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
     // Find Svelte files
     let scan_start = Instant::now();
     let extensions = svelte_config.file_extensions();
-    let files: Vec<Utf8PathBuf> = WalkDir::new(&workspace)
+    let walked = walk_workspace(&workspace, &ignore_set);
+    // Plain scripts aren't checked, but their imports tell the project pass
+    // which components are used (e.g. `main.ts` mounting `App.svelte`).
+    let project_pass = !args.skip_project_checks && args.single_file.is_none();
+    let (files, script_files): (Vec<_>, Vec<_>) = walked
         .into_iter()
-        .filter_entry(|entry| {
-            if entry.depth() == 0 {
-                return true;
-            }
-            if !entry.file_type().is_dir() {
-                return true;
-            }
-            let path = match Utf8Path::from_path(entry.path()) {
-                Some(path) => path,
-                None => return true,
-            };
-            let relative = path.strip_prefix(&workspace).unwrap_or(path);
-            !is_ignored_dir(&ignore_set, relative)
-        })
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter_map(|e| Utf8PathBuf::try_from(e.into_path()).ok())
-        .filter(|p| {
+        .filter_map(|p| {
             let file_name = p.file_name().unwrap_or("");
-            extensions.iter().any(|ext| file_name.ends_with(ext))
-        })
-        .filter(|p| {
-            let relative = p.strip_prefix(&workspace).unwrap_or(p);
-            !ignore_set.is_match(to_forward_slash(relative).as_str())
+            if extensions.iter().any(|ext| file_name.ends_with(ext)) {
+                Some((p, true))
+            } else if project_pass && is_project_script(file_name) {
+                Some((p, false))
+            } else {
+                None
+            }
         })
-        .collect();
+        .partition(|(_, is_svelte)| *is_svelte);
+    let files: Vec<Utf8PathBuf> = files.into_iter().map(|(p, _)| p).collect();
+    let script_files: Vec<Utf8PathBuf> = script_files.into_iter().map(|(p, _)| p).collect();
     let file_scan_time = if timings_enabled {
         Some(scan_start.elapsed())
     } else {
@@ -480,12 +545,13 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
 
     // Split off files whose extension we don't natively understand (e.g. `.svx`
     // from mdsvex). They were registered in `svelte.config.js#extensions` so
-    // they showed up in the walk, but we can't transform them — feeding them to
-    // the Svelte/TS pipeline would either be silently dropped or break tsgo. We
-    // warn the user once per extension and exclude them from everything below.
+    // they showed up in the walk. With preprocessors configured they are kept
+    // and checked through their preprocessed Svelte output; otherwise feeding
+    // them to the Svelte/TS pipeline would either be silently dropped or break
+    // tsgo, so we warn the user once per extension and exclude them.
     let (files, unsupported_files): (Vec<_>, Vec<_>) = files
         .into_iter()
-        .partition(|f| SvelteFileKind::from_path(f).is_some());
+        .partition(|f| svelte_config.has_preprocessors || SvelteFileKind::from_path(f).is_some());
 
     if !unsupported_files.is_empty() {
         let user_extensions = svelte_config.unsupported_extensions();
@@ -531,15 +597,31 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
         });
     }
 
+    // Started on first use and kept for the whole run so watch-mode re-checks
+    // reuse the warm compiler workers.
+    let compiler = CompilerBridge::new(&svelte_config);
+
+    // Base diagnostic options (filename will be set per-file)
+    let mut diag_options = DiagnosticOptions::all();
+    diag_options.svelte_version = svelte_version;
+    if TsgoRunner::is_sveltekit_project(&workspace) {
+        let routes = workspace.join(svelte_config.kit.routes_dir());
+        diag_options = diag_options.with_sveltekit_routes(routes.as_str());
+    }
+
     if args.watch {
         run_watch_mode(
             &args,
             &workspace,
             files,
+            script_files,
+            &ignore_set,
             file_scan_time,
             use_nodenext_imports,
             compiler_bun_options,
+            &diag_options,
             &extra_paths,
+            &compiler,
         )
         .await
     } else {
@@ -547,24 +629,31 @@ pub async fn run(args: Args) -> Result<CheckSummary, OrchestratorError> {
             &args,
             &workspace,
             files,
+            &script_files,
             file_scan_time,
             use_nodenext_imports,
             compiler_bun_options,
+            &diag_options,
             &extra_paths,
+            &compiler,
         )
         .await
     }
 }
 
 /// Runs a single check pass.
+#[allow(clippy::too_many_arguments)]
 async fn run_single_check(
     args: &Args,
     workspace: &Utf8Path,
     files: Vec<Utf8PathBuf>,
+    script_files: &[Utf8PathBuf],
     file_scan_time: Option<std::time::Duration>,
     use_nodenext_imports: bool,
     compiler_bun_options: BunCompileOptions,
+    base_diag_options: &DiagnosticOptions,
     extra_paths: &HashMap<String, Vec<String>>,
+    compiler: &CompilerBridge,
 ) -> Result<CheckSummary, OrchestratorError> {
     let total_start = Instant::now();
     let timings_enabled = args.timings
@@ -576,28 +665,103 @@ async fn run_single_check(
     let warning_count = AtomicUsize::new(0);
     let compiler_warning_settings = parse_compiler_warnings(args.compiler_warnings.as_deref())?;
 
-    // Base diagnostic options (filename will be set per-file)
-    let base_diag_options = DiagnosticOptions::all();
+    // Project-wide checks need every file, so they don't run for --single-file.
+    let project_pass = !args.skip_project_checks && args.single_file.is_none();
 
     struct FileOutput {
         text: Option<String>,
         json: Vec<FormattedDiagnostic>,
     }
 
+    /// Native diagnostics for compiler warnings, held back until the compiler
+    /// reports which ones the project's `warningFilter` / `onwarn` drops. The
+    /// filter is only known once the bridge has loaded svelte.config, so
+    /// every such warning waits for the compiler. `<style>` parse errors wait
+    /// too: they are only reported when the compiler could not check the file.
+    struct DeferredWarnings {
+        file_path: Utf8PathBuf,
+        source: String,
+        diagnostics: Vec<svelte_diagnostics::Diagnostic>,
+    }
+
     struct FileResult {
         file_path: Utf8PathBuf,
         output: Option<FileOutput>,
+        deferred_warnings: Option<DeferredWarnings>,
         transformed: Option<(Utf8PathBuf, TransformedFile)>,
         compiler_input: Option<BunInput>,
-    }
-
-    // Separate files by kind: components (.svelte) vs modules (.svelte.ts/.svelte.js)
+        /// Codes and 1-based lines of the native parse errors reported for
+        /// this file.
+        parse_errors: Vec<(&'static str, u32)>,
+        /// Codes and 1-based lines of native warnings that the Svelte
+        /// compiler also reports.
+        compiler_warnings: Vec<(&'static str, u32)>,
+        /// `@ts-ignore` / `@ts-expect-error` comments in the markup.
+        ts_pragmas: Vec<MarkupTsPragma>,
+        /// Imports and component usages for the project pass.
+        project_file: Option<ProjectFile>,
+    }
+
+    // Separate files by kind: components (.svelte) vs modules (.svelte.ts/.svelte.js),
+    // plus custom extensions (.svx) that are checked through their preprocessed output
+    let (custom_files, files): (Vec<_>, Vec<_>) = files
+        .into_iter()
+        .partition(|f| SvelteFileKind::from_path(f).is_none());
     let (component_files, module_files): (Vec<_>, Vec<_>) = files
         .into_iter()
         .partition(|f| SvelteFileKind::from_path(f) == Some(SvelteFileKind::Component));
 
     let svelte_start = Instant::now();
 
+    // Preprocess custom-extension files up front; the resulting Svelte markup
+    // goes through the component pipeline below with positions mapped back.
+    let mut preprocess_diagnostics = Vec::new();
+    let mut unpreprocessed_files = Vec::new();
+    let mut component_files: Vec<(Utf8PathBuf, Option<PreprocessedSource>)> =
+        component_files.into_iter().map(|f| (f, None)).collect();
+    if !custom_files.is_empty() {
+        let sources = custom_files
+            .into_iter()
+            .filter_map(|file_path| match fs::read_to_string(&file_path) {
+                Ok(source) => Some((file_path, source)),
+                Err(e) => {
+                    eprintln!("Failed to read {}: {}", file_path, e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let filenames: Vec<Utf8PathBuf> = sources.iter().map(|(f, _)| f.clone()).collect();
+        match compiler
+            .preprocess(workspace, args.js_runtime.into(), sources)
+            .await
+        {
+            Ok(results) => {
+                for result in results {
+                    match result.output {
+                        Some(output) => component_files.push((result.filename, Some(output))),
+                        None if result.diagnostics.is_empty() => {
+                            unpreprocessed_files.push(result.filename)
+                        }
+                        None => preprocess_diagnostics.extend(result.diagnostics),
+                    }
+                }
+            }
+            // Without the bridge these files can't be checked at all, so each
+            // one fails rather than silently dropping out of the run.
+            Err(e) => {
+                preprocess_diagnostics.extend(
+                    filenames
+                        .into_iter()
+                        .map(|file| preprocess_failure_diagnostic(file, &e)),
+                );
+            }
+        }
+    }
+    for line in format_unpreprocessed_warnings(&unpreprocessed_files, &compiler.custom_extensions())
+    {
+        eprintln!("{}", line);
+    }
+
     // Resolve the cache root once so each transform can compute the eventual
     // generated `.svelte.ts` path. This lets the transformer rewrite relative
     // imports reaching outside the workspace so they resolve from the generated
@@ -609,16 +773,25 @@ async fn run_single_check(
     // Process component files (.svelte) in parallel: parse, run Svelte diagnostics, and transform
     let component_results: Vec<FileResult> = component_files
         .par_iter()
-        .map(|file_path| {
-            let source = match fs::read_to_string(file_path) {
+        .map(|(file_path, preprocessed)| {
+            let source = match preprocessed {
+                Some(preprocessed) => Ok(preprocessed.code.clone()),
+                None => fs::read_to_string(file_path),
+            };
+            let source = match source {
                 Ok(s) => s,
                 Err(e) => {
                     eprintln!("Failed to read {}: {}", file_path, e);
                     return FileResult {
                         file_path: file_path.clone(),
                         output: None,
+                        deferred_warnings: None,
                         transformed: None,
                         compiler_input: None,
+                        parse_errors: Vec::new(),
+                        compiler_warnings: Vec::new(),
+                        ts_pragmas: Vec::new(),
+                        project_file: None,
                     };
                 }
             };
@@ -643,24 +816,80 @@ async fn run_single_check(
             // Collect parse errors
             let mut all_diagnostics = Vec::new();
 
-            // Convert parse errors to diagnostics
+            // Convert parse errors to diagnostics under the Svelte compiler's
+            // codes. They are errors, so `--compiler-warnings` leaves them alone.
             for error in &parse_result.errors {
-                all_diagnostics.push(svelte_diagnostics::Diagnostic::new(
-                    svelte_diagnostics::DiagnosticCode::ParseError,
-                    error.to_string(),
-                    error.span,
-                ));
+                all_diagnostics.push(svelte_diagnostics::Diagnostic::from_parse_error(error));
             }
 
             // Run Svelte diagnostics with filename for component checks
-            let file_diag_options = base_diag_options
+            let mut file_diag_options = base_diag_options
                 .clone()
                 .with_filename(file_path.to_string());
-            let svelte_diags = check_svelte(&parse_result.document, file_diag_options);
+            // tsgo reports undeclared names in TypeScript components itself.
+            if !args.skip_tsgo && is_typescript_component(&parse_result.document) {
+                file_diag_options =
+                    file_diag_options.with_rule("missing-declaration", RuleLevel::Off);
+            }
+            let mut svelte_diags = check_svelte(&parse_result.document, &source, file_diag_options);
+            apply_native_warning_settings(&mut svelte_diags, &compiler_warning_settings);
+            // Diagnostics on preprocessed markup are reported against the file as written.
+            let original_source = match preprocessed {
+                Some(preprocessed) => {
+                    for diag in all_diagnostics.iter_mut().chain(svelte_diags.iter_mut()) {
+                        map_preprocessed_diagnostic(diag, preprocessed);
+                    }
+                    preprocessed.source.as_str()
+                }
+                None => source.as_str(),
+            };
+            let line_index = LineIndex::new(original_source);
+            let parse_errors = all_diagnostics
+                .iter()
+                .filter(|diag| !is_css_parse_error(&diag.code))
+                .filter_map(|diag| {
+                    let line = line_index.line_col(diag.span.start)?.line + 1;
+                    Some((diag.code.as_str(), line))
+                })
+                .collect();
+            let compiler_warnings = svelte_diags
+                .iter()
+                .filter(|diag| diag.code.is_compiler_warning())
+                .filter_map(|diag| {
+                    let line = line_index.line_col(diag.span.start)?.line + 1;
+                    Some((diag.code.as_str(), line))
+                })
+                .collect();
             all_diagnostics.extend(svelte_diags);
+            let ts_pragmas = Ignores::collect(&parse_result.document)
+                .ts_pragmas()
+                .iter()
+                .filter_map(|pragma| match preprocessed {
+                    Some(preprocessed) => {
+                        let pragma = TsPragma {
+                            kind: pragma.kind,
+                            comment: map_preprocessed_span(pragma.comment, preprocessed),
+                            span: map_preprocessed_span(pragma.span, preprocessed),
+                        };
+                        MarkupTsPragma::new(&pragma, &line_index)
+                    }
+                    None => MarkupTsPragma::new(pragma, &line_index),
+                })
+                .collect();
 
             all_diagnostics.retain(|diag| include_svelte_severity(diag.severity, args.threshold));
 
+            let (deferred, immediate): (Vec<_>, Vec<_>) =
+                all_diagnostics.into_iter().partition(|diag| {
+                    diag.code.is_compiler_warning() || is_css_parse_error(&diag.code)
+                });
+            all_diagnostics = immediate;
+            let deferred_warnings = (!deferred.is_empty()).then(|| DeferredWarnings {
+                file_path: file_path.clone(),
+                source: original_source.to_string(),
+                diagnostics: deferred,
+            });
+
             // Transform for TypeScript checking (if JS diagnostics enabled and not skipping tsgo)
             // Also transform if emit_ts or emit_source_map is enabled (for debugging)
             let mut transformed = None;
@@ -722,12 +951,16 @@ async fn run_single_check(
                     let virtual_path = virtual_path_for(file_path, workspace, true);
 
                     let tsx_code = transform_result.tsx_code;
+                    let mut source_map = transform_result.source_map;
+                    if let Some(preprocessed) = preprocessed {
+                        source_map.map_original(|span| map_preprocessed_span(span, preprocessed));
+                    }
                     let transformed_file = TransformedFile {
                         original_path: file_path.clone(),
                         generated_line_index: LineIndex::new(&tsx_code),
                         tsx_content: tsx_code,
-                        source_map: transform_result.source_map,
-                        original_line_index: LineIndex::new(&source),
+                        source_map,
+                        original_line_index: line_index.clone(),
                     };
 
                     transformed = Some((virtual_path, transformed_file));
@@ -755,32 +988,48 @@ async fn run_single_check(
                     text: if output_json {
                         None
                     } else {
-                        Some(formatter.format(&all_diagnostics, relative_path, &source))
+                        Some(formatter.format(&all_diagnostics, relative_path, original_source))
                     },
                     json: if output_json {
-                        Formatter::format_json_diagnostics(&all_diagnostics, relative_path, &source)
+                        Formatter::format_json_diagnostics(
+                            &all_diagnostics,
+                            relative_path,
+                            original_source,
+                        )
                     } else {
                         Vec::new()
                     },
                 })
             };
 
+            // The bridge preprocesses custom-extension files again before compiling.
             let compiler_input = Some(BunInput {
                 filename: file_path.clone(),
-                source: source.clone(),
+                source: original_source.to_string(),
+                kind: BunInputKind::Component,
                 options: compiler_bun_options.clone(),
             });
 
+            let project_file = project_pass.then(|| {
+                ProjectFile::component(file_path.as_std_path(), &parse_result.document, &source)
+            });
+
             FileResult {
                 file_path: file_path.clone(),
                 output,
+                deferred_warnings,
                 transformed,
                 compiler_input,
+                parse_errors,
+                compiler_warnings,
+                ts_pragmas,
+                project_file,
             }
         })
         .collect();
 
-    // Process module files (.svelte.ts/.svelte.js) in parallel: transform runes only
+    // Process module files (.svelte.ts/.svelte.js) in parallel: transform runes and
+    // queue them for `compileModule`
     let module_results: Vec<FileResult> = module_files
         .par_iter()
         .map(|file_path| {
@@ -791,8 +1040,13 @@ async fn run_single_check(
                     return FileResult {
                         file_path: file_path.clone(),
                         output: None,
+                        deferred_warnings: None,
                         transformed: None,
                         compiler_input: None,
+                        parse_errors: Vec::new(),
+                        compiler_warnings: Vec::new(),
+                        ts_pragmas: Vec::new(),
+                        project_file: None,
                     };
                 }
             };
@@ -816,6 +1070,12 @@ async fn run_single_check(
 
             // Collect any errors from invalid rune usage (e.g., $props in module files)
             let mut all_diagnostics: Vec<svelte_diagnostics::Diagnostic> = Vec::new();
+            // `compileModule` reports the same misuse as `rune_invalid_usage`.
+            let parse_errors = transform_result
+                .errors
+                .iter()
+                .map(|error| ("rune_invalid_usage", error.line as u32))
+                .collect();
             for error in &transform_result.errors {
                 // Compute byte offset from line/column
                 let offset = line_column_to_offset(&source, error.line, error.column);
@@ -889,11 +1149,28 @@ async fn run_single_check(
                 })
             };
 
+            // Modules always compile in runes mode, so `runes` is not forwarded.
+            let compiler_input = Some(BunInput {
+                filename: file_path.clone(),
+                source: source.clone(),
+                kind: BunInputKind::Module,
+                options: BunCompileOptions {
+                    runes: None,
+                    ..compiler_bun_options.clone()
+                },
+            });
+
             FileResult {
                 file_path: file_path.clone(),
                 output,
+                deferred_warnings: None,
                 transformed,
-                compiler_input: None,
+                compiler_input,
+                parse_errors,
+                compiler_warnings: Vec::new(),
+                ts_pragmas: Vec::new(),
+                project_file: project_pass
+                    .then(|| ProjectFile::script(file_path.as_std_path(), &source)),
             }
         })
         .collect();
@@ -904,7 +1181,27 @@ async fn run_single_check(
     let mut compiler_inputs: Vec<BunInput> = Vec::new();
     let mut compiler_sources: HashMap<Utf8PathBuf, String> = HashMap::new();
     let mut files_with_diagnostics: HashSet<Utf8PathBuf> = HashSet::new();
+    let mut native_parse_errors: HashSet<(Utf8PathBuf, &'static str, u32)> = HashSet::new();
+    let mut native_warnings: HashSet<(Utf8PathBuf, &'static str, u32)> = HashSet::new();
+    let mut markup_ts_pragmas: HashMap<Utf8PathBuf, Vec<MarkupTsPragma>> = HashMap::new();
+    let mut project_files: Vec<ProjectFile> = Vec::new();
+    let mut deferred_warnings: Vec<DeferredWarnings> = Vec::new();
     for result in component_results.into_iter().chain(module_results) {
+        if let Some(deferred) = result.deferred_warnings {
+            deferred_warnings.push(deferred);
+        }
+        if let Some(project_file) = result.project_file {
+            project_files.push(project_file);
+        }
+        for &(code, line) in &result.parse_errors {
+            native_parse_errors.insert((result.file_path.clone(), code, line));
+        }
+        for &(code, line) in &result.compiler_warnings {
+            native_warnings.insert((result.file_path.clone(), code, line));
+        }
+        if !result.ts_pragmas.is_empty() {
+            markup_ts_pragmas.insert(result.file_path.clone(), result.ts_pragmas);
+        }
         if result.output.is_some() {
             files_with_diagnostics.insert(result.file_path);
         }
@@ -925,10 +1222,68 @@ async fn run_single_check(
         }
     }
 
+    if project_pass {
+        let preprocessed_sources: HashMap<&Utf8PathBuf, &PreprocessedSource> = component_files
+            .iter()
+            .filter_map(|(file_path, preprocessed)| Some((file_path, preprocessed.as_ref()?)))
+            .collect();
+        let project_diagnostics = run_project_checks(
+            workspace,
+            project_files,
+            script_files,
+            extra_paths,
+            base_diag_options,
+        );
+        for (file_path, mut diagnostics) in project_diagnostics {
+            diagnostics.retain(|diag| include_svelte_severity(diag.severity, args.threshold));
+            if diagnostics.is_empty() {
+                continue;
+            }
+            let Ok(source) = fs::read_to_string(&file_path) else {
+                continue;
+            };
+            if let Some(preprocessed) = preprocessed_sources.get(&file_path) {
+                for diag in &mut diagnostics {
+                    map_preprocessed_diagnostic(diag, preprocessed);
+                }
+            }
+            for diag in &diagnostics {
+                match diag.severity {
+                    Severity::Error => {
+                        error_count.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Severity::Warning => {
+                        warning_count.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Severity::Hint => {}
+                }
+            }
+            let relative_path = file_path.strip_prefix(workspace).unwrap_or(&file_path);
+            outputs.push(FileOutput {
+                text: if output_json {
+                    None
+                } else {
+                    Some(formatter.format(&diagnostics, relative_path, &source))
+                },
+                json: if output_json {
+                    Formatter::format_json_diagnostics(&diagnostics, relative_path, &source)
+                } else {
+                    Vec::new()
+                },
+            });
+            files_with_diagnostics.insert(file_path);
+        }
+    }
+
     let svelte_time = svelte_start.elapsed();
 
     // Calculate total file count for summary
-    let total_file_count = component_files.len() + module_files.len();
+    let preprocess_failed_files: HashSet<&Utf8PathBuf> = preprocess_diagnostics
+        .iter()
+        .map(|diag| &diag.file)
+        .collect();
+    let total_file_count =
+        component_files.len() + module_files.len() + preprocess_failed_files.len();
 
     let mut json_output = Vec::new();
 
@@ -945,6 +1300,33 @@ async fn run_single_check(
         }
     }
 
+    // Preprocessor failures on custom-extension files, reported like compiler
+    // diagnostics.
+    preprocess_diagnostics.retain(|diag| include_compiler_severity(diag.severity, args.threshold));
+    for diag in &preprocess_diagnostics {
+        files_with_diagnostics.insert(diag.file.clone());
+        match diag.severity {
+            BunDiagnosticSeverity::Error => {
+                error_count.fetch_add(1, Ordering::Relaxed);
+            }
+            BunDiagnosticSeverity::Warning => {
+                warning_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+    if output_json {
+        json_output.extend(format_compiler_diagnostics_json(
+            &preprocess_diagnostics,
+            workspace,
+            &compiler_sources,
+        ));
+    } else {
+        print!(
+            "{}",
+            format_compiler_diagnostics(&preprocess_diagnostics, workspace, args.output)
+        );
+    }
+
     let transformed_count = if args.skip_tsgo {
         0
     } else {
@@ -963,13 +1345,25 @@ async fn run_single_check(
         result: Result<TsgoCheckOutput, OrchestratorError>,
     }
 
+    let ssr_pass = match args.ssr {
+        SsrArg::Auto => TsgoRunner::is_sveltekit_project(workspace),
+        SsrArg::On => true,
+        SsrArg::Off => false,
+    };
+
     let compiler_future = async {
         if compiler_inputs.is_empty() {
             return None;
         }
 
+        if ssr_pass {
+            let server_inputs = server_compiler_inputs(&compiler_inputs);
+            compiler_inputs.extend(server_inputs);
+        }
         let bun_start = Instant::now();
-        let result = run_bun_check(workspace, compiler_inputs).await;
+        let result = compiler
+            .check(workspace, args.js_runtime.into(), compiler_inputs)
+            .await;
         Some(CompilerRun {
             elapsed: bun_start.elapsed(),
             result,
@@ -1013,16 +1407,75 @@ async fn run_single_check(
         })
     };
 
-    let (compiler_run, tsgo_run) = tokio::join!(compiler_future, tsgo_future);
+    let (mut compiler_run, tsgo_run) = tokio::join!(compiler_future, tsgo_future);
 
     let mut compiler_total_time = None;
 
+    // Print the held-back native warnings the project's filter keeps.
+    let compiler_checked = matches!(compiler_run.as_ref().map(|run| &run.result), Some(Ok(_)));
+    let suppressed_warnings = match compiler_run.as_mut().map(|run| &mut run.result) {
+        Some(Ok(diagnostics)) => take_suppressed_warnings(diagnostics),
+        _ => HashSet::new(),
+    };
+    for deferred in deferred_warnings {
+        let line_index = LineIndex::new(&deferred.source);
+        let diagnostics: Vec<_> = deferred
+            .diagnostics
+            .into_iter()
+            .filter(|diag| !(compiler_checked && is_css_parse_error(&diag.code)))
+            .filter(|diag| {
+                let Some(line) = line_index.line_col(diag.span.start).map(|lc| lc.line + 1) else {
+                    return true;
+                };
+                !suppressed_warnings.contains(&(
+                    deferred.file_path.clone(),
+                    diag.code.as_str().to_string(),
+                    line,
+                ))
+            })
+            .collect();
+        if diagnostics.is_empty() {
+            continue;
+        }
+        for diag in &diagnostics {
+            match diag.severity {
+                Severity::Error => {
+                    error_count.fetch_add(1, Ordering::Relaxed);
+                }
+                Severity::Warning => {
+                    warning_count.fetch_add(1, Ordering::Relaxed);
+                }
+                Severity::Hint => {}
+            }
+        }
+        let relative_path = deferred
+            .file_path
+            .strip_prefix(workspace)
+            .unwrap_or(&deferred.file_path);
+        if output_json {
+            json_output.extend(Formatter::format_json_diagnostics(
+                &diagnostics,
+                relative_path,
+                &deferred.source,
+            ));
+        } else {
+            print!(
+                "{}",
+                formatter.format(&diagnostics, relative_path, &deferred.source)
+            );
+        }
+        files_with_diagnostics.insert(deferred.file_path);
+    }
+
     // Print Svelte compiler diagnostics first to preserve output ordering.
     if let Some(run) = compiler_run {
         compiler_total_time = Some(run.elapsed);
         match run.result {
             Ok(mut diagnostics) => {
+                dedup_compiler_diagnostics(&mut diagnostics);
                 apply_compiler_warning_settings(&mut diagnostics, &compiler_warning_settings);
+                drop_native_parse_duplicates(&mut diagnostics, &native_parse_errors);
+                drop_native_warning_duplicates(&mut diagnostics, &native_warnings);
                 diagnostics.retain(|diag| include_compiler_severity(diag.severity, args.threshold));
 
                 // Count and print compiler diagnostics
@@ -1068,6 +1521,7 @@ async fn run_single_check(
         match run.result {
             Ok(output) => {
                 let mut ts_diagnostics = output.diagnostics;
+                apply_markup_ts_pragmas(&mut ts_diagnostics, &markup_ts_pragmas);
                 ts_diagnostics.retain(|diag| include_ts_severity(diag.severity, args.threshold));
 
                 // Count and print TypeScript diagnostics
@@ -1245,6 +1699,63 @@ async fn run_single_check(
     Ok(summary)
 }
 
+/// Returns whether a file is a plain JavaScript or TypeScript module whose
+/// imports the project pass follows. Declaration files import nothing at
+/// runtime.
+fn is_project_script(file_name: &str) -> bool {
+    const EXTENSIONS: [&str; 6] = [".ts", ".js", ".mts", ".mjs", ".cts", ".cjs"];
+    EXTENSIONS.iter().any(|ext| file_name.ends_with(ext))
+        && ![".d.ts", ".d.mts", ".d.cts"]
+            .iter()
+            .any(|ext| file_name.ends_with(ext))
+}
+
+/// Returns the import aliases the project pass resolves: SvelteKit's `$lib`
+/// and the aliases from `svelte.config.js`, in tsconfig `paths` form.
+fn project_aliases(
+    workspace: &Utf8Path,
+    extra_paths: &HashMap<String, Vec<String>>,
+) -> Vec<(String, Utf8PathBuf)> {
+    let mut aliases = vec![("$lib".to_string(), workspace.join("src/lib"))];
+    for (alias, targets) in extra_paths {
+        let alias = alias.strip_suffix("/*").unwrap_or(alias);
+        let Some(target) = targets.first() else {
+            continue;
+        };
+        let target = target.strip_suffix("/*").unwrap_or(target);
+        aliases.retain(|(existing, _)| existing != alias);
+        aliases.push((alias.to_string(), workspace.join(target)));
+    }
+    aliases
+}
+
+/// Runs the project-wide checks over the summaries of the checked files and
+/// the plain scripts of the workspace, grouping diagnostics by file.
+fn run_project_checks(
+    workspace: &Utf8Path,
+    mut files: Vec<ProjectFile>,
+    script_files: &[Utf8PathBuf],
+    extra_paths: &HashMap<String, Vec<String>>,
+    options: &DiagnosticOptions,
+) -> BTreeMap<Utf8PathBuf, Vec<svelte_diagnostics::Diagnostic>> {
+    files.par_extend(script_files.par_iter().filter_map(|path| {
+        let source = fs::read_to_string(path).ok()?;
+        Some(ProjectFile::script(path.as_std_path(), &source))
+    }));
+    let mut project = Project::new(files).with_root(workspace.as_std_path());
+    for (alias, dir) in project_aliases(workspace, extra_paths) {
+        project = project.with_alias(alias, dir.into_std_path_buf());
+    }
+    let mut by_file: BTreeMap<Utf8PathBuf, Vec<svelte_diagnostics::Diagnostic>> = BTreeMap::new();
+    for reported in project.check(options) {
+        let Ok(path) = Utf8PathBuf::from_path_buf(reported.path) else {
+            continue;
+        };
+        by_file.entry(path).or_default().push(reported.diagnostic);
+    }
+    by_file
+}
+
 /// Runs tsgo type-checking on transformed files.
 async fn run_tsgo_check(
     workspace: &Utf8Path,
@@ -1270,23 +1781,100 @@ async fn run_tsgo_check(
         .map_err(|e| OrchestratorError::TsgoError(e.to_string()))
 }
 
-/// Runs Svelte compiler diagnostics using bun.
-async fn run_bun_check(
-    workspace: &Utf8Path,
-    inputs: Vec<BunInput>,
-) -> Result<Vec<BunDiagnostic>, OrchestratorError> {
-    let bun_path = BunRunner::ensure_bun(Some(workspace))
-        .await
-        .map_err(|e| OrchestratorError::BunError(e.to_string()))?;
+/// Lazily started Svelte compiler runner shared by every check in a run.
+struct CompilerBridge {
+    runner: tokio::sync::OnceCell<BunRunner>,
+    /// Extensions from `svelte.config.js#extensions` we don't natively
+    /// support, used to label files no preprocessor handled.
+    custom_extensions: Vec<String>,
+}
 
-    let worker_count = bun_worker_count();
-    let runner = BunRunner::new(bun_path, workspace.to_owned(), worker_count)
-        .map_err(|e| OrchestratorError::BunError(e.to_string()))?;
+impl CompilerBridge {
+    fn new(svelte_config: &SvelteConfig) -> Self {
+        Self {
+            runner: tokio::sync::OnceCell::new(),
+            custom_extensions: svelte_config
+                .unsupported_extensions()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
 
-    runner
-        .check_files(inputs)
-        .await
-        .map_err(|e| OrchestratorError::BunError(e.to_string()))
+    fn custom_extensions(&self) -> Vec<&str> {
+        self.custom_extensions.iter().map(String::as_str).collect()
+    }
+
+    /// Runs Svelte compiler diagnostics using bun or node.
+    async fn check(
+        &self,
+        workspace: &Utf8Path,
+        runtime: JsRuntimePreference,
+        inputs: Vec<BunInput>,
+    ) -> Result<Vec<BunDiagnostic>, OrchestratorError> {
+        self.runner(workspace, runtime)
+            .await?
+            .check_files(inputs)
+            .await
+            .map_err(|e| OrchestratorError::BunError(e.to_string()))
+    }
+
+    /// Runs custom-extension files through the project's preprocessors.
+    async fn preprocess(
+        &self,
+        workspace: &Utf8Path,
+        runtime: JsRuntimePreference,
+        files: Vec<(Utf8PathBuf, String)>,
+    ) -> Result<Vec<BunPreprocessed>, OrchestratorError> {
+        self.runner(workspace, runtime)
+            .await?
+            .preprocess_files(files)
+            .await
+            .map_err(|e| OrchestratorError::BunError(e.to_string()))
+    }
+
+    async fn runner(
+        &self,
+        workspace: &Utf8Path,
+        runtime: JsRuntimePreference,
+    ) -> Result<&BunRunner, OrchestratorError> {
+        self.runner
+            .get_or_try_init(|| async {
+                let runtime = BunRunner::ensure_runtime(Some(workspace), runtime)
+                    .await
+                    .map_err(|e| OrchestratorError::BunError(e.to_string()))?;
+                let runner = BunRunner::new(runtime, workspace.to_owned(), bun_worker_count())
+                    .map_err(|e| OrchestratorError::BunError(e.to_string()))?;
+                Ok::<_, OrchestratorError>(match compiler_file_timeout() {
+                    Some(timeout) => runner.with_file_timeout(timeout),
+                    None => runner,
+                })
+            })
+            .await
+    }
+}
+
+/// An error for a custom-extension file that could not be preprocessed
+/// because the compiler bridge failed.
+fn preprocess_failure_diagnostic(file: Utf8PathBuf, error: &OrchestratorError) -> BunDiagnostic {
+    BunDiagnostic {
+        file,
+        code: "preprocess_error".to_string(),
+        message: format!("File was not checked: preprocessing failed: {error}"),
+        severity: BunDiagnosticSeverity::Error,
+        start: bun_runner::BunPosition { line: 1, column: 1 },
+        end: bun_runner::BunPosition { line: 1, column: 1 },
+        suppressed: false,
+    }
+}
+
+/// Per-file compiler timeout from `SVELTE_CHECK_RS_COMPILER_TIMEOUT` (seconds).
+fn compiler_file_timeout() -> Option<std::time::Duration> {
+    std::env::var("SVELTE_CHECK_RS_COMPILER_TIMEOUT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs > 0.0)
+        .map(std::time::Duration::from_secs_f64)
 }
 
 fn bun_worker_count() -> usize {
@@ -1597,6 +2185,208 @@ fn apply_compiler_warning_settings(
     });
 }
 
+/// Returns whether a component has a `lang="ts"` script.
+fn is_typescript_component(doc: &SvelteDocument) -> bool {
+    [&doc.instance_script, &doc.module_script]
+        .into_iter()
+        .flatten()
+        .any(|script| script.lang == ScriptLang::TypeScript)
+}
+
+/// Applies `--compiler-warnings` to native diagnostics that carry a compiler
+/// warning code (e.g. `css_unused_selector`).
+fn apply_native_warning_settings(
+    diagnostics: &mut Vec<svelte_diagnostics::Diagnostic>,
+    settings: &HashMap<String, CompilerWarningLevel>,
+) {
+    diagnostics.retain_mut(|diag| {
+        if let Some(level) = settings.get(diag.code.as_str()) {
+            match level {
+                CompilerWarningLevel::Ignore => return false,
+                CompilerWarningLevel::Error => diag.severity = Severity::Error,
+            }
+        }
+        true
+    });
+}
+
+/// Removes the warnings the project's `warningFilter` / `onwarn` dropped and
+/// returns their `(file, code, line)` keys.
+fn take_suppressed_warnings(
+    diagnostics: &mut Vec<BunDiagnostic>,
+) -> HashSet<(Utf8PathBuf, String, u32)> {
+    let mut suppressed = HashSet::new();
+    diagnostics.retain(|diag| {
+        if diag.suppressed {
+            suppressed.insert((diag.file.clone(), diag.code.clone(), diag.start.line));
+        }
+        !diag.suppressed
+    });
+    suppressed
+}
+
+/// Returns copies of the component inputs compiled with `generate: "server"`.
+/// Some warnings, such as `node_invalid_placement_ssr`, only come from server
+/// compilation.
+fn server_compiler_inputs(inputs: &[BunInput]) -> Vec<BunInput> {
+    inputs
+        .iter()
+        .filter(|input| input.kind == BunInputKind::Component)
+        .map(|input| {
+            let mut input = input.clone();
+            input.options.generate = Some("server".to_string());
+            input
+        })
+        .collect()
+}
+
+/// Drops compiler diagnostics repeated with the same code and range in the
+/// same file, such as warnings reported by both the client and server passes.
+/// The first occurrence is kept.
+fn dedup_compiler_diagnostics(diagnostics: &mut Vec<BunDiagnostic>) {
+    let mut seen = HashSet::new();
+    diagnostics.retain(|diag| {
+        seen.insert((
+            diag.file.clone(),
+            diag.code.clone(),
+            (diag.start.line, diag.start.column),
+            (diag.end.line, diag.end.column),
+        ))
+    });
+}
+
+/// Drops compiler warnings that a native check already reported on the same
+/// line of the same file.
+fn drop_native_warning_duplicates(
+    diagnostics: &mut Vec<BunDiagnostic>,
+    native_warnings: &HashSet<(Utf8PathBuf, &'static str, u32)>,
+) {
+    if native_warnings.is_empty() {
+        return;
+    }
+    diagnostics.retain(|diag| {
+        !native_warnings.contains(&(diag.file.clone(), diag.code.as_str(), diag.start.line))
+    });
+}
+
+/// A markup `@ts-ignore` / `@ts-expect-error` comment, in the 1-based
+/// positions of mapped tsgo diagnostics.
+#[derive(Debug, Clone)]
+struct MarkupTsPragma {
+    kind: TsPragmaKind,
+    comment_start: DiagnosticPosition,
+    comment_end: DiagnosticPosition,
+    /// The (line, column) range of the node the comment applies to.
+    start: (u32, u32),
+    end: (u32, u32),
+}
+
+impl MarkupTsPragma {
+    fn new(pragma: &TsPragma, line_index: &LineIndex) -> Option<Self> {
+        let position = |offset| {
+            let line_col = line_index.line_col(offset)?;
+            Some(DiagnosticPosition {
+                line: line_col.line + 1,
+                column: line_col.col + 1,
+                offset: u32::from(offset),
+            })
+        };
+        let start = position(pragma.span.start)?;
+        let end = position(pragma.span.end)?;
+        Some(Self {
+            kind: pragma.kind,
+            comment_start: position(pragma.comment.start)?,
+            comment_end: position(pragma.comment.end)?,
+            start: (start.line, start.column),
+            end: (end.line, end.column),
+        })
+    }
+
+    fn covers(&self, position: &DiagnosticPosition) -> bool {
+        let at = (position.line, position.column);
+        self.start <= at && at < self.end
+    }
+}
+
+/// Drops tsgo diagnostics inside markup covered by a `@ts-ignore` or
+/// `@ts-expect-error` comment, and reports `@ts-expect-error` comments that
+/// cover none as TS2578, like TypeScript does for unused directives.
+fn apply_markup_ts_pragmas(
+    diagnostics: &mut Vec<TsgoDiagnostic>,
+    pragmas: &HashMap<Utf8PathBuf, Vec<MarkupTsPragma>>,
+) {
+    if pragmas.is_empty() {
+        return;
+    }
+    let mut used: HashSet<(Utf8PathBuf, usize)> = HashSet::new();
+    diagnostics.retain(|diag| {
+        if diag.position_unknown {
+            return true;
+        }
+        let Some(file_pragmas) = pragmas.get(&diag.file) else {
+            return true;
+        };
+        let covering: Vec<usize> = file_pragmas
+            .iter()
+            .enumerate()
+            .filter(|(_, pragma)| pragma.covers(&diag.start))
+            .map(|(index, _)| index)
+            .collect();
+        for &index in &covering {
+            used.insert((diag.file.clone(), index));
+        }
+        covering.is_empty()
+    });
+
+    let mut unused: Vec<TsgoDiagnostic> = Vec::new();
+    for (file, file_pragmas) in pragmas {
+        for (index, pragma) in file_pragmas.iter().enumerate() {
+            if pragma.kind == TsPragmaKind::ExpectError && !used.contains(&(file.clone(), index)) {
+                unused.push(TsgoDiagnostic {
+                    file: file.clone(),
+                    start: pragma.comment_start.clone(),
+                    end: pragma.comment_end.clone(),
+                    message: "Unused '@ts-expect-error' directive.".to_string(),
+                    code: "TS2578".to_string(),
+                    severity: tsgo_runner::DiagnosticSeverity::Error,
+                    position_unknown: false,
+                });
+            }
+        }
+    }
+    if !unused.is_empty() {
+        diagnostics.extend(unused);
+        diagnostics.sort_by(|a, b| {
+            (&a.file, a.start.line, a.start.column).cmp(&(&b.file, b.start.line, b.start.column))
+        });
+    }
+}
+
+/// Returns whether `code` is a parse error from the forgiving `<style>`
+/// parser. The compiler reports real CSS syntax errors itself.
+fn is_css_parse_error(code: &DiagnosticCode) -> bool {
+    matches!(code, DiagnosticCode::Parse(code) if code.is_css())
+}
+
+/// Drops compiler errors that repeat a parse error already reported by the
+/// native parser: same file, same code and same line.
+fn drop_native_parse_duplicates(
+    diagnostics: &mut Vec<BunDiagnostic>,
+    native_parse_errors: &HashSet<(Utf8PathBuf, &'static str, u32)>,
+) {
+    if native_parse_errors.is_empty() {
+        return;
+    }
+    diagnostics.retain(|diag| {
+        diag.severity != BunDiagnosticSeverity::Error
+            || !native_parse_errors.contains(&(
+                diag.file.clone(),
+                diag.code.as_str(),
+                diag.start.line,
+            ))
+    });
+}
+
 fn duration_ms(duration: std::time::Duration) -> f64 {
     duration.as_secs_f64() * 1000.0
 }
@@ -1720,29 +2510,38 @@ fn read_env_bool(name: &str) -> Option<bool> {
 }
 
 /// Runs in watch mode.
+#[allow(clippy::too_many_arguments)]
 async fn run_watch_mode(
     args: &Args,
     workspace: &Utf8Path,
     initial_files: Vec<Utf8PathBuf>,
+    mut script_files: Vec<Utf8PathBuf>,
+    ignore_set: &globset::GlobSet,
     file_scan_time: Option<std::time::Duration>,
     use_nodenext_imports: bool,
     compiler_bun_options: BunCompileOptions,
+    base_diag_options: &DiagnosticOptions,
     extra_paths: &HashMap<String, Vec<String>>,
+    compiler: &CompilerBridge,
 ) -> Result<CheckSummary, OrchestratorError> {
     use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
     use std::time::Duration;
 
     println!("Starting watch mode...\n");
+    let project_pass = !args.skip_project_checks && args.single_file.is_none();
 
     // Initial check
     let _summary = run_single_check(
         args,
         workspace,
         initial_files.clone(),
+        &script_files,
         file_scan_time,
         use_nodenext_imports,
         compiler_bun_options.clone(),
+        base_diag_options,
         extra_paths,
+        compiler,
     )
     .await?;
 
@@ -1766,15 +2565,33 @@ async fn run_watch_mode(
     println!("Watching for changes... (Ctrl+C to stop)\n");
 
     while let Some(event) = rx.recv().await {
-        // Check if any Svelte files changed (.svelte, .svelte.ts, .svelte.js)
+        // Reading files during a check reports access events; only changes
+        // should trigger a re-check.
+        if event.kind.is_access() {
+            continue;
+        }
+
+        // Check if any Svelte files changed (.svelte, .svelte.ts, .svelte.js),
+        // including checked files with custom extensions (.svx)
         let svelte_changed = event.paths.iter().any(|p| {
             let path_str = p.to_string_lossy();
             path_str.ends_with(".svelte")
                 || path_str.ends_with(".svelte.ts")
                 || path_str.ends_with(".svelte.js")
+                || initial_files.iter().any(|f| f.as_std_path() == p)
         });
+        // Plain scripts feed the project pass's import graph.
+        let scripts_changed = project_pass
+            && event.paths.iter().any(|p| {
+                Utf8Path::from_path(p).is_some_and(|path| {
+                    is_project_script(path.file_name().unwrap_or(""))
+                        && !ignore_set.is_match(to_forward_slash(
+                            path.strip_prefix(workspace).unwrap_or(path),
+                        ))
+                })
+            });
 
-        if svelte_changed {
+        if svelte_changed || scripts_changed {
             if !args.preserve_watch_output {
                 // Clear screen
                 print!("\x1B[2J\x1B[1;1H");
@@ -1782,15 +2599,26 @@ async fn run_watch_mode(
 
             println!("File changed, re-checking...\n");
 
+            // Scripts may have been added or removed since the last run.
+            if project_pass {
+                script_files = walk_workspace(workspace, ignore_set)
+                    .into_iter()
+                    .filter(|p| is_project_script(p.file_name().unwrap_or("")))
+                    .collect();
+            }
+
             // Re-run check
             let _ = run_single_check(
                 args,
                 workspace,
                 initial_files.clone(),
+                &script_files,
                 file_scan_time,
                 use_nodenext_imports,
                 compiler_bun_options.clone(),
+                base_diag_options,
                 extra_paths,
+                compiler,
             )
             .await;
         }
@@ -1801,6 +2629,25 @@ async fn run_watch_mode(
     ))
 }
 
+/// Maps a span in preprocessor output back to the file as written.
+fn map_preprocessed_span(span: Span, preprocessed: &PreprocessedSource) -> Span {
+    let start = preprocessed.original_offset(u32::from(span.start));
+    let end = preprocessed.original_offset(u32::from(span.end)).max(start);
+    Span::new(start, end)
+}
+
+/// Moves a native diagnostic found in preprocessor output onto the file as
+/// written.
+fn map_preprocessed_diagnostic(
+    diag: &mut svelte_diagnostics::Diagnostic,
+    preprocessed: &PreprocessedSource,
+) {
+    diag.span = map_preprocessed_span(diag.span, preprocessed);
+    for suggestion in &mut diag.suggestions {
+        suggestion.span = map_preprocessed_span(suggestion.span, preprocessed);
+    }
+}
+
 /// Converts a 1-indexed line and column to a byte offset in the source.
 fn line_column_to_offset(source: &str, line: usize, column: usize) -> u32 {
     let mut current_line = 1;
@@ -1833,6 +2680,7 @@ fn line_column_to_offset(source: &str, line: usize, column: usize) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bun_runner::BunPosition;
 
     #[test]
     fn test_relative_workspace() {
@@ -1978,6 +2826,216 @@ mod tests {
         let _ = relative_import_path(&rel, &abs);
     }
 
+    #[test]
+    fn test_server_compiler_inputs_only_cover_components() {
+        let input = |filename: &str, kind| BunInput {
+            filename: Utf8PathBuf::from(filename),
+            source: String::new(),
+            kind,
+            options: BunCompileOptions::default(),
+        };
+        let inputs = vec![
+            input("src/App.svelte", BunInputKind::Component),
+            input("src/state.svelte.ts", BunInputKind::Module),
+        ];
+
+        let server = server_compiler_inputs(&inputs);
+        assert_eq!(server.len(), 1);
+        assert_eq!(server[0].filename, "src/App.svelte");
+        assert_eq!(server[0].options.generate.as_deref(), Some("server"));
+        assert_eq!(inputs[0].options.generate, None);
+    }
+
+    #[test]
+    fn test_dedup_compiler_diagnostics_by_code_and_range() {
+        let diagnostic = |code: &str, line, message: &str| BunDiagnostic {
+            file: Utf8PathBuf::from("src/App.svelte"),
+            code: code.to_string(),
+            message: message.to_string(),
+            severity: BunDiagnosticSeverity::Warning,
+            start: BunPosition { line, column: 1 },
+            end: BunPosition { line, column: 8 },
+            suppressed: false,
+        };
+        let mut diagnostics = vec![
+            diagnostic("a11y_missing_attribute", 1, "client"),
+            diagnostic("node_invalid_placement_ssr", 2, "server"),
+            diagnostic("a11y_missing_attribute", 1, "server"),
+            diagnostic("a11y_missing_attribute", 3, "server"),
+        ];
+
+        dedup_compiler_diagnostics(&mut diagnostics);
+        let kept: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.start.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                ("a11y_missing_attribute", 1, "client"),
+                ("node_invalid_placement_ssr", 2, "server"),
+                ("a11y_missing_attribute", 3, "server"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_native_warning_duplicates() {
+        let diagnostic = |code: &str, line| BunDiagnostic {
+            file: Utf8PathBuf::from("/a.svelte"),
+            code: code.to_string(),
+            message: String::new(),
+            severity: BunDiagnosticSeverity::Warning,
+            start: BunPosition { line, column: 3 },
+            end: BunPosition { line, column: 5 },
+            suppressed: false,
+        };
+        let mut diagnostics = vec![
+            diagnostic("css_unused_selector", 4),
+            diagnostic("css_unused_selector", 7),
+            diagnostic("a11y_missing_attribute", 4),
+        ];
+        let native = HashSet::from([(Utf8PathBuf::from("/a.svelte"), "css_unused_selector", 4)]);
+
+        drop_native_warning_duplicates(&mut diagnostics, &native);
+
+        let remaining: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.start.line))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![("css_unused_selector", 7), ("a11y_missing_attribute", 4)]
+        );
+    }
+
+    #[test]
+    fn test_take_suppressed_warnings() {
+        let diagnostic = |code: &str, line, suppressed| BunDiagnostic {
+            file: Utf8PathBuf::from("/a.svelte"),
+            code: code.to_string(),
+            message: String::new(),
+            severity: BunDiagnosticSeverity::Warning,
+            start: BunPosition { line, column: 1 },
+            end: BunPosition { line, column: 1 },
+            suppressed,
+        };
+        let mut diagnostics = vec![
+            diagnostic("a11y_missing_attribute", 2, true),
+            diagnostic("a11y_missing_attribute", 5, false),
+        ];
+
+        let suppressed = take_suppressed_warnings(&mut diagnostics);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].start.line, 5);
+        assert_eq!(
+            suppressed,
+            HashSet::from([(
+                Utf8PathBuf::from("/a.svelte"),
+                "a11y_missing_attribute".to_string(),
+                2
+            )])
+        );
+    }
+
+    #[test]
+    fn test_apply_markup_ts_pragmas() {
+        let position = |line, column| DiagnosticPosition {
+            line,
+            column,
+            offset: 0,
+        };
+        let diagnostic = |line, column| TsgoDiagnostic {
+            file: Utf8PathBuf::from("/a.svelte"),
+            start: position(line, column),
+            end: position(line, column + 1),
+            message: String::new(),
+            code: "TS2322".to_string(),
+            severity: tsgo_runner::DiagnosticSeverity::Error,
+            position_unknown: false,
+        };
+        let pragma = |kind, line| MarkupTsPragma {
+            kind,
+            comment_start: position(line - 1, 1),
+            comment_end: position(line - 1, 20),
+            start: (line, 1),
+            end: (line, 30),
+        };
+        let mut diagnostics = vec![diagnostic(3, 5), diagnostic(6, 2), diagnostic(9, 4)];
+        let pragmas = HashMap::from([(
+            Utf8PathBuf::from("/a.svelte"),
+            vec![
+                pragma(TsPragmaKind::Ignore, 3),
+                pragma(TsPragmaKind::ExpectError, 6),
+                pragma(TsPragmaKind::ExpectError, 8),
+            ],
+        )]);
+
+        apply_markup_ts_pragmas(&mut diagnostics, &pragmas);
+
+        let remaining: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.start.line))
+            .collect();
+        assert_eq!(remaining, vec![("TS2578", 7), ("TS2322", 9)]);
+    }
+
+    #[test]
+    fn test_drop_native_parse_duplicates() {
+        let diagnostic = |file: &str, code: &str, line, severity| BunDiagnostic {
+            file: Utf8PathBuf::from(file),
+            code: code.to_string(),
+            message: String::new(),
+            severity,
+            start: BunPosition { line, column: 1 },
+            end: BunPosition { line, column: 1 },
+            suppressed: false,
+        };
+        let mut diagnostics = vec![
+            diagnostic(
+                "/a.svelte",
+                "block_unclosed",
+                1,
+                BunDiagnosticSeverity::Error,
+            ),
+            diagnostic(
+                "/a.svelte",
+                "block_unclosed",
+                4,
+                BunDiagnosticSeverity::Error,
+            ),
+            diagnostic(
+                "/b.svelte",
+                "block_unclosed",
+                1,
+                BunDiagnosticSeverity::Error,
+            ),
+            diagnostic(
+                "/a.svelte",
+                "state_referenced_locally",
+                1,
+                BunDiagnosticSeverity::Warning,
+            ),
+        ];
+        let native = HashSet::from([(Utf8PathBuf::from("/a.svelte"), "block_unclosed", 1)]);
+
+        drop_native_parse_duplicates(&mut diagnostics, &native);
+
+        let remaining: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.file.as_str(), d.code.as_str(), d.start.line))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                ("/a.svelte", "block_unclosed", 4),
+                ("/b.svelte", "block_unclosed", 1),
+                ("/a.svelte", "state_referenced_locally", 1),
+            ]
+        );
+    }
+
     #[test]
     fn test_line_column_to_offset() {
         let source = "line1\nline2\nline3";
@@ -2054,11 +3112,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_unpreprocessed_warnings_uses_configured_extension() {
+        let files = vec![Utf8PathBuf::from("src/notes.svelte.md")];
+        let lines = format_unpreprocessed_warnings(&files, &[".md", ".svelte.md"]);
+        assert_eq!(
+            lines,
+            vec!["warning: 1 file with unpreprocessed extension (.svelte.md) skipped".to_string()]
+        );
+    }
+
     #[test]
     fn test_format_unsupported_warnings_empty() {
         assert!(format_unsupported_warnings(&[], &[]).is_empty());
     }
 
+    #[test]
+    fn test_is_project_script() {
+        assert!(is_project_script("main.ts"));
+        assert!(is_project_script("hooks.server.js"));
+        assert!(is_project_script("vite.config.mts"));
+        assert!(!is_project_script("app.d.ts"));
+        assert!(!is_project_script("styles.css"));
+    }
+
+    #[test]
+    fn test_project_aliases_default_to_lib() {
+        let workspace = Utf8Path::new("/app");
+        let mut extra_paths = HashMap::new();
+        extra_paths.insert(
+            "$components/*".to_string(),
+            vec!["./src/components/*".to_string()],
+        );
+        let mut aliases = project_aliases(workspace, &extra_paths);
+        aliases.sort();
+        assert_eq!(
+            aliases,
+            [
+                (
+                    "$components".to_string(),
+                    Utf8PathBuf::from("/app/./src/components")
+                ),
+                ("$lib".to_string(), Utf8PathBuf::from("/app/src/lib")),
+            ]
+        );
+
+        extra_paths.insert("$lib/*".to_string(), vec!["src/shared/*".to_string()]);
+        let aliases = project_aliases(workspace, &extra_paths);
+        assert!(aliases.contains(&("$lib".to_string(), Utf8PathBuf::from("/app/src/shared"))));
+        assert_eq!(
+            aliases.iter().filter(|(alias, _)| alias == "$lib").count(),
+            1
+        );
+    }
+
     #[test]
     fn test_tsconfig_pattern_matching() {
         // Test that normalized patterns work with globset
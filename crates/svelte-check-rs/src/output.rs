@@ -139,6 +139,10 @@ impl Formatter {
                 ));
             }
 
+            if let Some(url) = diag.code.docs_url() {
+                output.push_str(&format!("  see {}\n", url));
+            }
+
             output.push('\n');
         }
 
@@ -301,7 +305,7 @@ mod tests {
     fn test_format_human() {
         let formatter = Formatter::new(OutputFormat::Human);
         let diag = Diagnostic::new(
-            DiagnosticCode::A11yStructure,
+            DiagnosticCode::Rule("a11y-structure"),
             "Skipped heading level",
             Span::new(TextSize::from(0), TextSize::from(5)),
         );
@@ -315,7 +319,7 @@ mod tests {
     fn test_format_json() {
         let formatter = Formatter::new(OutputFormat::Json);
         let diag = Diagnostic::new(
-            DiagnosticCode::A11yStructure,
+            DiagnosticCode::Rule("a11y-structure"),
             "Skipped heading level",
             Span::new(TextSize::from(0), TextSize::from(5)),
         );
@@ -325,6 +329,34 @@ mod tests {
         assert!(output.contains("test.svelte"));
     }
 
+    #[test]
+    fn test_format_parse_error_uses_svelte_code() {
+        let source = "{#if x}";
+        let error = svelte_parser::ParseError::new(
+            svelte_parser::ParseErrorKind::UnclosedBlock {
+                block_type: "if".to_string(),
+            },
+            Span::new(TextSize::from(0), TextSize::from(7)),
+        );
+        let diag = Diagnostic::from_parse_error(&error);
+
+        let human = Formatter::new(OutputFormat::Human).format(
+            std::slice::from_ref(&diag),
+            Utf8Path::new("test.svelte"),
+            source,
+        );
+        assert!(human.contains("(block_unclosed)"));
+
+        let verbose = Formatter::new(OutputFormat::HumanVerbose).format(
+            &[diag],
+            Utf8Path::new("test.svelte"),
+            source,
+        );
+        assert!(
+            verbose.contains("see https://svelte.dev/docs/svelte/compiler-errors#block_unclosed")
+        );
+    }
+
     #[test]
     fn test_summary() {
         let summary = CheckSummary {
@@ -2,6 +2,7 @@
 //!
 //! These tests verify that:
 //! - Compiler errors are reported with correct codes and locations
+//! - Rune modules (`.svelte.ts`) go through `compileModule`
 //!
 //! Note: Tests are skipped on Windows due to bun/path handling differences.
 
@@ -190,3 +191,22 @@ fn test_const_tag_invalid_placement_reported() {
         diagnostics
     );
 }
+
+#[test]
+fn test_module_state_invalid_export_reported() {
+    let fixture_path = fixtures_dir().join("compiler-errors");
+    let diagnostics = compiler_diagnostics(&fixture_path);
+
+    let matching = diagnostics.iter().filter(|d| {
+        d.filename == "src/counter.svelte.ts"
+            && d.code == "state_invalid_export"
+            && d.start.line == 1
+            && d.source == "svelte"
+    });
+
+    assert!(
+        matching.count() >= 1,
+        "Expected state_invalid_export diagnostic in src/counter.svelte.ts. Got: {:#?}",
+        diagnostics
+    );
+}
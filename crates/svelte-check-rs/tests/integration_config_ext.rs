@@ -12,6 +12,10 @@
 //! svelte-check-rs parses the vite config STATICALLY with SWC (best-effort
 //! literal-case approximation) and falls through to svelte.config otherwise.
 //!
+//! The last tests cover the installed Svelte version read from
+//! `node_modules/svelte/package.json`: it is shown by `--show-config`, and
+//! projects on Svelte 4 or earlier are rejected.
+//!
 //! Each test builds a self-contained project on disk under `target/test-tmp/`
 //! and runs the CLI with `--show-config --skip-tsgo`. `--show-config` exits
 //! before tsgo or bun are invoked and prints the resolved `kit.alias` (read
@@ -355,3 +359,55 @@ export default defineConfig({
         "expected fall-through to svelte.config.js $lib alias when vite has only bare sveltekit(), got stderr:\n{stderr}"
     );
 }
+
+fn install_svelte(project: &Path, version: &str) {
+    let package = project.join("node_modules/svelte");
+    fs::create_dir_all(&package).expect("create node_modules/svelte");
+    write(
+        &package.join("package.json"),
+        &format!("{{\"name\": \"svelte\", \"version\": \"{version}\"}}\n"),
+    );
+}
+
+/// `--show-config` reports the Svelte version version-dependent checks use.
+#[test]
+fn test_show_config_reports_installed_svelte_version() {
+    let project = make_project("svelte_version_shown");
+    install_svelte(&project, "5.36.2");
+    write_minimal_tsconfig(&project);
+    write(&project.join("src/App.svelte"), "<p>hi</p>\n");
+
+    let RunOutput {
+        exit_code, stderr, ..
+    } = run_show_config(&project);
+
+    assert_eq!(exit_code, 0, "exit code should be 0, stderr: {stderr}");
+    assert!(
+        stderr.contains("svelte: 5.36.2"),
+        "expected the installed Svelte version, got stderr:\n{stderr}"
+    );
+}
+
+/// Svelte 4 projects fail up front instead of producing bogus diagnostics.
+#[test]
+fn test_svelte_4_project_is_rejected() {
+    let project = make_project("svelte_4_rejected");
+    install_svelte(&project, "4.2.19");
+    write_minimal_tsconfig(&project);
+    write(&project.join("src/App.svelte"), "<p>hi</p>\n");
+
+    ensure_binary_built();
+    let output = Command::new(binary_path())
+        .arg("--workspace")
+        .arg(&project)
+        .arg("--skip-tsgo")
+        .output()
+        .expect("Failed to execute svelte-check-rs");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(
+        stderr.contains("Svelte 4.2.19 is not supported"),
+        "expected an unsupported version error, got stderr:\n{stderr}"
+    );
+}
@@ -3,7 +3,9 @@
 //! Each test builds a self-contained project on disk under `target/test-tmp/`,
 //! runs the CLI against it with `--list-files --skip-tsgo`, and asserts on
 //! stdout/stderr. `--list-files` exits before tsgo or bun are invoked, so
-//! these tests don't require `bun install` or `tsgo` to be present.
+//! these tests don't require `bun install` or `tsgo` to be present. The
+//! preprocessing tests run the compiler bridge under node against a stub
+//! `svelte/compiler`, and are skipped when node is not installed.
 
 #![cfg(not(target_os = "windows"))]
 
@@ -209,3 +211,253 @@ fn test_no_warning_when_only_native_extensions_present() {
         stderr
     );
 }
+
+/// A preprocessor that prepends a banner line to `.svx` files, with a source
+/// map pointing every following line one line up.
+const BANNER_PREPROCESSOR_CONFIG: &str = r#"const banner = {
+  markup({ content, filename }) {
+    if (!filename.endsWith('.svx')) return;
+    const lines = content.split('\n');
+    const mappings = ';' + lines.map((_, i) => (i === 0 ? 'AAAA' : 'AACA')).join(';');
+    return { code: '<!-- svx -->\n' + content, map: { version: 3, sources: [filename], names: [], mappings } };
+  }
+};
+export default { extensions: ['.svelte', '.svx', '.md'], preprocess: [banner] };
+"#;
+
+/// With `preprocess` configured, custom-extension files are checked rather
+/// than skipped.
+#[test]
+fn test_preprocessed_extension_is_listed() {
+    let project = make_project("preprocessed_svx_listed");
+    write(
+        &project.join("svelte.config.js"),
+        BANNER_PREPROCESSOR_CONFIG,
+    );
+    write_minimal_tsconfig(&project);
+    write(&project.join("src/App.svelte"), "<p>hi</p>\n");
+    write(&project.join("src/post.svx"), "# A post\n");
+
+    let RunOutput {
+        exit_code,
+        stdout,
+        stderr,
+    } = run_list_files(&project);
+
+    assert_eq!(exit_code, 0, "exit code should be 0, stderr: {}", stderr);
+    assert!(
+        stdout.contains("src/post.svx"),
+        "expected .svx file to be listed, got stdout:\n{}",
+        stdout
+    );
+    assert!(
+        !stderr.contains("unregistered extension"),
+        "no skip warning expected with preprocessors configured, got stderr:\n{}",
+        stderr
+    );
+}
+
+/// A preprocessor like [`BANNER_PREPROCESSOR_CONFIG`] that also turns the
+/// heading into HTML, and returns `map: ''` like some mdsvex versions.
+const NO_MAP_PREPROCESSOR_CONFIG: &str = r#"const banner = {
+  markup({ content, filename }) {
+    if (!filename.endsWith('.svx')) return;
+    const code = content.replace(/^# (.*)$/m, '<h1>$1</h1>');
+    return { code: '<!-- svx -->\n' + code, map: '' };
+  }
+};
+export default { extensions: ['.svelte', '.svx', '.md'], preprocess: [banner] };
+"#;
+
+/// `NO_MAP_PREPROCESSOR_CONFIG`, but the config and its preprocessor log to
+/// stdout.
+const LOGGING_PREPROCESSOR_CONFIG: &str = r#"console.log('loading config');
+const banner = {
+  markup({ content, filename }) {
+    console.info('preprocessing', filename);
+    process.stdout.write('raw write\n');
+    if (!filename.endsWith('.svx')) return;
+    const code = content.replace(/^# (.*)$/m, '<h1>$1</h1>');
+    return { code: '<!-- svx -->\n' + code, map: '' };
+  }
+};
+export default { extensions: ['.svelte', '.svx', '.md'], preprocess: [banner] };
+"#;
+
+/// A stub `svelte/compiler` that runs `markup` preprocessors and warns with
+/// `stub_img` on the line of the first `<img`.
+const PREPROCESSING_STUB_COMPILER: &str = r#"export function compile(code) {
+  const line = code.split('\n').findIndex((l) => l.includes('<img')) + 1;
+  const warnings = line ? [{ code: 'stub_img', message: 'img', start: { line, column: 0 }, end: { line, column: 4 } }] : [];
+  return { warnings };
+}
+export function compileModule() { return { warnings: [] }; }
+export async function preprocess(source, preprocessors, { filename }) {
+  let code = source;
+  let map;
+  for (const p of [].concat(preprocessors)) {
+    const result = p.markup && (await p.markup({ content: code, filename }));
+    if (result) ({ code, map } = result);
+  }
+  return { code, map, dependencies: [] };
+}
+"#;
+
+/// Creates a project with `svelte_config`, the stub compiler (unless
+/// `compiler` is `None`), a `.svx` post with an `<img>` on line 5 and an
+/// unhandled `.md` file, then checks it under node. Returns `None` when node
+/// is not installed.
+fn run_preprocessed_project(
+    name: &str,
+    svelte_config: &str,
+    compiler: Option<&str>,
+) -> Option<RunOutput> {
+    if Command::new("node").arg("--version").output().is_err() {
+        eprintln!("Skipping test: node not found");
+        return None;
+    }
+    let project = make_project(name);
+    write(&project.join("package.json"), "{\"type\":\"module\"}\n");
+    write(&project.join("svelte.config.js"), svelte_config);
+    write_minimal_tsconfig(&project);
+    if let Some(compiler) = compiler {
+        let svelte = project.join("node_modules/svelte");
+        fs::create_dir_all(&svelte).expect("create stub svelte");
+        write(
+            &svelte.join("package.json"),
+            r#"{"name":"svelte","version":"5.0.0","type":"module","exports":{"./compiler":"./compiler.js"}}"#,
+        );
+        write(&svelte.join("compiler.js"), compiler);
+    }
+    write(
+        &project.join("src/post.svx"),
+        "# A post\n\nSome text.\n\n<img src=\"cat.png\">\n",
+    );
+    write(&project.join("src/notes.md"), "# Notes\n");
+
+    ensure_binary_built();
+    let output = Command::new(binary_path())
+        .arg("--workspace")
+        .arg(&project)
+        .args(["--skip-tsgo", "--js-runtime", "node", "--output", "machine"])
+        .output()
+        .expect("Failed to execute svelte-check-rs");
+    Some(RunOutput {
+        exit_code: output.status.code().unwrap_or(-1),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    })
+}
+
+/// Native and compiler diagnostics on preprocessed `.svx` output are reported
+/// at their position in the `.svx` file, and files no preprocessor handled
+/// are skipped with a warning.
+#[test]
+fn test_preprocessed_extension_diagnostics_map_back() {
+    let Some(RunOutput { stdout, stderr, .. }) = run_preprocessed_project(
+        "preprocessed_svx_checked",
+        BANNER_PREPROCESSOR_CONFIG,
+        Some(PREPROCESSING_STUB_COMPILER),
+    ) else {
+        return;
+    };
+
+    assert!(
+        stdout.contains("src/post.svx:5:1") && stdout.contains("a11y_missing_attribute"),
+        "expected the missing alt warning on line 5 of post.svx, got stdout:\n{}\nstderr:\n{}",
+        stdout,
+        stderr
+    );
+    assert!(
+        stdout.contains("src/post.svx:5:1:5:5 img (stub_img)"),
+        "expected the compiler warning on line 5 of post.svx, got stdout:\n{}",
+        stdout
+    );
+    assert!(
+        stderr.contains("warning: 1 file with unpreprocessed extension (.md) skipped"),
+        "expected a skip warning for notes.md, got stderr:\n{}",
+        stderr
+    );
+}
+
+/// Preprocessors that return no source map still get positions in the
+/// original file, for lines they passed through unchanged.
+#[test]
+fn test_preprocessed_extension_without_source_map_maps_back() {
+    let Some(RunOutput { stdout, stderr, .. }) = run_preprocessed_project(
+        "preprocessed_svx_no_map",
+        NO_MAP_PREPROCESSOR_CONFIG,
+        Some(PREPROCESSING_STUB_COMPILER),
+    ) else {
+        return;
+    };
+
+    assert!(
+        stdout.contains("src/post.svx:5:1") && stdout.contains("a11y_missing_attribute"),
+        "expected the missing alt warning on line 5 of post.svx, got stdout:\n{}\nstderr:\n{}",
+        stdout,
+        stderr
+    );
+    assert!(
+        stdout.contains("src/post.svx:5:1:5:5 img (stub_img)"),
+        "expected the compiler warning on line 5 of post.svx, got stdout:\n{}\nstderr:\n{}",
+        stdout,
+        stderr
+    );
+}
+
+/// Output the config and preprocessors write to stdout does not corrupt the
+/// compiler bridge's protocol.
+#[test]
+fn test_config_logging_to_stdout_keeps_compiler_diagnostics() {
+    let Some(RunOutput { stdout, stderr, .. }) = run_preprocessed_project(
+        "preprocessed_svx_logging_config",
+        LOGGING_PREPROCESSOR_CONFIG,
+        Some(PREPROCESSING_STUB_COMPILER),
+    ) else {
+        return;
+    };
+
+    assert!(
+        stdout.contains("src/post.svx:5:1:5:5 img (stub_img)"),
+        "expected the compiler warning on line 5 of post.svx, got stdout:\n{}\nstderr:\n{}",
+        stdout,
+        stderr
+    );
+    assert!(
+        !stdout.contains("loading config") && !stdout.contains("raw write"),
+        "expected config output to stay off stdout, got stdout:\n{}",
+        stdout
+    );
+}
+
+/// When the compiler bridge can't start, custom-extension files fail the
+/// run instead of silently going unchecked.
+#[test]
+fn test_preprocessing_failure_fails_custom_extension_files() {
+    let Some(RunOutput {
+        exit_code,
+        stdout,
+        stderr,
+    }) = run_preprocessed_project(
+        "preprocessed_svx_no_bridge",
+        BANNER_PREPROCESSOR_CONFIG,
+        None,
+    )
+    else {
+        return;
+    };
+
+    assert_eq!(
+        exit_code, 1,
+        "expected a failing exit code, stderr:\n{}",
+        stderr
+    );
+    assert!(
+        stdout.contains("ERROR src/post.svx:1:1")
+            && stdout.contains("File was not checked: preprocessing failed"),
+        "expected a preprocessing error for post.svx, got stdout:\n{}\nstderr:\n{}",
+        stdout,
+        stderr
+    );
+}
@@ -0,0 +1,161 @@
+//! Integration tests for the server-side (`generate: "server"`) compile pass.
+//!
+//! Each test builds a project under `target/test-tmp/` with a stub
+//! `svelte/compiler` that reports one warning in both passes and one only when
+//! compiling for the server, then runs the CLI with `--js-runtime node` and
+//! `--skip-tsgo`. The stub keeps the tests independent of bun downloads and
+//! real Svelte releases.
+
+#![cfg(not(target_os = "windows"))]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn binary_path() -> PathBuf {
+    if let Ok(path) = std::env::var("CARGO_BIN_EXE_svelte-check-rs") {
+        return PathBuf::from(path);
+    }
+    if let Some(path) = option_env!("CARGO_BIN_EXE_svelte-check-rs") {
+        return PathBuf::from(path);
+    }
+    workspace_root()
+        .join("target")
+        .join("debug")
+        .join("svelte-check-rs")
+}
+
+static BIN_READY: OnceLock<()> = OnceLock::new();
+
+fn ensure_binary_built() {
+    BIN_READY.get_or_init(|| {
+        let _ = Command::new("cargo")
+            .args(["build", "-p", "svelte-check-rs"])
+            .output();
+    });
+}
+
+fn write(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dir");
+    }
+    fs::write(path, contents).unwrap_or_else(|e| panic!("write {}: {}", path.display(), e));
+}
+
+const STUB_COMPILER: &str = r#"export function compile(source, options) {
+  const warnings = [
+    { code: 'shared_warning', message: 'both passes', start: { line: 1, column: 0 }, end: { line: 1, column: 3 } }
+  ];
+  if (options.generate === 'server') {
+    warnings.push({ code: 'node_invalid_placement_ssr', message: 'server only', start: { line: 2, column: 0 }, end: { line: 2, column: 3 } });
+  }
+  return { warnings };
+}
+export function compileModule() { return { warnings: [] }; }
+"#;
+
+/// Creates a project using the stub compiler, optionally with
+/// `@sveltejs/kit` installed.
+fn make_project(name: &str, sveltekit: bool) -> PathBuf {
+    let dir = workspace_root()
+        .join("target")
+        .join("test-tmp")
+        .join("integration_ssr")
+        .join(name);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).expect("clear previous test dir");
+    }
+    write(&dir.join("package.json"), "{\"type\":\"module\"}\n");
+    write(
+        &dir.join("node_modules/svelte/package.json"),
+        r#"{"name":"svelte","version":"5.36.0","type":"module","exports":{"./compiler":"./compiler.js"}}"#,
+    );
+    write(&dir.join("node_modules/svelte/compiler.js"), STUB_COMPILER);
+    if sveltekit {
+        write(
+            &dir.join("node_modules/@sveltejs/kit/package.json"),
+            r#"{"name":"@sveltejs/kit","version":"2.0.0"}"#,
+        );
+    }
+    write(&dir.join("src/App.svelte"), "<p>\n<div></div>\n</p>\n");
+    dir
+}
+
+fn run_machine(project: &Path, extra: &[&str]) -> String {
+    ensure_binary_built();
+    let output = Command::new(binary_path())
+        .arg("--workspace")
+        .arg(project)
+        .args(["--skip-tsgo", "--js-runtime", "node", "--output", "machine"])
+        .args(extra)
+        .output()
+        .expect("Failed to execute svelte-check-rs");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn node_available() -> bool {
+    if Command::new("node").arg("--version").output().is_err() {
+        eprintln!("Skipping test: node not found");
+        return false;
+    }
+    true
+}
+
+/// SvelteKit projects get the server pass by default; warnings both passes
+/// report are printed once.
+#[test]
+fn test_sveltekit_project_reports_server_only_warnings() {
+    if !node_available() {
+        return;
+    }
+    let project = make_project("sveltekit_auto", true);
+    let stdout = run_machine(&project, &[]);
+
+    assert_eq!(
+        stdout.matches("node_invalid_placement_ssr").count(),
+        1,
+        "expected the server-only warning once, got stdout:\n{stdout}"
+    );
+    assert_eq!(
+        stdout.matches("shared_warning").count(),
+        1,
+        "expected the shared warning once, got stdout:\n{stdout}"
+    );
+}
+
+/// Plain Svelte projects only compile for the client unless `--ssr on`.
+#[test]
+fn test_server_pass_follows_ssr_flag() {
+    if !node_available() {
+        return;
+    }
+    let project = make_project("plain_svelte", false);
+    let stdout = run_machine(&project, &[]);
+    assert!(
+        !stdout.contains("node_invalid_placement_ssr"),
+        "expected no server pass without SvelteKit, got stdout:\n{stdout}"
+    );
+
+    let stdout = run_machine(&project, &["--ssr", "on"]);
+    assert!(
+        stdout.contains("node_invalid_placement_ssr"),
+        "expected the server pass with --ssr on, got stdout:\n{stdout}"
+    );
+
+    let kit_project = make_project("sveltekit_off", true);
+    let stdout = run_machine(&kit_project, &["--ssr", "off"]);
+    assert!(
+        !stdout.contains("node_invalid_placement_ssr"),
+        "expected no server pass with --ssr off, got stdout:\n{stdout}"
+    );
+}
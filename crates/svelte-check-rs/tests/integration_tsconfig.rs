@@ -1535,7 +1535,9 @@ fn test_use_directive_no_parse_errors() {
     let parse_errors: Vec<_> = diagnostics
         .iter()
         .filter(|d| {
-            d.filename == "src/routes/use-directives/+page.svelte" && d.code == "parse-error"
+            d.filename == "src/routes/use-directives/+page.svelte"
+                && d.source == "svelte"
+                && d.diagnostic_type == "Error"
         })
         .collect();
 
@@ -1650,7 +1652,8 @@ fn test_issue_7_use_directive_member_access() {
     // The key assertion: use:formSelect.enhance should NOT cause a parse error
     let parse_error = diagnostics.iter().find(|d| {
         d.filename == "src/routes/use-directives/+page.svelte"
-            && d.code == "parse-error"
+            && d.source == "svelte"
+            && d.diagnostic_type == "Error"
             && d.message.contains("expected '>'")
     });
 
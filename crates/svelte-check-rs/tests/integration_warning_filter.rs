@@ -0,0 +1,160 @@
+//! Integration tests for svelte.config `warningFilter` / `onwarn` handling of
+//! warnings that native checks also report, and for native `<style>` parse
+//! errors, which defer to the compiler.
+//!
+//! Each test builds a project under `target/test-tmp/` with a stub
+//! `svelte/compiler` that reports the same `a11y_missing_attribute` warning as
+//! the native a11y check, then runs the CLI with `--js-runtime node` and
+//! `--skip-tsgo`.
+
+#![cfg(not(target_os = "windows"))]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn binary_path() -> PathBuf {
+    if let Ok(path) = std::env::var("CARGO_BIN_EXE_svelte-check-rs") {
+        return PathBuf::from(path);
+    }
+    if let Some(path) = option_env!("CARGO_BIN_EXE_svelte-check-rs") {
+        return PathBuf::from(path);
+    }
+    workspace_root()
+        .join("target")
+        .join("debug")
+        .join("svelte-check-rs")
+}
+
+static BIN_READY: OnceLock<()> = OnceLock::new();
+
+fn ensure_binary_built() {
+    BIN_READY.get_or_init(|| {
+        let _ = Command::new("cargo")
+            .args(["build", "-p", "svelte-check-rs"])
+            .output();
+    });
+}
+
+fn write(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dir");
+    }
+    fs::write(path, contents).unwrap_or_else(|e| panic!("write {}: {}", path.display(), e));
+}
+
+const STUB_COMPILER: &str = r#"export function compile() {
+  return {
+    warnings: [
+      { code: 'a11y_missing_attribute', message: '`<img>` element should have an alt attribute', start: { line: 1, column: 0 }, end: { line: 1, column: 20 } }
+    ]
+  };
+}
+export function compileModule() { return { warnings: [] }; }
+"#;
+
+/// Creates a project using the stub compiler and the given svelte.config.js.
+fn make_project(name: &str, svelte_config: Option<&str>) -> PathBuf {
+    let dir = workspace_root()
+        .join("target")
+        .join("test-tmp")
+        .join("integration_warning_filter")
+        .join(name);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).expect("clear previous test dir");
+    }
+    write(&dir.join("package.json"), "{\"type\":\"module\"}\n");
+    write(
+        &dir.join("node_modules/svelte/package.json"),
+        r#"{"name":"svelte","version":"5.36.0","type":"module","exports":{"./compiler":"./compiler.js"}}"#,
+    );
+    write(&dir.join("node_modules/svelte/compiler.js"), STUB_COMPILER);
+    if let Some(config) = svelte_config {
+        write(&dir.join("svelte.config.js"), config);
+    }
+    write(&dir.join("src/App.svelte"), "<img src=\"cat.png\">\n");
+    dir
+}
+
+fn run_machine(project: &Path) -> String {
+    ensure_binary_built();
+    let output = Command::new(binary_path())
+        .arg("--workspace")
+        .arg(project)
+        .args(["--skip-tsgo", "--js-runtime", "node", "--output", "machine"])
+        .output()
+        .expect("Failed to execute svelte-check-rs");
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn node_available() -> bool {
+    if Command::new("node").arg("--version").output().is_err() {
+        eprintln!("Skipping test: node not found");
+        return false;
+    }
+    true
+}
+
+/// A warning reported both natively and by the compiler is printed once.
+#[test]
+fn test_native_and_compiler_warning_reported_once() {
+    if !node_available() {
+        return;
+    }
+    let project = make_project("no_filter", None);
+    let stdout = run_machine(&project);
+    assert_eq!(
+        stdout.matches("a11y_missing_attribute").count(),
+        1,
+        "expected the a11y warning once, got stdout:\n{stdout}"
+    );
+}
+
+/// A filter the config parser can't see, here spread in from a shared
+/// object, still drops the native copy of the warning.
+#[test]
+fn test_warning_filter_behind_spread_drops_native_warning() {
+    if !node_available() {
+        return;
+    }
+    let project = make_project(
+        "spread_filter",
+        Some(
+            "const sharedConfig = {\n  compilerOptions: {\n    warningFilter: (warning) => !warning.code.startsWith('a11y')\n  }\n};\n\nexport default { ...sharedConfig };\n",
+        ),
+    );
+    let stdout = run_machine(&project);
+    assert!(
+        !stdout.contains("a11y_missing_attribute"),
+        "expected the filtered warning to be dropped, got stdout:\n{stdout}"
+    );
+}
+
+/// The compiler decides CSS syntax, so a native `<style>` parse error is not
+/// reported once the compiler has checked the file.
+#[test]
+fn test_native_css_parse_error_defers_to_compiler() {
+    if !node_available() {
+        return;
+    }
+    let project = make_project("css_parse_error", None);
+    write(
+        &project.join("src/App.svelte"),
+        "<p>hi</p>\n<style>\n  p { color: ; }\n</style>\n",
+    );
+    let stdout = run_machine(&project);
+    assert!(
+        !stdout.contains("css_empty_declaration"),
+        "expected the native CSS parse error to be dropped, got stdout:\n{stdout}"
+    );
+}
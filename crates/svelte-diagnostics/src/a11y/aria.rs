@@ -0,0 +1,724 @@
+//! WAI-ARIA attribute and role tables, following the `aria-query` data the
+//! Svelte compiler checks against.
+
+/// The value type of an ARIA attribute.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum AriaType {
+    String,
+    Id,
+    IdList,
+    Integer,
+    Number,
+    Boolean,
+    Tristate,
+    Token(&'static [&'static str]),
+    TokenList(&'static [&'static str]),
+}
+
+/// ARIA attributes without the `aria-` prefix. Sorted for binary search.
+pub(super) const ATTRIBUTES: &[(&str, AriaType)] = &[
+    ("activedescendant", AriaType::Id),
+    ("atomic", AriaType::Boolean),
+    (
+        "autocomplete",
+        AriaType::Token(&["inline", "list", "both", "none"]),
+    ),
+    ("braillelabel", AriaType::String),
+    ("brailleroledescription", AriaType::String),
+    ("busy", AriaType::Boolean),
+    ("checked", AriaType::Tristate),
+    ("colcount", AriaType::Integer),
+    ("colindex", AriaType::Integer),
+    ("colindextext", AriaType::String),
+    ("colspan", AriaType::Integer),
+    ("controls", AriaType::IdList),
+    (
+        "current",
+        AriaType::Token(&["page", "step", "location", "date", "time", "true", "false"]),
+    ),
+    ("describedby", AriaType::IdList),
+    ("description", AriaType::String),
+    ("details", AriaType::Id),
+    ("disabled", AriaType::Boolean),
+    (
+        "dropeffect",
+        AriaType::TokenList(&["copy", "execute", "link", "move", "none", "popup"]),
+    ),
+    ("errormessage", AriaType::Id),
+    ("expanded", AriaType::Boolean),
+    ("flowto", AriaType::IdList),
+    ("grabbed", AriaType::Boolean),
+    (
+        "haspopup",
+        AriaType::Token(&["false", "true", "menu", "listbox", "tree", "grid", "dialog"]),
+    ),
+    ("hidden", AriaType::Boolean),
+    (
+        "invalid",
+        AriaType::Token(&["grammar", "false", "spelling", "true"]),
+    ),
+    ("keyshortcuts", AriaType::String),
+    ("label", AriaType::String),
+    ("labelledby", AriaType::IdList),
+    ("level", AriaType::Integer),
+    ("live", AriaType::Token(&["assertive", "off", "polite"])),
+    ("modal", AriaType::Boolean),
+    ("multiline", AriaType::Boolean),
+    ("multiselectable", AriaType::Boolean),
+    (
+        "orientation",
+        AriaType::Token(&["vertical", "undefined", "horizontal"]),
+    ),
+    ("owns", AriaType::IdList),
+    ("placeholder", AriaType::String),
+    ("posinset", AriaType::Integer),
+    ("pressed", AriaType::Tristate),
+    ("readonly", AriaType::Boolean),
+    (
+        "relevant",
+        AriaType::TokenList(&["additions", "all", "removals", "text"]),
+    ),
+    ("required", AriaType::Boolean),
+    ("roledescription", AriaType::String),
+    ("rowcount", AriaType::Integer),
+    ("rowindex", AriaType::Integer),
+    ("rowindextext", AriaType::String),
+    ("rowspan", AriaType::Integer),
+    ("selected", AriaType::Boolean),
+    ("setsize", AriaType::Integer),
+    (
+        "sort",
+        AriaType::Token(&["ascending", "descending", "none", "other"]),
+    ),
+    ("valuemax", AriaType::Number),
+    ("valuemin", AriaType::Number),
+    ("valuenow", AriaType::Number),
+    ("valuetext", AriaType::String),
+];
+
+/// Returns the type of an ARIA attribute, given without the `aria-` prefix.
+pub(super) fn attribute_type(name: &str) -> Option<AriaType> {
+    ATTRIBUTES
+        .binary_search_by(|(attribute, _)| attribute.cmp(&name))
+        .ok()
+        .map(|index| ATTRIBUTES[index].1)
+}
+
+/// How a role is classified for the interaction checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RoleKind {
+    /// An abstract role, which may not be used in content.
+    Abstract,
+    /// A widget or window role, or one treated as such (`toolbar`,
+    /// `tabpanel`, `cell`).
+    Interactive,
+    /// A document structure role, plus `progressbar` whose value is read-only.
+    NonInteractive,
+    /// `generic`, which has no semantics.
+    Generic,
+}
+
+/// A WAI-ARIA role.
+#[derive(Debug)]
+pub(super) struct Role {
+    pub name: &'static str,
+    pub kind: RoleKind,
+    /// Attributes the role requires.
+    pub required: &'static [&'static str],
+    /// Attributes the role supports besides the global ones.
+    pub props: &'static [&'static str],
+}
+
+impl Role {
+    /// Returns whether the role supports an ARIA attribute.
+    pub fn supports(&self, attribute: &str) -> bool {
+        GLOBAL_PROPS.contains(&attribute)
+            || self.props.contains(&attribute)
+            || self.required.contains(&attribute)
+    }
+}
+
+/// Attributes every role supports.
+const GLOBAL_PROPS: &[&str] = &[
+    "aria-atomic",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-dropeffect",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-hidden",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-live",
+    "aria-owns",
+    "aria-relevant",
+    "aria-roledescription",
+];
+
+const CELL_PROPS: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+];
+
+const GRIDCELL_PROPS: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-haspopup",
+    "aria-invalid",
+    "aria-readonly",
+    "aria-required",
+    "aria-selected",
+];
+
+const HEADER_CELL_PROPS: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-haspopup",
+    "aria-invalid",
+    "aria-readonly",
+    "aria-required",
+    "aria-selected",
+    "aria-sort",
+];
+
+const CHECKBOX_PROPS: &[&str] = &[
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-invalid",
+    "aria-readonly",
+    "aria-required",
+];
+
+const COMPOSITE_PROPS: &[&str] = &["aria-activedescendant", "aria-disabled", "aria-orientation"];
+
+const GRID_PROPS: &[&str] = &[
+    "aria-activedescendant",
+    "aria-colcount",
+    "aria-disabled",
+    "aria-multiselectable",
+    "aria-readonly",
+    "aria-rowcount",
+];
+
+const LINK_PROPS: &[&str] = &["aria-disabled", "aria-expanded", "aria-haspopup"];
+
+const LISTITEM_PROPS: &[&str] = &["aria-level", "aria-posinset", "aria-setsize"];
+
+const MENUITEM_PROPS: &[&str] = &[
+    "aria-disabled",
+    "aria-expanded",
+    "aria-haspopup",
+    "aria-posinset",
+    "aria-setsize",
+];
+
+const RANGE_PROPS: &[&str] = &[
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+const SEPARATOR_PROPS: &[&str] = &[
+    "aria-disabled",
+    "aria-orientation",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+const TEXTBOX_PROPS: &[&str] = &[
+    "aria-activedescendant",
+    "aria-autocomplete",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-haspopup",
+    "aria-invalid",
+    "aria-multiline",
+    "aria-placeholder",
+    "aria-readonly",
+    "aria-required",
+];
+
+const TREE_PROPS: &[&str] = &[
+    "aria-activedescendant",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-invalid",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-required",
+];
+
+const TREEGRID_PROPS: &[&str] = &[
+    "aria-activedescendant",
+    "aria-colcount",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-invalid",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-readonly",
+    "aria-required",
+    "aria-rowcount",
+];
+
+macro_rules! role {
+    ($name:literal, $kind:ident) => {
+        role!($name, $kind, &[], &[])
+    };
+    ($name:literal, $kind:ident, $required:expr, $props:expr) => {
+        Role {
+            name: $name,
+            kind: RoleKind::$kind,
+            required: $required,
+            props: $props,
+        }
+    };
+}
+
+/// All roles known to `aria-query`, including the DPUB and graphics modules.
+/// Sorted for binary search.
+pub(super) const ROLES: &[Role] = &[
+    role!("alert", NonInteractive),
+    role!("alertdialog", Interactive, &[], &["aria-modal"]),
+    role!(
+        "application",
+        NonInteractive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-expanded",
+            "aria-haspopup",
+            "aria-invalid",
+        ]
+    ),
+    role!(
+        "article",
+        NonInteractive,
+        &[],
+        &["aria-posinset", "aria-setsize"]
+    ),
+    role!("banner", NonInteractive),
+    role!("blockquote", NonInteractive),
+    role!(
+        "button",
+        Interactive,
+        &[],
+        &[
+            "aria-disabled",
+            "aria-expanded",
+            "aria-haspopup",
+            "aria-pressed",
+        ]
+    ),
+    role!("caption", NonInteractive),
+    role!("cell", Interactive, &[], CELL_PROPS),
+    role!("checkbox", Interactive, &["aria-checked"], CHECKBOX_PROPS),
+    role!("code", NonInteractive),
+    role!("columnheader", Interactive, &[], HEADER_CELL_PROPS),
+    role!(
+        "combobox",
+        Interactive,
+        &["aria-controls", "aria-expanded"],
+        &[
+            "aria-activedescendant",
+            "aria-autocomplete",
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-haspopup",
+            "aria-invalid",
+            "aria-readonly",
+            "aria-required",
+        ]
+    ),
+    role!("command", Abstract),
+    role!("complementary", NonInteractive),
+    role!("composite", Abstract),
+    role!("contentinfo", NonInteractive),
+    role!("definition", NonInteractive),
+    role!("deletion", NonInteractive),
+    role!("dialog", Interactive, &[], &["aria-modal"]),
+    role!("directory", NonInteractive),
+    role!("doc-abstract", NonInteractive),
+    role!("doc-acknowledgments", NonInteractive),
+    role!("doc-afterword", NonInteractive),
+    role!("doc-appendix", NonInteractive),
+    role!("doc-backlink", Interactive, &[], LINK_PROPS),
+    role!("doc-biblioentry", NonInteractive, &[], LISTITEM_PROPS),
+    role!("doc-bibliography", NonInteractive),
+    role!("doc-biblioref", Interactive, &[], LINK_PROPS),
+    role!("doc-chapter", NonInteractive),
+    role!("doc-colophon", NonInteractive),
+    role!("doc-conclusion", NonInteractive),
+    role!("doc-cover", NonInteractive),
+    role!("doc-credit", NonInteractive),
+    role!("doc-credits", NonInteractive),
+    role!("doc-dedication", NonInteractive),
+    role!("doc-endnote", NonInteractive, &[], LISTITEM_PROPS),
+    role!("doc-endnotes", NonInteractive),
+    role!("doc-epigraph", NonInteractive),
+    role!("doc-epilogue", NonInteractive),
+    role!("doc-errata", NonInteractive),
+    role!("doc-example", NonInteractive),
+    role!("doc-footnote", NonInteractive),
+    role!("doc-foreword", NonInteractive),
+    role!("doc-glossary", NonInteractive),
+    role!("doc-glossref", Interactive, &[], LINK_PROPS),
+    role!("doc-index", NonInteractive),
+    role!("doc-introduction", NonInteractive),
+    role!("doc-noteref", Interactive, &[], LINK_PROPS),
+    role!("doc-notice", NonInteractive),
+    role!("doc-pagebreak", NonInteractive, &[], SEPARATOR_PROPS),
+    role!("doc-pagefooter", NonInteractive),
+    role!("doc-pageheader", NonInteractive),
+    role!("doc-pagelist", NonInteractive),
+    role!("doc-part", NonInteractive),
+    role!("doc-preface", NonInteractive),
+    role!("doc-prologue", NonInteractive),
+    role!("doc-pullquote", NonInteractive),
+    role!("doc-qna", NonInteractive),
+    role!("doc-subtitle", NonInteractive),
+    role!("doc-tip", NonInteractive),
+    role!("doc-toc", NonInteractive),
+    role!("document", NonInteractive),
+    role!("emphasis", NonInteractive),
+    role!("feed", NonInteractive),
+    role!("figure", NonInteractive),
+    role!("form", NonInteractive),
+    role!("generic", Generic),
+    role!("graphics-document", NonInteractive),
+    role!("graphics-object", NonInteractive),
+    role!("graphics-symbol", NonInteractive),
+    role!("grid", Interactive, &[], GRID_PROPS),
+    role!("gridcell", Interactive, &[], GRIDCELL_PROPS),
+    role!(
+        "group",
+        NonInteractive,
+        &[],
+        &["aria-activedescendant", "aria-disabled"]
+    ),
+    role!("heading", NonInteractive, &["aria-level"], &[]),
+    role!("img", NonInteractive),
+    role!("input", Abstract),
+    role!("insertion", NonInteractive),
+    role!("landmark", Abstract),
+    role!("link", Interactive, &[], LINK_PROPS),
+    role!("list", NonInteractive),
+    role!(
+        "listbox",
+        Interactive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-expanded",
+            "aria-invalid",
+            "aria-multiselectable",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-required",
+        ]
+    ),
+    role!("listitem", NonInteractive, &[], LISTITEM_PROPS),
+    role!("log", NonInteractive),
+    role!("main", NonInteractive),
+    role!("mark", NonInteractive),
+    role!("marquee", NonInteractive),
+    role!("math", NonInteractive),
+    role!("menu", Interactive, &[], COMPOSITE_PROPS),
+    role!("menubar", Interactive, &[], COMPOSITE_PROPS),
+    role!("menuitem", Interactive, &[], MENUITEM_PROPS),
+    role!(
+        "menuitemcheckbox",
+        Interactive,
+        &["aria-checked"],
+        MENUITEM_PROPS
+    ),
+    role!(
+        "menuitemradio",
+        Interactive,
+        &["aria-checked"],
+        MENUITEM_PROPS
+    ),
+    role!("meter", NonInteractive, &["aria-valuenow"], RANGE_PROPS),
+    role!("navigation", NonInteractive),
+    role!("none", NonInteractive),
+    role!("note", NonInteractive),
+    role!(
+        "option",
+        Interactive,
+        &[],
+        &[
+            "aria-checked",
+            "aria-disabled",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ]
+    ),
+    role!("paragraph", NonInteractive),
+    role!("presentation", NonInteractive),
+    role!("progressbar", NonInteractive, &[], RANGE_PROPS),
+    role!(
+        "radio",
+        Interactive,
+        &["aria-checked"],
+        &["aria-disabled", "aria-posinset", "aria-setsize"]
+    ),
+    role!(
+        "radiogroup",
+        Interactive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-invalid",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-required",
+        ]
+    ),
+    role!("range", Abstract),
+    role!("region", NonInteractive),
+    role!("roletype", Abstract),
+    role!(
+        "row",
+        Interactive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-colindex",
+            "aria-disabled",
+            "aria-expanded",
+            "aria-level",
+            "aria-posinset",
+            "aria-rowindex",
+            "aria-selected",
+            "aria-setsize",
+        ]
+    ),
+    role!("rowgroup", NonInteractive),
+    role!("rowheader", Interactive, &[], HEADER_CELL_PROPS),
+    role!(
+        "scrollbar",
+        Interactive,
+        &["aria-controls", "aria-valuenow"],
+        &[
+            "aria-disabled",
+            "aria-orientation",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuetext",
+        ]
+    ),
+    role!("search", NonInteractive),
+    role!("searchbox", Interactive, &[], TEXTBOX_PROPS),
+    role!("section", Abstract),
+    role!("sectionhead", Abstract),
+    role!("select", Abstract),
+    role!("separator", NonInteractive, &[], SEPARATOR_PROPS),
+    role!(
+        "slider",
+        Interactive,
+        &["aria-valuenow"],
+        &[
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-haspopup",
+            "aria-invalid",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuetext",
+        ]
+    ),
+    role!(
+        "spinbutton",
+        Interactive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-disabled",
+            "aria-errormessage",
+            "aria-invalid",
+            "aria-readonly",
+            "aria-required",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuenow",
+            "aria-valuetext",
+        ]
+    ),
+    role!("status", NonInteractive),
+    role!("strong", NonInteractive),
+    role!("structure", Abstract),
+    role!("subscript", NonInteractive),
+    role!("superscript", NonInteractive),
+    role!("switch", Interactive, &["aria-checked"], CHECKBOX_PROPS),
+    role!(
+        "tab",
+        Interactive,
+        &[],
+        &[
+            "aria-disabled",
+            "aria-expanded",
+            "aria-haspopup",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ]
+    ),
+    role!(
+        "table",
+        NonInteractive,
+        &[],
+        &["aria-colcount", "aria-rowcount"]
+    ),
+    role!(
+        "tablist",
+        Interactive,
+        &[],
+        &[
+            "aria-activedescendant",
+            "aria-disabled",
+            "aria-multiselectable",
+            "aria-orientation",
+        ]
+    ),
+    role!("tabpanel", Interactive),
+    role!("term", NonInteractive),
+    role!("textbox", Interactive, &[], TEXTBOX_PROPS),
+    role!("time", NonInteractive),
+    role!("timer", NonInteractive),
+    role!("toolbar", Interactive, &[], COMPOSITE_PROPS),
+    role!("tooltip", NonInteractive),
+    role!("tree", Interactive, &[], TREE_PROPS),
+    role!("treegrid", Interactive, &[], TREEGRID_PROPS),
+    role!(
+        "treeitem",
+        Interactive,
+        &[],
+        &[
+            "aria-checked",
+            "aria-disabled",
+            "aria-expanded",
+            "aria-haspopup",
+            "aria-level",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ]
+    ),
+    role!("widget", Abstract),
+    role!("window", Abstract),
+];
+
+/// Returns a role by name.
+pub(super) fn role(name: &str) -> Option<&'static Role> {
+    ROLES
+        .binary_search_by(|role| role.name.cmp(name))
+        .ok()
+        .map(|index| &ROLES[index])
+}
+
+/// Returns the kind of a role, or `None` for unknown roles.
+pub(super) fn role_kind(name: Option<&str>) -> Option<RoleKind> {
+    name.and_then(role).map(|role| role.kind)
+}
+
+/// Returns whether a role removes an element's semantics.
+pub(super) fn is_presentation_role(name: Option<&str>) -> bool {
+    matches!(name, Some("presentation" | "none"))
+}
+
+/// Returns the closest of `candidates` to `name`, if it is close enough to
+/// be a likely typo (a Levenshtein similarity above 0.7, as in the
+/// compiler's `fuzzymatch`).
+pub(super) fn fuzzy_match<'c>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'c str>,
+) -> Option<&'c str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (similarity(name, candidate), candidate))
+        .filter(|(score, _)| *score > 0.7)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_are_sorted() {
+        assert!(ATTRIBUTES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(ROLES.windows(2).all(|pair| pair[0].name < pair[1].name));
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let attributes = ATTRIBUTES.iter().map(|(name, _)| *name);
+        assert_eq!(
+            fuzzy_match("labeledby", attributes.clone()),
+            Some("labelledby")
+        );
+        assert_eq!(fuzzy_match("foo", attributes), None);
+        assert_eq!(
+            fuzzy_match("buton", ROLES.iter().map(|role| role.name)),
+            Some("button")
+        );
+    }
+}
@@ -0,0 +1,922 @@
+//! The compiler's per-element a11y checks.
+
+use super::aria::{self, AriaType};
+use super::elements::{
+    is_abstract_role, is_interactive_role, is_non_interactive_role, Attr, AttrValue, ElementFacts,
+    Interactivity,
+};
+use super::A11yCode;
+use crate::{Diagnostic, DiagnosticCode};
+use source_map::Span;
+use svelte_parser::TemplateNode;
+
+/// Elements that are never rendered, so ARIA has no effect on them.
+const INVISIBLE_ELEMENTS: &[&str] = &["meta", "html", "script", "style"];
+
+const DISTRACTING_ELEMENTS: &[&str] = &["blink", "marquee"];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Elements a `<label>` can be associated with.
+const LABELABLE: &[&str] = &[
+    "button", "input", "keygen", "meter", "output", "progress", "select", "textarea",
+];
+
+const INTERACTIVE_HANDLERS: &[&str] = &[
+    "keypress",
+    "keydown",
+    "keyup",
+    "click",
+    "contextmenu",
+    "dblclick",
+    "drag",
+    "dragend",
+    "dragenter",
+    "dragexit",
+    "dragleave",
+    "dragover",
+    "dragstart",
+    "drop",
+    "mousedown",
+    "mouseenter",
+    "mouseleave",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+];
+
+const RECOMMENDED_INTERACTIVE_HANDLERS: &[&str] = &[
+    "click",
+    "mousedown",
+    "mouseup",
+    "keypress",
+    "keydown",
+    "keyup",
+];
+
+/// Interactive roles that non-interactive elements may take on.
+fn interactive_role_exceptions(name: &str) -> &'static [&'static str] {
+    match name {
+        "ul" | "ol" => &[
+            "listbox",
+            "menu",
+            "menubar",
+            "radiogroup",
+            "tablist",
+            "tree",
+            "treegrid",
+        ],
+        "li" => &["menuitem", "option", "row", "tab", "treeitem"],
+        "table" => &["grid"],
+        "td" => &["gridcell"],
+        "fieldset" => &["radiogroup", "presentation"],
+        _ => &[],
+    }
+}
+
+/// Attributes of which an element needs at least one.
+fn required_attributes(name: &str) -> Option<&'static [&'static str]> {
+    Some(match name {
+        "area" => &["alt", "aria-label", "aria-labelledby"],
+        "html" => &["lang"],
+        "iframe" => &["title"],
+        "img" => &["alt"],
+        "object" => &["title", "aria-label", "aria-labelledby"],
+        _ => return None,
+    })
+}
+
+/// Runs every check on one element.
+pub(super) fn check_element(el: &ElementFacts<'_>, out: &mut Vec<Diagnostic>) {
+    let mut warn = |code: A11yCode, message: String, span: Span| {
+        out.push(Diagnostic::new(DiagnosticCode::A11y(code), message, span));
+    };
+    let node = el.el.span;
+    let name = el.name;
+
+    for attr in &el.attributes {
+        let attr_name = attr.name.to_ascii_lowercase();
+        if let Some(aria_name) = attr_name.strip_prefix("aria-") {
+            check_aria_attribute(el, attr, &attr_name, aria_name, &mut warn);
+        }
+        match attr_name.as_str() {
+            "role" => check_role(el, attr, &mut warn),
+            "accesskey" => warn(
+                A11yCode::Accesskey,
+                "Avoid using accesskey".into(),
+                attr.span,
+            ),
+            "autofocus" if name != "dialog" && !el.is_parent(&["dialog"]) => warn(
+                A11yCode::Autofocus,
+                "Avoid using autofocus".into(),
+                attr.span,
+            ),
+            "scope" if name != "th" => warn(
+                A11yCode::MisplacedScope,
+                "The scope attribute should only be used with `<th>` elements".into(),
+                attr.span,
+            ),
+            "tabindex"
+                if attr
+                    .static_value()
+                    .and_then(|value| value.trim().parse::<f64>().ok())
+                    .is_some_and(|value| value > 0.0) =>
+            {
+                warn(
+                    A11yCode::PositiveTabindex,
+                    "Avoid tabindex values above zero".into(),
+                    attr.span,
+                )
+            }
+            _ => {}
+        }
+    }
+
+    let role = el.attr("role");
+    let role_static = role.and_then(Attr::text);
+    let interactivity = el.interactivity();
+    let hidden = el.is_hidden_from_screen_reader();
+
+    if el.handles("click")
+        && !hidden
+        && (role.is_none()
+            || role_static.is_some_and(|role| !aria::is_presentation_role(Some(role))))
+        && interactivity != Interactivity::Interactive
+        && !el.has_spread
+        && !["keydown", "keyup", "keypress"]
+            .iter()
+            .any(|event| el.handles(event))
+    {
+        warn(
+            A11yCode::ClickEventsHaveKeyEvents,
+            "Visible, non-interactive elements with a click event must be accompanied by a \
+             keyboard event handler. Consider whether an interactive element such as \
+             `<button type=\"button\">` or `<a>` might be more appropriate"
+                .into(),
+            node,
+        );
+    }
+
+    if interactivity != Interactivity::Interactive && !is_interactive_role(role_static) {
+        if let Some(tabindex) = el.attr("tabindex") {
+            let non_negative = match tabindex.text() {
+                Some(value) => parse_number(value).is_some_and(|value| value >= 0.0),
+                None => true,
+            };
+            if non_negative {
+                warn(
+                    A11yCode::NoNoninteractiveTabindex,
+                    "noninteractive element cannot have nonnegative tabIndex value".into(),
+                    node,
+                );
+            }
+        }
+    }
+
+    let role_value = if role.is_some() {
+        role_static
+    } else {
+        el.implicit_role()
+    };
+    if let Some(role_definition) = role_value.and_then(aria::role) {
+        for attr in &el.attributes {
+            if !attr.name.starts_with("aria-")
+                || aria::attribute_type(&attr.name[5..]).is_none()
+                || role_definition.supports(attr.name)
+            {
+                continue;
+            }
+            if role.is_none() {
+                warn(
+                    A11yCode::RoleSupportsAriaPropsImplicit,
+                    format!(
+                        "The attribute '{}' is not supported by the role '{}'. This role is \
+                         implicit on the element `<{name}>`",
+                        attr.name, role_definition.name
+                    ),
+                    attr.span,
+                );
+            } else {
+                warn(
+                    A11yCode::RoleSupportsAriaProps,
+                    format!(
+                        "The attribute '{}' is not supported by the role '{}'",
+                        attr.name, role_definition.name
+                    ),
+                    attr.span,
+                );
+            }
+        }
+    }
+
+    let has_interactive_handlers = el
+        .handlers
+        .iter()
+        .any(|handler| RECOMMENDED_INTERACTIVE_HANDLERS.contains(handler));
+    if !el.has_spread
+        && !el.has_contenteditable_attr
+        && !hidden
+        && !aria::is_presentation_role(role_static)
+        && ((interactivity != Interactivity::Interactive && is_non_interactive_role(role_static))
+            || (interactivity == Interactivity::NonInteractive && role.is_none()))
+        && has_interactive_handlers
+    {
+        warn(
+            A11yCode::NoNoninteractiveElementInteractions,
+            format!(
+                "Non-interactive element `<{name}>` should not be assigned mouse or keyboard \
+                 event listeners"
+            ),
+            node,
+        );
+    }
+
+    let has_dynamic_role = role.is_some() && role_static.is_none();
+    if !el.has_spread
+        && !has_dynamic_role
+        && !hidden
+        && !aria::is_presentation_role(role_static)
+        && interactivity == Interactivity::Static
+        && !is_interactive_role(role_static)
+        && !is_non_interactive_role(role_static)
+        && !is_abstract_role(role_static)
+    {
+        let handlers: Vec<&str> = INTERACTIVE_HANDLERS
+            .iter()
+            .copied()
+            .filter(|handler| el.handles(handler))
+            .collect();
+        if !handlers.is_empty() {
+            warn(
+                A11yCode::NoStaticElementInteractions,
+                format!(
+                    "`<{name}>` with a {} handler must have an ARIA role",
+                    list(&handlers, "or")
+                ),
+                node,
+            );
+        }
+    }
+
+    if !el.has_spread {
+        if name == "a" {
+            let href = el.attr("href").or_else(|| el.attr("xlink:href"));
+            if let Some(href) = href {
+                if let Some(value) = href.static_value() {
+                    if value.is_empty() || value == "#" || is_javascript_url(value) {
+                        warn(
+                            A11yCode::InvalidAttribute,
+                            format!("'{value}' is not a valid {} attribute", href.name),
+                            href.span,
+                        );
+                    }
+                }
+            } else if el.static_value("id").is_none_or(str::is_empty)
+                && el.static_value("name").is_none_or(str::is_empty)
+                && el.static_value("aria-disabled") != Some("true")
+            {
+                warn_missing_attribute(&mut warn, node, name, &["href"]);
+            }
+        } else if let Some(required) = required_attributes(name) {
+            if !required.iter().any(|attribute| el.has(attribute)) {
+                warn_missing_attribute(&mut warn, node, name, required);
+            }
+        }
+    }
+
+    if name == "input" {
+        let input_type = el.attr("type");
+        let type_value = input_type.and_then(Attr::static_value);
+        if type_value == Some("image") && !el.has_spread {
+            let required = ["alt", "aria-label", "aria-labelledby"];
+            if !required.iter().any(|attribute| el.has(attribute)) {
+                warn_missing_attribute(&mut warn, node, "input type=\"image\"", &required);
+            }
+        }
+        if let (Some(_), Some(autocomplete)) = (input_type, el.attr("autocomplete")) {
+            let valid = match autocomplete.value {
+                AttrValue::True => false,
+                AttrValue::Text(value) => is_valid_autocomplete(value),
+                AttrValue::Dynamic => true,
+            };
+            if !valid {
+                warn(
+                    A11yCode::AutocompleteValid,
+                    format!(
+                        "'{}' is an invalid value for 'autocomplete' on `<input type=\"{}\">`",
+                        autocomplete.static_value().unwrap_or_default(),
+                        type_value.unwrap_or("...")
+                    ),
+                    autocomplete.span,
+                );
+            }
+        }
+    }
+
+    if name == "img" && !el.has_spread && el.static_value("aria-hidden").is_none() {
+        if let Some(alt) = el.text("alt") {
+            if ["image", "picture", "photo"]
+                .iter()
+                .any(|word| contains_word(alt, word))
+            {
+                warn(
+                    A11yCode::ImgRedundantAlt,
+                    "Screenreaders already announce `<img>` elements as an image".into(),
+                    node,
+                );
+            }
+        }
+    }
+
+    if name == "label" && !el.has_spread && !el.has("for") && !has_input_child(el.children()) {
+        warn(
+            A11yCode::LabelHasAssociatedControl,
+            "A form label must be associated with a control".into(),
+            node,
+        );
+    }
+
+    if name == "video"
+        && !el.has("muted")
+        && el.static_value("aria-hidden") != Some("true")
+        && !el.has_spread
+        && !has_captions(el.children())
+    {
+        warn(
+            A11yCode::MediaHasCaption,
+            "`<video>` elements must have a `<track kind=\"captions\">`".into(),
+            node,
+        );
+    }
+
+    if name == "figcaption" && !el.is_parent(&["figure"]) {
+        warn(
+            A11yCode::FigcaptionParent,
+            "`<figcaption>` must be an immediate child of `<figure>`".into(),
+            node,
+        );
+    }
+
+    if name == "figure" {
+        let children: Vec<&TemplateNode> = el
+            .children()
+            .iter()
+            .filter(|child| match child {
+                TemplateNode::Comment(_) => false,
+                TemplateNode::Text(text) => !text.data.trim().is_empty(),
+                _ => true,
+            })
+            .collect();
+        let caption = children.iter().position(
+            |child| matches!(child, TemplateNode::Element(child) if child.name == "figcaption"),
+        );
+        if let Some(index) = caption.filter(|&index| index != 0 && index != children.len() - 1) {
+            warn(
+                A11yCode::FigcaptionIndex,
+                "`<figcaption>` must be first or last child of `<figure>`".into(),
+                children[index].span(),
+            );
+        }
+    }
+
+    if DISTRACTING_ELEMENTS.contains(&name) {
+        warn(
+            A11yCode::DistractingElements,
+            format!("Avoid `<{name}>` elements"),
+            node,
+        );
+    }
+
+    let is_labelled = el.has("aria-label") || el.has("aria-labelledby") || el.has("title");
+    if (name == "button" || (name == "a" && el.has("href")))
+        && !el.has_spread
+        && !is_labelled
+        && el.static_value("aria-hidden") != Some("true")
+        && !has_content(el.children())
+    {
+        warn(
+            A11yCode::ConsiderExplicitLabel,
+            "Buttons and links should either contain text or have an `aria-label`, \
+             `aria-labelledby` or `title` attribute"
+                .into(),
+            node,
+        );
+    }
+
+    if HEADINGS.contains(&name)
+        && !el.has_spread
+        && !is_labelled
+        && !el.has_contenteditable_binding
+        && !has_content(el.children())
+    {
+        warn(
+            A11yCode::MissingContent,
+            format!("`<{name}>` element should contain text"),
+            node,
+        );
+    }
+
+    if !el.has_spread {
+        for (event, accompanied_by) in [("mouseover", "focus"), ("mouseout", "blur")] {
+            if el.handles(event) && !el.handles(accompanied_by) {
+                warn(
+                    A11yCode::MouseEventsHaveKeyEvents,
+                    format!("'{event}' event must be accompanied by '{accompanied_by}' event"),
+                    node,
+                );
+            }
+        }
+    }
+}
+
+fn check_aria_attribute(
+    el: &ElementFacts<'_>,
+    attr: &Attr<'_>,
+    full_name: &str,
+    aria_name: &str,
+    warn: &mut impl FnMut(A11yCode, String, Span),
+) {
+    let name = el.name;
+    if INVISIBLE_ELEMENTS.contains(&name) {
+        warn(
+            A11yCode::AriaAttributes,
+            format!("`<{name}>` should not have aria-* attributes"),
+            attr.span,
+        );
+    }
+    let Some(aria_type) = aria::attribute_type(aria_name) else {
+        let suggestion = aria::fuzzy_match(aria_name, aria::ATTRIBUTES.iter().map(|(n, _)| *n));
+        let message = match suggestion {
+            Some(suggestion) => {
+                format!("Unknown aria attribute 'aria-{aria_name}'. Did you mean '{suggestion}'?")
+            }
+            None => format!("Unknown aria attribute 'aria-{aria_name}'"),
+        };
+        warn(A11yCode::UnknownAriaAttribute, message, attr.span);
+        return;
+    };
+    if full_name == "aria-hidden" && HEADINGS.contains(&name) {
+        warn(
+            A11yCode::Hidden,
+            format!("`<{name}>` element should not be hidden"),
+            attr.span,
+        );
+    }
+    let value = match attr.value {
+        AttrValue::True => "",
+        AttrValue::Text(text) => text,
+        AttrValue::Dynamic => return,
+    };
+    if let Some((code, message)) = validate_aria_value(full_name, aria_type, value) {
+        warn(code, message, attr.span);
+    }
+    if full_name == "aria-activedescendant"
+        && el.interactivity() != Interactivity::Interactive
+        && !el.has("tabindex")
+        && !el.has_spread
+    {
+        warn(
+            A11yCode::AriaActivedescendantHasTabindex,
+            "An element with an aria-activedescendant attribute should have a tabindex value"
+                .into(),
+            attr.span,
+        );
+    }
+}
+
+/// Checks an ARIA attribute value against the attribute's type. Boolean
+/// attributes are checked as the empty string.
+fn validate_aria_value(name: &str, aria_type: AriaType, value: &str) -> Option<(A11yCode, String)> {
+    let valid = match aria_type {
+        AriaType::String | AriaType::Id => !value.is_empty(),
+        AriaType::Number => parse_number(value).is_some(),
+        AriaType::Boolean => value == "true" || value == "false",
+        AriaType::IdList => !value.is_empty(),
+        AriaType::Integer => parse_number(value).is_some_and(|number| number.fract() == 0.0),
+        AriaType::Token(values) => values.contains(&value.to_lowercase().as_str()),
+        AriaType::TokenList(values) => value
+            .to_lowercase()
+            .split_whitespace()
+            .all(|token| values.contains(&token)),
+        AriaType::Tristate => matches!(value, "true" | "false" | "mixed"),
+    };
+    if valid {
+        return None;
+    }
+    Some(match aria_type {
+        AriaType::String | AriaType::Id => (
+            A11yCode::IncorrectAriaAttributeType,
+            format!("The value of '{name}' must be a non-empty string"),
+        ),
+        AriaType::Number => (
+            A11yCode::IncorrectAriaAttributeType,
+            format!("The value of '{name}' must be a number"),
+        ),
+        AriaType::Boolean => (
+            A11yCode::IncorrectAriaAttributeTypeBoolean,
+            format!("The value of '{name}' must be either 'true' or 'false'. It cannot be empty"),
+        ),
+        AriaType::IdList => (
+            A11yCode::IncorrectAriaAttributeTypeIdlist,
+            format!(
+                "The value of '{name}' must be a space-separated list of strings that represent \
+                 DOM element IDs"
+            ),
+        ),
+        AriaType::Integer => (
+            A11yCode::IncorrectAriaAttributeTypeInteger,
+            format!("The value of '{name}' must be an integer"),
+        ),
+        AriaType::Token(values) => (
+            A11yCode::IncorrectAriaAttributeTypeToken,
+            format!(
+                "The value of '{name}' must be exactly one of {}",
+                list(values, "or")
+            ),
+        ),
+        AriaType::TokenList(values) => (
+            A11yCode::IncorrectAriaAttributeTypeTokenlist,
+            format!(
+                "The value of '{name}' must be a space-separated list of one or more of {}",
+                list(values, "or")
+            ),
+        ),
+        AriaType::Tristate => (
+            A11yCode::IncorrectAriaAttributeTypeTristate,
+            format!("The value of '{name}' must be exactly one of true, false, or mixed"),
+        ),
+    })
+}
+
+fn check_role(
+    el: &ElementFacts<'_>,
+    attr: &Attr<'_>,
+    warn: &mut impl FnMut(A11yCode, String, Span),
+) {
+    let name = el.name;
+    if INVISIBLE_ELEMENTS.contains(&name) {
+        warn(
+            A11yCode::MisplacedRole,
+            format!("`<{name}>` should not have role attribute"),
+            attr.span,
+        );
+    }
+    let AttrValue::Text(value) = attr.value else {
+        return;
+    };
+    let node = el.el.span;
+    let implicit_role = el.implicit_role();
+    let interactivity = el.interactivity();
+    for current in value.split_whitespace() {
+        let role = aria::role(current);
+        match role {
+            Some(role) if role.kind == aria::RoleKind::Abstract => warn(
+                A11yCode::NoAbstractRole,
+                format!("Abstract role '{current}' is forbidden"),
+                attr.span,
+            ),
+            Some(_) => {}
+            None => {
+                let message = match aria::fuzzy_match(
+                    current,
+                    aria::ROLES
+                        .iter()
+                        .filter(|role| role.kind != aria::RoleKind::Abstract)
+                        .map(|role| role.name),
+                ) {
+                    Some(suggestion) => {
+                        format!("Unknown role '{current}'. Did you mean '{suggestion}'?")
+                    }
+                    None => format!("Unknown role '{current}'"),
+                };
+                warn(A11yCode::UnknownRole, message, attr.span);
+            }
+        }
+
+        let redundant = Some(current) == implicit_role
+            // `<ul role="list">` restores semantics removed by `list-style: none`.
+            && !["ul", "ol", "li"].contains(&name)
+            // An `<a>` without href is not a link.
+            && (name != "a" || el.has("href"));
+        let nested_redundant = !el.is_parent(&["section", "article"])
+            && matches!(
+                (name, current),
+                ("header", "banner") | ("footer", "contentinfo")
+            );
+        if redundant || nested_redundant {
+            warn(
+                A11yCode::NoRedundantRoles,
+                format!("Redundant role '{current}'"),
+                attr.span,
+            );
+        }
+
+        if let Some(role) = role {
+            if !el.is_semantic_role_element(current)
+                && !el.has_spread
+                && role.required.iter().any(|prop| !el.has(prop))
+            {
+                let props: Vec<String> = role
+                    .required
+                    .iter()
+                    .map(|prop| format!("\"{prop}\""))
+                    .collect();
+                let props: Vec<&str> = props.iter().map(String::as_str).collect();
+                warn(
+                    A11yCode::RoleHasRequiredAriaProps,
+                    format!(
+                        "Elements with the ARIA role \"{current}\" must have the following \
+                         attributes defined: {}",
+                        list(&props, "and")
+                    ),
+                    attr.span,
+                );
+            }
+        }
+
+        let current_role = Some(current);
+        if !el.has_spread
+            && !el.has_disabled_attribute()
+            && !el.is_hidden_from_screen_reader()
+            && !aria::is_presentation_role(current_role)
+            && is_interactive_role(current_role)
+            && interactivity == Interactivity::Static
+            && !el.has("tabindex")
+            && el
+                .handlers
+                .iter()
+                .any(|handler| RECOMMENDED_INTERACTIVE_HANDLERS.contains(handler))
+        {
+            warn(
+                A11yCode::InteractiveSupportsFocus,
+                format!(
+                    "Elements with the '{current}' interactive role must have a tabindex value"
+                ),
+                node,
+            );
+        }
+
+        if !el.has_spread
+            && interactivity == Interactivity::Interactive
+            && (is_non_interactive_role(current_role) || aria::is_presentation_role(current_role))
+        {
+            warn(
+                A11yCode::NoInteractiveElementToNoninteractiveRole,
+                format!("`<{name}>` cannot have role '{current}'"),
+                node,
+            );
+        }
+
+        if !el.has_spread
+            && interactivity == Interactivity::NonInteractive
+            && is_interactive_role(current_role)
+            && !interactive_role_exceptions(name).contains(&current)
+        {
+            warn(
+                A11yCode::NoNoninteractiveElementToInteractiveRole,
+                format!(
+                    "Non-interactive element `<{name}>` cannot have interactive role '{current}'"
+                ),
+                node,
+            );
+        }
+    }
+}
+
+fn warn_missing_attribute(
+    warn: &mut impl FnMut(A11yCode, String, Span),
+    span: Span,
+    name: &str,
+    attributes: &[&str],
+) {
+    let article = if attributes[0].starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    };
+    let sequence = match attributes {
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} or {last}", rest.join(", ")),
+        [] => String::new(),
+    };
+    warn(
+        A11yCode::MissingAttribute,
+        format!("`<{name}>` element should have {article} {sequence} attribute"),
+        span,
+    );
+}
+
+/// Joins words as the compiler does: `a, b or c`.
+fn list(items: &[&str], conjunction: &str) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} {conjunction} {last}", rest.join(", ")),
+    }
+}
+
+/// Parses a number the way `+value` does in JavaScript, rejecting the empty
+/// string.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+fn is_javascript_url(value: &str) -> bool {
+    value
+        .trim_start_matches(|c: char| !c.is_alphanumeric() && c != '_')
+        .get(..11)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("javascript:"))
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|part| part.eq_ignore_ascii_case(word))
+}
+
+/// Returns whether a `<label>` contains something that can be its control.
+fn has_input_child(nodes: &[TemplateNode]) -> bool {
+    nodes.iter().any(|node| match node {
+        TemplateNode::SvelteElement(_)
+        | TemplateNode::Component(_)
+        | TemplateNode::RenderTag(_) => true,
+        TemplateNode::Element(el) if LABELABLE.contains(&el.name.as_str()) || el.name == "slot" => {
+            true
+        }
+        _ => crate::traversal::child_lists(node)
+            .into_iter()
+            .any(has_input_child),
+    })
+}
+
+/// Returns whether a `<video>` has a captions track.
+fn has_captions(children: &[TemplateNode]) -> bool {
+    let track = children.iter().find_map(|child| match child {
+        TemplateNode::Element(el) if el.name == "track" => Some(el),
+        _ => None,
+    });
+    track.is_some_and(|track| {
+        track.attributes.iter().any(|attribute| match attribute {
+            svelte_parser::Attribute::Spread(_) => true,
+            svelte_parser::Attribute::Normal(attr) => {
+                attr.name == "kind"
+                    && matches!(&attr.value, svelte_parser::AttributeValue::Text(text) if text.value == "captions")
+            }
+            _ => false,
+        })
+    })
+}
+
+/// Returns whether an element has content a screen reader can announce.
+/// Anything that isn't whitespace or an empty element is assumed to.
+fn has_content(nodes: &[TemplateNode]) -> bool {
+    nodes.iter().any(|node| match node {
+        TemplateNode::Text(text) => !text.data.trim().is_empty(),
+        TemplateNode::Element(el) => {
+            (el.name == "img"
+                && el.attributes.iter().any(
+                    |attribute| matches!(attribute, svelte_parser::Attribute::Normal(attr) if attr.name == "alt"),
+                ))
+                || has_content(&el.children)
+        }
+        _ => true,
+    })
+}
+
+/// Returns whether an `autocomplete` value is a valid autofill detail token
+/// list.
+fn is_valid_autocomplete(value: &str) -> bool {
+    const ADDRESS_TYPES: &[&str] = &["shipping", "billing"];
+    const FIELD_NAMES: &[&str] = &[
+        "",
+        "on",
+        "off",
+        "name",
+        "honorific-prefix",
+        "given-name",
+        "additional-name",
+        "family-name",
+        "honorific-suffix",
+        "nickname",
+        "username",
+        "new-password",
+        "current-password",
+        "one-time-code",
+        "organization-title",
+        "organization",
+        "street-address",
+        "address-line1",
+        "address-line2",
+        "address-line3",
+        "address-level4",
+        "address-level3",
+        "address-level2",
+        "address-level1",
+        "country",
+        "country-name",
+        "postal-code",
+        "cc-name",
+        "cc-given-name",
+        "cc-additional-name",
+        "cc-family-name",
+        "cc-number",
+        "cc-exp",
+        "cc-exp-month",
+        "cc-exp-year",
+        "cc-csc",
+        "cc-type",
+        "transaction-currency",
+        "transaction-amount",
+        "language",
+        "bday",
+        "bday-day",
+        "bday-month",
+        "bday-year",
+        "sex",
+        "url",
+        "photo",
+        "webauthn",
+    ];
+    const CONTACT_TYPES: &[&str] = &["home", "work", "mobile", "fax", "pager"];
+    const CONTACT_FIELD_NAMES: &[&str] = &[
+        "tel",
+        "tel-country-code",
+        "tel-national",
+        "tel-area-code",
+        "tel-local",
+        "tel-local-prefix",
+        "tel-local-suffix",
+        "tel-extension",
+        "email",
+        "impp",
+    ];
+
+    if value.is_empty() {
+        return true;
+    }
+    let lowered = value.trim().to_lowercase();
+    let mut tokens: &[&str] = &lowered.split_whitespace().collect::<Vec<_>>();
+    // `"   "` splits into nothing, which the compiler sees as one empty token.
+    let empty = [""];
+    if tokens.is_empty() {
+        tokens = &empty;
+    }
+    let skip = |tokens: &mut &[&str], allowed: &[&str]| {
+        let matched = tokens.first().is_some_and(|token| allowed.contains(token));
+        if matched {
+            *tokens = &tokens[1..];
+        }
+        matched
+    };
+    if tokens
+        .first()
+        .is_some_and(|token| token.starts_with("section-"))
+    {
+        tokens = &tokens[1..];
+    }
+    skip(&mut tokens, ADDRESS_TYPES);
+    if !skip(&mut tokens, FIELD_NAMES) {
+        skip(&mut tokens, CONTACT_TYPES);
+        if !skip(&mut tokens, CONTACT_FIELD_NAMES) {
+            return false;
+        }
+    }
+    skip(&mut tokens, &["webauthn"]);
+    tokens.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list() {
+        assert_eq!(list(&["a"], "or"), "a");
+        assert_eq!(list(&["a", "b"], "or"), "a or b");
+        assert_eq!(list(&["a", "b", "c"], "and"), "a, b and c");
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        for valid in [
+            "",
+            "on",
+            "email",
+            "billing street-address",
+            "section-a work tel",
+            "username webauthn",
+        ] {
+            assert!(is_valid_autocomplete(valid), "{valid}");
+        }
+        for invalid in ["true", "incorrect", "street-address billing", "work name"] {
+            assert!(!is_valid_autocomplete(invalid), "{invalid}");
+        }
+    }
+}
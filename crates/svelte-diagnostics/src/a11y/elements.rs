@@ -0,0 +1,294 @@
+//! What the a11y checks know about an element: its attributes and event
+//! handlers, its implicit role and whether it is interactive.
+
+use super::aria::{self, RoleKind};
+use crate::traversal::Traversal;
+use source_map::Span;
+use svelte_parser::{Attribute, AttributeValue, DirectiveKind, Element, TemplateNode};
+
+/// The value of an attribute as far as it is known statically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AttrValue<'a> {
+    /// A boolean attribute without a value.
+    True,
+    /// A plain text value.
+    Text(&'a str),
+    /// A value containing expressions.
+    Dynamic,
+}
+
+/// A plain (non-directive, non-event) attribute.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Attr<'a> {
+    pub name: &'a str,
+    pub value: AttrValue<'a>,
+    pub span: Span,
+}
+
+impl<'a> Attr<'a> {
+    /// Returns the value as a string, or `"true"` for boolean attributes, as
+    /// the compiler's `get_static_value` does for string comparisons.
+    pub fn static_value(&self) -> Option<&'a str> {
+        match self.value {
+            AttrValue::True => Some("true"),
+            AttrValue::Text(text) => Some(text),
+            AttrValue::Dynamic => None,
+        }
+    }
+
+    /// Returns the text value; boolean attributes have none.
+    pub fn text(&self) -> Option<&'a str> {
+        match self.value {
+            AttrValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// Whether an element is interactive, by its tag and attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Interactivity {
+    Interactive,
+    NonInteractive,
+    Static,
+}
+
+/// An element with its attributes collected for the checks.
+pub(super) struct ElementFacts<'a> {
+    pub el: &'a Element,
+    pub name: &'a str,
+    pub attributes: Vec<Attr<'a>>,
+    /// Event names handled through `on:event` or `onevent={...}`.
+    pub handlers: Vec<&'a str>,
+    pub has_spread: bool,
+    pub has_contenteditable_attr: bool,
+    pub has_contenteditable_binding: bool,
+    traversal: &'a Traversal<'a>,
+    index: usize,
+}
+
+impl<'a> ElementFacts<'a> {
+    /// Collects the facts about the element at `index` of `traversal`.
+    pub fn new(el: &'a Element, traversal: &'a Traversal<'a>, index: usize) -> Self {
+        let mut facts = Self {
+            el,
+            name: el.name.as_str(),
+            attributes: Vec::new(),
+            handlers: Vec::new(),
+            has_spread: false,
+            has_contenteditable_attr: false,
+            has_contenteditable_binding: false,
+            traversal,
+            index,
+        };
+        for attribute in &el.attributes {
+            match attribute {
+                Attribute::Normal(attr) => {
+                    let value = match &attr.value {
+                        AttributeValue::True => AttrValue::True,
+                        AttributeValue::Text(text) => AttrValue::Text(&text.value),
+                        AttributeValue::Expression(_) | AttributeValue::Concat(_) => {
+                            AttrValue::Dynamic
+                        }
+                    };
+                    facts.attribute(attr.name.as_str(), value, attr.span);
+                }
+                Attribute::Shorthand(attr) => {
+                    facts.attribute(attr.name.as_str(), AttrValue::Dynamic, attr.span);
+                }
+                Attribute::Spread(_) => facts.has_spread = true,
+                Attribute::Directive(directive) => match directive.kind {
+                    DirectiveKind::On => facts.handlers.push(directive.name.as_str()),
+                    DirectiveKind::Bind
+                        if matches!(directive.name.as_str(), "innerHTML" | "textContent") =>
+                    {
+                        facts.has_contenteditable_binding = true;
+                    }
+                    _ => {}
+                },
+                Attribute::Attach(_) | Attribute::CssCustomProperty { .. } => {}
+            }
+        }
+        facts
+    }
+
+    fn attribute(&mut self, name: &'a str, value: AttrValue<'a>, span: Span) {
+        if value == AttrValue::Dynamic {
+            if let Some(event) = name.strip_prefix("on") {
+                self.handlers.push(event);
+                return;
+            }
+        }
+        if name == "contenteditable" {
+            self.has_contenteditable_attr = true;
+        }
+        self.attributes.push(Attr { name, value, span });
+    }
+
+    /// Returns an attribute by name.
+    pub fn attr(&self, name: &str) -> Option<&Attr<'a>> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+
+    /// Returns whether the element has an attribute.
+    pub fn has(&self, name: &str) -> bool {
+        self.attr(name).is_some()
+    }
+
+    /// Returns the static value of an attribute (see [`Attr::static_value`]).
+    pub fn static_value(&self, name: &str) -> Option<&'a str> {
+        self.attr(name).and_then(Attr::static_value)
+    }
+
+    /// Returns the text value of an attribute.
+    pub fn text(&self, name: &str) -> Option<&'a str> {
+        self.attr(name).and_then(Attr::text)
+    }
+
+    /// Returns whether the element handles an event.
+    pub fn handles(&self, event: &str) -> bool {
+        self.handlers.contains(&event)
+    }
+
+    /// Returns the child nodes.
+    pub fn children(&self) -> &'a [TemplateNode] {
+        &self.el.children
+    }
+
+    /// Returns whether the closest enclosing element is one of `names`. An
+    /// enclosing `<svelte:element>` could be anything, so it counts.
+    pub fn is_parent(&self, names: &[&str]) -> bool {
+        for ancestor in self.traversal.ancestors(self.index) {
+            match ancestor.node {
+                TemplateNode::Element(el) => return names.contains(&el.name.as_str()),
+                TemplateNode::SvelteElement(_) => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Returns the element's implicit ARIA role.
+    pub fn implicit_role(&self) -> Option<&'static str> {
+        match self.name {
+            "menuitem" => match self.static_value("type")? {
+                "command" => Some("menuitem"),
+                "checkbox" => Some("menuitemcheckbox"),
+                "radio" => Some("menuitemradio"),
+                _ => None,
+            },
+            "input" => {
+                let input_type = self.static_value("type")?;
+                if self.has("list")
+                    && matches!(input_type, "email" | "search" | "tel" | "text" | "url")
+                {
+                    return Some("combobox");
+                }
+                match input_type {
+                    "button" | "image" | "reset" | "submit" => Some("button"),
+                    "checkbox" => Some("checkbox"),
+                    "radio" => Some("radio"),
+                    "range" => Some("slider"),
+                    "number" => Some("spinbutton"),
+                    "email" | "tel" | "text" | "url" => Some("textbox"),
+                    "search" => Some("searchbox"),
+                    _ => None,
+                }
+            }
+            name => implicit_semantics(name),
+        }
+    }
+
+    /// Returns whether the element natively has `role`, so an explicit role
+    /// attribute needs none of the role's required attributes.
+    pub fn is_semantic_role_element(&self, role: &str) -> bool {
+        self.implicit_role() == Some(role)
+            || (self.name == "input"
+                && self.static_value("type") == Some("checkbox")
+                && role == "switch")
+    }
+
+    /// Classifies the element by its tag and attributes.
+    pub fn interactivity(&self) -> Interactivity {
+        use Interactivity::*;
+        match self.name {
+            "a" | "area" if self.has("href") => Interactive,
+            "input" if self.static_value("type") != Some("hidden") => Interactive,
+            "audio" | "button" | "datalist" | "dialog" | "embed" | "menuitem" | "option"
+            | "select" | "summary" | "td" | "textarea" | "th" | "tr" | "video" => Interactive,
+            "section" if self.has("aria-label") || self.has("aria-labelledby") => NonInteractive,
+            "address" | "article" | "aside" | "blockquote" | "caption" | "code" | "dd" | "del"
+            | "details" | "dfn" | "dl" | "dt" | "em" | "fieldset" | "figcaption" | "figure"
+            | "footer" | "form" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "hr" | "html"
+            | "img" | "ins" | "label" | "legend" | "li" | "main" | "mark" | "math" | "menu"
+            | "meter" | "nav" | "ol" | "optgroup" | "output" | "p" | "progress" | "ruby"
+            | "search" | "strong" | "sub" | "sup" | "table" | "tbody" | "tfoot" | "thead"
+            | "time" | "ul" => NonInteractive,
+            _ => Static,
+        }
+    }
+
+    /// Returns whether the element is hidden from screen readers.
+    pub fn is_hidden_from_screen_reader(&self) -> bool {
+        if self.name == "input" && self.text("type") == Some("hidden") {
+            return true;
+        }
+        self.static_value("aria-hidden") == Some("true")
+    }
+
+    /// Returns whether the element is disabled.
+    pub fn has_disabled_attribute(&self) -> bool {
+        self.static_value("disabled")
+            .is_some_and(|value| !value.is_empty())
+            || self.static_value("aria-disabled") == Some("true")
+    }
+}
+
+/// Implicit roles by tag name.
+fn implicit_semantics(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "a" | "area" | "link" => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "body" => "document",
+        "button" | "summary" => "button",
+        "datalist" => "listbox",
+        "dd" => "definition",
+        "dfn" | "dt" => "term",
+        "dialog" => "dialog",
+        "details" | "fieldset" | "optgroup" => "group",
+        "figure" => "figure",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "hr" => "separator",
+        "img" => "img",
+        "li" => "listitem",
+        "main" => "main",
+        "menu" | "ol" | "ul" => "list",
+        "meter" | "progress" => "progressbar",
+        "nav" => "navigation",
+        "option" => "option",
+        "output" => "status",
+        "section" => "region",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "textarea" => "textbox",
+        "tr" => "row",
+        _ => return None,
+    })
+}
+
+/// Returns whether a role is interactive.
+pub(super) fn is_interactive_role(role: Option<&str>) -> bool {
+    aria::role_kind(role) == Some(RoleKind::Interactive)
+}
+
+/// Returns whether a role is non-interactive.
+pub(super) fn is_non_interactive_role(role: Option<&str>) -> bool {
+    aria::role_kind(role) == Some(RoleKind::NonInteractive)
+}
+
+/// Returns whether a role is abstract.
+pub(super) fn is_abstract_role(role: Option<&str>) -> bool {
+    aria::role_kind(role) == Some(RoleKind::Abstract)
+}
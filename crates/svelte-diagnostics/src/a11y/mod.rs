@@ -1,104 +1,65 @@
 //! Accessibility (a11y) checks.
 //!
-//! Currently only heading structure checks are implemented. All other a11y
-//! warnings are provided by the Svelte compiler.
+//! Besides the heading structure check, this module ports the Svelte
+//! compiler's a11y warnings, so components get accessibility feedback when
+//! the compiler can't run. Each compiler warning is its own rule
+//! ([`A11yRule`]) reporting under the compiler's code and message, which lets
+//! the CLI drop the compiler's copy when both run. The element checks run
+//! once per document and are shared by all of these rules.
 
-use crate::{Diagnostic, DiagnosticCode};
-use svelte_parser::{ElseBranch, SvelteDocument, TemplateNode};
+mod aria;
+mod checks;
+mod elements;
+
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use svelte_parser::{SvelteDocument, TemplateNode};
 
 /// Runs a11y checks on a document.
-pub fn check(doc: &SvelteDocument) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-    let mut last_heading: Option<u8> = None;
-    check_fragment(&doc.fragment.nodes, &mut diagnostics, &mut last_heading);
-    diagnostics
+pub fn check(doc: &SvelteDocument, source: &str) -> Vec<Diagnostic> {
+    HeadingStructure.check(&RuleContext::new(doc, source, None))
 }
 
-fn check_fragment(
-    nodes: &[TemplateNode],
-    diagnostics: &mut Vec<Diagnostic>,
-    last_heading: &mut Option<u8>,
-) {
-    for node in nodes {
-        match node {
-            TemplateNode::Element(el) => {
-                if let Some(level) = get_heading_level(el.name.as_str()) {
-                    if let Some(prev_level) = *last_heading {
-                        if level > prev_level + 1 {
-                            diagnostics.push(Diagnostic::new(
-                                DiagnosticCode::A11yStructure,
-                                format!(
-                                    "A11y: Heading levels should not be skipped (h{} followed by h{})",
-                                    prev_level, level
-                                ),
-                                el.span,
-                            ));
-                        }
-                    }
-                    *last_heading = Some(level);
-                }
+/// `a11y-structure`: heading levels should not skip a level.
+pub struct HeadingStructure;
 
-                check_fragment(&el.children, diagnostics, last_heading);
-            }
-            TemplateNode::Component(comp) => {
-                check_fragment(&comp.children, diagnostics, last_heading);
-            }
-            TemplateNode::SvelteElement(el) => {
-                check_fragment(&el.children, diagnostics, last_heading);
-            }
-            TemplateNode::IfBlock(block) => {
-                check_if_block(block, diagnostics, last_heading);
-            }
-            TemplateNode::EachBlock(block) => {
-                check_fragment(&block.body.nodes, diagnostics, last_heading);
-                if let Some(fallback) = &block.fallback {
-                    check_fragment(&fallback.nodes, diagnostics, last_heading);
-                }
-            }
-            TemplateNode::AwaitBlock(block) => {
-                if let Some(pending) = &block.pending {
-                    check_fragment(&pending.nodes, diagnostics, last_heading);
-                }
-                if let Some(then) = &block.then {
-                    check_fragment(&then.body.nodes, diagnostics, last_heading);
-                }
-                if let Some(catch) = &block.catch {
-                    check_fragment(&catch.body.nodes, diagnostics, last_heading);
-                }
-            }
-            TemplateNode::KeyBlock(block) => {
-                check_fragment(&block.body.nodes, diagnostics, last_heading);
-            }
-            TemplateNode::SnippetBlock(block) => {
-                check_fragment(&block.body.nodes, diagnostics, last_heading);
-            }
-            TemplateNode::Text(_)
-            | TemplateNode::Comment(_)
-            | TemplateNode::Expression(_)
-            | TemplateNode::HtmlTag(_)
-            | TemplateNode::ConstTag(_)
-            | TemplateNode::DeclarationTag(_)
-            | TemplateNode::DebugTag(_)
-            | TemplateNode::RenderTag(_) => {}
-        }
+static HEADING_STRUCTURE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("a11y-structure"),
+    category: RuleCategory::A11y,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for HeadingStructure {
+    fn meta(&self) -> &'static RuleMeta {
+        &HEADING_STRUCTURE
     }
-}
 
-fn check_if_block(
-    block: &svelte_parser::IfBlock,
-    diagnostics: &mut Vec<Diagnostic>,
-    last_heading: &mut Option<u8>,
-) {
-    check_fragment(&block.consequent.nodes, diagnostics, last_heading);
-    if let Some(alternate) = &block.alternate {
-        match alternate {
-            ElseBranch::Else(fragment) => {
-                check_fragment(&fragment.nodes, diagnostics, last_heading);
-            }
-            ElseBranch::ElseIf(nested) => {
-                check_if_block(nested, diagnostics, last_heading);
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut last_heading: Option<u8> = None;
+        for visited in ctx.traversal().nodes() {
+            let TemplateNode::Element(el) = visited.node else {
+                continue;
+            };
+            let Some(level) = get_heading_level(el.name.as_str()) else {
+                continue;
+            };
+            if let Some(prev_level) = last_heading {
+                if level > prev_level + 1 {
+                    diagnostics.push(Diagnostic::new(
+                        HEADING_STRUCTURE.code,
+                        format!(
+                            "A11y: Heading levels should not be skipped (h{} followed by h{})",
+                            prev_level, level
+                        ),
+                        el.span,
+                    ));
+                }
             }
+            last_heading = Some(level);
         }
+        diagnostics
     }
 }
 
@@ -114,24 +75,124 @@ fn get_heading_level(tag: &str) -> Option<u8> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use svelte_parser::parse;
-
-    #[test]
-    fn test_heading_structure_valid() {
-        let doc = parse(r#"<h1>Title</h1><h2>Section</h2><h3>Subsection</h3>"#).document;
-        let diagnostics = check(&doc);
-        assert!(diagnostics.is_empty());
+macro_rules! a11y_codes {
+    ($($variant:ident => $code:literal,)*) => {
+        /// The a11y warning codes of the Svelte compiler.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum A11yCode {
+            $(
+                #[doc = concat!("`", $code, "`")]
+                $variant,
+            )*
+        }
+
+        impl A11yCode {
+            /// All codes, in alphabetical order.
+            pub const ALL: &'static [A11yCode] = &[$(A11yCode::$variant),*];
+
+            /// Returns the compiler's code.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(A11yCode::$variant => $code,)*
+                }
+            }
+
+            fn meta(&self) -> &'static RuleMeta {
+                match self {
+                    $(A11yCode::$variant => {
+                        static META: RuleMeta = RuleMeta {
+                            code: DiagnosticCode::A11y(A11yCode::$variant),
+                            category: RuleCategory::A11y,
+                            default_severity: Severity::Warning,
+                            docs_url: Some(concat!(
+                                "https://svelte.dev/docs/svelte/compiler-warnings#",
+                                $code
+                            )),
+                            fixable: false,
+                        };
+                        &META
+                    })*
+                }
+            }
+        }
+    };
+}
+
+a11y_codes! {
+    Accesskey => "a11y_accesskey",
+    AriaActivedescendantHasTabindex => "a11y_aria_activedescendant_has_tabindex",
+    AriaAttributes => "a11y_aria_attributes",
+    AutocompleteValid => "a11y_autocomplete_valid",
+    Autofocus => "a11y_autofocus",
+    ClickEventsHaveKeyEvents => "a11y_click_events_have_key_events",
+    ConsiderExplicitLabel => "a11y_consider_explicit_label",
+    DistractingElements => "a11y_distracting_elements",
+    FigcaptionIndex => "a11y_figcaption_index",
+    FigcaptionParent => "a11y_figcaption_parent",
+    Hidden => "a11y_hidden",
+    ImgRedundantAlt => "a11y_img_redundant_alt",
+    IncorrectAriaAttributeType => "a11y_incorrect_aria_attribute_type",
+    IncorrectAriaAttributeTypeBoolean => "a11y_incorrect_aria_attribute_type_boolean",
+    IncorrectAriaAttributeTypeId => "a11y_incorrect_aria_attribute_type_id",
+    IncorrectAriaAttributeTypeIdlist => "a11y_incorrect_aria_attribute_type_idlist",
+    IncorrectAriaAttributeTypeInteger => "a11y_incorrect_aria_attribute_type_integer",
+    IncorrectAriaAttributeTypeToken => "a11y_incorrect_aria_attribute_type_token",
+    IncorrectAriaAttributeTypeTokenlist => "a11y_incorrect_aria_attribute_type_tokenlist",
+    IncorrectAriaAttributeTypeTristate => "a11y_incorrect_aria_attribute_type_tristate",
+    InteractiveSupportsFocus => "a11y_interactive_supports_focus",
+    InvalidAttribute => "a11y_invalid_attribute",
+    LabelHasAssociatedControl => "a11y_label_has_associated_control",
+    MediaHasCaption => "a11y_media_has_caption",
+    MisplacedRole => "a11y_misplaced_role",
+    MisplacedScope => "a11y_misplaced_scope",
+    MissingAttribute => "a11y_missing_attribute",
+    MissingContent => "a11y_missing_content",
+    MouseEventsHaveKeyEvents => "a11y_mouse_events_have_key_events",
+    NoAbstractRole => "a11y_no_abstract_role",
+    NoInteractiveElementToNoninteractiveRole => "a11y_no_interactive_element_to_noninteractive_role",
+    NoNoninteractiveElementInteractions => "a11y_no_noninteractive_element_interactions",
+    NoNoninteractiveElementToInteractiveRole => "a11y_no_noninteractive_element_to_interactive_role",
+    NoNoninteractiveTabindex => "a11y_no_noninteractive_tabindex",
+    NoRedundantRoles => "a11y_no_redundant_roles",
+    NoStaticElementInteractions => "a11y_no_static_element_interactions",
+    PositiveTabindex => "a11y_positive_tabindex",
+    RoleHasRequiredAriaProps => "a11y_role_has_required_aria_props",
+    RoleSupportsAriaProps => "a11y_role_supports_aria_props",
+    RoleSupportsAriaPropsImplicit => "a11y_role_supports_aria_props_implicit",
+    UnknownAriaAttribute => "a11y_unknown_aria_attribute",
+    UnknownRole => "a11y_unknown_role",
+}
+
+/// A ported compiler a11y warning.
+pub struct A11yRule(pub A11yCode);
+
+impl Rule for A11yRule {
+    fn meta(&self) -> &'static RuleMeta {
+        self.0.meta()
     }
 
-    #[test]
-    fn test_heading_structure_skipped() {
-        let doc = parse(r#"<h1>Title</h1><h3>Skipped h2</h3>"#).document;
-        let diagnostics = check(&doc);
-        assert!(diagnostics
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let code = DiagnosticCode::A11y(self.0);
+        ctx.a11y_warnings()
             .iter()
-            .any(|d| matches!(d.code, DiagnosticCode::A11yStructure)));
+            .filter(|diagnostic| diagnostic.code == code)
+            .cloned()
+            .collect()
     }
 }
+
+/// Runs the compiler's element checks on every element of a document.
+pub(crate) fn compiler_warnings(ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+    let traversal = ctx.traversal();
+    let mut diagnostics = Vec::new();
+    for (index, visited) in traversal.nodes().iter().enumerate() {
+        if let TemplateNode::Element(el) = visited.node {
+            let element = elements::ElementFacts::new(el, traversal, index);
+            checks::check_element(&element, &mut diagnostics);
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,641 @@
+use super::*;
+use svelte_parser::parse;
+
+#[test]
+fn test_heading_structure_valid() {
+    let source = r#"<h1>Title</h1><h2>Section</h2><h3>Subsection</h3>"#;
+    let doc = parse(source).document;
+    let diagnostics = check(&doc, source);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn test_heading_structure_skipped() {
+    let source = r#"<h1>Title</h1><h3>Skipped h2</h3>"#;
+    let doc = parse(source).document;
+    let diagnostics = check(&doc, source);
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code.as_str() == "a11y-structure"));
+}
+
+fn compiler_codes(source: &str) -> Vec<&'static str> {
+    let doc = parse(source).document;
+    crate::check(&doc, source, crate::DiagnosticOptions::all())
+        .into_iter()
+        .filter(|d| matches!(d.code, DiagnosticCode::A11y(_)))
+        .map(|d| d.code.as_str())
+        .collect()
+}
+
+#[test]
+fn test_missing_alt() {
+    let source = r#"<img src="a.png"><img src="b.png" alt="">"#;
+    let doc = parse(source).document;
+    let diagnostics = crate::check(&doc, source, crate::DiagnosticOptions::all());
+    let missing: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code.as_str() == "a11y_missing_attribute")
+        .collect();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(
+        missing[0].message,
+        "`<img>` element should have an alt attribute"
+    );
+    assert_eq!(u32::from(missing[0].span.start), 0);
+}
+
+#[test]
+fn test_click_handler_on_div() {
+    assert_eq!(
+        compiler_codes(r#"<div onclick={go}>x</div>"#),
+        [
+            "a11y_click_events_have_key_events",
+            "a11y_no_static_element_interactions"
+        ]
+    );
+    assert!(compiler_codes(r#"<button onclick={go}>x</button>"#).is_empty());
+    assert!(compiler_codes(r#"<div {...rest} onclick={go}>x</div>"#).is_empty());
+}
+
+#[test]
+fn test_misspelled_aria_names() {
+    let source = r#"<div aria-labeledby="x" role="buton">x</div>"#;
+    let doc = parse(source).document;
+    let messages: Vec<_> = crate::check(&doc, source, crate::DiagnosticOptions::all())
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    assert!(messages.contains(
+        &"Unknown aria attribute 'aria-labeledby'. Did you mean 'labelledby'?".to_string()
+    ));
+    assert!(messages.contains(&"Unknown role 'buton'. Did you mean 'button'?".to_string()));
+}
+
+#[test]
+fn test_redundant_role() {
+    assert_eq!(
+        compiler_codes(r#"<nav role="navigation"><a href="/">Home</a></nav>"#),
+        ["a11y_no_redundant_roles"]
+    );
+}
+
+#[test]
+fn test_dynamic_values_are_not_checked() {
+    assert!(compiler_codes(r#"<a href={url} role={role}>Link</a>"#).is_empty());
+}
+
+#[test]
+fn test_svelte_ignore() {
+    let source = "<!-- svelte-ignore a11y_missing_attribute -->\n<img src=\"a.png\">";
+    assert!(compiler_codes(source).is_empty());
+}
+
+#[test]
+fn test_codes_are_sorted() {
+    assert!(A11yCode::ALL
+        .windows(2)
+        .all(|pair| pair[0].as_str() < pair[1].as_str()));
+}
+
+/// Returns the messages reported under one compiler a11y code.
+fn messages(source: &str, code: &str) -> Vec<String> {
+    let doc = parse(source).document;
+    crate::check(&doc, source, crate::DiagnosticOptions::all())
+        .into_iter()
+        .filter(|d| d.code.as_str() == code)
+        .map(|d| d.message)
+        .collect()
+}
+
+#[test]
+fn test_accesskey() {
+    assert_eq!(
+        messages(r#"<div accesskey="z">x</div>"#, "a11y_accesskey"),
+        ["Avoid using accesskey"]
+    );
+    assert!(messages("<div>x</div>", "a11y_accesskey").is_empty());
+}
+
+#[test]
+fn test_aria_activedescendant_has_tabindex() {
+    let code = "a11y_aria_activedescendant_has_tabindex";
+    assert_eq!(
+        messages(r#"<div aria-activedescendant="item">x</div>"#, code),
+        ["An element with an aria-activedescendant attribute should have a tabindex value"]
+    );
+    assert!(messages(
+        r#"<div aria-activedescendant="item" tabindex="-1">x</div>"#,
+        code
+    )
+    .is_empty());
+    assert!(messages(r#"<input aria-activedescendant="item">"#, code).is_empty());
+}
+
+#[test]
+fn test_aria_attributes() {
+    assert_eq!(
+        messages(r#"<meta aria-hidden="false">"#, "a11y_aria_attributes"),
+        ["`<meta>` should not have aria-* attributes"]
+    );
+    assert!(messages(
+        r#"<div aria-hidden="false">x</div>"#,
+        "a11y_aria_attributes"
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_autocomplete_valid() {
+    let code = "a11y_autocomplete_valid";
+    assert_eq!(
+        messages(r#"<input type="text" autocomplete="incorrect">"#, code),
+        ["'incorrect' is an invalid value for 'autocomplete' on `<input type=\"text\">`"]
+    );
+    assert_eq!(
+        messages(r#"<input type="text" autocomplete>"#, code),
+        ["'true' is an invalid value for 'autocomplete' on `<input type=\"text\">`"]
+    );
+    assert!(messages(r#"<input type="email" autocomplete="work email">"#, code).is_empty());
+    assert!(messages(r#"<input type="text" autocomplete={value}>"#, code).is_empty());
+}
+
+#[test]
+fn test_autofocus() {
+    assert_eq!(
+        messages("<input autofocus>", "a11y_autofocus"),
+        ["Avoid using autofocus"]
+    );
+    assert!(messages("<dialog><input autofocus></dialog>", "a11y_autofocus").is_empty());
+}
+
+#[test]
+fn test_click_events_have_key_events() {
+    let code = "a11y_click_events_have_key_events";
+    assert_eq!(
+        messages("<div onclick={go}>x</div>", code),
+        ["Visible, non-interactive elements with a click event must be accompanied by a keyboard event handler. Consider whether an interactive element such as `<button type=\"button\">` or `<a>` might be more appropriate"]
+    );
+    assert!(messages("<div onclick={go} onkeydown={go}>x</div>", code).is_empty());
+    assert!(messages(r#"<div aria-hidden="true" onclick={go}>x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_consider_explicit_label() {
+    let code = "a11y_consider_explicit_label";
+    assert_eq!(
+        messages("<button></button>", code),
+        ["Buttons and links should either contain text or have an `aria-label`, `aria-labelledby` or `title` attribute"]
+    );
+    assert!(messages(r#"<button aria-label="Close"></button>"#, code).is_empty());
+    assert!(messages(r#"<a href="/"><img src="a.png" alt="Home"></a>"#, code).is_empty());
+}
+
+#[test]
+fn test_distracting_elements() {
+    assert_eq!(
+        messages("<marquee>News</marquee>", "a11y_distracting_elements"),
+        ["Avoid `<marquee>` elements"]
+    );
+    assert!(messages("<p>News</p>", "a11y_distracting_elements").is_empty());
+}
+
+#[test]
+fn test_figcaption_index() {
+    let code = "a11y_figcaption_index";
+    assert_eq!(
+        messages(
+            r#"<figure><img src="a.png" alt="A"><figcaption>Caption</figcaption><p>More</p></figure>"#,
+            code
+        ),
+        ["`<figcaption>` must be first or last child of `<figure>`"]
+    );
+    assert!(messages(
+        r#"<figure><img src="a.png" alt="A"><figcaption>Caption</figcaption></figure>"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_figcaption_parent() {
+    let code = "a11y_figcaption_parent";
+    assert_eq!(
+        messages("<div><figcaption>Caption</figcaption></div>", code),
+        ["`<figcaption>` must be an immediate child of `<figure>`"]
+    );
+    assert!(messages("<figure><figcaption>Caption</figcaption></figure>", code).is_empty());
+}
+
+#[test]
+fn test_hidden() {
+    assert_eq!(
+        messages(r#"<h2 aria-hidden="true">Title</h2>"#, "a11y_hidden"),
+        ["`<h2>` element should not be hidden"]
+    );
+    assert!(messages(r#"<p aria-hidden="true">Text</p>"#, "a11y_hidden").is_empty());
+}
+
+#[test]
+fn test_img_redundant_alt() {
+    let code = "a11y_img_redundant_alt";
+    assert_eq!(
+        messages(r#"<img src="a.png" alt="Photo of a cat">"#, code),
+        ["Screenreaders already announce `<img>` elements as an image"]
+    );
+    assert!(messages(r#"<img src="a.png" alt="A cat">"#, code).is_empty());
+    assert!(messages(
+        r#"<img src="a.png" alt="Photography" aria-hidden="true">"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type() {
+    let code = "a11y_incorrect_aria_attribute_type";
+    assert_eq!(
+        messages(r#"<div aria-label="">x</div>"#, code),
+        ["The value of 'aria-label' must be a non-empty string"]
+    );
+    assert_eq!(
+        messages(r#"<input type="range" aria-valuenow="high">"#, code),
+        ["The value of 'aria-valuenow' must be a number"]
+    );
+    assert!(messages(r#"<div aria-label="Menu">x</div>"#, code).is_empty());
+    assert!(messages("<div aria-label={label}>x</div>", code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_boolean() {
+    let code = "a11y_incorrect_aria_attribute_type_boolean";
+    assert_eq!(
+        messages(r#"<div aria-busy="yes">x</div>"#, code),
+        ["The value of 'aria-busy' must be either 'true' or 'false'. It cannot be empty"]
+    );
+    assert!(messages(r#"<div aria-busy="true">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_id() {
+    // The compiler checks ID attributes like strings and reports them under
+    // the generic code, so this one never fires.
+    assert!(messages(
+        r#"<div aria-errormessage="">x</div>"#,
+        "a11y_incorrect_aria_attribute_type_id"
+    )
+    .is_empty());
+    assert_eq!(
+        messages(
+            r#"<div aria-errormessage="">x</div>"#,
+            "a11y_incorrect_aria_attribute_type"
+        ),
+        ["The value of 'aria-errormessage' must be a non-empty string"]
+    );
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_idlist() {
+    let code = "a11y_incorrect_aria_attribute_type_idlist";
+    assert_eq!(
+        messages(r#"<div aria-labelledby="">x</div>"#, code),
+        ["The value of 'aria-labelledby' must be a space-separated list of strings that represent DOM element IDs"]
+    );
+    assert!(messages(r#"<div aria-labelledby="title subtitle">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_integer() {
+    let code = "a11y_incorrect_aria_attribute_type_integer";
+    assert_eq!(
+        messages(r#"<div role="heading" aria-level="1.5">x</div>"#, code),
+        ["The value of 'aria-level' must be an integer"]
+    );
+    assert!(messages(r#"<div role="heading" aria-level="2">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_token() {
+    let code = "a11y_incorrect_aria_attribute_type_token";
+    assert_eq!(
+        messages(r#"<div aria-live="loud">x</div>"#, code),
+        ["The value of 'aria-live' must be exactly one of assertive, off or polite"]
+    );
+    assert!(messages(r#"<div aria-live="Polite">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_tokenlist() {
+    let code = "a11y_incorrect_aria_attribute_type_tokenlist";
+    assert_eq!(
+        messages(r#"<div aria-relevant="additions everything">x</div>"#, code),
+        ["The value of 'aria-relevant' must be a space-separated list of one or more of additions, all, removals or text"]
+    );
+    assert!(messages(r#"<div aria-relevant="additions text">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_incorrect_aria_attribute_type_tristate() {
+    let code = "a11y_incorrect_aria_attribute_type_tristate";
+    assert_eq!(
+        messages(
+            r#"<div role="checkbox" tabindex="0" aria-checked="maybe">x</div>"#,
+            code
+        ),
+        ["The value of 'aria-checked' must be exactly one of true, false, or mixed"]
+    );
+    assert!(messages(
+        r#"<div role="checkbox" tabindex="0" aria-checked="mixed">x</div>"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_interactive_supports_focus() {
+    let code = "a11y_interactive_supports_focus";
+    assert_eq!(
+        messages(
+            r#"<div role="button" onclick={go} onkeydown={go}>x</div>"#,
+            code
+        ),
+        ["Elements with the 'button' interactive role must have a tabindex value"]
+    );
+    assert!(messages(
+        r#"<div role="button" tabindex="0" onclick={go} onkeydown={go}>x</div>"#,
+        code
+    )
+    .is_empty());
+    assert!(messages(r#"<div role="button">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_invalid_attribute() {
+    let code = "a11y_invalid_attribute";
+    assert_eq!(
+        messages(r##"<a href="#">Top</a>"##, code),
+        ["'#' is not a valid href attribute"]
+    );
+    assert_eq!(
+        messages(r#"<a href="javascript:void(0)">Run</a>"#, code),
+        ["'javascript:void(0)' is not a valid href attribute"]
+    );
+    assert!(messages(r##"<a href="#top">Top</a>"##, code).is_empty());
+}
+
+#[test]
+fn test_label_has_associated_control() {
+    let code = "a11y_label_has_associated_control";
+    assert_eq!(
+        messages("<label>Name</label>", code),
+        ["A form label must be associated with a control"]
+    );
+    assert!(messages(r#"<label for="name">Name</label>"#, code).is_empty());
+    assert!(messages("<label>Name <input></label>", code).is_empty());
+    assert!(messages("<label>Name <Input /></label>", code).is_empty());
+}
+
+#[test]
+fn test_media_has_caption() {
+    let code = "a11y_media_has_caption";
+    assert_eq!(
+        messages(r#"<video src="a.mp4"></video>"#, code),
+        ["`<video>` elements must have a `<track kind=\"captions\">`"]
+    );
+    assert!(messages(
+        r#"<video src="a.mp4"><track kind="captions" src="a.vtt"></video>"#,
+        code
+    )
+    .is_empty());
+    assert!(messages(r#"<video src="a.mp4" muted></video>"#, code).is_empty());
+}
+
+#[test]
+fn test_misplaced_role() {
+    assert_eq!(
+        messages(r#"<meta role="button">"#, "a11y_misplaced_role"),
+        ["`<meta>` should not have role attribute"]
+    );
+    assert!(messages(r#"<div role="banner">x</div>"#, "a11y_misplaced_role").is_empty());
+}
+
+#[test]
+fn test_misplaced_scope() {
+    let code = "a11y_misplaced_scope";
+    assert_eq!(
+        messages(r#"<table><tr><td scope="row">x</td></tr></table>"#, code),
+        ["The scope attribute should only be used with `<th>` elements"]
+    );
+    assert!(messages(r#"<table><tr><th scope="row">x</th></tr></table>"#, code).is_empty());
+}
+
+#[test]
+fn test_missing_attribute() {
+    let code = "a11y_missing_attribute";
+    assert_eq!(
+        messages("<a>Home</a>", code),
+        ["`<a>` element should have a href attribute"]
+    );
+    assert_eq!(
+        messages(r#"<input type="image" src="a.png">"#, code),
+        ["`<input type=\"image\">` element should have an alt, aria-label or aria-labelledby attribute"]
+    );
+    assert_eq!(
+        messages("<iframe></iframe>", code),
+        ["`<iframe>` element should have a title attribute"]
+    );
+    assert!(messages(r#"<a id="top">Top</a>"#, code).is_empty());
+    assert!(messages(r#"<iframe title="Map"></iframe>"#, code).is_empty());
+}
+
+#[test]
+fn test_missing_content() {
+    let code = "a11y_missing_content";
+    assert_eq!(
+        messages("<h1></h1>", code),
+        ["`<h1>` element should contain text"]
+    );
+    assert!(messages("<h1>Title</h1>", code).is_empty());
+    assert!(messages(r#"<h1 aria-label="Title"></h1>"#, code).is_empty());
+}
+
+#[test]
+fn test_mouse_events_have_key_events() {
+    let code = "a11y_mouse_events_have_key_events";
+    assert_eq!(
+        messages(r#"<div onmouseover={show} onmouseout={hide}>x</div>"#, code),
+        [
+            "'mouseover' event must be accompanied by 'focus' event",
+            "'mouseout' event must be accompanied by 'blur' event",
+        ]
+    );
+    assert!(messages(
+        r#"<div onmouseover={show} onfocus={show} onmouseout={hide} onblur={hide}>x</div>"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_no_abstract_role() {
+    assert_eq!(
+        messages(r#"<div role="widget">x</div>"#, "a11y_no_abstract_role"),
+        ["Abstract role 'widget' is forbidden"]
+    );
+    assert!(messages(r#"<div role="region">x</div>"#, "a11y_no_abstract_role").is_empty());
+}
+
+#[test]
+fn test_no_interactive_element_to_noninteractive_role() {
+    let code = "a11y_no_interactive_element_to_noninteractive_role";
+    assert_eq!(
+        messages(r#"<button role="article">Post</button>"#, code),
+        ["`<button>` cannot have role 'article'"]
+    );
+    assert!(messages(r#"<button role="menuitem">Open</button>"#, code).is_empty());
+}
+
+#[test]
+fn test_no_noninteractive_element_interactions() {
+    let code = "a11y_no_noninteractive_element_interactions";
+    assert_eq!(
+        messages("<h1 onclick={go}>Title</h1>", code),
+        ["Non-interactive element `<h1>` should not be assigned mouse or keyboard event listeners"]
+    );
+    assert!(messages("<h1>Title</h1>", code).is_empty());
+    assert!(messages("<button onclick={go}>Go</button>", code).is_empty());
+}
+
+#[test]
+fn test_no_noninteractive_element_to_interactive_role() {
+    let code = "a11y_no_noninteractive_element_to_interactive_role";
+    assert_eq!(
+        messages(r#"<h1 role="button">Title</h1>"#, code),
+        ["Non-interactive element `<h1>` cannot have interactive role 'button'"]
+    );
+    assert!(messages(r#"<ul role="menu"><li>x</li></ul>"#, code).is_empty());
+}
+
+#[test]
+fn test_no_noninteractive_tabindex() {
+    let code = "a11y_no_noninteractive_tabindex";
+    assert_eq!(
+        messages(r#"<div tabindex="0">x</div>"#, code),
+        ["noninteractive element cannot have nonnegative tabIndex value"]
+    );
+    assert!(messages(r#"<div tabindex="-1">x</div>"#, code).is_empty());
+    assert!(messages(r#"<div role="button" tabindex="0">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_no_redundant_roles() {
+    let code = "a11y_no_redundant_roles";
+    assert_eq!(
+        messages(r#"<button role="button">Go</button>"#, code),
+        ["Redundant role 'button'"]
+    );
+    assert!(messages(r#"<ul role="list"><li>x</li></ul>"#, code).is_empty());
+}
+
+#[test]
+fn test_no_static_element_interactions() {
+    let code = "a11y_no_static_element_interactions";
+    assert_eq!(
+        messages("<div onkeydown={go} onmouseup={go}>x</div>", code),
+        ["`<div>` with a keydown or mouseup handler must have an ARIA role"]
+    );
+    assert!(messages(
+        r#"<div role="button" tabindex="0" onclick={go} onkeydown={go}>x</div>"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_positive_tabindex() {
+    let code = "a11y_positive_tabindex";
+    assert_eq!(
+        messages(r#"<button tabindex="1">Go</button>"#, code),
+        ["Avoid tabindex values above zero"]
+    );
+    assert!(messages(r#"<button tabindex="0">Go</button>"#, code).is_empty());
+}
+
+#[test]
+fn test_role_has_required_aria_props() {
+    let code = "a11y_role_has_required_aria_props";
+    assert_eq!(
+        messages(r#"<div role="checkbox" tabindex="0">x</div>"#, code),
+        ["Elements with the ARIA role \"checkbox\" must have the following attributes defined: \"aria-checked\""]
+    );
+    assert!(messages(
+        r#"<div role="checkbox" tabindex="0" aria-checked="false">x</div>"#,
+        code
+    )
+    .is_empty());
+    assert!(messages(r#"<input type="checkbox" role="checkbox">"#, code).is_empty());
+}
+
+#[test]
+fn test_role_supports_aria_props() {
+    let code = "a11y_role_supports_aria_props";
+    assert_eq!(
+        messages(
+            r#"<div role="link" tabindex="0" aria-checked="true">x</div>"#,
+            code
+        ),
+        ["The attribute 'aria-checked' is not supported by the role 'link'"]
+    );
+    assert!(messages(
+        r#"<div role="checkbox" tabindex="0" aria-checked="true">x</div>"#,
+        code
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_role_supports_aria_props_implicit() {
+    let code = "a11y_role_supports_aria_props_implicit";
+    assert_eq!(
+        messages(r#"<a href="/" aria-checked="true">Home</a>"#, code),
+        ["The attribute 'aria-checked' is not supported by the role 'link'. This role is implicit on the element `<a>`"]
+    );
+    assert!(messages(r#"<a href="/" aria-current="page">Home</a>"#, code).is_empty());
+}
+
+#[test]
+fn test_unknown_aria_attribute() {
+    let code = "a11y_unknown_aria_attribute";
+    assert_eq!(
+        messages(r#"<div aria-labeledby="title">x</div>"#, code),
+        ["Unknown aria attribute 'aria-labeledby'. Did you mean 'labelledby'?"]
+    );
+    assert_eq!(
+        messages(r#"<div aria-zzzzzzzz="x">x</div>"#, code),
+        ["Unknown aria attribute 'aria-zzzzzzzz'"]
+    );
+    assert!(messages(r#"<div aria-labelledby="title">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_unknown_role() {
+    let code = "a11y_unknown_role";
+    assert_eq!(
+        messages(r#"<div role="buton">x</div>"#, code),
+        ["Unknown role 'buton'. Did you mean 'button'?"]
+    );
+    assert_eq!(
+        messages(r#"<div role="zzzzzzzz">x</div>"#, code),
+        ["Unknown role 'zzzzzzzz'"]
+    );
+    assert!(messages(r#"<div role="button" tabindex="0">x</div>"#, code).is_empty());
+}
+
+#[test]
+fn test_every_code_is_tested() {
+    let source = include_str!("tests.rs");
+    for code in A11yCode::ALL {
+        let test = format!("fn test_{}(", &code.as_str()["a11y_".len()..]);
+        assert!(source.contains(&test), "missing {test}");
+    }
+}
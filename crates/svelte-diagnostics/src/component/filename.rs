@@ -0,0 +1,452 @@
+//! Checks that need the component's filename: `component-name-case`,
+//! `sveltekit-route-file`, `layout-missing-children` and `self-import`.
+//!
+//! Each rule does nothing when the filename is unknown, and
+//! `sveltekit-route-file` only runs in SvelteKit projects.
+
+use crate::project::graph::{normalize, resolve_relative};
+use crate::semantic::parse_module;
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use source_map::Span;
+use std::path::Path;
+use svelte_parser::{ScriptLang, TemplateNode};
+use swc_ecma_ast::{ImportSpecifier, ModuleDecl, ModuleItem};
+
+/// `component-name-case`: component filenames should be PascalCase.
+pub struct ComponentNameCase;
+
+static COMPONENT_NAME_CASE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("component-name-case"),
+    category: RuleCategory::Component,
+    // Some projects name components in kebab-case on purpose.
+    default_severity: Severity::Hint,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for ComponentNameCase {
+    fn meta(&self) -> &'static RuleMeta {
+        &COMPONENT_NAME_CASE
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let Some(name) = ctx.filename().and_then(component_name) else {
+            return Vec::new();
+        };
+        // SvelteKit route files are named by convention, not as components.
+        if name.starts_with('+') || is_pascal_case(name) {
+            return Vec::new();
+        }
+        vec![Diagnostic::new(
+            COMPONENT_NAME_CASE.code,
+            format!(
+                "Component filename '{name}.svelte' should be PascalCase ('{}.svelte')",
+                to_pascal_case(name)
+            ),
+            file_span(),
+        )]
+    }
+}
+
+/// `sveltekit-route-file`: misnamed or misplaced SvelteKit route files.
+pub struct SvelteKitRouteFile;
+
+static SVELTEKIT_ROUTE_FILE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("sveltekit-route-file"),
+    category: RuleCategory::Component,
+    // A naming convention; SvelteKit itself ignores such files.
+    default_severity: Severity::Warning,
+    docs_url: Some("https://svelte.dev/docs/kit/routing"),
+    fixable: false,
+};
+
+impl Rule for SvelteKitRouteFile {
+    fn meta(&self) -> &'static RuleMeta {
+        &SVELTEKIT_ROUTE_FILE
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let (Some(filename), Some(routes)) = (ctx.filename(), ctx.sveltekit_routes()) else {
+            return Vec::new();
+        };
+        let Some(name) = component_name(filename).filter(|name| name.starts_with('+')) else {
+            return Vec::new();
+        };
+        let message = match route_kind(name) {
+            RouteKind::Server => format!(
+                "'{name}' is a server module and must be a .js or .ts file, not a component"
+            ),
+            RouteKind::Unknown => format!(
+                "'{name}.svelte' is not a SvelteKit route file; files starting with '+' are reserved for +page, +layout and +error"
+            ),
+            RouteKind::Component if !Path::new(filename).starts_with(routes) => format!(
+                "'{name}.svelte' only has an effect inside the routes directory ({routes})"
+            ),
+            RouteKind::Component => return Vec::new(),
+        };
+        vec![Diagnostic::new(
+            SVELTEKIT_ROUTE_FILE.code,
+            message,
+            file_span(),
+        )]
+    }
+}
+
+/// `layout-missing-children`: `+layout.svelte` files that never render the
+/// page.
+///
+/// Only runs in SvelteKit projects, on layouts inside the routes directory.
+pub struct LayoutMissingChildren;
+
+static LAYOUT_MISSING_CHILDREN: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("layout-missing-children"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: Some("https://svelte.dev/docs/kit/routing#layout"),
+    fixable: false,
+};
+
+impl Rule for LayoutMissingChildren {
+    fn meta(&self) -> &'static RuleMeta {
+        &LAYOUT_MISSING_CHILDREN
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let (Some(filename), Some(routes)) = (ctx.filename(), ctx.sveltekit_routes()) else {
+            return Vec::new();
+        };
+        if !Path::new(filename).starts_with(routes) {
+            return Vec::new();
+        }
+        let Some(name) = component_name(filename) else {
+            return Vec::new();
+        };
+        if name != "+layout" && !name.starts_with("+layout@") {
+            return Vec::new();
+        }
+        let model = ctx.semantic();
+        if model.is_partial() {
+            return Vec::new();
+        }
+        // Legacy layouts render the page through `<slot />`.
+        let has_slot =
+            ctx.traversal().nodes().iter().any(
+                |visited| matches!(visited.node, TemplateNode::Element(el) if el.name == "slot"),
+            );
+        let uses_children = model
+            .references()
+            .iter()
+            .any(|reference| reference.name == "children");
+        if has_slot || uses_children {
+            return Vec::new();
+        }
+        vec![Diagnostic::new(
+            LAYOUT_MISSING_CHILDREN.code,
+            format!(
+                "'{name}.svelte' never renders its children, so no page below it will appear; add {{@render children()}}"
+            ),
+            file_span(),
+        )]
+    }
+}
+
+/// `self-import`: components that import themselves and then never render
+/// themselves, or render themselves unconditionally.
+///
+/// Importing itself is how a Svelte 5 component recurses, so a self-import
+/// that is rendered under a condition is fine.
+pub struct SelfImport;
+
+static SELF_IMPORT: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("self-import"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for SelfImport {
+    fn meta(&self) -> &'static RuleMeta {
+        &SELF_IMPORT
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let Some(filename) = ctx.filename() else {
+            return Vec::new();
+        };
+        let own_path = normalize(Path::new(filename));
+        let doc = ctx.doc();
+        let mut diagnostics = Vec::new();
+        for script in [&doc.module_script, &doc.instance_script]
+            .into_iter()
+            .flatten()
+        {
+            let typescript = script.lang == ScriptLang::TypeScript;
+            let Some((module, start_pos)) = parse_module(&script.content, typescript) else {
+                continue;
+            };
+            let base = u32::from(script.content_span.start);
+            for item in &module.body {
+                let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+                    continue;
+                };
+                let Some(specifier) = import.src.value.as_str() else {
+                    continue;
+                };
+                if resolve_relative(&own_path, specifier).as_ref() != Some(&own_path) {
+                    continue;
+                }
+                let local = import
+                    .specifiers
+                    .iter()
+                    .find_map(|specifier| match specifier {
+                        ImportSpecifier::Default(default) => Some(default.local.sym.as_str()),
+                        _ => None,
+                    });
+                let message = match local.map(|local| self_renders(ctx, local)) {
+                    Some(Renders::Never) | None => {
+                        "Component imports itself but never renders itself".to_string()
+                    }
+                    Some(Renders::Unconditionally) => {
+                        "Component renders itself unconditionally, which recurses forever; render it inside {#if} or {#each}".to_string()
+                    }
+                    Some(Renders::Conditionally) => continue,
+                };
+                let offset = |pos: swc_common::BytePos| base + (pos.0 - start_pos.0);
+                diagnostics.push(Diagnostic::new(
+                    SELF_IMPORT.code,
+                    message,
+                    Span::new(offset(import.span.lo), offset(import.span.hi)),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// How a component renders itself.
+enum Renders {
+    Never,
+    Conditionally,
+    Unconditionally,
+}
+
+/// Returns how the template renders the component `name`. Inside blocks,
+/// snippets and other components' content it may not render at all.
+fn self_renders(ctx: &RuleContext<'_>, name: &str) -> Renders {
+    let traversal = ctx.traversal();
+    let mut renders = Renders::Never;
+    for (index, visited) in traversal.nodes().iter().enumerate() {
+        let TemplateNode::Component(component) = visited.node else {
+            continue;
+        };
+        if component.name != name {
+            continue;
+        }
+        let conditional = traversal.ancestors(index).any(|ancestor| {
+            matches!(
+                ancestor.node,
+                TemplateNode::IfBlock(_)
+                    | TemplateNode::EachBlock(_)
+                    | TemplateNode::AwaitBlock(_)
+                    | TemplateNode::SnippetBlock(_)
+                    | TemplateNode::Component(_)
+                    | TemplateNode::SvelteElement(_)
+            )
+        });
+        if !conditional {
+            return Renders::Unconditionally;
+        }
+        renders = Renders::Conditionally;
+    }
+    renders
+}
+
+/// What a `+`-prefixed filename is to SvelteKit.
+enum RouteKind {
+    /// `+page`, `+layout` or `+error`, optionally with a `@` layout reset.
+    Component,
+    /// `+server`, `+page.server` or `+layout.server`.
+    Server,
+    Unknown,
+}
+
+fn route_kind(name: &str) -> RouteKind {
+    match name {
+        "+server" | "+page.server" | "+layout.server" => RouteKind::Server,
+        "+error" => RouteKind::Component,
+        _ => {
+            let base = name.split_once('@').map_or(name, |(base, _)| base);
+            if matches!(base, "+page" | "+layout") {
+                RouteKind::Component
+            } else {
+                RouteKind::Unknown
+            }
+        }
+    }
+}
+
+/// Returns a `.svelte` filename without its directory and extension.
+fn component_name(filename: &str) -> Option<&str> {
+    let file = filename.rsplit(['/', '\\']).next()?;
+    file.strip_suffix(".svelte").filter(|name| !name.is_empty())
+}
+
+/// Returns whether a name is PascalCase: it starts with an uppercase letter
+/// and has no separators.
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase) && name.chars().all(char::is_alphanumeric)
+}
+
+/// Converts `my-component` or `my_component` to `MyComponent`.
+fn to_pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The span filename diagnostics are reported at: the start of the file.
+fn file_span() -> Span {
+    Span::empty(0u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn messages(rule: &dyn Rule, filename: &str, source: &str) -> Vec<String> {
+        let doc = parse(source).document;
+        let ctx = RuleContext::new(&doc, source, Some(filename))
+            .with_sveltekit_routes(Some("src/routes"));
+        rule.check(&ctx)
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("my-component"), "MyComponent");
+        assert_eq!(to_pascal_case("my_component"), "MyComponent");
+        assert_eq!(to_pascal_case("mycomponent"), "Mycomponent");
+        assert_eq!(to_pascal_case("button"), "Button");
+    }
+
+    #[test]
+    fn test_component_name_case() {
+        assert_eq!(
+            messages(&ComponentNameCase, "src/lib/my-button.svelte", ""),
+            vec!["Component filename 'my-button.svelte' should be PascalCase ('MyButton.svelte')"]
+        );
+        for filename in [
+            "src/lib/MyButton.svelte",
+            "src/routes/+page.svelte",
+            "src/routes/+layout@.svelte",
+            "src/lib/store.svelte.ts",
+        ] {
+            assert!(
+                messages(&ComponentNameCase, filename, "").is_empty(),
+                "{filename}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sveltekit_route_files() {
+        assert_eq!(
+            messages(&SvelteKitRouteFile, "src/routes/api/+server.svelte", ""),
+            vec!["'+server' is a server module and must be a .js or .ts file, not a component"]
+        );
+        assert_eq!(
+            messages(&SvelteKitRouteFile, "src/routes/+pages.svelte", ""),
+            vec!["'+pages.svelte' is not a SvelteKit route file; files starting with '+' are reserved for +page, +layout and +error"]
+        );
+        assert_eq!(
+            messages(&SvelteKitRouteFile, "src/lib/+page.svelte", ""),
+            vec!["'+page.svelte' only has an effect inside the routes directory (src/routes)"]
+        );
+        assert_eq!(
+            messages(&SvelteKitRouteFile, "src/lib/routes/+page.svelte", ""),
+            vec!["'+page.svelte' only has an effect inside the routes directory (src/routes)"]
+        );
+        for filename in [
+            "src/routes/+page.svelte",
+            "src/routes/(app)/+layout@(app).svelte",
+            "src/routes/blog/+error.svelte",
+        ] {
+            assert!(
+                messages(&SvelteKitRouteFile, filename, "").is_empty(),
+                "{filename}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sveltekit_route_files_need_sveltekit() {
+        let doc = parse("").document;
+        let ctx = RuleContext::new(&doc, "", Some("src/lib/+pages.svelte"));
+        assert!(SvelteKitRouteFile.check(&ctx).is_empty());
+
+        let ctx = ctx.with_sveltekit_routes(Some("src/app"));
+        assert_eq!(SvelteKitRouteFile.check(&ctx).len(), 1);
+        let page = RuleContext::new(&doc, "", Some("src/app/blog/+page.svelte"))
+            .with_sveltekit_routes(Some("src/app"));
+        assert!(SvelteKitRouteFile.check(&page).is_empty());
+    }
+
+    #[test]
+    fn test_layout_missing_children() {
+        let filename = "src/routes/+layout.svelte";
+        assert_eq!(
+            messages(&LayoutMissingChildren, filename, "<nav>Menu</nav>"),
+            vec!["'+layout.svelte' never renders its children, so no page below it will appear; add {@render children()}"]
+        );
+        let runes = "<script>\n    let { children } = $props();\n</script>\n<nav>Menu</nav>\n{@render children()}";
+        assert!(messages(&LayoutMissingChildren, filename, runes).is_empty());
+        let legacy = "<nav>Menu</nav>\n<slot />";
+        assert!(messages(&LayoutMissingChildren, filename, legacy).is_empty());
+        assert!(messages(&LayoutMissingChildren, "src/routes/+page.svelte", "").is_empty());
+        assert!(messages(&LayoutMissingChildren, "src/lib/+layout.svelte", "").is_empty());
+    }
+
+    #[test]
+    fn test_layout_missing_children_needs_sveltekit() {
+        let doc = parse("").document;
+        let ctx = RuleContext::new(&doc, "", Some("src/routes/+layout.svelte"));
+        assert!(LayoutMissingChildren.check(&ctx).is_empty());
+
+        let ctx = ctx.with_sveltekit_routes(Some("src/routes"));
+        assert_eq!(LayoutMissingChildren.check(&ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_self_import() {
+        let filename = "src/lib/Tree.svelte";
+        let recursive = r#"<script>
+    import Tree from './Tree.svelte';
+    let { node } = $props();
+</script>
+{#each node.children as child}<Tree node={child} />{/each}"#;
+        assert!(messages(&SelfImport, filename, recursive).is_empty());
+
+        let forever = "<script>\n    import Tree from '../lib/Tree.svelte';\n</script>\n<Tree />";
+        assert_eq!(
+            messages(&SelfImport, filename, forever),
+            vec!["Component renders itself unconditionally, which recurses forever; render it inside {#if} or {#each}"]
+        );
+
+        let unused = "<script>\n    import Tree from './Tree.svelte';\n    import Leaf from './Leaf.svelte';\n</script>\n<Leaf />";
+        assert_eq!(
+            messages(&SelfImport, filename, unused),
+            vec!["Component imports itself but never renders itself"]
+        );
+    }
+}
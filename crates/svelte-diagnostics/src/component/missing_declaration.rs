@@ -0,0 +1,602 @@
+//! `missing-declaration`: template identifiers that are not declared.
+
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+
+/// Names the browser, the JavaScript runtime and Svelte provide without a
+/// declaration: the `declare var` and `declare function` globals of
+/// TypeScript's `lib.es*.d.ts` and `lib.dom.d.ts`. `HTML*` and `SVG*`
+/// interfaces are matched by prefix in [`is_global`]. Window properties with
+/// generic names (`name`, `status`, `top`, `open`, ...) are left out: in a
+/// template they are almost always a missing declaration. Sorted for binary
+/// search.
+const GLOBALS: &[&str] = &[
+    "$$events",
+    "$$props",
+    "$$restProps",
+    "$$slots",
+    "AbortController",
+    "AbortSignal",
+    "AbstractRange",
+    "AggregateError",
+    "AnalyserNode",
+    "Animation",
+    "AnimationEffect",
+    "AnimationEvent",
+    "AnimationPlaybackEvent",
+    "AnimationTimeline",
+    "Array",
+    "ArrayBuffer",
+    "Atomics",
+    "Attr",
+    "Audio",
+    "AudioBuffer",
+    "AudioBufferSourceNode",
+    "AudioContext",
+    "AudioDestinationNode",
+    "AudioListener",
+    "AudioNode",
+    "AudioParam",
+    "AudioProcessingEvent",
+    "AudioScheduledSourceNode",
+    "AudioWorklet",
+    "AudioWorkletNode",
+    "BarProp",
+    "BaseAudioContext",
+    "BeforeUnloadEvent",
+    "BigInt",
+    "BigInt64Array",
+    "BigUint64Array",
+    "BiquadFilterNode",
+    "Blob",
+    "BlobEvent",
+    "Boolean",
+    "BroadcastChannel",
+    "ByteLengthQueuingStrategy",
+    "CDATASection",
+    "CSS",
+    "CSSAnimation",
+    "CSSConditionRule",
+    "CSSContainerRule",
+    "CSSCounterStyleRule",
+    "CSSFontFaceRule",
+    "CSSGroupingRule",
+    "CSSImportRule",
+    "CSSKeyframeRule",
+    "CSSKeyframesRule",
+    "CSSLayerBlockRule",
+    "CSSMediaRule",
+    "CSSNamespaceRule",
+    "CSSPageRule",
+    "CSSRule",
+    "CSSRuleList",
+    "CSSStyleDeclaration",
+    "CSSStyleRule",
+    "CSSStyleSheet",
+    "CSSSupportsRule",
+    "CSSTransition",
+    "Cache",
+    "CacheStorage",
+    "CanvasGradient",
+    "CanvasPattern",
+    "CanvasRenderingContext2D",
+    "ChannelMergerNode",
+    "ChannelSplitterNode",
+    "CharacterData",
+    "Clipboard",
+    "ClipboardEvent",
+    "ClipboardItem",
+    "CloseEvent",
+    "Comment",
+    "CompositionEvent",
+    "CompressionStream",
+    "ConstantSourceNode",
+    "ConvolverNode",
+    "CountQueuingStrategy",
+    "Credential",
+    "CredentialsContainer",
+    "Crypto",
+    "CryptoKey",
+    "CustomElementRegistry",
+    "CustomEvent",
+    "DOMException",
+    "DOMImplementation",
+    "DOMMatrix",
+    "DOMMatrixReadOnly",
+    "DOMParser",
+    "DOMPoint",
+    "DOMPointReadOnly",
+    "DOMQuad",
+    "DOMRect",
+    "DOMRectList",
+    "DOMRectReadOnly",
+    "DOMStringList",
+    "DOMStringMap",
+    "DOMTokenList",
+    "DataTransfer",
+    "DataTransferItem",
+    "DataTransferItemList",
+    "DataView",
+    "Date",
+    "DecompressionStream",
+    "DelayNode",
+    "DeviceMotionEvent",
+    "DeviceOrientationEvent",
+    "Document",
+    "DocumentFragment",
+    "DocumentTimeline",
+    "DocumentType",
+    "DragEvent",
+    "DynamicsCompressorNode",
+    "Element",
+    "ElementInternals",
+    "Error",
+    "ErrorEvent",
+    "EvalError",
+    "Event",
+    "EventSource",
+    "EventTarget",
+    "File",
+    "FileList",
+    "FileReader",
+    "FileSystemDirectoryHandle",
+    "FileSystemFileHandle",
+    "FileSystemHandle",
+    "FinalizationRegistry",
+    "Float32Array",
+    "Float64Array",
+    "FocusEvent",
+    "FontFace",
+    "FontFaceSet",
+    "FontFaceSetLoadEvent",
+    "FormData",
+    "FormDataEvent",
+    "Function",
+    "GainNode",
+    "Gamepad",
+    "GamepadButton",
+    "GamepadEvent",
+    "Geolocation",
+    "GeolocationCoordinates",
+    "GeolocationPosition",
+    "GeolocationPositionError",
+    "HashChangeEvent",
+    "Headers",
+    "History",
+    "IDBCursor",
+    "IDBCursorWithValue",
+    "IDBDatabase",
+    "IDBFactory",
+    "IDBIndex",
+    "IDBKeyRange",
+    "IDBObjectStore",
+    "IDBOpenDBRequest",
+    "IDBRequest",
+    "IDBTransaction",
+    "IDBVersionChangeEvent",
+    "IIRFilterNode",
+    "IdleDeadline",
+    "Image",
+    "ImageBitmap",
+    "ImageBitmapRenderingContext",
+    "ImageData",
+    "Infinity",
+    "InputEvent",
+    "Int16Array",
+    "Int32Array",
+    "Int8Array",
+    "InternalError",
+    "IntersectionObserver",
+    "IntersectionObserverEntry",
+    "Intl",
+    "Iterator",
+    "JSON",
+    "KeyboardEvent",
+    "KeyframeEffect",
+    "Location",
+    "Lock",
+    "LockManager",
+    "Map",
+    "Math",
+    "MathMLElement",
+    "MediaCapabilities",
+    "MediaDeviceInfo",
+    "MediaDevices",
+    "MediaElementAudioSourceNode",
+    "MediaError",
+    "MediaKeys",
+    "MediaList",
+    "MediaMetadata",
+    "MediaQueryList",
+    "MediaQueryListEvent",
+    "MediaRecorder",
+    "MediaSession",
+    "MediaSource",
+    "MediaStream",
+    "MediaStreamAudioDestinationNode",
+    "MediaStreamAudioSourceNode",
+    "MediaStreamTrack",
+    "MessageChannel",
+    "MessageEvent",
+    "MessagePort",
+    "MimeType",
+    "MimeTypeArray",
+    "MouseEvent",
+    "MutationObserver",
+    "MutationRecord",
+    "NaN",
+    "NamedNodeMap",
+    "Navigator",
+    "Node",
+    "NodeFilter",
+    "NodeIterator",
+    "NodeList",
+    "Notification",
+    "Number",
+    "Object",
+    "OfflineAudioContext",
+    "OffscreenCanvas",
+    "OffscreenCanvasRenderingContext2D",
+    "Option",
+    "OscillatorNode",
+    "PageTransitionEvent",
+    "PannerNode",
+    "Path2D",
+    "Performance",
+    "PerformanceEntry",
+    "PerformanceMark",
+    "PerformanceMeasure",
+    "PerformanceNavigationTiming",
+    "PerformanceObserver",
+    "PerformanceObserverEntryList",
+    "PerformanceResourceTiming",
+    "PeriodicWave",
+    "PermissionStatus",
+    "Permissions",
+    "PictureInPictureWindow",
+    "Plugin",
+    "PluginArray",
+    "PointerEvent",
+    "PopStateEvent",
+    "ProcessingInstruction",
+    "ProgressEvent",
+    "Promise",
+    "PromiseRejectionEvent",
+    "Proxy",
+    "PublicKeyCredential",
+    "PushManager",
+    "PushSubscription",
+    "RTCDataChannel",
+    "RTCIceCandidate",
+    "RTCPeerConnection",
+    "RTCSessionDescription",
+    "RadioNodeList",
+    "Range",
+    "RangeError",
+    "ReadableByteStreamController",
+    "ReadableStream",
+    "ReadableStreamBYOBReader",
+    "ReadableStreamBYOBRequest",
+    "ReadableStreamDefaultController",
+    "ReadableStreamDefaultReader",
+    "ReferenceError",
+    "Reflect",
+    "RegExp",
+    "Request",
+    "ResizeObserver",
+    "ResizeObserverEntry",
+    "ResizeObserverSize",
+    "Response",
+    "Screen",
+    "ScreenOrientation",
+    "SecurityPolicyViolationEvent",
+    "Selection",
+    "ServiceWorker",
+    "ServiceWorkerContainer",
+    "ServiceWorkerRegistration",
+    "Set",
+    "ShadowRoot",
+    "SharedArrayBuffer",
+    "SharedWorker",
+    "SourceBuffer",
+    "SourceBufferList",
+    "SpeechSynthesis",
+    "SpeechSynthesisEvent",
+    "SpeechSynthesisUtterance",
+    "SpeechSynthesisVoice",
+    "StaticRange",
+    "StereoPannerNode",
+    "Storage",
+    "StorageEvent",
+    "StorageManager",
+    "String",
+    "StyleSheet",
+    "StyleSheetList",
+    "SubmitEvent",
+    "SubtleCrypto",
+    "Symbol",
+    "SyntaxError",
+    "Text",
+    "TextDecoder",
+    "TextDecoderStream",
+    "TextEncoder",
+    "TextEncoderStream",
+    "TextMetrics",
+    "TextTrack",
+    "TextTrackCue",
+    "TextTrackCueList",
+    "TextTrackList",
+    "TimeRanges",
+    "ToggleEvent",
+    "Touch",
+    "TouchEvent",
+    "TouchList",
+    "TrackEvent",
+    "TransformStream",
+    "TransformStreamDefaultController",
+    "TransitionEvent",
+    "TreeWalker",
+    "TypeError",
+    "UIEvent",
+    "URIError",
+    "URL",
+    "URLSearchParams",
+    "Uint16Array",
+    "Uint32Array",
+    "Uint8Array",
+    "Uint8ClampedArray",
+    "VTTCue",
+    "ValidityState",
+    "VideoFrame",
+    "VisualViewport",
+    "WaveShaperNode",
+    "WeakMap",
+    "WeakRef",
+    "WeakSet",
+    "WebAssembly",
+    "WebGL2RenderingContext",
+    "WebGLBuffer",
+    "WebGLProgram",
+    "WebGLRenderingContext",
+    "WebGLShader",
+    "WebGLTexture",
+    "WebSocket",
+    "WheelEvent",
+    "Window",
+    "Worker",
+    "Worklet",
+    "WritableStream",
+    "WritableStreamDefaultController",
+    "WritableStreamDefaultWriter",
+    "XMLDocument",
+    "XMLHttpRequest",
+    "XMLHttpRequestEventTarget",
+    "XMLHttpRequestUpload",
+    "XMLSerializer",
+    "XPathEvaluator",
+    "XPathExpression",
+    "XPathResult",
+    "XSLTProcessor",
+    "alert",
+    "arguments",
+    "atob",
+    "btoa",
+    "caches",
+    "cancelAnimationFrame",
+    "cancelIdleCallback",
+    "clearInterval",
+    "clearTimeout",
+    "clientInformation",
+    "confirm",
+    "console",
+    "createImageBitmap",
+    "crossOriginIsolated",
+    "crypto",
+    "customElements",
+    "decodeURI",
+    "decodeURIComponent",
+    "devicePixelRatio",
+    "document",
+    "encodeURI",
+    "encodeURIComponent",
+    "escape",
+    "eval",
+    "fetch",
+    "frameElement",
+    "getComputedStyle",
+    "getSelection",
+    "global",
+    "globalThis",
+    "history",
+    "indexedDB",
+    "innerHeight",
+    "innerWidth",
+    "isFinite",
+    "isNaN",
+    "isSecureContext",
+    "localStorage",
+    "location",
+    "locationbar",
+    "matchMedia",
+    "menubar",
+    "moveBy",
+    "moveTo",
+    "navigator",
+    "outerHeight",
+    "outerWidth",
+    "pageXOffset",
+    "pageYOffset",
+    "parseFloat",
+    "parseInt",
+    "performance",
+    "personalbar",
+    "postMessage",
+    "process",
+    "prompt",
+    "queueMicrotask",
+    "reportError",
+    "requestAnimationFrame",
+    "requestIdleCallback",
+    "resizeBy",
+    "resizeTo",
+    "screen",
+    "screenLeft",
+    "screenTop",
+    "screenX",
+    "screenY",
+    "scrollBy",
+    "scrollTo",
+    "scrollX",
+    "scrollY",
+    "scrollbars",
+    "sessionStorage",
+    "setInterval",
+    "setTimeout",
+    "speechSynthesis",
+    "statusbar",
+    "structuredClone",
+    "toolbar",
+    "undefined",
+    "unescape",
+    "visualViewport",
+    "window",
+];
+
+/// `missing-declaration`: identifiers in the template that no script,
+/// template scope or global declares.
+pub struct MissingDeclaration;
+
+static MISSING_DECLARATION: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("missing-declaration"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for MissingDeclaration {
+    fn meta(&self) -> &'static RuleMeta {
+        &MISSING_DECLARATION
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let model = ctx.semantic();
+        // Without a script's bindings every use of them would be reported.
+        if model.is_partial() {
+            return Vec::new();
+        }
+        model
+            .unresolved_references()
+            .filter(|reference| reference.in_template && !is_global(&reference.name))
+            .map(|reference| {
+                Diagnostic::new(
+                    MISSING_DECLARATION.code,
+                    format!("'{}' is not defined", reference.name),
+                    reference.span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns whether `name` needs no declaration. Runes are left to
+/// `invalid-rune-usage`.
+fn is_global(name: &str) -> bool {
+    GLOBALS.binary_search(&name).is_ok()
+        || name.starts_with("HTML")
+        || name.starts_with("SVG")
+        || super::RUNES
+            .iter()
+            .any(|rune| rune.split('.').next() == Some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn missing(source: &str) -> Vec<String> {
+        let doc = parse(source).document;
+        MissingDeclaration
+            .check(&RuleContext::new(&doc, source, None))
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_globals_are_sorted() {
+        assert!(GLOBALS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_reports_undeclared_template_identifiers() {
+        let source = r#"<script>
+    let count = 0;
+</script>
+<button on:click={() => (count = total)}>{count} {label}</button>
+<input bind:value={query} />
+{@render footer()}"#;
+        assert_eq!(
+            missing(source),
+            vec![
+                "'total' is not defined",
+                "'label' is not defined",
+                "'query' is not defined",
+                "'footer' is not defined",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declarations_from_every_scope() {
+        let source = r#"<script context="module">
+    export const PAGE_SIZE = 10;
+</script>
+<script>
+    import { writable } from 'svelte/store';
+    let { items } = $props();
+    const selected = writable(null);
+    $: total = items.length;
+</script>
+{#each items.slice(0, PAGE_SIZE) as item, i (item.id)}
+    {@const label = `${i}: ${item.name}`}
+    {@render row(label, $selected)}
+{/each}
+{#snippet row(text, active)}
+    <p class:active>{text} {total} {window.innerWidth} {console}</p>
+{/snippet}
+<p>{$$restProps.title}</p>"#;
+        assert!(missing(source).is_empty(), "{:?}", missing(source));
+    }
+
+    #[test]
+    fn test_browser_globals() {
+        let source = r#"<svelte:window on:resize={() => scrollTo(0, innerWidth)} />
+<p>{getSelection()} {new Audio()} {new Worker('w.js')} {Notification.permission}</p>
+<p>{AbortSignal.timeout(10)} {ReadableStream} {Atomics} {SVGElement} {HTMLInputElement}</p>
+<p>{PointerEvent} {SubmitEvent} {InputEvent} {FocusEvent}</p>
+<p>{name} {status}</p>"#;
+        assert_eq!(
+            missing(source),
+            vec!["'name' is not defined", "'status' is not defined"]
+        );
+    }
+
+    #[test]
+    fn test_reactive_destructuring_declares_names() {
+        let source = r#"<script>
+    export let obj;
+    export let pair;
+    $: ({ a, b: renamed, c = 1 } = obj);
+    $: [x, , ...rest] = pair;
+</script>
+<p>{a} {renamed} {c} {x} {rest} {b}</p>"#;
+        assert_eq!(missing(source), vec!["'b' is not defined"]);
+    }
+
+    #[test]
+    fn test_skips_components_with_unparsable_scripts() {
+        let source = "<script>\n    let = ;\n</script>\n<p>{count}</p>";
+        assert!(missing(source).is_empty());
+    }
+}
@@ -4,12 +4,27 @@
 //! - Invalid rune usage
 //! - Component naming conventions
 //! - Missing declarations
-
-use crate::{Diagnostic, DiagnosticCode};
-use svelte_parser::{
-    AwaitBlock, EachBlock, ElseBranch, Fragment, IfBlock, KeyBlock, SnippetBlock, SvelteDocument,
-    SvelteElement, TemplateNode,
-};
+//! - Unused props, snippets and derived values, and `$state` that is never
+//!   reassigned
+//! - Reactivity hazards in `$effect` and `$derived`
+//! - Filename conventions: PascalCase components, SvelteKit route files and
+//!   self-imports
+//! - Runes and syntax newer than the installed Svelte version
+
+mod filename;
+mod missing_declaration;
+pub(crate) mod reactivity;
+mod unavailable;
+mod unused;
+
+pub use filename::{ComponentNameCase, LayoutMissingChildren, SelfImport, SvelteKitRouteFile};
+pub use missing_declaration::MissingDeclaration;
+pub use reactivity::{DerivedSideEffect, EffectUpdateLoop, MissingStateSnapshot, PreferDerived};
+pub use unavailable::UnavailableFeature;
+pub use unused::{UnreassignedState, UnusedDerived, UnusedProp, UnusedSnippet};
+
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use svelte_parser::{SvelteDocument, TemplateNode};
 
 /// Rune function names that are only valid in specific contexts.
 const RUNES: &[&str] = &[
@@ -23,6 +38,7 @@ const RUNES: &[&str] = &[
     "$effect.tracking",
     "$effect.root",
     "$props",
+    "$props.id",
     "$bindable",
     "$inspect",
     "$inspect.trace",
@@ -36,43 +52,50 @@ pub struct ComponentCheckOptions {
     pub filename: Option<String>,
 }
 
-/// Runs component checks on a document.
-pub fn check(doc: &SvelteDocument, _options: &ComponentCheckOptions) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-
-    // Check for invalid rune usage outside script blocks
-    diagnostics.extend(check_template_rune_usage(doc));
-
+/// Runs the template rune check and, given a filename, the component name
+/// check on a document. Other component rules run through the
+/// [`registry`](crate::registry).
+pub fn check(
+    doc: &SvelteDocument,
+    source: &str,
+    options: &ComponentCheckOptions,
+) -> Vec<Diagnostic> {
+    let ctx = RuleContext::new(doc, source, options.filename.as_deref());
+    let mut diagnostics = TemplateRuneUsage.check(&ctx);
+    diagnostics.extend(ComponentNameCase.check(&ctx));
     diagnostics
 }
 
-/// Checks for rune usage in template expressions (which is invalid).
-fn check_template_rune_usage(doc: &SvelteDocument) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
+/// `invalid-rune-usage`: runes called from template expressions.
+pub struct TemplateRuneUsage;
 
-    // Check for runes in template nodes
-    check_fragment_for_runes(&doc.fragment, &mut diagnostics);
-
-    diagnostics
-}
+static TEMPLATE_RUNE_USAGE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("invalid-rune-usage"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Error,
+    docs_url: None,
+    fixable: false,
+};
 
-/// Recursively checks a fragment for invalid rune usage.
-fn check_fragment_for_runes(fragment: &Fragment, diagnostics: &mut Vec<Diagnostic>) {
-    for node in &fragment.nodes {
-        check_node_for_runes(node, diagnostics);
+impl Rule for TemplateRuneUsage {
+    fn meta(&self) -> &'static RuleMeta {
+        &TEMPLATE_RUNE_USAGE
     }
-}
 
-/// Recursively checks a node for invalid rune usage.
-fn check_node_for_runes(node: &TemplateNode, diagnostics: &mut Vec<Diagnostic>) {
-    match node {
-        TemplateNode::Expression(expr_tag) => {
-            // Check if expression contains a rune call
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for visited in ctx.traversal().nodes() {
+            // NOTE: declaration tags legitimately allow runes in their
+            // initializer (e.g. `{let label = $state(...)}`), so only plain
+            // expression tags are checked.
+            let TemplateNode::Expression(expr_tag) = visited.node else {
+                continue;
+            };
             let expr_text = &expr_tag.expression;
             for rune in RUNES {
                 if contains_rune_call(expr_text, rune) {
                     diagnostics.push(Diagnostic::new(
-                        DiagnosticCode::InvalidRuneUsage,
+                        TEMPLATE_RUNE_USAGE.code,
                         format!(
                             "{}() can only be used inside a $derived or $effect, or at the top level of a component",
                             rune
@@ -82,96 +105,10 @@ fn check_node_for_runes(node: &TemplateNode, diagnostics: &mut Vec<Diagnostic>)
                 }
             }
         }
-        TemplateNode::Element(element) => {
-            for child in &element.children {
-                check_node_for_runes(child, diagnostics);
-            }
-        }
-        TemplateNode::Component(component) => {
-            for child in &component.children {
-                check_node_for_runes(child, diagnostics);
-            }
-        }
-        TemplateNode::SvelteElement(svelte_element) => {
-            check_svelte_element_for_runes(svelte_element, diagnostics);
-        }
-        TemplateNode::IfBlock(if_block) => {
-            check_if_block_for_runes(if_block, diagnostics);
-        }
-        TemplateNode::EachBlock(each_block) => {
-            check_each_block_for_runes(each_block, diagnostics);
-        }
-        TemplateNode::AwaitBlock(await_block) => {
-            check_await_block_for_runes(await_block, diagnostics);
-        }
-        TemplateNode::KeyBlock(key_block) => {
-            check_key_block_for_runes(key_block, diagnostics);
-        }
-        TemplateNode::SnippetBlock(snippet_block) => {
-            check_snippet_block_for_runes(snippet_block, diagnostics);
-        }
-        // Text, comments, and tag nodes don't need checking.
-        // NOTE: declaration tags legitimately allow runes in their initializer
-        // (e.g. `{let label = $state(...)}`), so they MUST stay in the no-op
-        // group and must NOT be routed through the Expression rune-rejection
-        // logic above.
-        TemplateNode::Text(_)
-        | TemplateNode::Comment(_)
-        | TemplateNode::HtmlTag(_)
-        | TemplateNode::ConstTag(_)
-        | TemplateNode::DeclarationTag(_)
-        | TemplateNode::DebugTag(_)
-        | TemplateNode::RenderTag(_) => {}
+        diagnostics
     }
 }
 
-fn check_svelte_element_for_runes(element: &SvelteElement, diagnostics: &mut Vec<Diagnostic>) {
-    for child in &element.children {
-        check_node_for_runes(child, diagnostics);
-    }
-}
-
-fn check_if_block_for_runes(if_block: &IfBlock, diagnostics: &mut Vec<Diagnostic>) {
-    check_fragment_for_runes(&if_block.consequent, diagnostics);
-    if let Some(ref alternate) = if_block.alternate {
-        match alternate {
-            ElseBranch::Else(fragment) => {
-                check_fragment_for_runes(fragment, diagnostics);
-            }
-            ElseBranch::ElseIf(nested_if) => {
-                check_if_block_for_runes(nested_if, diagnostics);
-            }
-        }
-    }
-}
-
-fn check_each_block_for_runes(each_block: &EachBlock, diagnostics: &mut Vec<Diagnostic>) {
-    check_fragment_for_runes(&each_block.body, diagnostics);
-    if let Some(ref fallback) = each_block.fallback {
-        check_fragment_for_runes(fallback, diagnostics);
-    }
-}
-
-fn check_await_block_for_runes(await_block: &AwaitBlock, diagnostics: &mut Vec<Diagnostic>) {
-    if let Some(ref pending) = await_block.pending {
-        check_fragment_for_runes(pending, diagnostics);
-    }
-    if let Some(ref then) = await_block.then {
-        check_fragment_for_runes(&then.body, diagnostics);
-    }
-    if let Some(ref catch) = await_block.catch {
-        check_fragment_for_runes(&catch.body, diagnostics);
-    }
-}
-
-fn check_key_block_for_runes(key_block: &KeyBlock, diagnostics: &mut Vec<Diagnostic>) {
-    check_fragment_for_runes(&key_block.body, diagnostics);
-}
-
-fn check_snippet_block_for_runes(snippet_block: &SnippetBlock, diagnostics: &mut Vec<Diagnostic>) {
-    check_fragment_for_runes(&snippet_block.body, diagnostics);
-}
-
 /// Checks if an expression contains a rune function call.
 fn contains_rune_call(expr: &str, rune: &str) -> bool {
     // Simple check: look for the rune name followed by (
@@ -200,102 +137,91 @@ mod tests {
 
     #[test]
     fn test_empty_component() {
-        let doc = parse("").document;
-        let diagnostics = check(&doc, &ComponentCheckOptions::default());
+        let source = "";
+        let doc = parse(source).document;
+        let diagnostics = check(&doc, source, &ComponentCheckOptions::default());
         assert!(diagnostics.is_empty());
     }
 
-    // TODO: Add component name checking
-    // #[test]
-    // fn test_lowercase_component_name() {
-    //     let doc = parse("").document;
-    //     let options = ComponentCheckOptions {
-    //         filename: Some("myComponent.svelte".to_string()),
-    //     };
-    //     let diagnostics = check(&doc, &options);
-    //     assert_eq!(diagnostics.len(), 1);
-    //     assert!(matches!(
-    //         diagnostics[0].code,
-    //         DiagnosticCode::ComponentNameLowercase
-    //     ));
-    // }
+    #[test]
+    fn test_lowercase_component_name() {
+        let source = "";
+        let doc = parse(source).document;
+        let options = ComponentCheckOptions {
+            filename: Some("myComponent.svelte".to_string()),
+        };
+        let diagnostics = check(&doc, source, &options);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_str(), "component-name-case");
+    }
 
-    // #[test]
-    // fn test_pascalcase_component_name() {
-    //     let doc = parse("").document;
-    //     let options = ComponentCheckOptions {
-    //         filename: Some("MyComponent.svelte".to_string()),
-    //     };
-    //     let diagnostics = check(&doc, &options);
-    //     assert!(diagnostics.is_empty());
-    // }
+    #[test]
+    fn test_pascalcase_component_name() {
+        let source = "";
+        let doc = parse(source).document;
+        let options = ComponentCheckOptions {
+            filename: Some("MyComponent.svelte".to_string()),
+        };
+        let diagnostics = check(&doc, source, &options);
+        assert!(diagnostics.is_empty());
+    }
 
     #[test]
     fn test_sveltekit_special_files() {
-        let doc = parse("").document;
+        let source = "";
+        let doc = parse(source).document;
 
         // +page.svelte should not trigger warning
         let options = ComponentCheckOptions {
             filename: Some("+page.svelte".to_string()),
         };
-        let diagnostics = check(&doc, &options);
+        let diagnostics = check(&doc, source, &options);
         assert!(diagnostics.is_empty());
 
         // +layout.svelte should not trigger warning
         let options = ComponentCheckOptions {
             filename: Some("+layout.svelte".to_string()),
         };
-        let diagnostics = check(&doc, &options);
+        let diagnostics = check(&doc, source, &options);
         assert!(diagnostics.is_empty());
     }
 
     #[test]
     fn test_rune_in_template() {
-        let doc = parse(r#"<button>{$state(0)}</button>"#).document;
-        let diagnostics = check(&doc, &ComponentCheckOptions::default());
+        let source = r#"<button>{$state(0)}</button>"#;
+        let doc = parse(source).document;
+        let diagnostics = check(&doc, source, &ComponentCheckOptions::default());
         assert_eq!(diagnostics.len(), 1);
-        assert!(matches!(
-            diagnostics[0].code,
-            DiagnosticCode::InvalidRuneUsage
-        ));
+        assert_eq!(diagnostics[0].code.as_str(), "invalid-rune-usage");
     }
 
     #[test]
     fn test_derived_in_template() {
-        let doc = parse(r#"<span>{$derived(count * 2)}</span>"#).document;
-        let diagnostics = check(&doc, &ComponentCheckOptions::default());
+        let source = r#"<span>{$derived(count * 2)}</span>"#;
+        let doc = parse(source).document;
+        let diagnostics = check(&doc, source, &ComponentCheckOptions::default());
         assert_eq!(diagnostics.len(), 1);
-        assert!(matches!(
-            diagnostics[0].code,
-            DiagnosticCode::InvalidRuneUsage
-        ));
+        assert_eq!(diagnostics[0].code.as_str(), "invalid-rune-usage");
     }
 
     #[test]
     fn test_normal_expression_in_template() {
         // Regular expressions should not trigger warnings
-        let doc = parse(r#"<span>{count + 1}</span>"#).document;
-        let diagnostics = check(&doc, &ComponentCheckOptions::default());
+        let source = r#"<span>{count + 1}</span>"#;
+        let doc = parse(source).document;
+        let diagnostics = check(&doc, source, &ComponentCheckOptions::default());
         assert!(diagnostics.is_empty());
     }
 
     #[test]
     fn test_dollar_sign_not_rune() {
         // A regular $ variable should not trigger
-        let doc = parse(r#"<span>{$myStore}</span>"#).document;
-        let diagnostics = check(&doc, &ComponentCheckOptions::default());
+        let source = r#"<span>{$myStore}</span>"#;
+        let doc = parse(source).document;
+        let diagnostics = check(&doc, source, &ComponentCheckOptions::default());
         assert!(diagnostics.is_empty());
     }
 
-    // TODO: Add to_pascal_case helper
-    // #[test]
-    // fn test_to_pascal_case() {
-    //     assert_eq!(to_pascal_case("my-component"), "MyComponent");
-    //     assert_eq!(to_pascal_case("my_component"), "MyComponent");
-    //     assert_eq!(to_pascal_case("mycomponent"), "Mycomponent");
-    //     assert_eq!(to_pascal_case("button"), "Button");
-    // }
-
     #[test]
     fn test_contains_rune_call() {
         assert!(contains_rune_call("$state(0)", "$state"));
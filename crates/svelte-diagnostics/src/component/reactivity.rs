@@ -0,0 +1,633 @@
+//! Reactivity hazards in `$effect` and `$derived`: `effect-update-loop`,
+//! `derived-side-effect`, `prefer-derived` and `missing-state-snapshot`.
+//!
+//! The scripts are walked once per document for all four rules. Names are
+//! resolved through the [`SemanticModel`], so a hazard only involves
+//! `$state` the component actually declares.
+
+use crate::semantic::{parse_module, rune_callee, BindingId, BindingKind, SemanticModel};
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use source_map::Span;
+use svelte_parser::ScriptLang;
+use swc_common::{BytePos, Spanned};
+use swc_ecma_ast::{
+    ArrowExpr, AssignExpr, AssignOp, AssignTarget, BlockStmtOrExpr, CallExpr, Callee, Class, Expr,
+    ExprOrSpread, Function, Ident, MemberExpr, Pat, SimpleAssignTarget, Stmt, UpdateExpr,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// Array, `Map` and `Set` methods that mutate their receiver. Sorted for
+/// binary search.
+const MUTATING_METHODS: &[&str] = &[
+    "add",
+    "clear",
+    "copyWithin",
+    "delete",
+    "fill",
+    "pop",
+    "push",
+    "reverse",
+    "set",
+    "shift",
+    "sort",
+    "splice",
+    "unshift",
+];
+
+/// Methods that change the DOM, storage or history. Names shared with common
+/// non-DOM APIs (`append` on `FormData`, `remove` on `DOMTokenList`) are left
+/// out. Sorted for binary search.
+const SIDE_EFFECT_METHODS: &[&str] = &[
+    "appendChild",
+    "insertAdjacentElement",
+    "insertAdjacentHTML",
+    "insertBefore",
+    "prepend",
+    "pushState",
+    "removeAttribute",
+    "removeChild",
+    "removeItem",
+    "replaceChildren",
+    "replaceState",
+    "replaceWith",
+    "setAttribute",
+    "setItem",
+];
+
+macro_rules! hazard_rule {
+    ($rule:ident, $meta:ident, $code:literal, $severity:ident, $doc:literal) => {
+        #[doc = $doc]
+        pub struct $rule;
+
+        static $meta: RuleMeta = RuleMeta {
+            code: DiagnosticCode::Rule($code),
+            category: RuleCategory::Component,
+            default_severity: Severity::$severity,
+            docs_url: None,
+            fixable: false,
+        };
+
+        impl Rule for $rule {
+            fn meta(&self) -> &'static RuleMeta {
+                &$meta
+            }
+
+            fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+                ctx.reactivity_hazards()
+                    .iter()
+                    .filter(|diagnostic| diagnostic.code == $meta.code)
+                    .cloned()
+                    .collect()
+            }
+        }
+    };
+}
+
+hazard_rule!(
+    EffectUpdateLoop,
+    EFFECT_UPDATE_LOOP,
+    "effect-update-loop",
+    Warning,
+    "`effect-update-loop`: `$effect`s that write `$state` they also read."
+);
+hazard_rule!(
+    DerivedSideEffect,
+    DERIVED_SIDE_EFFECT,
+    "derived-side-effect",
+    Warning,
+    "`derived-side-effect`: assignments, `$state` mutations, `fetch` and DOM changes inside `$derived`."
+);
+hazard_rule!(
+    PreferDerived,
+    PREFER_DERIVED,
+    "prefer-derived",
+    Hint,
+    "`prefer-derived`: `$effect`s that only copy a reactive value into `$state`."
+);
+hazard_rule!(
+    MissingStateSnapshot,
+    MISSING_STATE_SNAPSHOT,
+    "missing-state-snapshot",
+    Warning,
+    "`missing-state-snapshot`: `$state` proxies passed to `structuredClone` or `postMessage`."
+);
+
+/// Runs the hazard analysis on both scripts of a document.
+pub(crate) fn hazards(ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+    let doc = ctx.doc();
+    let model = ctx.semantic();
+    let mut diagnostics = Vec::new();
+    for script in [&doc.module_script, &doc.instance_script]
+        .into_iter()
+        .flatten()
+    {
+        let typescript = script.lang == ScriptLang::TypeScript;
+        let Some((module, start_pos)) = parse_module(&script.content, typescript) else {
+            continue;
+        };
+        let mut walker = Walker {
+            positions: Positions {
+                base: u32::from(script.content_span.start),
+                start_pos,
+            },
+            model,
+            diagnostics: &mut diagnostics,
+        };
+        module.visit_with(&mut walker);
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+    diagnostics
+}
+
+/// Maps swc positions of a parsed script to document offsets.
+#[derive(Clone, Copy)]
+struct Positions {
+    base: u32,
+    start_pos: BytePos,
+}
+
+impl Positions {
+    fn span(&self, span: swc_common::Span) -> Span {
+        let offset = |pos: BytePos| self.base.wrapping_add(pos.0 - self.start_pos.0);
+        Span::new(offset(span.lo), offset(span.hi))
+    }
+
+    /// Returns the span of an identifier's name; swc extends binding
+    /// identifiers over their type annotation.
+    fn ident(&self, ident: &Ident) -> Span {
+        let start = u32::from(self.span(ident.span).start);
+        Span::new(start, start + ident.sym.len() as u32)
+    }
+}
+
+struct Walker<'m, 'd> {
+    positions: Positions,
+    model: &'m SemanticModel,
+    diagnostics: &'d mut Vec<Diagnostic>,
+}
+
+impl Walker<'_, '_> {
+    /// Returns the binding an identifier refers to.
+    fn binding_of(&self, ident: &Ident) -> Option<BindingId> {
+        self.model.binding_at(self.positions.ident(ident).start)
+    }
+
+    /// Returns the kind of the binding an identifier refers to.
+    fn kind_of(&self, ident: &Ident) -> Option<BindingKind> {
+        Some(self.model.binding(self.binding_of(ident)?).kind)
+    }
+
+    /// Returns whether any of `idents` refers to `binding`.
+    fn refers_to(&self, idents: &[Ident], binding: Option<BindingId>) -> bool {
+        binding.is_some() && idents.iter().any(|ident| self.binding_of(ident) == binding)
+    }
+
+    fn is_state(&self, ident: &Ident) -> bool {
+        matches!(
+            self.kind_of(ident),
+            Some(BindingKind::State | BindingKind::RawState)
+        )
+    }
+
+    /// Returns whether an identifier refers to a value a `$derived` could
+    /// track: state, a derived value or a prop.
+    fn is_reactive(&self, ident: &Ident) -> bool {
+        matches!(
+            self.kind_of(ident),
+            Some(
+                BindingKind::State
+                    | BindingKind::RawState
+                    | BindingKind::Derived
+                    | BindingKind::Prop
+                    | BindingKind::BindableProp
+                    | BindingKind::RestProp
+            )
+        )
+    }
+
+    fn effect(&mut self, call: &CallExpr) {
+        let Some(body) = call.args.first().and_then(|arg| callback_body(&arg.expr)) else {
+            return;
+        };
+        if let Some((target, value)) = single_assignment(&body) {
+            let mut accesses = Accesses::default();
+            value.visit_with(&mut accesses);
+            let target_binding = self.binding_of(target);
+            // Only a value computed from other reactive values is a derivation.
+            let derives = accesses
+                .reads
+                .iter()
+                .any(|read| self.is_reactive(read) && self.binding_of(read) != target_binding);
+            if self.is_state(target) && derives && !self.refers_to(&accesses.reads, target_binding)
+            {
+                self.diagnostics.push(Diagnostic::new(
+                    PREFER_DERIVED.code,
+                    format!(
+                        "This $effect only assigns '{}'; declare it with $derived instead",
+                        target.sym
+                    ),
+                    self.positions.span(call.span),
+                ));
+                return;
+            }
+        }
+
+        let mut accesses = Accesses::default();
+        body.visit_with(&mut accesses);
+        let mut reported: Vec<BindingId> = Vec::new();
+        for (ident, also_reads) in &accesses.writes {
+            let Some(binding) = self.binding_of(ident) else {
+                continue;
+            };
+            if reported.contains(&binding) || !self.is_state(ident) {
+                continue;
+            }
+            if *also_reads || self.refers_to(&accesses.reads, Some(binding)) {
+                reported.push(binding);
+                self.diagnostics.push(Diagnostic::new(
+                    EFFECT_UPDATE_LOOP.code,
+                    format!(
+                        "This $effect reads and writes '{}', which can cause an infinite update loop",
+                        ident.sym
+                    ),
+                    self.positions.ident(ident),
+                ));
+            }
+        }
+    }
+
+    fn derived(&mut self, call: &CallExpr) {
+        let mut effects = SideEffects {
+            walker: self,
+            scope: call.span,
+            found: Vec::new(),
+        };
+        call.args.visit_with(&mut effects);
+        let found = effects.found;
+        for (span, what) in found {
+            self.diagnostics.push(Diagnostic::new(
+                DERIVED_SIDE_EFFECT.code,
+                format!("$derived should not have side effects, but this {what}"),
+                self.positions.span(span),
+            ));
+        }
+    }
+
+    fn snapshot(&mut self, callee: &str, arg: Option<&ExprOrSpread>) {
+        let Some(Expr::Ident(ident)) = arg.map(|arg| &*arg.expr) else {
+            return;
+        };
+        // `$state.raw` values are not proxied.
+        if self.kind_of(ident) != Some(BindingKind::State) {
+            return;
+        }
+        self.diagnostics.push(Diagnostic::new(
+            MISSING_STATE_SNAPSHOT.code,
+            format!(
+                "'{name}' is a $state proxy, which {callee} cannot clone; pass $state.snapshot({name}) instead",
+                name = ident.sym
+            ),
+            self.positions.ident(ident),
+        ));
+    }
+}
+
+impl Visit for Walker<'_, '_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call) = expr {
+            match rune_callee(expr).as_deref() {
+                Some("$effect" | "$effect.pre") => self.effect(call),
+                Some("$derived" | "$derived.by") => self.derived(call),
+                Some("structuredClone") => self.snapshot("structuredClone", call.args.first()),
+                Some(callee) if callee.ends_with(".postMessage") => {
+                    self.snapshot("postMessage", call.args.first())
+                }
+                _ => {}
+            }
+        }
+        expr.visit_children_with(self);
+    }
+}
+
+/// Returns the body of an inline callback.
+fn callback_body(expr: &Expr) -> Option<BlockStmtOrExpr> {
+    match expr {
+        Expr::Arrow(arrow) => Some((*arrow.body).clone()),
+        Expr::Fn(function) => function
+            .function
+            .body
+            .clone()
+            .map(BlockStmtOrExpr::BlockStmt),
+        _ => None,
+    }
+}
+
+/// Returns the target and value of a callback body that is a single
+/// `name = value` assignment.
+fn single_assignment(body: &BlockStmtOrExpr) -> Option<(&Ident, &Expr)> {
+    let expr = match body {
+        BlockStmtOrExpr::Expr(expr) => &**expr,
+        BlockStmtOrExpr::BlockStmt(block) => match block.stmts.as_slice() {
+            [Stmt::Expr(stmt)] => &*stmt.expr,
+            _ => return None,
+        },
+    };
+    let Expr::Assign(assign) = expr else {
+        return None;
+    };
+    let AssignTarget::Simple(SimpleAssignTarget::Ident(target)) = &assign.left else {
+        return None;
+    };
+    (assign.op == AssignOp::Assign).then_some((&target.id, &*assign.right))
+}
+
+/// The identifiers an effect reads and writes when it runs. Nested functions
+/// run later (or inside `untrack`), so they are skipped.
+#[derive(Default)]
+struct Accesses {
+    reads: Vec<Ident>,
+    /// Written identifiers, and whether the write reads the old value too
+    /// (`count++`, `count += 1`).
+    writes: Vec<(Ident, bool)>,
+}
+
+impl Visit for Accesses {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.reads.push(ident.clone());
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        let compound = assign.op != AssignOp::Assign;
+        match &assign.left {
+            AssignTarget::Simple(SimpleAssignTarget::Ident(target)) => {
+                self.writes.push((target.id.clone(), compound));
+            }
+            AssignTarget::Pat(pat) => {
+                let mut targets = PatTargets::default();
+                pat.visit_with(&mut targets);
+                self.writes
+                    .extend(targets.0.into_iter().map(|ident| (ident, false)));
+            }
+            left => left.visit_with(self),
+        }
+        assign.right.visit_with(self);
+    }
+
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        match &*update.arg {
+            Expr::Ident(ident) => self.writes.push((ident.clone(), true)),
+            arg => arg.visit_with(self),
+        }
+    }
+
+    fn visit_arrow_expr(&mut self, _arrow: &ArrowExpr) {}
+
+    fn visit_function(&mut self, _function: &Function) {}
+
+    fn visit_class(&mut self, _class: &Class) {}
+}
+
+/// The identifiers a destructuring assignment writes.
+#[derive(Default)]
+struct PatTargets(Vec<Ident>);
+
+impl Visit for PatTargets {
+    fn visit_binding_ident(&mut self, ident: &swc_ecma_ast::BindingIdent) {
+        self.0.push(ident.id.clone());
+    }
+
+    // Default values are not targets.
+    fn visit_expr(&mut self, _expr: &Expr) {}
+
+    fn visit_pat(&mut self, pat: &Pat) {
+        if let Pat::Expr(expr) = pat {
+            if let Expr::Ident(ident) = &**expr {
+                self.0.push(ident.clone());
+            }
+            return;
+        }
+        pat.visit_children_with(self);
+    }
+}
+
+/// Collects the side effects inside a `$derived` call. Names declared inside
+/// the call are local, so assigning them is fine.
+struct SideEffects<'w, 'm, 'd> {
+    walker: &'w Walker<'m, 'd>,
+    scope: swc_common::Span,
+    found: Vec<(swc_common::Span, String)>,
+}
+
+impl SideEffects<'_, '_, '_> {
+    /// Returns whether `ident` refers to something declared outside the
+    /// `$derived` call (including globals).
+    fn is_outer(&self, ident: &Ident) -> bool {
+        let positions = self.walker.positions;
+        let model = self.walker.model;
+        let scope = positions.span(self.scope);
+        match model.binding_at(positions.ident(ident).start) {
+            Some(binding) => {
+                let declared = model.binding(binding).span;
+                declared.start < scope.start || scope.end < declared.end
+            }
+            None => true,
+        }
+    }
+
+    fn assigned(&mut self, target: &Expr, span: swc_common::Span, verb: &str) {
+        match target {
+            Expr::Ident(ident) if self.is_outer(ident) => {
+                self.found.push((span, format!("{verb} '{}'", ident.sym)));
+            }
+            Expr::Member(member) => {
+                if let Some(root) = member_root(member).filter(|root| self.is_outer(root)) {
+                    self.found.push((span, format!("mutates '{}'", root.sym)));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit for SideEffects<'_, '_, '_> {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        match &assign.left {
+            AssignTarget::Simple(SimpleAssignTarget::Ident(target)) => {
+                self.assigned(&Expr::Ident(target.id.clone()), assign.span, "assigns to")
+            }
+            AssignTarget::Simple(SimpleAssignTarget::Member(member)) => {
+                self.assigned(&Expr::Member(member.clone()), assign.span, "assigns to")
+            }
+            _ => {}
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        self.assigned(&update.arg, update.span, "updates");
+        update.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            match &**callee {
+                Expr::Ident(ident) if ident.sym == "fetch" && self.is_outer(ident) => {
+                    self.found.push((call.span(), "calls fetch()".into()));
+                }
+                Expr::Member(member) => {
+                    let method = member.prop.as_ident().map(|prop| prop.sym.as_str());
+                    let root = member_root(member).filter(|root| self.is_outer(root));
+                    if let (Some(method), Some(root)) = (method, root) {
+                        if SIDE_EFFECT_METHODS.binary_search(&method).is_ok() {
+                            self.found.push((call.span(), format!("calls {method}()")));
+                        } else if MUTATING_METHODS.binary_search(&method).is_ok()
+                            && self.walker.is_state(root)
+                        {
+                            self.found.push((
+                                call.span(),
+                                format!("mutates '{}' with {method}()", root.sym),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+/// Returns the identifier a member chain starts at (`a` in `a.b.c`).
+fn member_root(member: &MemberExpr) -> Option<&Ident> {
+    match &*member.obj {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Member(inner) => member_root(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn messages(rule: &dyn Rule, script: &str) -> Vec<String> {
+        let source = format!("<script>\n{script}\n</script>");
+        let doc = parse(&source).document;
+        rule.check(&RuleContext::new(&doc, &source, None))
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_side_effect_methods_are_sorted() {
+        assert!(SIDE_EFFECT_METHODS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(MUTATING_METHODS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_effect_update_loop() {
+        let script = r#"
+    let count = $state(0);
+    let log = $state([]);
+    let total = $state(0);
+    let ready = $state(false);
+    $effect(() => {
+        count++;
+        log = [...log, count];
+        total = 1;
+        setTimeout(() => (ready = !ready));
+    });
+    let value = $state(0);
+    const values = [1, 2];
+    $effect(() => {
+        for (const value of values) console.log(value);
+        value = values.length;
+    });"#;
+        assert_eq!(
+            messages(&EffectUpdateLoop, script),
+            vec![
+                "This $effect reads and writes 'count', which can cause an infinite update loop",
+                "This $effect reads and writes 'log', which can cause an infinite update loop",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prefer_derived() {
+        let script = r#"
+    let count = $state(0);
+    let double = $state(0);
+    let plain = 0;
+    $effect(() => {
+        double = count * 2;
+    });
+    $effect(() => (plain = count));
+    let items = $state([]);
+    $effect(() => {
+        items = [];
+    });
+    $effect.pre(() => {
+        double = count * 2;
+        console.log(double);
+    });"#;
+        assert_eq!(
+            messages(&PreferDerived, script),
+            vec!["This $effect only assigns 'double'; declare it with $derived instead"]
+        );
+    }
+
+    #[test]
+    fn test_derived_side_effects() {
+        let script = r#"
+    let count = $state(0);
+    let calls = 0;
+    const cache = {};
+    const form = new FormData();
+    let items = $state([]);
+    const log = [];
+    const a = $derived(calls++ + count);
+    const c = $derived(items.push(count) + log.push(count));
+    const b = $derived.by(() => {
+        let sum = 0;
+        const parts = [];
+        for (let i = 0; i < count; i++) sum += i;
+        parts.push(sum);
+        cache.last = sum;
+        form.append('sum', sum);
+        fetch('/log');
+        document.body.appendChild(document.createElement('p'));
+        return sum;
+    });"#;
+        assert_eq!(
+            messages(&DerivedSideEffect, script),
+            vec![
+                "$derived should not have side effects, but this updates 'calls'",
+                "$derived should not have side effects, but this mutates 'items' with push()",
+                "$derived should not have side effects, but this mutates 'cache'",
+                "$derived should not have side effects, but this calls fetch()",
+                "$derived should not have side effects, but this calls appendChild()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_state_snapshot() {
+        let script = r#"
+    let items = $state([]);
+    let raw = $state.raw({});
+    const worker = new Worker('w.js');
+    const copy = structuredClone(items);
+    const ok = structuredClone($state.snapshot(items));
+    worker.postMessage(items);
+    worker.postMessage(raw);"#;
+        assert_eq!(
+            messages(&MissingStateSnapshot, script),
+            vec![
+                "'items' is a $state proxy, which structuredClone cannot clone; pass $state.snapshot(items) instead",
+                "'items' is a $state proxy, which postMessage cannot clone; pass $state.snapshot(items) instead",
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! `unavailable-feature`: syntax and runes newer than the installed Svelte.
+//!
+//! Does nothing unless the installed version is known.
+
+use crate::version::SvelteFeature;
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use source_map::Span;
+use svelte_parser::{Attribute, TemplateNode};
+
+/// `unavailable-feature`: features the installed Svelte version lacks.
+pub struct UnavailableFeature;
+
+static UNAVAILABLE_FEATURE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unavailable-feature"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Error,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for UnavailableFeature {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNAVAILABLE_FEATURE
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let Some(version) = ctx.svelte_version() else {
+            return Vec::new();
+        };
+        let mut used = Vec::new();
+        for reference in ctx.semantic().unresolved_references() {
+            if let Some(found) = rune_member(ctx.source(), &reference.name, reference.span) {
+                used.push(found);
+            }
+        }
+        for visited in ctx.traversal().nodes() {
+            let attributes = match visited.node {
+                TemplateNode::Element(el) => &el.attributes,
+                TemplateNode::Component(comp) => &comp.attributes,
+                TemplateNode::SvelteElement(el) => &el.attributes,
+                TemplateNode::DeclarationTag(tag) => {
+                    used.push((SvelteFeature::DeclarationTag, tag.span));
+                    continue;
+                }
+                _ => continue,
+            };
+            for attribute in attributes {
+                if let Attribute::Attach(attach) = attribute {
+                    used.push((SvelteFeature::AttachTag, attach.span));
+                }
+            }
+        }
+
+        used.into_iter()
+            .filter(|(feature, _)| !version.supports(*feature))
+            .map(|(feature, span)| {
+                Diagnostic::new(
+                    UNAVAILABLE_FEATURE.code,
+                    format!(
+                        "{} requires Svelte {} or later, but {version} is installed",
+                        feature.name(),
+                        feature.introduced_in()
+                    ),
+                    span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the gated rune a reference such as `$state` starts, with the span
+/// of `$state.eager`.
+fn rune_member(source: &str, name: &str, span: Span) -> Option<(SvelteFeature, Span)> {
+    if !name.starts_with('$') {
+        return None;
+    }
+    let end = usize::from(span.end);
+    let rest = source.get(end..)?.strip_prefix('.')?;
+    let len = rest
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '$'))
+        .unwrap_or(rest.len());
+    let feature = SvelteFeature::for_rune(&format!("{name}.{}", &rest[..len]))?;
+    Some((feature, Span::new(span.start, (end + 1 + len) as u32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UNAVAILABLE_FEATURE;
+    use crate::version::SvelteVersion;
+    use crate::{check, DiagnosticOptions};
+    use svelte_parser::parse;
+
+    fn unavailable(source: &str, version: Option<SvelteVersion>) -> Vec<String> {
+        let doc = parse(source).document;
+        let mut options = DiagnosticOptions::all();
+        options.svelte_version = version;
+        check(&doc, source, options)
+            .into_iter()
+            .filter(|d| d.code == UNAVAILABLE_FEATURE.code)
+            .map(|d| source[d.span.start.into()..d.span.end.into()].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_runes_newer_than_installed() {
+        let source = "<script>\n  let id = $props.id();\n  let count = $state.eager(0);\n</script>\n\n<p>{id} {count}</p>";
+        assert_eq!(
+            unavailable(source, Some(SvelteVersion::new(5, 20, 0))),
+            vec!["$state.eager"]
+        );
+        assert_eq!(
+            unavailable(source, Some(SvelteVersion::new(5, 0, 0))),
+            vec!["$props.id", "$state.eager"]
+        );
+    }
+
+    #[test]
+    fn test_flags_template_syntax_newer_than_installed() {
+        let source = "<div {@attach tooltip}></div>\n{#if open}{const label = 'x'}{label}{/if}";
+        let found = unavailable(source, Some(SvelteVersion::new(5, 28, 0)));
+        assert_eq!(found, vec!["{@attach tooltip}", "{const label = 'x'}"]);
+    }
+
+    #[test]
+    fn test_unknown_version_assumes_latest() {
+        let source = "<div {@attach tooltip}></div>";
+        assert!(unavailable(source, None).is_empty());
+        assert!(unavailable(source, Some(SvelteVersion::new(5, 29, 0))).is_empty());
+    }
+}
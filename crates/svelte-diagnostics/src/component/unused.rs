@@ -0,0 +1,347 @@
+//! Declarations that are never used: `unused-prop`, `unused-snippet`,
+//! `unused-derived` and `unreassigned-state`.
+//!
+//! The compiler reports none of these (it only warns about unused
+//! `export let` props in legacy mode), and tsgo only reports unused props
+//! under `noUnusedLocals` and never bindable ones, which the generated code
+//! marks as used.
+
+use crate::semantic::{Binding, BindingId, BindingKind, SemanticModel};
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use svelte_parser::TemplateNode;
+
+/// `unused-prop`: `$props()` properties that are never read.
+pub struct UnusedProp;
+
+static UNUSED_PROP: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unused-prop"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for UnusedProp {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNUSED_PROP
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let model = ctx.semantic();
+        if model.is_partial() || !model.has_props_rune() {
+            return Vec::new();
+        }
+        // With a rest element, destructuring a prop is also how it is kept
+        // out of the rest (`let { class: _, ...rest } = $props()`).
+        if instance_bindings(model).any(|(_, binding)| binding.kind == BindingKind::RestProp) {
+            return Vec::new();
+        }
+        instance_bindings(model)
+            .filter(|(id, binding)| match binding.kind {
+                BindingKind::Prop => !is_read(model, *id),
+                // Writes to a bindable prop reach the parent.
+                BindingKind::BindableProp => model.references_to(*id).next().is_none(),
+                _ => false,
+            })
+            .filter(|(_, binding)| !binding.name.starts_with('_'))
+            .map(|(_, binding)| {
+                Diagnostic::new(
+                    UNUSED_PROP.code,
+                    format!("Prop '{}' is never used", binding.name),
+                    binding.span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// `unused-snippet`: snippets that are never rendered or passed on.
+pub struct UnusedSnippet;
+
+static UNUSED_SNIPPET: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unused-snippet"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for UnusedSnippet {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNUSED_SNIPPET
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let model = ctx.semantic();
+        if model.is_partial() {
+            return Vec::new();
+        }
+        model
+            .binding_ids()
+            .map(|id| (id, model.binding(id)))
+            .filter(|(id, binding)| {
+                binding.kind == BindingKind::Snippet
+                    && model.references_to(*id).next().is_none()
+                    && !is_component_prop(ctx, binding)
+            })
+            .map(|(_, binding)| {
+                Diagnostic::new(
+                    UNUSED_SNIPPET.code,
+                    format!(
+                        "Snippet '{}' is never rendered or passed to a component",
+                        binding.name
+                    ),
+                    binding.span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// `unused-derived`: `$derived` values that are never read.
+pub struct UnusedDerived;
+
+static UNUSED_DERIVED: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unused-derived"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for UnusedDerived {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNUSED_DERIVED
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let model = ctx.semantic();
+        if model.is_partial() {
+            return Vec::new();
+        }
+        model
+            .binding_ids()
+            .map(|id| (id, model.binding(id)))
+            .filter(|(id, binding)| {
+                binding.kind == BindingKind::Derived
+                    && !binding.name.starts_with('_')
+                    && !is_read(model, *id)
+            })
+            .map(|(_, binding)| {
+                Diagnostic::new(
+                    UNUSED_DERIVED.code,
+                    format!("Derived value '{}' is never read", binding.name),
+                    binding.span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// `unreassigned-state`: `let` declarations with `$state` that are never
+/// reassigned.
+pub struct UnreassignedState;
+
+static UNREASSIGNED_STATE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unreassigned-state"),
+    category: RuleCategory::Component,
+    // A style suggestion rather than a likely bug.
+    default_severity: Severity::Hint,
+    docs_url: None,
+    fixable: false,
+};
+
+impl Rule for UnreassignedState {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNREASSIGNED_STATE
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        let model = ctx.semantic();
+        if model.is_partial() {
+            return Vec::new();
+        }
+        model
+            .binding_ids()
+            .map(|id| (id, model.binding(id)))
+            .filter(|(id, binding)| {
+                matches!(binding.kind, BindingKind::State | BindingKind::RawState)
+                    && !binding.is_const
+                    && !model.references_to(*id).any(|reference| reference.is_write)
+            })
+            .map(|(_, binding)| {
+                Diagnostic::new(
+                    UNREASSIGNED_STATE.code,
+                    format!(
+                        "'{}' is declared with $state but never reassigned. Declare it with `const`, or use a plain value if it is never mutated either",
+                        binding.name
+                    ),
+                    binding.span,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the bindings of the instance script.
+fn instance_bindings(model: &SemanticModel) -> impl Iterator<Item = (BindingId, &Binding)> {
+    model
+        .binding_ids()
+        .map(|id| (id, model.binding(id)))
+        .filter(|(_, binding)| binding.scope == model.instance_scope())
+}
+
+/// Returns whether anything reads `binding`.
+fn is_read(model: &SemanticModel, binding: BindingId) -> bool {
+    model
+        .references_to(binding)
+        .any(|reference| reference.is_read)
+}
+
+/// Returns whether a snippet is declared directly inside a component, which
+/// passes it as a prop.
+fn is_component_prop(ctx: &RuleContext<'_>, snippet: &Binding) -> bool {
+    let nodes = ctx.traversal().nodes();
+    // The innermost snippet block containing the name is the one declaring
+    // it; nested blocks start after the name.
+    nodes
+        .iter()
+        .filter(|visited| match visited.node {
+            TemplateNode::SnippetBlock(block) => {
+                block.span.start <= snippet.span.start && snippet.span.end <= block.span.end
+            }
+            _ => false,
+        })
+        .max_by_key(|visited| visited.node.span().start)
+        .and_then(|visited| visited.parent)
+        .is_some_and(|parent| {
+            matches!(
+                nodes[parent].node,
+                TemplateNode::Component(_) | TemplateNode::SvelteElement(_)
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn messages(rule: &dyn Rule, source: &str) -> Vec<String> {
+        let doc = parse(source).document;
+        rule.check(&RuleContext::new(&doc, source, None))
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_props() {
+        let source = r#"<script>
+    let { title, subtitle, open = $bindable(false), value = $bindable(), _internal, size = 'md' } = $props();
+    subtitle = 'x';
+    value = 1;
+</script>
+<h1>{title}</h1>"#;
+        assert_eq!(
+            messages(&UnusedProp, source),
+            vec![
+                "Prop 'subtitle' is never used",
+                "Prop 'open' is never used",
+                "Prop 'size' is never used",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_updated_props_are_used() {
+        let source = r#"<script>
+    let { count = $bindable(0), step, visits } = $props();
+    function increment() {
+        count += step;
+        visits++;
+    }
+</script>
+<button onclick={increment}>+</button>"#;
+        assert!(messages(&UnusedProp, source).is_empty());
+    }
+
+    #[test]
+    fn test_unused_props_skips_rest_and_legacy_props() {
+        let rest = r#"<script>
+    let { class: _, variant, ...rest } = $props();
+</script>
+<button {...rest}>x</button>"#;
+        assert!(messages(&UnusedProp, rest).is_empty());
+
+        let legacy = "<script>\n    export let name;\n</script>";
+        assert!(messages(&UnusedProp, legacy).is_empty());
+    }
+
+    #[test]
+    fn test_unused_snippets() {
+        let source = r#"<script module>
+    export { shared };
+</script>
+<script>
+    import Table from './Table.svelte';
+    let { children } = $props();
+</script>
+{#snippet shared()}<p>exported</p>{/snippet}
+{#snippet row(item)}<td>{item}</td>{/snippet}
+{#snippet orphan()}<p>never</p>{/snippet}
+{#snippet passed()}<p>prop</p>{/snippet}
+<Table {row} footer={passed}>
+    {#snippet header()}
+        {#snippet nested()}<b>unused</b>{/snippet}
+        <th>Name</th>
+    {/snippet}
+</Table>
+{@render children()}"#;
+        assert_eq!(
+            messages(&UnusedSnippet, source),
+            vec![
+                "Snippet 'orphan' is never rendered or passed to a component",
+                "Snippet 'nested' is never rendered or passed to a component",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_derived() {
+        let source = r#"<script>
+    let count = $state(0);
+    const double = $derived(count * 2);
+    const triple = $derived.by(() => count * 3);
+</script>
+<button onclick={() => count++}>{double}</button>"#;
+        assert_eq!(
+            messages(&UnusedDerived, source),
+            vec!["Derived value 'triple' is never read"]
+        );
+    }
+
+    #[test]
+    fn test_unreassigned_state() {
+        let source = r#"<script>
+    let count = $state(0);
+    let name = $state('');
+    let items = $state([]);
+    let open = $state(false);
+    const todos = $state([]);
+    items.push(1);
+</script>
+<input bind:value={name} />
+<button onclick={() => count++}>{items.length} {todos.length} {open}</button>"#;
+        let messages = messages(&UnreassignedState, source);
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with("'items' is declared with $state"));
+        assert!(messages[1].starts_with("'open' is declared with $state"));
+    }
+
+    #[test]
+    fn test_skips_components_with_unparsable_scripts() {
+        let source = "<script>\n    let = ;\n    let x = $state(0);\n</script>";
+        assert!(messages(&UnreassignedState, source).is_empty());
+    }
+}
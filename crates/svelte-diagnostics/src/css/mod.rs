@@ -0,0 +1,753 @@
+//! Stylesheet checks.
+//!
+//! Reports `css_unused_selector` for selectors in a component's `<style>` that
+//! cannot match any element in its template, mirroring the Svelte compiler's
+//! warning so it is available without running the compiler.
+//!
+//! The matching errs on the side of "used": anything whose rendered shape is
+//! not known statically (dynamic `class`/`id` values, spread attributes,
+//! `<svelte:element>`, component slot content, snippet bodies, `{@render}`
+//! output) is assumed to be able to match.
+
+use crate::{Diagnostic, DiagnosticCode, Rule, RuleCategory, RuleContext, RuleMeta, Severity};
+use std::collections::HashSet;
+use svelte_parser::css::{
+    AttributeSelector, CombinatorKind, ComplexSelector, CssNode, PseudoClassArgs, RelativeSelector,
+    SimpleSelector,
+};
+use svelte_parser::{
+    Attribute, AttributeValue, AttributeValuePart, DirectiveKind, ElseBranch, Fragment, IfBlock,
+    SvelteDocument, SvelteElementKind, TemplateNode,
+};
+
+/// Runs stylesheet checks on a document.
+pub fn check(doc: &SvelteDocument, source: &str) -> Vec<Diagnostic> {
+    UnusedSelector.check(&RuleContext::new(doc, source, None))
+}
+
+/// `css_unused_selector`: selectors that match nothing in the template.
+pub struct UnusedSelector;
+
+pub(crate) static UNUSED_SELECTOR: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("css_unused_selector"),
+    category: RuleCategory::Css,
+    default_severity: Severity::Warning,
+    docs_url: Some("https://svelte.dev/docs/svelte/compiler-warnings#css_unused_selector"),
+    fixable: false,
+};
+
+impl Rule for UnusedSelector {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNUSED_SELECTOR
+    }
+
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+        check_unused_selectors(ctx.doc())
+    }
+}
+
+fn check_unused_selectors(doc: &SvelteDocument) -> Vec<Diagnostic> {
+    let Some(style) = &doc.style else {
+        return Vec::new();
+    };
+    let Some(stylesheet) = &style.stylesheet else {
+        return Vec::new();
+    };
+    if style.global {
+        return Vec::new();
+    }
+
+    let tree = ElementTree::build(&doc.fragment);
+    let mut checker = Checker {
+        tree: &tree,
+        source: &style.content,
+        offset: u32::from(style.content_span.start),
+        diagnostics: Vec::new(),
+    };
+    checker.check_nodes(&stylesheet.children, &Scope::Top);
+    checker.diagnostics
+}
+
+/// Where an element's parent is.
+#[derive(Debug, Clone, Copy)]
+enum Parent {
+    /// Another element of this component.
+    Element(usize),
+    /// The top of the component.
+    Root,
+    /// Not known statically (slot content, snippet bodies).
+    Unknown,
+}
+
+/// An element of the template, reduced to what selectors can see.
+struct ElementInfo<'a> {
+    /// The lowercased tag name; `None` for `<svelte:element>`.
+    name: Option<String>,
+    attributes: &'a [Attribute],
+    parent: Parent,
+    /// Elements that may precede this one under the same parent.
+    siblings: Vec<usize>,
+    /// Whether content that is not known statically may precede it.
+    siblings_unknown: bool,
+}
+
+/// The flattened element tree of a template.
+struct ElementTree<'a> {
+    elements: Vec<ElementInfo<'a>>,
+}
+
+/// The sibling context while walking one parent's children.
+#[derive(Clone, Default)]
+struct Siblings {
+    previous: Vec<usize>,
+    unknown: bool,
+}
+
+impl<'a> ElementTree<'a> {
+    fn build(fragment: &'a Fragment) -> Self {
+        let mut tree = Self {
+            elements: Vec::new(),
+        };
+        tree.walk(&fragment.nodes, Parent::Root, &mut Siblings::default());
+        tree
+    }
+
+    fn push(
+        &mut self,
+        name: Option<String>,
+        attributes: &'a [Attribute],
+        children: &'a [TemplateNode],
+        parent: Parent,
+        siblings: &mut Siblings,
+    ) {
+        let index = self.elements.len();
+        self.elements.push(ElementInfo {
+            name,
+            attributes,
+            parent,
+            siblings: siblings.previous.clone(),
+            siblings_unknown: siblings.unknown,
+        });
+        self.walk(children, Parent::Element(index), &mut Siblings::default());
+        siblings.previous.push(index);
+    }
+
+    fn walk(&mut self, nodes: &'a [TemplateNode], parent: Parent, siblings: &mut Siblings) {
+        for node in nodes {
+            match node {
+                TemplateNode::Element(el) => {
+                    let name = Some(el.name.to_ascii_lowercase());
+                    self.push(name, &el.attributes, &el.children, parent, siblings);
+                }
+                TemplateNode::SvelteElement(el) => match el.kind {
+                    SvelteElementKind::Element => {
+                        self.push(None, &el.attributes, &el.children, parent, siblings);
+                    }
+                    SvelteElementKind::Self_ | SvelteElementKind::Component => {
+                        self.walk_component(&el.children, siblings);
+                    }
+                    SvelteElementKind::Head => {
+                        self.walk(&el.children, Parent::Root, &mut Siblings::default());
+                    }
+                    SvelteElementKind::Fragment
+                    | SvelteElementKind::Boundary
+                    | SvelteElementKind::Window
+                    | SvelteElementKind::Document
+                    | SvelteElementKind::Body
+                    | SvelteElementKind::Options => self.walk(&el.children, parent, siblings),
+                },
+                TemplateNode::Component(comp) => self.walk_component(&comp.children, siblings),
+                TemplateNode::IfBlock(block) => {
+                    let mut branches = Vec::new();
+                    self.walk_if(block, parent, siblings, &mut branches);
+                    merge(siblings, branches);
+                }
+                TemplateNode::EachBlock(block) => {
+                    let mut body = siblings.clone();
+                    self.walk(&block.body.nodes, parent, &mut body);
+                    // Later iterations render after earlier ones, so every
+                    // element of the body may precede every other.
+                    let repeated = body.previous[siblings.previous.len()..].to_vec();
+                    for &index in &repeated {
+                        let element = &mut self.elements[index];
+                        for &other in &repeated {
+                            if !element.siblings.contains(&other) {
+                                element.siblings.push(other);
+                            }
+                        }
+                        element.siblings_unknown |= body.unknown;
+                    }
+                    let mut branches = vec![body];
+                    if let Some(fallback) = &block.fallback {
+                        let mut branch = siblings.clone();
+                        self.walk(&fallback.nodes, parent, &mut branch);
+                        branches.push(branch);
+                    }
+                    merge(siblings, branches);
+                }
+                TemplateNode::AwaitBlock(block) => {
+                    let mut branches = Vec::new();
+                    let fragments = [
+                        block.pending.as_ref(),
+                        block.then.as_ref().map(|then| &then.body),
+                        block.catch.as_ref().map(|catch| &catch.body),
+                    ];
+                    for fragment in fragments.into_iter().flatten() {
+                        let mut branch = siblings.clone();
+                        self.walk(&fragment.nodes, parent, &mut branch);
+                        branches.push(branch);
+                    }
+                    merge(siblings, branches);
+                }
+                TemplateNode::KeyBlock(block) => self.walk(&block.body.nodes, parent, siblings),
+                TemplateNode::SnippetBlock(block) => {
+                    // Rendered wherever `{@render}` puts it.
+                    let mut body = Siblings {
+                        previous: Vec::new(),
+                        unknown: true,
+                    };
+                    self.walk(&block.body.nodes, Parent::Unknown, &mut body);
+                }
+                TemplateNode::RenderTag(_) => siblings.unknown = true,
+                TemplateNode::Text(_)
+                | TemplateNode::Comment(_)
+                | TemplateNode::Expression(_)
+                | TemplateNode::HtmlTag(_)
+                | TemplateNode::ConstTag(_)
+                | TemplateNode::DeclarationTag(_)
+                | TemplateNode::DebugTag(_) => {}
+            }
+        }
+    }
+
+    /// Walks a component's slot content, which the component places wherever
+    /// it likes, and marks its own output as unknown siblings.
+    fn walk_component(&mut self, children: &'a [TemplateNode], siblings: &mut Siblings) {
+        let mut content = Siblings {
+            previous: Vec::new(),
+            unknown: true,
+        };
+        self.walk(children, Parent::Unknown, &mut content);
+        siblings.unknown = true;
+    }
+
+    fn walk_if(
+        &mut self,
+        block: &'a IfBlock,
+        parent: Parent,
+        siblings: &Siblings,
+        branches: &mut Vec<Siblings>,
+    ) {
+        let mut consequent = siblings.clone();
+        self.walk(&block.consequent.nodes, parent, &mut consequent);
+        branches.push(consequent);
+        match &block.alternate {
+            Some(ElseBranch::Else(fragment)) => {
+                let mut branch = siblings.clone();
+                self.walk(&fragment.nodes, parent, &mut branch);
+                branches.push(branch);
+            }
+            Some(ElseBranch::ElseIf(nested)) => self.walk_if(nested, parent, siblings, branches),
+            // The block may render nothing.
+            None => branches.push(siblings.clone()),
+        }
+    }
+
+    fn parent(&self, element: usize) -> Parent {
+        self.elements[element].parent
+    }
+}
+
+/// Folds the sibling contexts of alternative branches back into `siblings`.
+fn merge(siblings: &mut Siblings, branches: Vec<Siblings>) {
+    for branch in branches {
+        for index in branch.previous {
+            if !siblings.previous.contains(&index) {
+                siblings.previous.push(index);
+            }
+        }
+        siblings.unknown |= branch.unknown;
+    }
+}
+
+/// The rule a selector is nested in.
+enum Scope {
+    /// A top-level rule.
+    Top,
+    /// A nested rule whose parent matched these elements.
+    Nested(HashSet<usize>),
+    /// A nested rule whose parent may match anything (e.g. a `:global(...)`
+    /// parent).
+    Any,
+}
+
+struct Checker<'a, 'b> {
+    tree: &'b ElementTree<'a>,
+    source: &'b str,
+    offset: u32,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_, '_> {
+    fn check_nodes(&mut self, nodes: &[CssNode], scope: &Scope) {
+        for node in nodes {
+            match node {
+                CssNode::Rule(rule) => {
+                    if rule.is_global_block() && rule.prelude.selectors.len() == 1 {
+                        let complex = &rule.prelude.selectors[0];
+                        // `.a :global { ... }` still needs `.a` to match.
+                        if complex.children.len() > 1 {
+                            let scoped = &complex.children[..complex.children.len() - 1];
+                            if self.matching(scoped, scope).is_empty() {
+                                self.report(complex);
+                            }
+                        }
+                        continue;
+                    }
+
+                    let mut matched = HashSet::new();
+                    let mut any = false;
+                    for complex in &rule.prelude.selectors {
+                        if is_global(complex) {
+                            any = true;
+                            continue;
+                        }
+                        let elements = self.matching(&complex.children, scope);
+                        if elements.is_empty() {
+                            self.report(complex);
+                        }
+                        matched.extend(elements);
+                    }
+                    let nested = if any {
+                        Scope::Any
+                    } else {
+                        Scope::Nested(matched)
+                    };
+                    self.check_nodes(&rule.block.children, &nested);
+                }
+                CssNode::Atrule(atrule) => {
+                    if atrule.is_keyframes() {
+                        continue;
+                    }
+                    if let Some(block) = &atrule.block {
+                        self.check_nodes(&block.children, scope);
+                    }
+                }
+                CssNode::Declaration(_) => {}
+            }
+        }
+    }
+
+    fn report(&mut self, complex: &ComplexSelector) {
+        let start = (u32::from(complex.span.start) - self.offset) as usize;
+        let end = (u32::from(complex.span.end) - self.offset) as usize;
+        let text = self.source.get(start..end).unwrap_or_default().trim();
+        self.diagnostics.push(Diagnostic::new(
+            UNUSED_SELECTOR.code,
+            format!("Unused CSS selector \"{text}\""),
+            complex.span,
+        ));
+    }
+
+    /// Returns the elements the selector can match.
+    fn matching(&self, children: &[RelativeSelector], scope: &Scope) -> HashSet<usize> {
+        (0..self.tree.elements.len())
+            .filter(|&element| self.matches(children, element, scope, true))
+            .collect()
+    }
+
+    /// Whether `children` can match `element`, checking the selector right to
+    /// left. `relative` is set when the selector is the whole selector of a
+    /// rule, so an implicit or leading-combinator relation to a nesting
+    /// parent applies.
+    fn matches(
+        &self,
+        children: &[RelativeSelector],
+        element: usize,
+        scope: &Scope,
+        relative: bool,
+    ) -> bool {
+        let Some((last, rest)) = children.split_last() else {
+            return true;
+        };
+        if !self.matches_compound(&last.selectors, element, scope) {
+            return false;
+        }
+
+        if rest.is_empty() {
+            if !relative {
+                return true;
+            }
+            let nested = match scope {
+                Scope::Top | Scope::Any => return true,
+                Scope::Nested(set) => set,
+            };
+            if last.combinator.is_none() && has_nesting(children) {
+                return true;
+            }
+            let kind = last
+                .combinator
+                .map_or(CombinatorKind::Descendant, |c| c.kind);
+            return self.related(element, kind, &|candidate| nested.contains(&candidate));
+        }
+
+        // A `:global(...)` compound matches anything, including elements
+        // outside this component, so nothing further left can be checked.
+        if rest.last().is_some_and(is_global_compound) {
+            return true;
+        }
+
+        let kind = last
+            .combinator
+            .map_or(CombinatorKind::Descendant, |c| c.kind);
+        self.related(element, kind, &|candidate| {
+            self.matches(rest, candidate, scope, relative)
+        })
+    }
+
+    /// Whether some element related to `element` by `kind` satisfies
+    /// `predicate`. Relations that are not known statically count as
+    /// satisfied.
+    fn related(
+        &self,
+        element: usize,
+        kind: CombinatorKind,
+        predicate: &dyn Fn(usize) -> bool,
+    ) -> bool {
+        match kind {
+            CombinatorKind::Descendant => {
+                let mut current = element;
+                loop {
+                    match self.tree.parent(current) {
+                        Parent::Element(parent) => {
+                            if predicate(parent) {
+                                return true;
+                            }
+                            current = parent;
+                        }
+                        Parent::Root => return false,
+                        Parent::Unknown => return true,
+                    }
+                }
+            }
+            CombinatorKind::Child => match self.tree.parent(element) {
+                Parent::Element(parent) => predicate(parent),
+                Parent::Root => false,
+                Parent::Unknown => true,
+            },
+            CombinatorKind::NextSibling | CombinatorKind::SubsequentSibling => {
+                let info = &self.tree.elements[element];
+                info.siblings_unknown || info.siblings.iter().any(|&sibling| predicate(sibling))
+            }
+            CombinatorKind::Column => true,
+        }
+    }
+
+    fn matches_compound(
+        &self,
+        selectors: &[SimpleSelector],
+        element: usize,
+        scope: &Scope,
+    ) -> bool {
+        let info = &self.tree.elements[element];
+        selectors.iter().all(|simple| match simple {
+            SimpleSelector::Type { name, .. } => info
+                .name
+                .as_deref()
+                .is_none_or(|tag| tag.eq_ignore_ascii_case(name)),
+            SimpleSelector::Class { name, .. } => may_have_class(info.attributes, name),
+            SimpleSelector::Id { name, .. } => {
+                may_have_value(info.attributes, "id", |value| value == name.as_str())
+            }
+            SimpleSelector::Attribute(attribute) => may_match_attribute(info.attributes, attribute),
+            SimpleSelector::Nesting { .. } => match scope {
+                Scope::Top | Scope::Any => true,
+                Scope::Nested(set) => set.contains(&element),
+            },
+            SimpleSelector::PseudoClass(pseudo) => match (&*pseudo.name, &pseudo.args) {
+                ("is" | "where", Some(PseudoClassArgs::Selectors(list))) => {
+                    list.selectors.iter().any(|complex| {
+                        is_global(complex) || self.matches(&complex.children, element, scope, false)
+                    })
+                }
+                _ => true,
+            },
+            SimpleSelector::Universal { .. }
+            | SimpleSelector::PseudoElement { .. }
+            | SimpleSelector::Percentage { .. } => true,
+        })
+    }
+}
+
+/// Whether the whole selector targets elements outside the component.
+fn is_global(complex: &ComplexSelector) -> bool {
+    let starts_bare = complex.children.first().is_some_and(|relative| {
+        relative.selectors.first().is_some_and(
+            |simple| matches!(simple, SimpleSelector::PseudoClass(p) if p.name == "global" && p.args.is_none()),
+        )
+    });
+    starts_bare || complex.children.last().is_some_and(is_global_compound)
+}
+
+/// Whether a compound is exactly `:global(...)`, possibly with pseudo parts.
+fn is_global_compound(relative: &RelativeSelector) -> bool {
+    relative.selectors.first().is_some_and(
+        |simple| matches!(simple, SimpleSelector::PseudoClass(p) if p.name == "global"),
+    )
+}
+
+fn has_nesting(children: &[RelativeSelector]) -> bool {
+    children.iter().any(|relative| {
+        relative.selectors.iter().any(|simple| match simple {
+            SimpleSelector::Nesting { .. } => true,
+            SimpleSelector::PseudoClass(pseudo) => match &pseudo.args {
+                Some(PseudoClassArgs::Selectors(list)) => list
+                    .selectors
+                    .iter()
+                    .any(|complex| has_nesting(&complex.children)),
+                _ => false,
+            },
+            _ => false,
+        })
+    })
+}
+
+/// Whether an element with these attributes may carry `class`.
+fn may_have_class(attributes: &[Attribute], class: &str) -> bool {
+    attributes.iter().any(|attribute| match attribute {
+        Attribute::Directive(directive) => {
+            directive.kind == DirectiveKind::Class && directive.name == class
+        }
+        _ => false,
+    }) || may_have_value(attributes, "class", |value| {
+        value.split_ascii_whitespace().any(|name| name == class)
+    })
+}
+
+/// Whether the attribute `name` may have a value satisfying `predicate`.
+/// Dynamic values and spreads always may.
+fn may_have_value(attributes: &[Attribute], name: &str, predicate: impl Fn(&str) -> bool) -> bool {
+    attributes.iter().any(|attribute| match attribute {
+        Attribute::Spread(_) => true,
+        Attribute::Shorthand(shorthand) => shorthand.name.eq_ignore_ascii_case(name),
+        Attribute::Normal(normal) if normal.name.eq_ignore_ascii_case(name) => {
+            match static_value(&normal.value) {
+                Some(value) => predicate(&value),
+                None => true,
+            }
+        }
+        _ => false,
+    })
+}
+
+/// Returns the value of an attribute if it contains no expressions.
+fn static_value(value: &AttributeValue) -> Option<String> {
+    match value {
+        AttributeValue::True => Some(String::new()),
+        AttributeValue::Text(text) => Some(text.value.clone()),
+        AttributeValue::Expression(_) => None,
+        AttributeValue::Concat(parts) => parts
+            .iter()
+            .map(|part| match part {
+                AttributeValuePart::Text(text) => Some(text.value.as_str()),
+                AttributeValuePart::Expression(_) => None,
+            })
+            .collect(),
+    }
+}
+
+fn may_match_attribute(attributes: &[Attribute], selector: &AttributeSelector) -> bool {
+    let name = selector.name.as_str();
+    let bound = attributes.iter().any(|attribute| match attribute {
+        Attribute::Directive(directive) => match directive.kind {
+            DirectiveKind::Bind => directive.name.eq_ignore_ascii_case(name),
+            DirectiveKind::Class => name.eq_ignore_ascii_case("class"),
+            DirectiveKind::StyleDirective => name.eq_ignore_ascii_case("style"),
+            _ => false,
+        },
+        _ => false,
+    });
+    if bound {
+        return true;
+    }
+
+    let (Some(matcher), Some(expected)) = (&selector.matcher, &selector.value) else {
+        return may_have_value(attributes, name, |_| true);
+    };
+    let insensitive = selector
+        .flags
+        .as_deref()
+        .is_some_and(|flags| flags.eq_ignore_ascii_case("i"));
+    let normalize = |value: &str| {
+        if insensitive {
+            value.to_ascii_lowercase()
+        } else {
+            value.to_string()
+        }
+    };
+    let expected = normalize(expected);
+    may_have_value(attributes, name, |value| {
+        let value = normalize(value);
+        match matcher.as_str() {
+            "=" => value == expected,
+            "~=" => value.split_ascii_whitespace().any(|word| word == expected),
+            "|=" => value == expected || value.starts_with(&format!("{expected}-")),
+            "^=" => value.starts_with(&expected),
+            "$=" => value.ends_with(&expected),
+            "*=" => value.contains(&expected),
+            _ => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn unused(source: &str) -> Vec<String> {
+        let doc = parse(source).document;
+        check(&doc, source)
+            .into_iter()
+            .map(|diagnostic| {
+                assert_eq!(diagnostic.code, UNUSED_SELECTOR.code);
+                diagnostic.message
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_class_selector() {
+        let diagnostics = unused("<div class=\"a\"></div>\n<style>.a {} .b {}</style>");
+        assert_eq!(diagnostics, vec!["Unused CSS selector \".b\""]);
+    }
+
+    #[test]
+    fn test_type_id_and_attribute_selectors() {
+        let source = r#"<input id="name" type="text">
+<style>
+  input {}
+  #name {}
+  [type="text"] {}
+  [type^="te"] {}
+  p {}
+  #other {}
+  [type="number"] {}
+</style>"#;
+        assert_eq!(
+            unused(source),
+            vec![
+                "Unused CSS selector \"p\"",
+                "Unused CSS selector \"#other\"",
+                "Unused CSS selector \"[type=\"number\"]\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_class_directive_and_dynamic_class_are_used() {
+        let source = r#"<div class:active={on}></div>
+<span class={cls}></span>
+<p class="x {y}"></p>
+<style>.active {} span.anything {} p.other {} div.missing {}</style>"#;
+        assert_eq!(unused(source), vec!["Unused CSS selector \"div.missing\""]);
+    }
+
+    #[test]
+    fn test_spread_and_svelte_element_are_used() {
+        let source = r#"<div {...rest}></div>
+<svelte:element this={tag} class="x"></svelte:element>
+<style>div.any {} #any {} article.x {} section.y {}</style>"#;
+        assert_eq!(unused(source), vec!["Unused CSS selector \"section.y\""]);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let source = r#"<ul><li><a>link</a></li></ul>
+<p></p><span></span>
+<style>
+  ul a {}
+  ul > li {}
+  ul > a {}
+  p + span {}
+  span ~ p {}
+  li a span {}
+</style>"#;
+        assert_eq!(
+            unused(source),
+            vec![
+                "Unused CSS selector \"ul > a\"",
+                "Unused CSS selector \"span ~ p\"",
+                "Unused CSS selector \"li a span\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_if_and_each_branches() {
+        let source = r#"{#if a}<p class="yes"></p>{:else if b}<b></b>{:else}<i></i>{/if}
+<span></span>
+{#each items as item}<li></li>{:else}<em></em>{/each}
+<style>.yes {} b {} i {} em {} p + span {} b + span {} li + li {} i + p {}</style>"#;
+        assert_eq!(unused(source), vec!["Unused CSS selector \"i + p\""]);
+    }
+
+    #[test]
+    fn test_global_selectors_are_skipped() {
+        let source = r#"<div class="a"></div>
+<style>
+  :global(.x) {}
+  :global .x .y {}
+  .a :global(.x) {}
+  .b :global(.x) {}
+  :global(body) .a {}
+  :global(body) .c {}
+  :global { .z {} }
+</style>"#;
+        assert_eq!(
+            unused(source),
+            vec!["Unused CSS selector \":global(body) .c\""]
+        );
+    }
+
+    #[test]
+    fn test_component_content_and_snippets_are_lenient() {
+        let source = r#"<Card><p class="body"></p></Card>
+{#snippet row()}<td></td>{/snippet}
+<style>.wrapper .body {} tr > td {} .body {} table {}</style>"#;
+        assert_eq!(unused(source), vec!["Unused CSS selector \"table\""]);
+    }
+
+    #[test]
+    fn test_nested_rules() {
+        let source = r#"<div class="a"><span></span></div>
+<style>
+  .a {
+    span {}
+    em {}
+    &.a {}
+    &.b {}
+  }
+</style>"#;
+        assert_eq!(
+            unused(source),
+            vec!["Unused CSS selector \"em\"", "Unused CSS selector \"&.b\""]
+        );
+    }
+
+    #[test]
+    fn test_keyframes_and_media() {
+        let source = r#"<div></div>
+<style>
+  @keyframes fade { from {} to {} }
+  @media (min-width: 1px) { div {} p {} }
+</style>"#;
+        assert_eq!(unused(source), vec!["Unused CSS selector \"p\""]);
+    }
+
+    #[test]
+    fn test_preprocessed_style_is_skipped() {
+        let source = "<div></div>\n<style lang=\"scss\">.missing {}</style>";
+        assert!(unused(source).is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 //! Diagnostic types.
 
+use crate::a11y::A11yCode;
 use source_map::Span;
+use svelte_parser::{ParseError, ParseErrorCode};
 
 /// A diagnostic message.
 #[derive(Debug, Clone)]
@@ -29,6 +31,15 @@ impl Diagnostic {
         }
     }
 
+    /// Creates a diagnostic for a template parse error.
+    pub fn from_parse_error(error: &ParseError) -> Self {
+        Self::new(
+            DiagnosticCode::Parse(error.code()),
+            error.to_string(),
+            error.span,
+        )
+    }
+
     /// Adds a suggestion to this diagnostic.
     pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
         self.suggestions.push(suggestion);
@@ -59,42 +70,65 @@ pub struct Suggestion {
 }
 
 /// Diagnostic codes for all checks.
+///
+/// Rules name their code in their [`RuleMeta`](crate::RuleMeta); the
+/// severity and documentation of a code come from the rule that reports it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DiagnosticCode {
-    // === A11y Codes ===
-    /// `a11y-structure`: Heading structure
-    A11yStructure,
-
-    // === Component Codes ===
-    /// `missing-declaration`
-    MissingDeclaration,
-    /// `invalid-rune-usage`
-    InvalidRuneUsage,
-
-    // === Parse Codes ===
-    /// `parse-error`: Syntax error during parsing
+    /// An a11y warning ported from the Svelte compiler (e.g.
+    /// `a11y_missing_attribute`)
+    A11y(A11yCode),
+    /// The code of a rule, built in (e.g. `unused-prop`) or registered
+    /// outside this crate
+    Rule(&'static str),
+    /// `parse-error`: Syntax error outside the template parser (e.g. while
+    /// transforming rune modules)
     ParseError,
+    /// A template parse error, reported under the Svelte compiler's code
+    /// (e.g. `block_unclosed`)
+    Parse(ParseErrorCode),
 }
 
 impl DiagnosticCode {
     /// Returns the default severity for this diagnostic code.
+    ///
+    /// Codes owned by a built-in rule use the rule's metadata.
     pub fn default_severity(&self) -> Severity {
+        if let Some(meta) = crate::rule::builtin_meta(*self) {
+            return meta.default_severity;
+        }
         match self {
-            DiagnosticCode::InvalidRuneUsage => Severity::Error,
-            DiagnosticCode::MissingDeclaration => Severity::Error,
-            DiagnosticCode::ParseError => Severity::Error,
-
-            DiagnosticCode::A11yStructure => Severity::Warning,
+            // The `<style>` parser is forgiving and may reject CSS the
+            // compiler accepts.
+            DiagnosticCode::Parse(code) if code.is_css() => Severity::Warning,
+            DiagnosticCode::ParseError | DiagnosticCode::Parse(_) => Severity::Error,
+            _ => Severity::Warning,
         }
     }
 
     /// Returns the diagnostic code as a string.
     pub fn as_str(&self) -> &'static str {
         match self {
-            DiagnosticCode::A11yStructure => "a11y-structure",
-            DiagnosticCode::MissingDeclaration => "missing-declaration",
-            DiagnosticCode::InvalidRuneUsage => "invalid-rune-usage",
+            DiagnosticCode::A11y(code) => code.as_str(),
+            DiagnosticCode::Rule(code) => code,
             DiagnosticCode::ParseError => "parse-error",
+            DiagnosticCode::Parse(code) => code.as_str(),
+        }
+    }
+
+    /// Returns whether the Svelte compiler reports this code too, with the
+    /// same meaning.
+    pub fn is_compiler_warning(&self) -> bool {
+        matches!(self, DiagnosticCode::A11y(_)) || *self == crate::css::UNUSED_SELECTOR.code
+    }
+
+    /// Returns the documentation page for this code, if there is one.
+    pub fn docs_url(&self) -> Option<String> {
+        match self {
+            DiagnosticCode::Parse(code) => Some(code.docs_url()),
+            _ => crate::rule::builtin_meta(*self)
+                .and_then(|meta| meta.docs_url)
+                .map(str::to_string),
         }
     }
 }
@@ -0,0 +1,296 @@
+//! `svelte-ignore` comments and template type-check pragmas.
+//!
+//! As in the Svelte compiler, a `<!-- svelte-ignore code ... -->` comment
+//! applies to the next sibling node and everything inside it. Comments and
+//! whitespace between the two are skipped, so several ignore comments can be
+//! stacked. Codes match in both the legacy (`a11y-structure`) and the
+//! underscore (`a11y_structure`) spelling. In `<style>`, a
+//! `/* svelte-ignore code */` comment applies to the next rule.
+//!
+//! `<!-- @ts-ignore -->` and `<!-- @ts-expect-error -->` comments follow the
+//! same scoping and suppress type-check diagnostics in markup, where a
+//! `// @ts-ignore` line is not possible.
+
+use crate::traversal::child_lists;
+use source_map::{ByteOffset, Span};
+use svelte_parser::css::CssNode;
+use svelte_parser::{Style, SvelteDocument, TemplateNode};
+
+/// A region in which some diagnostic codes are ignored.
+#[derive(Debug, Clone)]
+pub struct IgnoredRegion {
+    /// The span of the node the comment applies to.
+    pub span: Span,
+    /// The ignored codes, normalized with [`normalize_code`].
+    pub codes: Vec<String>,
+}
+
+/// The kind of a template type-check pragma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsPragmaKind {
+    /// `<!-- @ts-ignore -->`
+    Ignore,
+    /// `<!-- @ts-expect-error -->`; reported when nothing is suppressed.
+    ExpectError,
+}
+
+/// A template type-check pragma.
+#[derive(Debug, Clone)]
+pub struct TsPragma {
+    /// The kind of pragma.
+    pub kind: TsPragmaKind,
+    /// The span of the comment.
+    pub comment: Span,
+    /// The span of the node the comment applies to.
+    pub span: Span,
+}
+
+/// The ignore comments and pragmas of a document.
+#[derive(Debug, Clone, Default)]
+pub struct Ignores {
+    regions: Vec<IgnoredRegion>,
+    ts_pragmas: Vec<TsPragma>,
+}
+
+impl Ignores {
+    /// Collects the comments of a document.
+    pub fn collect(doc: &SvelteDocument) -> Self {
+        let mut ignores = Self::default();
+        ignores.siblings(&doc.fragment.nodes);
+        if let Some(style) = &doc.style {
+            ignores.style(style);
+        }
+        ignores
+    }
+
+    /// Returns the `svelte-ignore` regions.
+    pub fn regions(&self) -> &[IgnoredRegion] {
+        &self.regions
+    }
+
+    /// Returns the template type-check pragmas.
+    pub fn ts_pragmas(&self) -> &[TsPragma] {
+        &self.ts_pragmas
+    }
+
+    /// Returns whether `code` is ignored at `offset`.
+    pub fn is_ignored(&self, code: &str, offset: ByteOffset) -> bool {
+        let code = normalize_code(code);
+        self.regions.iter().any(|region| {
+            region.span.start <= offset && offset < region.span.end && region.codes.contains(&code)
+        })
+    }
+
+    fn siblings(&mut self, nodes: &[TemplateNode]) {
+        let mut codes = Vec::new();
+        let mut pragmas = Vec::new();
+        for node in nodes {
+            match node {
+                TemplateNode::Comment(comment) => {
+                    if let Some(ignored) = parse_svelte_ignore(&comment.data) {
+                        codes.extend(ignored);
+                    } else if let Some(kind) = parse_ts_pragma(&comment.data) {
+                        pragmas.push((kind, comment.span));
+                    }
+                    continue;
+                }
+                TemplateNode::Text(text) if text.data.trim().is_empty() => continue,
+                _ => {}
+            }
+            let span = node.span();
+            if !codes.is_empty() {
+                self.regions.push(IgnoredRegion {
+                    span,
+                    codes: std::mem::take(&mut codes),
+                });
+            }
+            for (kind, comment) in pragmas.drain(..) {
+                self.ts_pragmas.push(TsPragma {
+                    kind,
+                    comment,
+                    span,
+                });
+            }
+            for children in child_lists(node) {
+                self.siblings(children);
+            }
+        }
+    }
+
+    fn style(&mut self, style: &Style) {
+        let Some(sheet) = &style.stylesheet else {
+            return;
+        };
+        let mut starts = Vec::new();
+        collect_css_nodes(&sheet.children, &mut starts);
+        starts.sort_by_key(|span| span.start);
+
+        let base = u32::from(style.content_span.start);
+        let content = style.content.as_str();
+        let mut rest = 0;
+        while let Some(open) = content[rest..].find("/*") {
+            let body_start = rest + open + 2;
+            let Some(close) = content[body_start..].find("*/") else {
+                break;
+            };
+            let body = &content[body_start..body_start + close];
+            rest = body_start + close + 2;
+            let Some(codes) = parse_svelte_ignore(body) else {
+                continue;
+            };
+            let comment_end = ByteOffset::from(base + rest as u32);
+            let Some(&span) = starts.iter().find(|span| span.start >= comment_end) else {
+                continue;
+            };
+            // Only a rule directly after the comment is covered.
+            let between = &content[rest..(u32::from(span.start) - base) as usize];
+            if is_blank_css(between) {
+                self.regions.push(IgnoredRegion { span, codes });
+            }
+        }
+    }
+}
+
+fn collect_css_nodes(nodes: &[CssNode], spans: &mut Vec<Span>) {
+    for node in nodes {
+        match node {
+            CssNode::Rule(rule) => {
+                spans.push(rule.span);
+                collect_css_nodes(&rule.block.children, spans);
+            }
+            CssNode::Atrule(atrule) => {
+                spans.push(atrule.span);
+                if let Some(block) = &atrule.block {
+                    collect_css_nodes(&block.children, spans);
+                }
+            }
+            CssNode::Declaration(_) => {}
+        }
+    }
+}
+
+/// Returns whether CSS text is only whitespace and comments.
+fn is_blank_css(mut text: &str) -> bool {
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return true;
+        }
+        let Some(comment) = text.strip_prefix("/*") else {
+            return false;
+        };
+        let Some(end) = comment.find("*/") else {
+            return false;
+        };
+        text = &comment[end + 2..];
+    }
+}
+
+/// Normalizes a diagnostic code for comparison: legacy codes use `-` where
+/// current ones use `_`.
+pub fn normalize_code(code: &str) -> String {
+    code.replace('-', "_")
+}
+
+/// Returns the codes of a `svelte-ignore` comment body.
+fn parse_svelte_ignore(text: &str) -> Option<Vec<String>> {
+    let rest = text.trim_start().strip_prefix("svelte-ignore")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(
+        rest.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|code| !code.is_empty())
+            .map(normalize_code)
+            .collect(),
+    )
+}
+
+/// Returns the pragma of a `@ts-ignore` / `@ts-expect-error` comment body.
+/// Text after the pragma (a reason) is allowed.
+fn parse_ts_pragma(text: &str) -> Option<TsPragmaKind> {
+    let text = text.trim();
+    let word = text.split_whitespace().next()?;
+    match word {
+        "@ts-ignore" => Some(TsPragmaKind::Ignore),
+        "@ts-expect-error" => Some(TsPragmaKind::ExpectError),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn offset_of(source: &str, needle: &str) -> ByteOffset {
+        ByteOffset::from(source.find(needle).unwrap() as u32)
+    }
+
+    #[test]
+    fn test_applies_to_next_sibling_and_descendants() {
+        let source = "<!-- svelte-ignore a11y-structure -->\n<div><h3>a</h3></div>\n<h4>b</h4>";
+        let ignores = Ignores::collect(&parse(source).document);
+        assert!(ignores.is_ignored("a11y-structure", offset_of(source, "<h3>")));
+        assert!(ignores.is_ignored("a11y_structure", offset_of(source, "<h3>")));
+        assert!(!ignores.is_ignored("a11y-structure", offset_of(source, "<h4>")));
+        assert!(!ignores.is_ignored("missing-declaration", offset_of(source, "<h3>")));
+    }
+
+    #[test]
+    fn test_stacked_comments_and_code_lists() {
+        let source = "<!-- svelte-ignore a11y_structure, missing_declaration -->\n\
+                      <!-- svelte-ignore css_unused_selector -->\n<p>{x}</p>";
+        let ignores = Ignores::collect(&parse(source).document);
+        let at = offset_of(source, "{x}");
+        for code in [
+            "a11y-structure",
+            "missing-declaration",
+            "css_unused_selector",
+        ] {
+            assert!(ignores.is_ignored(code, at), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_text_breaks_the_scope() {
+        let source = "<!-- svelte-ignore missing_declaration -->\ntext <p>{x}</p>";
+        let ignores = Ignores::collect(&parse(source).document);
+        assert!(!ignores.is_ignored("missing_declaration", offset_of(source, "{x}")));
+    }
+
+    #[test]
+    fn test_nested_blocks() {
+        let source =
+            "{#if a}\n<!-- svelte-ignore missing_declaration -->\n<p>{x}</p>{:else}<p>{y}</p>{/if}";
+        let ignores = Ignores::collect(&parse(source).document);
+        assert!(ignores.is_ignored("missing_declaration", offset_of(source, "{x}")));
+        assert!(!ignores.is_ignored("missing_declaration", offset_of(source, "{y}")));
+    }
+
+    #[test]
+    fn test_style_comments() {
+        let source = "<style>\n/* svelte-ignore css_unused_selector */\n.a { color: red }\n.b { color: blue }\n</style>";
+        let ignores = Ignores::collect(&parse(source).document);
+        assert!(ignores.is_ignored("css_unused_selector", offset_of(source, ".a")));
+        assert!(!ignores.is_ignored("css_unused_selector", offset_of(source, ".b")));
+    }
+
+    #[test]
+    fn test_ts_pragmas() {
+        let source = "<!-- @ts-expect-error wrong on purpose -->\n<Child value={1} />\n<!-- @ts-ignore -->\n<p>{x}</p>\n<!-- @ts-nocheck -->";
+        let ignores = Ignores::collect(&parse(source).document);
+        let pragmas: Vec<_> = ignores
+            .ts_pragmas()
+            .iter()
+            .map(|pragma| (pragma.kind, pragma.span.start))
+            .collect();
+        assert_eq!(
+            pragmas,
+            vec![
+                (TsPragmaKind::ExpectError, offset_of(source, "<Child")),
+                (TsPragmaKind::Ignore, offset_of(source, "<p>")),
+            ]
+        );
+    }
+}
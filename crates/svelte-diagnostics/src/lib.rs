@@ -1,8 +1,17 @@
 //! Svelte-specific diagnostics for svelte-check-rs.
 //!
 //! This crate provides diagnostics for:
-//! - Accessibility (a11y) checks
+//! - Accessibility (a11y) checks, including ports of the compiler's a11y warnings
 //! - Component validation (invalid rune usage, missing declarations)
+//! - Unused `<style>` selectors
+//! - Project-wide checks over the imports between files (see [`project`])
+//!
+//! Rules that need to know what names mean share a [`SemanticModel`] of the
+//! component's scopes, bindings and references.
+//!
+//! Each check is a [`Rule`] in the [`registry`]; rules can be enabled per
+//! category or configured one by one through [`DiagnosticOptions`], and
+//! silenced in place with `<!-- svelte-ignore code -->` (see [`ignore`]).
 //!
 //! # Example
 //!
@@ -12,7 +21,7 @@
 //!
 //! let source = r#"<img src="photo.jpg">"#;
 //! let doc = parse(source);
-//! let diagnostics = check(&doc.document, DiagnosticOptions::default());
+//! let diagnostics = check(&doc.document, source, DiagnosticOptions::default());
 //!
 //! for diagnostic in diagnostics {
 //!     println!("{}: {}", diagnostic.code, diagnostic.message);
@@ -21,11 +30,24 @@
 
 pub mod a11y;
 pub mod component;
+pub mod css;
 mod diagnostic;
+pub mod ignore;
+pub mod project;
+pub mod rule;
+pub mod semantic;
+pub mod traversal;
+pub mod version;
 
 pub use component::ComponentCheckOptions;
 pub use diagnostic::{Diagnostic, DiagnosticCode, Severity};
+pub use ignore::Ignores;
+pub use project::{Project, ProjectFile};
+pub use rule::{registry, Rule, RuleCategory, RuleContext, RuleLevel, RuleMeta, RuleRegistry};
+pub use semantic::SemanticModel;
+pub use version::{SvelteFeature, SvelteVersion};
 
+use rustc_hash::FxHashMap;
 use svelte_parser::SvelteDocument;
 
 /// Options for diagnostic checking.
@@ -35,8 +57,18 @@ pub struct DiagnosticOptions {
     pub a11y: bool,
     /// Whether to run component checks.
     pub component: bool,
+    /// Whether to run stylesheet checks.
+    pub css: bool,
     /// The filename of the component (for naming checks).
     pub filename: Option<String>,
+    /// Per-rule levels keyed by code (e.g. `a11y-structure`). A configured
+    /// rule runs even when its category is disabled.
+    pub rules: FxHashMap<String, RuleLevel>,
+    /// The installed Svelte version. When unknown, the latest is assumed.
+    pub svelte_version: Option<SvelteVersion>,
+    /// The SvelteKit routes directory (`kit.files.routes`). SvelteKit route
+    /// file checks only run when it is set.
+    pub sveltekit_routes: Option<String>,
 }
 
 impl DiagnosticOptions {
@@ -45,7 +77,11 @@ impl DiagnosticOptions {
         Self {
             a11y: true,
             component: true,
+            css: true,
             filename: None,
+            rules: FxHashMap::default(),
+            svelte_version: None,
+            sveltekit_routes: None,
         }
     }
 
@@ -54,27 +90,46 @@ impl DiagnosticOptions {
         self.filename = Some(filename.into());
         self
     }
-}
 
-/// Runs all enabled diagnostic checks on a Svelte document.
-pub fn check(doc: &SvelteDocument, options: DiagnosticOptions) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
+    /// Sets the installed Svelte version.
+    pub fn with_svelte_version(mut self, version: SvelteVersion) -> Self {
+        self.svelte_version = Some(version);
+        self
+    }
 
-    if options.a11y {
-        diagnostics.extend(a11y::check(doc));
+    /// Marks the project as a SvelteKit project with routes in `routes`.
+    pub fn with_sveltekit_routes(mut self, routes: impl Into<String>) -> Self {
+        self.sveltekit_routes = Some(routes.into());
+        self
     }
 
-    if options.component {
-        let component_options = ComponentCheckOptions {
-            filename: options.filename.clone(),
-        };
-        diagnostics.extend(component::check(doc, &component_options));
+    /// Sets the level of a single rule.
+    pub fn with_rule(mut self, code: impl Into<String>, level: RuleLevel) -> Self {
+        self.rules.insert(code.into(), level);
+        self
     }
 
-    // Sort by position
-    diagnostics.sort_by_key(|d| d.span.start);
+    /// Returns the severity a rule runs at, or `None` if it is disabled.
+    pub fn severity_for(&self, meta: &RuleMeta) -> Option<Severity> {
+        match self.rules.get(meta.code.as_str()) {
+            Some(RuleLevel::Off) => None,
+            Some(RuleLevel::On(severity)) => Some(*severity),
+            None => {
+                let enabled = match meta.category {
+                    RuleCategory::A11y => self.a11y,
+                    RuleCategory::Component => self.component,
+                    RuleCategory::Css => self.css,
+                };
+                enabled.then_some(meta.default_severity)
+            }
+        }
+    }
+}
 
-    diagnostics
+/// Runs all enabled diagnostic checks on a Svelte document parsed from
+/// `source`.
+pub fn check(doc: &SvelteDocument, source: &str, options: DiagnosticOptions) -> Vec<Diagnostic> {
+    registry().check(doc, source, &options)
 }
 
 #[cfg(test)]
@@ -85,15 +140,17 @@ mod tests {
     #[test]
     fn test_check_empty_document() {
         let doc = parse("").document;
-        let diagnostics = check(&doc, DiagnosticOptions::all());
+        let diagnostics = check(&doc, "", DiagnosticOptions::all());
         assert!(diagnostics.is_empty());
     }
 
     #[test]
     fn test_check_with_a11y_issue() {
-        let doc = parse(r#"<h1>Title</h1><h3>Skipped</h3>"#).document;
+        let source = r#"<h1>Title</h1><h3>Skipped</h3>"#;
+        let doc = parse(source).document;
         let diagnostics = check(
             &doc,
+            source,
             DiagnosticOptions {
                 a11y: true,
                 ..Default::default()
@@ -103,7 +160,7 @@ mod tests {
         assert!(!diagnostics.is_empty());
         assert!(diagnostics
             .iter()
-            .any(|d| matches!(d.code, DiagnosticCode::A11yStructure)));
+            .any(|d| d.code.as_str() == "a11y-structure"));
     }
 }
 
@@ -159,6 +216,7 @@ mod fixture_tests {
 
             let diagnostics = check(
                 &result.document,
+                &source,
                 DiagnosticOptions {
                     component: true,
                     ..Default::default()
@@ -175,8 +233,38 @@ mod fixture_tests {
         }
     }
 
+    #[test]
+    fn test_invalid_a11y_fixtures_have_diagnostics() {
+        let fixtures_dir = get_fixtures_dir().join("invalid").join("a11y");
+        let files = collect_svelte_files(&fixtures_dir);
+
+        assert!(!files.is_empty(), "No a11y fixtures found");
+
+        for path in &files {
+            let source = fs::read_to_string(path).expect("Failed to read file");
+            let filename = path.file_name().unwrap().to_string_lossy();
+            let result = parse(&source);
+
+            let diagnostics = check(
+                &result.document,
+                &source,
+                DiagnosticOptions {
+                    a11y: true,
+                    ..Default::default()
+                },
+            );
+
+            assert!(
+                diagnostics
+                    .iter()
+                    .any(|d| matches!(d.code, DiagnosticCode::A11y(_))),
+                "Invalid a11y fixture {} should have compiler a11y warnings, but got none",
+                filename
+            );
+        }
+    }
+
     /// Tests that parser error fixtures produce parse errors
-    /// NOTE: Some fixtures don't produce errors due to known parser limitations
     #[test]
     fn test_parser_error_fixtures_have_errors() {
         let fixtures_dir = get_fixtures_dir().join("invalid").join("parser");
@@ -184,13 +272,6 @@ mod fixture_tests {
 
         assert!(!files.is_empty(), "No parser error fixtures found");
 
-        // Known parser limitations - these fixtures SHOULD produce errors but currently don't
-        // TODO: Fix parser to catch these errors
-        let known_parser_gaps = [
-            "UnclosedBlocks.svelte",     // Parser doesn't detect unclosed blocks
-            "InvalidBlockSyntax.svelte", // Parser is lenient with block syntax
-        ];
-
         let mut total_errors = 0;
         let mut files_with_errors = 0;
         let mut files_without_errors = Vec::new();
@@ -210,23 +291,16 @@ mod fixture_tests {
             println!("{}: {} parse errors", filename, result.errors.len());
         }
 
-        // Report files that didn't produce errors
-        if !files_without_errors.is_empty() {
-            println!("\n=== Files without parse errors (potential parser gaps) ===");
-            for f in &files_without_errors {
-                let is_known = known_parser_gaps.iter().any(|g| f.contains(g));
-                if is_known {
-                    println!("  {} (KNOWN LIMITATION)", f);
-                } else {
-                    println!("  {} (UNEXPECTED - may need investigation)", f);
-                }
-            }
-        }
-
-        // At least SOME parser fixtures should produce errors
+        // Duplicate attributes are left to the Svelte compiler.
+        let compiler_only = ["DuplicateAttributes.svelte"];
+        let unexpected: Vec<_> = files_without_errors
+            .iter()
+            .filter(|f| !compiler_only.contains(&f.as_str()))
+            .collect();
         assert!(
-            files_with_errors > 0,
-            "At least some parser error fixtures should produce errors"
+            unexpected.is_empty(),
+            "Parser error fixtures should produce parse errors, but these did not: {:?}",
+            unexpected
         );
 
         println!(
@@ -0,0 +1,639 @@
+//! Context keys: `context-never-set`, `context-never-read` and
+//! `context-type-mismatch`.
+//!
+//! `setContext`, `getContext` and `hasContext` calls are collected from every
+//! file with string keys, `const` keys declared at the top level of a file
+//! (usually `Symbol()`s), and keys imported from another file of the
+//! project. Getters and setters from `createContext()` are matched through
+//! their imports too. Keys that are computed, or come from packages, are
+//! not checked. String keys may be set by packages, so a string key that is
+//! only read is not reported.
+
+use super::{Project, ProjectDiagnostic};
+use crate::{Diagnostic, DiagnosticCode, RuleCategory, RuleMeta, Severity};
+use rustc_hash::{FxHashMap, FxHashSet};
+use smol_str::SmolStr;
+use source_map::Span;
+use swc_common::{BytePos, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, Expr, ImportDecl, ImportSpecifier, Lit, ModuleExportName, ModuleItem,
+    Pat, Stmt, TsAsExpr, TsSatisfiesExpr, TsType, VarDecl, VarDeclKind, VarDeclarator,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// The context calls and keys of one file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ContextFacts {
+    uses: Vec<ContextUse>,
+    /// Getter and setter names of `createContext()` results.
+    created: Vec<(Option<SmolStr>, Option<SmolStr>)>,
+    /// Top-level `const`s with a string value.
+    strings: FxHashMap<SmolStr, String>,
+}
+
+#[derive(Debug, Clone)]
+struct ContextUse {
+    access: Access,
+    key: KeyRef,
+    span: Span,
+    /// The type the value is set or read as, without whitespace.
+    ty: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Set,
+    Get,
+    Has,
+    /// A call to an imported function or a local `createContext()` result,
+    /// which may be a context getter or setter.
+    Call,
+}
+
+/// A key as written in a file.
+#[derive(Debug, Clone)]
+enum KeyRef {
+    String(String),
+    /// A top-level `const` of the file.
+    Local(SmolStr),
+    Imported {
+        specifier: String,
+        name: SmolStr,
+    },
+}
+
+/// A key resolved across the project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ContextKey {
+    String(String),
+    /// A `const` exported by a file.
+    Binding(usize, SmolStr),
+    /// A `createContext()` result of a file.
+    Created(usize, usize),
+}
+
+/// A context call whose key is resolved.
+#[derive(Debug)]
+pub(crate) struct ResolvedUse {
+    file: usize,
+    key: ContextKey,
+    access: Access,
+    span: Span,
+    ty: Option<String>,
+}
+
+impl ContextFacts {
+    /// Collects the context calls of a parsed script. `base` is the offset of
+    /// the script in the file.
+    pub(crate) fn collect(
+        &mut self,
+        module: &swc_ecma_ast::Module,
+        text: &str,
+        base: u32,
+        start_pos: BytePos,
+    ) {
+        let mut collector = Collector {
+            facts: self,
+            text,
+            base,
+            start_pos,
+            svelte_imports: FxHashMap::default(),
+            imports: FxHashMap::default(),
+            top_level: FxHashSet::default(),
+            annotations: FxHashMap::default(),
+            pending_type: None,
+        };
+        for item in &module.body {
+            collector.top_level_item(item);
+        }
+        module.visit_with(&mut collector);
+        let top_level = collector.top_level;
+        // Only calls that can reach a `createContext()` result matter.
+        let created: FxHashSet<&SmolStr> = self
+            .created
+            .iter()
+            .flat_map(|(getter, setter)| getter.iter().chain(setter))
+            .collect();
+        self.uses.retain(
+            |context_use| match (&context_use.access, &context_use.key) {
+                (Access::Call, KeyRef::Local(name)) => created.contains(name),
+                (_, KeyRef::Local(name)) => top_level.contains(name),
+                _ => true,
+            },
+        );
+    }
+}
+
+struct Collector<'a> {
+    facts: &'a mut ContextFacts,
+    text: &'a str,
+    base: u32,
+    start_pos: BytePos,
+    /// Local names of `svelte` imports, mapped to the imported name.
+    svelte_imports: FxHashMap<SmolStr, SmolStr>,
+    /// Local names imported from other modules: (specifier, imported name).
+    imports: FxHashMap<SmolStr, (String, SmolStr)>,
+    top_level: FxHashSet<SmolStr>,
+    /// Type annotations of variables, by name.
+    annotations: FxHashMap<SmolStr, String>,
+    /// A type written around the call being visited: `const x: T = call()`
+    /// or `call() as T`.
+    pending_type: Option<(swc_common::Span, String)>,
+}
+
+impl Collector<'_> {
+    fn top_level_item(&mut self, item: &ModuleItem) {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            ModuleItem::ModuleDecl(swc_ecma_ast::ModuleDecl::ExportDecl(export)) => {
+                match &export.decl {
+                    Decl::Var(var) => var,
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        if var.kind != VarDeclKind::Const {
+            return;
+        }
+        for declarator in &var.decls {
+            let Pat::Ident(ident) = &declarator.name else {
+                continue;
+            };
+            let name = SmolStr::new(&*ident.id.sym);
+            if let Some(Expr::Lit(Lit::Str(value))) = declarator.init.as_deref() {
+                if let Some(value) = value.value.as_str() {
+                    self.facts.strings.insert(name.clone(), value.to_string());
+                }
+            }
+            self.top_level.insert(name);
+        }
+    }
+
+    fn span(&self, span: swc_common::Span) -> Span {
+        let offset = |pos: BytePos| self.base + (pos.0 - self.start_pos.0);
+        Span::new(offset(span.lo), offset(span.hi))
+    }
+
+    /// Returns the source of a type, without whitespace.
+    fn type_text(&self, ty: &TsType) -> Option<String> {
+        let span = ty.span();
+        let start = (span.lo.0 - self.start_pos.0) as usize;
+        let end = (span.hi.0 - self.start_pos.0) as usize;
+        let text: String = self
+            .text
+            .get(start..end)?
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        // These say nothing about what the value is.
+        (!matches!(text.as_str(), "any" | "unknown")).then_some(text)
+    }
+
+    /// Returns the type a value expression has been given.
+    fn value_type(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::TsAs(TsAsExpr { type_ann, .. })
+            | Expr::TsSatisfies(TsSatisfiesExpr { type_ann, .. }) => self.type_text(type_ann),
+            Expr::Paren(paren) => self.value_type(&paren.expr),
+            Expr::Ident(ident) => self.annotations.get(&*ident.sym).cloned(),
+            _ => None,
+        }
+    }
+
+    fn key(&self, expr: &Expr) -> Option<KeyRef> {
+        match expr {
+            Expr::Lit(Lit::Str(value)) => Some(KeyRef::String(value.value.as_str()?.to_string())),
+            Expr::Ident(ident) => {
+                let name = SmolStr::new(&*ident.sym);
+                if let Some(value) = self.facts.strings.get(&name) {
+                    return Some(KeyRef::String(value.clone()));
+                }
+                Some(match self.imports.get(&name) {
+                    Some((specifier, imported)) => KeyRef::Imported {
+                        specifier: specifier.clone(),
+                        name: imported.clone(),
+                    },
+                    None => KeyRef::Local(name),
+                })
+            }
+            Expr::Paren(paren) => self.key(&paren.expr),
+            _ => None,
+        }
+    }
+}
+
+impl Visit for Collector<'_> {
+    fn visit_import_decl(&mut self, node: &ImportDecl) {
+        let Some(specifier) = node.src.value.as_str() else {
+            return;
+        };
+        for import in &node.specifiers {
+            let (local, imported) = match import {
+                ImportSpecifier::Named(named) => {
+                    let imported = match &named.imported {
+                        Some(ModuleExportName::Ident(ident)) => SmolStr::new(&*ident.sym),
+                        Some(ModuleExportName::Str(name)) => match name.value.as_str() {
+                            Some(name) => SmolStr::new(name),
+                            None => continue,
+                        },
+                        None => SmolStr::new(&*named.local.sym),
+                    };
+                    (SmolStr::new(&*named.local.sym), imported)
+                }
+                ImportSpecifier::Default(default) => (
+                    SmolStr::new(&*default.local.sym),
+                    SmolStr::new_static("default"),
+                ),
+                ImportSpecifier::Namespace(_) => continue,
+            };
+            if specifier == "svelte" {
+                self.svelte_imports.insert(local, imported);
+            } else {
+                self.imports
+                    .insert(local, (specifier.to_string(), imported));
+            }
+        }
+    }
+
+    fn visit_var_decl(&mut self, node: &VarDecl) {
+        for declarator in &node.decls {
+            let Pat::Ident(ident) = &declarator.name else {
+                continue;
+            };
+            let annotation = ident
+                .type_ann
+                .as_ref()
+                .and_then(|ann| self.type_text(&ann.type_ann))
+                .or_else(|| {
+                    // `let theme = $state<Theme>(...)`
+                    let Some(Expr::Call(call)) = declarator.init.as_deref() else {
+                        return None;
+                    };
+                    let callee = crate::semantic::rune_callee(declarator.init.as_deref()?)?;
+                    if !matches!(callee.as_str(), "$state" | "$state.raw" | "$derived") {
+                        return None;
+                    }
+                    self.type_text(call.type_args.as_ref()?.params.first()?)
+                });
+            if let Some(annotation) = annotation {
+                self.annotations
+                    .insert(SmolStr::new(&*ident.id.sym), annotation);
+            }
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, node: &VarDeclarator) {
+        // `const [getTheme, setTheme] = createContext<Theme>()`
+        if let (Pat::Array(array), Some(Expr::Call(call))) = (&node.name, node.init.as_deref()) {
+            if let Callee::Expr(callee) = &call.callee {
+                if let Expr::Ident(ident) = &**callee {
+                    if self.svelte_imports.get(&*ident.sym).map(SmolStr::as_str)
+                        == Some("createContext")
+                    {
+                        let name = |index: usize| match array.elems.get(index) {
+                            Some(Some(Pat::Ident(ident))) => Some(SmolStr::new(&*ident.id.sym)),
+                            _ => None,
+                        };
+                        self.facts.created.push((name(0), name(1)));
+                    }
+                }
+            }
+        }
+        if let (Pat::Ident(ident), Some(init)) = (&node.name, node.init.as_deref()) {
+            if let Some(ann) = &ident.type_ann {
+                if let Some(ty) = self.type_text(&ann.type_ann) {
+                    self.pending_type = Some((init.span(), ty));
+                }
+            }
+        }
+        node.visit_children_with(self);
+        self.pending_type = None;
+    }
+
+    fn visit_ts_as_expr(&mut self, node: &TsAsExpr) {
+        if let Some(ty) = self.type_text(&node.type_ann) {
+            self.pending_type = Some((node.expr.span(), ty));
+        }
+        node.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        node.visit_children_with(self);
+        let Callee::Expr(callee) = &node.callee else {
+            return;
+        };
+        let Expr::Ident(callee) = &**callee else {
+            return;
+        };
+        let callee = SmolStr::new(&*callee.sym);
+        let access = match self.svelte_imports.get(&callee).map(SmolStr::as_str) {
+            Some("setContext") => Access::Set,
+            Some("getContext") => Access::Get,
+            Some("hasContext") => Access::Has,
+            Some(_) => return,
+            None => {
+                let key = match self.imports.get(&callee) {
+                    Some((specifier, name)) => KeyRef::Imported {
+                        specifier: specifier.clone(),
+                        name: name.clone(),
+                    },
+                    None => KeyRef::Local(callee),
+                };
+                let ty = match &self.pending_type {
+                    Some((span, ty)) if *span == node.span => Some(ty.clone()),
+                    _ => None,
+                };
+                self.facts.uses.push(ContextUse {
+                    access: Access::Call,
+                    key,
+                    span: self.span(node.span),
+                    ty,
+                });
+                return;
+            }
+        };
+        let Some(key) = node.args.first().and_then(|arg| self.key(&arg.expr)) else {
+            return;
+        };
+        let explicit = node
+            .type_args
+            .as_ref()
+            .and_then(|args| args.params.first())
+            .and_then(|ty| self.type_text(ty));
+        let ty = match access {
+            Access::Set => explicit.or_else(|| self.value_type(&node.args.get(1)?.expr)),
+            Access::Get => explicit.or_else(|| match &self.pending_type {
+                Some((span, ty)) if *span == node.span => Some(ty.clone()),
+                _ => None,
+            }),
+            Access::Has | Access::Call => None,
+        };
+        self.facts.uses.push(ContextUse {
+            access,
+            key,
+            span: self.span(node.span),
+            ty,
+        });
+    }
+}
+
+/// Resolves the keys of every context call in a project.
+pub(crate) fn resolve(project: &Project) -> Vec<ResolvedUse> {
+    let files = project.files();
+    let graph = project.graph();
+    // Where a name used in `file` is declared.
+    let binding = |file: usize, key: &KeyRef| -> Option<(usize, SmolStr)> {
+        match key {
+            KeyRef::String(_) => None,
+            KeyRef::Local(name) => Some((file, name.clone())),
+            KeyRef::Imported { specifier, name } => {
+                let import = files[file]
+                    .imports()
+                    .iter()
+                    .position(|import| &import.specifier == specifier)?;
+                let edge = graph
+                    .edges(file)
+                    .iter()
+                    .find(|edge| edge.import == import)?;
+                Some((edge.target, name.clone()))
+            }
+        }
+    };
+    let mut resolved = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        for context_use in &file.contexts().uses {
+            let (key, access) = match (&context_use.key, context_use.access) {
+                (KeyRef::String(value), access) => (ContextKey::String(value.clone()), access),
+                (key, Access::Call) => {
+                    let Some((target, name)) = binding(i, key) else {
+                        continue;
+                    };
+                    let created = &files[target].contexts().created;
+                    let Some((index, access)) =
+                        created
+                            .iter()
+                            .enumerate()
+                            .find_map(|(index, (getter, setter))| {
+                                if getter.as_ref() == Some(&name) {
+                                    Some((index, Access::Get))
+                                } else if setter.as_ref() == Some(&name) {
+                                    Some((index, Access::Set))
+                                } else {
+                                    None
+                                }
+                            })
+                    else {
+                        continue;
+                    };
+                    (ContextKey::Created(target, index), access)
+                }
+                (key, access) => {
+                    let Some((target, name)) = binding(i, key) else {
+                        continue;
+                    };
+                    match files[target].contexts().strings.get(&name) {
+                        Some(value) => (ContextKey::String(value.clone()), access),
+                        None => (ContextKey::Binding(target, name), access),
+                    }
+                }
+            };
+            resolved.push(ResolvedUse {
+                file: i,
+                key,
+                access,
+                span: context_use.span,
+                ty: context_use.ty.clone(),
+            });
+        }
+    }
+    resolved
+}
+
+/// Returns how a key is shown in messages.
+fn describe(project: &Project, key: &ContextKey) -> String {
+    match key {
+        ContextKey::String(value) => format!("'{value}'"),
+        ContextKey::Binding(_, name) => format!("`{name}`"),
+        ContextKey::Created(file, index) => {
+            let (getter, setter) = &project.files()[*file].contexts().created[*index];
+            let name = getter.as_ref().or(setter.as_ref());
+            format!(
+                "from `createContext()` ({})",
+                name.map_or("unnamed", SmolStr::as_str)
+            )
+        }
+    }
+}
+
+/// Groups the resolved uses by key.
+fn by_key(uses: &[ResolvedUse]) -> FxHashMap<&ContextKey, Vec<&ResolvedUse>> {
+    let mut keys: FxHashMap<&ContextKey, Vec<&ResolvedUse>> = FxHashMap::default();
+    for context_use in uses {
+        keys.entry(&context_use.key).or_default().push(context_use);
+    }
+    keys
+}
+
+fn report(
+    project: &Project,
+    context_use: &ResolvedUse,
+    diagnostic: Diagnostic,
+) -> ProjectDiagnostic {
+    ProjectDiagnostic {
+        path: project.files()[context_use.file].path().to_path_buf(),
+        diagnostic,
+    }
+}
+
+/// `context-never-set`: contexts read where no file sets them, which read
+/// as `undefined` at runtime. String keys are skipped, since packages may set
+/// them.
+pub struct ContextNeverSet;
+
+static CONTEXT_NEVER_SET: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("context-never-set"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: Some("https://svelte.dev/docs/svelte/context"),
+    fixable: false,
+};
+
+impl super::ProjectRule for ContextNeverSet {
+    fn meta(&self) -> &'static RuleMeta {
+        &CONTEXT_NEVER_SET
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let uses = project.contexts();
+        let mut diagnostics = Vec::new();
+        for (key, uses) in by_key(uses) {
+            if matches!(key, ContextKey::String(_)) || uses.iter().any(|u| u.access == Access::Set)
+            {
+                continue;
+            }
+            for context_use in uses.iter().filter(|u| u.access == Access::Get) {
+                diagnostics.push(report(
+                    project,
+                    context_use,
+                    Diagnostic::new(
+                        CONTEXT_NEVER_SET.code,
+                        format!(
+                            "Context {} is read here but never set, so it is always undefined",
+                            describe(project, key)
+                        ),
+                        context_use.span,
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// `context-never-read`: contexts set where no file reads them.
+pub struct ContextNeverRead;
+
+static CONTEXT_NEVER_READ: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("context-never-read"),
+    category: RuleCategory::Component,
+    // Dead code rather than a bug, and packages may read string keys.
+    default_severity: Severity::Hint,
+    docs_url: Some("https://svelte.dev/docs/svelte/context"),
+    fixable: false,
+};
+
+impl super::ProjectRule for ContextNeverRead {
+    fn meta(&self) -> &'static RuleMeta {
+        &CONTEXT_NEVER_READ
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let uses = project.contexts();
+        let mut diagnostics = Vec::new();
+        for (key, uses) in by_key(uses) {
+            if uses.iter().any(|u| u.access != Access::Set) {
+                continue;
+            }
+            for context_use in uses {
+                diagnostics.push(report(
+                    project,
+                    context_use,
+                    Diagnostic::new(
+                        CONTEXT_NEVER_READ.code,
+                        format!(
+                            "Context {} is set here but never read",
+                            describe(project, key)
+                        ),
+                        context_use.span,
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// `context-type-mismatch`: contexts read with a type annotation that none
+/// of the places setting them agree with.
+pub struct ContextTypeMismatch;
+
+static CONTEXT_TYPE_MISMATCH: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("context-type-mismatch"),
+    category: RuleCategory::Component,
+    // Annotations are compared as text, so equivalent spellings (an alias and
+    // its expansion, `Array<T>` and `T[]`) also differ.
+    default_severity: Severity::Hint,
+    docs_url: Some("https://svelte.dev/docs/svelte/context"),
+    fixable: false,
+};
+
+impl super::ProjectRule for ContextTypeMismatch {
+    fn meta(&self) -> &'static RuleMeta {
+        &CONTEXT_TYPE_MISMATCH
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let uses = project.contexts();
+        let mut diagnostics = Vec::new();
+        for (key, uses) in by_key(uses) {
+            // `createContext<T>()` types both ends already.
+            if matches!(key, ContextKey::Created(..)) {
+                continue;
+            }
+            let mut set_types: Vec<&str> = uses
+                .iter()
+                .filter(|u| u.access == Access::Set)
+                .filter_map(|u| u.ty.as_deref())
+                .collect();
+            set_types.sort_unstable();
+            set_types.dedup();
+            if set_types.is_empty() {
+                continue;
+            }
+            for context_use in uses.iter().filter(|u| u.access == Access::Get) {
+                let Some(ty) = context_use.ty.as_deref() else {
+                    continue;
+                };
+                if set_types.contains(&ty) {
+                    continue;
+                }
+                diagnostics.push(report(
+                    project,
+                    context_use,
+                    Diagnostic::new(
+                        CONTEXT_TYPE_MISMATCH.code,
+                        format!(
+                            "Context {} is read as `{ty}` but set as `{}`",
+                            describe(project, key),
+                            set_types.join("` or `")
+                        ),
+                        context_use.span,
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
@@ -0,0 +1,416 @@
+//! Per-file summaries for the project pass.
+
+use super::context::ContextFacts;
+use super::graph::normalize;
+use crate::ignore::Ignores;
+use crate::semantic::{leading_identifier, parse_module, rune_callee, BindingKind};
+use crate::RuleContext;
+use rustc_hash::FxHashMap;
+use smol_str::SmolStr;
+use source_map::Span;
+use std::path::{Path, PathBuf};
+use svelte_parser::{Attribute, AttributeValue, ScriptLang, SvelteDocument, TemplateNode};
+use swc_common::BytePos;
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, ExportAll, Expr, ImportDecl, ImportSpecifier, Lit, MemberProp,
+    MetaPropKind, ModuleItem, NamedExport, ObjectPatProp, Pat, PropName, Stmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// What kind of file a [`ProjectFile`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    /// A `.svelte` component.
+    Component,
+    /// A `.svelte.js` or `.svelte.ts` module.
+    Module,
+    /// A plain JavaScript or TypeScript file, which only contributes imports.
+    Script,
+}
+
+impl FileKind {
+    /// Returns the kind of the file at `path`.
+    pub fn from_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        if name.ends_with(".svelte") {
+            FileKind::Component
+        } else if name.ends_with(".svelte.js") || name.ends_with(".svelte.ts") {
+            FileKind::Module
+        } else {
+            FileKind::Script
+        }
+    }
+}
+
+/// An import, re-export, dynamic `import()` or `import.meta.glob` pattern.
+#[derive(Debug, Clone)]
+pub struct Import {
+    /// The module specifier.
+    pub specifier: String,
+    /// The span of the statement or call.
+    pub span: Span,
+    /// Whether only types are imported.
+    pub type_only: bool,
+    /// The local name of the default import.
+    pub default_local: Option<SmolStr>,
+    /// Whether the specifier is an `import.meta.glob` pattern, which imports
+    /// every file it matches.
+    pub glob: bool,
+}
+
+/// A snippet a component passes to a child component.
+#[derive(Debug, Clone)]
+pub(crate) struct PassedSnippet {
+    /// The prop the snippet is passed as.
+    pub(crate) prop: SmolStr,
+    pub(crate) span: Span,
+}
+
+/// A `<Child>` in a component's template.
+#[derive(Debug, Clone)]
+pub(crate) struct ComponentUsage {
+    /// The name the child is rendered under.
+    pub(crate) name: SmolStr,
+    pub(crate) snippets: Vec<PassedSnippet>,
+}
+
+/// What the project pass needs to know about one file.
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    path: PathBuf,
+    kind: FileKind,
+    imports: Vec<Import>,
+    usages: Vec<ComponentUsage>,
+    /// Whether each `$props()` property is referenced, keyed by prop name.
+    /// `None` when the props aren't known: legacy components, a rest
+    /// element, or a script that doesn't parse.
+    props: Option<FxHashMap<SmolStr, bool>>,
+    contexts: ContextFacts,
+    ignores: Ignores,
+}
+
+impl ProjectFile {
+    /// Summarizes a component parsed from `source`.
+    pub fn component(path: impl Into<PathBuf>, doc: &SvelteDocument, source: &str) -> Self {
+        let path = normalize(&path.into());
+        let ctx = RuleContext::new(doc, source, None);
+        let mut imports = Vec::new();
+        let mut contexts = ContextFacts::default();
+        for script in [&doc.module_script, &doc.instance_script]
+            .into_iter()
+            .flatten()
+        {
+            let typescript = script.lang == ScriptLang::TypeScript;
+            if let Some((module, start_pos)) = parse_module(&script.content, typescript) {
+                let base = u32::from(script.content_span.start);
+                let mut collector = ImportCollector {
+                    imports: &mut imports,
+                    base,
+                    start_pos,
+                };
+                module.visit_with(&mut collector);
+                contexts.collect(&module, &script.content, base, start_pos);
+            }
+        }
+        Self {
+            kind: FileKind::Component,
+            imports,
+            usages: usages(&ctx),
+            props: props(&ctx),
+            contexts,
+            ignores: Ignores::collect(doc),
+            path,
+        }
+    }
+
+    /// Summarizes a `.svelte.js`/`.svelte.ts` module or a plain script. A
+    /// file that doesn't parse has no imports.
+    pub fn script(path: impl Into<PathBuf>, source: &str) -> Self {
+        let path = normalize(&path.into());
+        let typescript = path
+            .extension()
+            .is_some_and(|ext| matches!(ext.to_str(), Some("ts" | "mts" | "cts" | "tsx")));
+        let mut imports = Vec::new();
+        let mut contexts = ContextFacts::default();
+        if let Some((module, start_pos)) = parse_module(source, typescript) {
+            let mut collector = ImportCollector {
+                imports: &mut imports,
+                base: 0,
+                start_pos,
+            };
+            module.visit_with(&mut collector);
+            contexts.collect(&module, source, 0, start_pos);
+        }
+        Self {
+            kind: FileKind::from_path(&path),
+            imports,
+            usages: Vec::new(),
+            props: None,
+            contexts,
+            ignores: Ignores::default(),
+            path,
+        }
+    }
+
+    /// Returns the normalized path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the kind of the file.
+    pub fn kind(&self) -> FileKind {
+        self.kind
+    }
+
+    /// Returns the imports of the file, in source order.
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    pub(crate) fn usages(&self) -> &[ComponentUsage] {
+        &self.usages
+    }
+
+    pub(crate) fn props(&self) -> Option<&FxHashMap<SmolStr, bool>> {
+        self.props.as_ref()
+    }
+
+    pub(crate) fn contexts(&self) -> &ContextFacts {
+        &self.contexts
+    }
+
+    pub(crate) fn ignores(&self) -> &Ignores {
+        &self.ignores
+    }
+}
+
+/// Collects static imports, re-exports, and `import()` and
+/// `import.meta.glob()` calls with string specifiers.
+struct ImportCollector<'a> {
+    imports: &'a mut Vec<Import>,
+    /// Document offset of the first byte of the parsed text.
+    base: u32,
+    /// swc position of the first byte of the parsed text.
+    start_pos: BytePos,
+}
+
+impl ImportCollector<'_> {
+    fn push(
+        &mut self,
+        specifier: &swc_ecma_ast::Str,
+        span: swc_common::Span,
+        type_only: bool,
+        default_local: Option<SmolStr>,
+        glob: bool,
+    ) {
+        let Some(specifier) = specifier.value.as_str() else {
+            return;
+        };
+        let offset = |pos: BytePos| self.base + (pos.0 - self.start_pos.0);
+        self.imports.push(Import {
+            specifier: specifier.to_string(),
+            span: Span::new(offset(span.lo), offset(span.hi)),
+            type_only,
+            default_local,
+            glob,
+        });
+    }
+}
+
+impl Visit for ImportCollector<'_> {
+    fn visit_import_decl(&mut self, node: &ImportDecl) {
+        let type_only = node.type_only
+            || (!node.specifiers.is_empty()
+                && node.specifiers.iter().all(|specifier| {
+                    matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only)
+                }));
+        let default_local = node
+            .specifiers
+            .iter()
+            .find_map(|specifier| match specifier {
+                ImportSpecifier::Default(default) => Some(SmolStr::new(&*default.local.sym)),
+                _ => None,
+            });
+        self.push(&node.src, node.span, type_only, default_local, false);
+    }
+
+    fn visit_named_export(&mut self, node: &NamedExport) {
+        if let Some(src) = &node.src {
+            self.push(src, node.span, node.type_only, None, false);
+        }
+    }
+
+    fn visit_export_all(&mut self, node: &ExportAll) {
+        self.push(&node.src, node.span, node.type_only, None, false);
+    }
+
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        if let (Callee::Import(_), Some(arg)) = (&node.callee, node.args.first()) {
+            if let Expr::Lit(Lit::Str(specifier)) = &*arg.expr {
+                self.push(specifier, node.span, false, None, false);
+            }
+        }
+        if is_import_meta_glob(&node.callee) {
+            if let Some(arg) = node.args.first() {
+                let patterns: Vec<&swc_ecma_ast::Str> = match &*arg.expr {
+                    Expr::Lit(Lit::Str(pattern)) => vec![pattern],
+                    Expr::Array(array) => array
+                        .elems
+                        .iter()
+                        .flatten()
+                        .filter_map(|elem| match &*elem.expr {
+                            Expr::Lit(Lit::Str(pattern)) => Some(pattern),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                for pattern in patterns {
+                    self.push(pattern, node.span, false, None, true);
+                }
+            }
+        }
+        node.visit_children_with(self);
+    }
+}
+
+/// Returns whether a callee is `import.meta.glob`.
+fn is_import_meta_glob(callee: &Callee) -> bool {
+    let Callee::Expr(expr) = callee else {
+        return false;
+    };
+    let Expr::Member(member) = &**expr else {
+        return false;
+    };
+    matches!(&*member.obj, Expr::MetaProp(meta) if meta.kind == MetaPropKind::ImportMeta)
+        && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "glob")
+}
+
+/// Returns the components rendered in a template and the snippets passed to
+/// each.
+fn usages(ctx: &RuleContext<'_>) -> Vec<ComponentUsage> {
+    let model = ctx.semantic();
+    let is_snippet = |name: &str| {
+        model
+            .bindings()
+            .iter()
+            .any(|binding| binding.kind == BindingKind::Snippet && binding.name == name)
+    };
+    let mut usages = Vec::new();
+    for visited in ctx.traversal().nodes() {
+        let TemplateNode::Component(component) = visited.node else {
+            continue;
+        };
+        // `<ui.Button>` and `<Foo.Bar>` don't name an import directly.
+        if component.name.contains('.') {
+            continue;
+        }
+        let mut snippets = Vec::new();
+        for attribute in &component.attributes {
+            match attribute {
+                Attribute::Normal(attr) => {
+                    if let AttributeValue::Expression(value) = &attr.value {
+                        if is_snippet(value.expression.trim()) {
+                            snippets.push(PassedSnippet {
+                                prop: attr.name.clone(),
+                                span: attr.span,
+                            });
+                        }
+                    }
+                }
+                Attribute::Shorthand(attr) if is_snippet(&attr.name) => {
+                    snippets.push(PassedSnippet {
+                        prop: attr.name.clone(),
+                        span: attr.span,
+                    });
+                }
+                _ => {}
+            }
+        }
+        let mut content = None;
+        for child in &component.children {
+            match child {
+                TemplateNode::SnippetBlock(block) => snippets.push(PassedSnippet {
+                    prop: SmolStr::new(leading_identifier(&block.name)),
+                    span: Span::new(block.span.start, block.parameters_span.end),
+                }),
+                TemplateNode::Text(text) if text.is_whitespace => {}
+                TemplateNode::Comment(_) | TemplateNode::ConstTag(_) => {}
+                child => {
+                    content.get_or_insert(child.span());
+                }
+            }
+        }
+        // Content outside snippet blocks becomes the `children` snippet.
+        if let Some(span) = content {
+            snippets.push(PassedSnippet {
+                prop: SmolStr::new_static("children"),
+                span,
+            });
+        }
+        usages.push(ComponentUsage {
+            name: component.name.clone(),
+            snippets,
+        });
+    }
+    usages
+}
+
+/// Returns whether each `$props()` property of a component is referenced.
+fn props(ctx: &RuleContext<'_>) -> Option<FxHashMap<SmolStr, bool>> {
+    let model = ctx.semantic();
+    if model.is_partial() || !model.has_props_rune() {
+        return None;
+    }
+    let script = ctx.doc().instance_script.as_ref()?;
+    let (module, _) = parse_module(&script.content, script.lang == ScriptLang::TypeScript)?;
+    let pattern = module.body.iter().find_map(|item| {
+        let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) = item else {
+            return None;
+        };
+        var.decls.iter().find_map(|declarator| {
+            let init = declarator.init.as_deref()?;
+            (rune_callee(init).as_deref() == Some("$props")).then_some(&declarator.name)
+        })
+    })?;
+    // `let props = $props()` passes every prop on as an object.
+    let Pat::Object(object) = pattern else {
+        return None;
+    };
+    let is_referenced = |local: &str| {
+        model
+            .resolve(model.instance_scope(), local)
+            .is_some_and(|id| model.references_to(id).next().is_some())
+    };
+    let mut props = FxHashMap::default();
+    for prop in &object.props {
+        match prop {
+            ObjectPatProp::KeyValue(kv) => {
+                let key = match &kv.key {
+                    PropName::Ident(ident) => SmolStr::new(&*ident.sym),
+                    PropName::Str(str) => SmolStr::new(str.value.as_str()?),
+                    _ => return None,
+                };
+                let local = match &*kv.value {
+                    Pat::Ident(ident) => Some(&ident.id.sym),
+                    Pat::Assign(assign) => match &*assign.left {
+                        Pat::Ident(ident) => Some(&ident.id.sym),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                // A nested pattern reads the prop itself.
+                props.insert(key, local.is_none_or(|local| is_referenced(local)));
+            }
+            ObjectPatProp::Assign(assign) => {
+                let name = &assign.key.id.sym;
+                props.insert(SmolStr::new(&**name), is_referenced(name));
+            }
+            ObjectPatProp::Rest(_) => return None,
+        }
+    }
+    Some(props)
+}
@@ -0,0 +1,325 @@
+//! Import resolution and the import graph.
+//!
+//! Specifiers are resolved lexically against the files of the project, the
+//! way Vite and TypeScript's `bundler` resolution would find them: relative
+//! paths and configured aliases, with or without an extension, and
+//! `index` files of directories. `import.meta.glob` patterns import every
+//! file of the project they match. Packages and anything outside the project
+//! stay unresolved.
+
+use super::ProjectFile;
+use globset::GlobBuilder;
+use rustc_hash::FxHashMap;
+use std::path::{Component as PathComponent, Path, PathBuf};
+
+/// A resolved import: `file.imports()[import]` refers to file `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    /// The index of the import in the importing file.
+    pub import: usize,
+    /// The index of the imported file.
+    pub target: usize,
+}
+
+/// The resolved imports between the files of a project, by file index.
+#[derive(Debug, Default)]
+pub struct ImportGraph {
+    index: FxHashMap<PathBuf, usize>,
+    edges: Vec<Vec<Edge>>,
+    importers: Vec<Vec<usize>>,
+    value_edges: Vec<Vec<usize>>,
+}
+
+impl ImportGraph {
+    pub(crate) fn build(files: &[ProjectFile], aliases: &[(String, PathBuf)]) -> Self {
+        let index: FxHashMap<PathBuf, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (file.path().to_path_buf(), i))
+            .collect();
+        let mut graph = Self {
+            edges: vec![Vec::new(); files.len()],
+            importers: vec![Vec::new(); files.len()],
+            value_edges: vec![Vec::new(); files.len()],
+            index,
+        };
+        for (from, file) in files.iter().enumerate() {
+            for (i, import) in file.imports().iter().enumerate() {
+                let targets = if import.glob {
+                    Self::expand_glob(files, file.path(), &import.specifier, aliases)
+                } else {
+                    Vec::from_iter(graph.resolve(file.path(), &import.specifier, aliases))
+                };
+                for target in targets {
+                    graph.edges[from].push(Edge { import: i, target });
+                    if target == from {
+                        continue;
+                    }
+                    if !graph.importers[target].contains(&from) {
+                        graph.importers[target].push(from);
+                    }
+                    if !import.type_only && !graph.value_edges[from].contains(&target) {
+                        graph.value_edges[from].push(target);
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// Returns the index of the file at a normalized path.
+    pub fn index_of(&self, path: &Path) -> Option<usize> {
+        self.index.get(path).copied()
+    }
+
+    /// Returns the resolved imports of a file.
+    pub fn edges(&self, file: usize) -> &[Edge] {
+        &self.edges[file]
+    }
+
+    /// Returns the other files that import a file.
+    pub fn importers(&self, file: usize) -> &[usize] {
+        &self.importers[file]
+    }
+
+    /// Returns every file that imports a file directly or indirectly, which
+    /// are the files a change to it can affect. The list is sorted and does
+    /// not include the file itself.
+    pub fn dependents(&self, file: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.importers.len()];
+        seen[file] = true;
+        let mut stack = vec![file];
+        let mut dependents = Vec::new();
+        while let Some(current) = stack.pop() {
+            for &importer in &self.importers[current] {
+                if !seen[importer] {
+                    seen[importer] = true;
+                    dependents.push(importer);
+                    stack.push(importer);
+                }
+            }
+        }
+        dependents.sort_unstable();
+        dependents
+    }
+
+    /// Returns the groups of files that import each other through runtime
+    /// (not type-only) imports: the strongly connected components with more
+    /// than one file, each sorted.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        Tarjan::new(&self.value_edges).run()
+    }
+
+    /// Returns a shortest runtime import path from `from` to `to` that stays
+    /// within `within`, including both ends.
+    pub(crate) fn path_within(&self, from: usize, to: usize, within: &[usize]) -> Vec<usize> {
+        let mut previous: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut queue = std::collections::VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                break;
+            }
+            for &next in &self.value_edges[current] {
+                if within.contains(&next) && next != from && !previous.contains_key(&next) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut path = vec![to];
+        let mut current = to;
+        while current != from {
+            let Some(&prev) = previous.get(&current) else {
+                break;
+            };
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+        path
+    }
+
+    fn resolve(
+        &self,
+        importer: &Path,
+        specifier: &str,
+        aliases: &[(String, PathBuf)],
+    ) -> Option<usize> {
+        let base = resolve_specifier(importer, specifier, aliases)?;
+        let base = base.to_string_lossy();
+        let mut candidates = vec![
+            base.to_string(),
+            format!("{base}.ts"),
+            format!("{base}.js"),
+            format!("{base}/index.ts"),
+            format!("{base}/index.js"),
+        ];
+        // TypeScript resolves `./store.svelte.js` to `store.svelte.ts`.
+        if let Some(stem) = base.strip_suffix(".js") {
+            candidates.push(format!("{stem}.ts"));
+        }
+        candidates
+            .iter()
+            .find_map(|candidate| self.index_of(Path::new(candidate)))
+    }
+
+    /// Returns the files an `import.meta.glob` pattern matches. Negated
+    /// patterns match nothing, so a file they exclude still counts as
+    /// imported.
+    fn expand_glob(
+        files: &[ProjectFile],
+        importer: &Path,
+        pattern: &str,
+        aliases: &[(String, PathBuf)],
+    ) -> Vec<usize> {
+        let Some(pattern) = resolve_specifier(importer, pattern, aliases) else {
+            return Vec::new();
+        };
+        let Ok(glob) = GlobBuilder::new(&pattern.to_string_lossy())
+            .literal_separator(true)
+            .build()
+        else {
+            return Vec::new();
+        };
+        let matcher = glob.compile_matcher();
+        files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| matcher.is_match(file.path()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Resolves a relative or aliased specifier to a path, without looking for
+/// the file.
+fn resolve_specifier(
+    importer: &Path,
+    specifier: &str,
+    aliases: &[(String, PathBuf)],
+) -> Option<PathBuf> {
+    if let Some(path) = resolve_relative(importer, specifier) {
+        return Some(path);
+    }
+    let (alias, dir) = aliases
+        .iter()
+        .filter(|(alias, _)| {
+            specifier == alias
+                || specifier
+                    .strip_prefix(alias.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|(alias, _)| alias.len())?;
+    let rest = specifier[alias.len()..].trim_start_matches('/');
+    Some(normalize(&dir.join(rest)))
+}
+
+/// Tarjan's strongly connected components, without recursion so deep import
+/// chains can't overflow the stack.
+struct Tarjan<'a> {
+    edges: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(edges: &'a [Vec<usize>]) -> Self {
+        Self {
+            edges,
+            index: vec![None; edges.len()],
+            lowlink: vec![0; edges.len()],
+            on_stack: vec![false; edges.len()],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        }
+    }
+
+    fn run(mut self) -> Vec<Vec<usize>> {
+        for node in 0..self.edges.len() {
+            if self.index[node].is_none() {
+                self.visit(node);
+            }
+        }
+        self.components
+    }
+
+    fn visit(&mut self, root: usize) {
+        // (node, next edge to follow)
+        let mut work = vec![(root, 0)];
+        self.open(root);
+        while let Some(&(node, edge)) = work.last() {
+            if let Some(&next) = self.edges[node].get(edge) {
+                if let Some(top) = work.last_mut() {
+                    top.1 += 1;
+                }
+                match self.index[next] {
+                    None => {
+                        self.open(next);
+                        work.push((next, 0));
+                    }
+                    Some(index) if self.on_stack[next] => {
+                        self.lowlink[node] = self.lowlink[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                self.lowlink[parent] = self.lowlink[parent].min(self.lowlink[node]);
+            }
+            if Some(self.lowlink[node]) == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort_unstable();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    fn open(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.lowlink[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+}
+
+/// Resolves a relative import specifier against the importing file.
+pub(crate) fn resolve_relative(importer: &Path, specifier: &str) -> Option<PathBuf> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+    Some(normalize(&importer.parent()?.join(specifier)))
+}
+
+/// Removes `.` and `..` components without touching the filesystem.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            PathComponent::CurDir => {}
+            PathComponent::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,168 @@
+//! Project-wide diagnostics.
+//!
+//! Rules in the [`registry`](crate::registry) see one document at a time.
+//! The checks here need every file of a project: a [`ProjectFile`] summarizes
+//! what a component, a `.svelte.js`/`.svelte.ts` module or a plain script
+//! imports and which snippets a component passes to its children, and a
+//! [`Project`] resolves those imports into an [`ImportGraph`] and runs the
+//! [`ProjectRule`]s over it:
+//! - `unused-component`: components no other file imports
+//! - `import-cycle`: import cycles through rune modules
+//! - `duplicate-component-name`: components sharing a filename
+//! - `unrendered-snippet`: snippets passed to a component that never renders
+//!   them
+//! - `context-never-set`, `context-never-read` and `context-type-mismatch`:
+//!   context keys that don't line up between `setContext` and `getContext`
+//!
+//! Summaries are built while files are checked one by one, so the project
+//! pass only resolves specifiers and walks the graph.
+
+mod context;
+mod file;
+pub(crate) mod graph;
+mod rules;
+
+pub use context::{ContextNeverRead, ContextNeverSet, ContextTypeMismatch};
+pub use file::{FileKind, Import, ProjectFile};
+pub use graph::ImportGraph;
+pub use rules::{DuplicateComponentName, ImportCycle, UnrenderedSnippet, UnusedComponent};
+
+use crate::{Diagnostic, DiagnosticOptions, RuleMeta};
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// A check over all files of a project.
+pub trait ProjectRule: Send + Sync {
+    /// Returns the rule's metadata.
+    fn meta(&self) -> &'static RuleMeta;
+
+    /// Runs the rule on a project.
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic>;
+}
+
+/// Returns the built-in project rules.
+pub fn rules() -> impl Iterator<Item = &'static dyn ProjectRule> {
+    static RULES: [&dyn ProjectRule; 7] = [
+        &UnusedComponent,
+        &ImportCycle,
+        &DuplicateComponentName,
+        &UnrenderedSnippet,
+        &ContextNeverSet,
+        &ContextNeverRead,
+        &ContextTypeMismatch,
+    ];
+    RULES.iter().copied()
+}
+
+/// A diagnostic reported in one file of a project.
+#[derive(Debug, Clone)]
+pub struct ProjectDiagnostic {
+    /// The file the diagnostic belongs to.
+    pub path: PathBuf,
+    /// The diagnostic, with a span in that file.
+    pub diagnostic: Diagnostic,
+}
+
+/// The files of a project and the imports between them.
+pub struct Project {
+    files: Vec<ProjectFile>,
+    root: Option<PathBuf>,
+    aliases: Vec<(String, PathBuf)>,
+    graph: OnceLock<ImportGraph>,
+    contexts: OnceLock<Vec<context::ResolvedUse>>,
+}
+
+impl Project {
+    /// Creates a project from file summaries.
+    pub fn new(files: Vec<ProjectFile>) -> Self {
+        Self {
+            files,
+            root: None,
+            aliases: Vec::new(),
+            graph: OnceLock::new(),
+            contexts: OnceLock::new(),
+        }
+    }
+
+    /// Sets the directory paths in messages are shown relative to.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(graph::normalize(&root.into()));
+        self
+    }
+
+    /// Resolves imports of `alias` and `alias/...` (e.g. `$lib`) from `dir`.
+    pub fn with_alias(mut self, alias: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        self.aliases
+            .push((alias.into(), graph::normalize(&dir.into())));
+        self.graph = OnceLock::new();
+        self.contexts = OnceLock::new();
+        self
+    }
+
+    /// Returns the files of the project.
+    pub fn files(&self) -> &[ProjectFile] {
+        &self.files
+    }
+
+    /// Returns the index of the file at `path`.
+    pub fn file_index(&self, path: &Path) -> Option<usize> {
+        self.graph().index_of(&graph::normalize(path))
+    }
+
+    /// Returns the import graph, resolving imports on first use.
+    pub fn graph(&self) -> &ImportGraph {
+        self.graph
+            .get_or_init(|| ImportGraph::build(&self.files, &self.aliases))
+    }
+
+    /// Returns the context calls of all files, with their keys resolved.
+    pub(crate) fn contexts(&self) -> &[context::ResolvedUse] {
+        self.contexts.get_or_init(|| context::resolve(self))
+    }
+
+    /// Returns `path` relative to the project root, with `/` separators.
+    pub fn display_path(&self, path: &Path) -> String {
+        let relative = self
+            .root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Runs the enabled project rules, at the severity `options` give them.
+    ///
+    /// Diagnostics silenced by a `<!-- svelte-ignore -->` comment in their
+    /// file are dropped.
+    pub fn check(&self, options: &DiagnosticOptions) -> Vec<ProjectDiagnostic> {
+        let by_path: FxHashMap<&Path, &ProjectFile> =
+            self.files.iter().map(|file| (file.path(), file)).collect();
+        let mut diagnostics: Vec<ProjectDiagnostic> = rules()
+            .filter_map(|rule| Some((rule, options.severity_for(rule.meta())?)))
+            .flat_map(|(rule, severity)| {
+                rule.check(self).into_iter().map(move |mut reported| {
+                    reported.diagnostic.severity = severity;
+                    reported
+                })
+            })
+            .filter(|reported| {
+                by_path.get(reported.path.as_path()).is_none_or(|file| {
+                    !file.ignores().is_ignored(
+                        reported.diagnostic.code.as_str(),
+                        reported.diagnostic.span.start,
+                    )
+                })
+            })
+            .collect();
+        diagnostics.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.diagnostic.span.start.cmp(&b.diagnostic.span.start))
+        });
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,245 @@
+//! The built-in [`ProjectRule`]s.
+
+use super::{FileKind, Project, ProjectDiagnostic, ProjectFile, ProjectRule};
+use crate::{Diagnostic, DiagnosticCode, RuleCategory, RuleMeta, Severity};
+use rustc_hash::FxHashMap;
+use source_map::Span;
+
+/// `unused-component`: components that no file in the project imports.
+///
+/// SvelteKit route files are loaded by the router, and files with a dot in
+/// their name (`Button.stories.svelte`, `Form.test.svelte`) by other tools.
+pub struct UnusedComponent;
+
+static UNUSED_COMPONENT: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unused-component"),
+    category: RuleCategory::Component,
+    // Computed `import()` calls can't be followed.
+    default_severity: Severity::Hint,
+    docs_url: None,
+    fixable: false,
+};
+
+impl ProjectRule for UnusedComponent {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNUSED_COMPONENT
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let graph = project.graph();
+        project
+            .files()
+            .iter()
+            .enumerate()
+            .filter(|(i, file)| {
+                component_name(file).is_some_and(|name| !name.contains('.'))
+                    && graph.importers(*i).is_empty()
+            })
+            .map(|(_, file)| {
+                report(
+                    file,
+                    Diagnostic::new(
+                        UNUSED_COMPONENT.code,
+                        "Component is never imported",
+                        file_span(),
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// `import-cycle`: files that import each other through a `.svelte.js` or
+/// `.svelte.ts` module.
+///
+/// A module in a cycle can run before the modules it imports have
+/// initialized their `$state`. Cycles between components only are left
+/// alone; mutually recursive components are a common pattern. Each cycle is
+/// reported once, at the import in its first module.
+pub struct ImportCycle;
+
+static IMPORT_CYCLE: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("import-cycle"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl ProjectRule for ImportCycle {
+    fn meta(&self) -> &'static RuleMeta {
+        &IMPORT_CYCLE
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let graph = project.graph();
+        let files = project.files();
+        let mut diagnostics = Vec::new();
+        for cycle in graph.cycles() {
+            // Reported once, on the first module of the cycle.
+            let Some((member, edge)) = cycle.iter().find_map(|&member| {
+                let file = &files[member];
+                if file.kind() != FileKind::Module {
+                    return None;
+                }
+                let edge = graph.edges(member).iter().find(|edge| {
+                    cycle.contains(&edge.target) && !file.imports()[edge.import].type_only
+                })?;
+                Some((member, edge))
+            }) else {
+                continue;
+            };
+            let file = &files[member];
+            let chain: Vec<String> = std::iter::once(member)
+                .chain(graph.path_within(edge.target, member, &cycle))
+                .map(|i| project.display_path(files[i].path()))
+                .collect();
+            diagnostics.push(report(
+                file,
+                Diagnostic::new(
+                    IMPORT_CYCLE.code,
+                    format!(
+                        "Import cycle: {}. A module in a cycle can run before its imports are initialized",
+                        chain.join(" -> ")
+                    ),
+                    file.imports()[edge.import].span,
+                ),
+            ));
+        }
+        diagnostics
+    }
+}
+
+/// `duplicate-component-name`: components in different directories with the
+/// same filename, which editors offer side by side as auto-imports.
+pub struct DuplicateComponentName;
+
+static DUPLICATE_COMPONENT_NAME: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("duplicate-component-name"),
+    category: RuleCategory::Component,
+    // Often deliberate, e.g. per-feature `Header.svelte` files.
+    default_severity: Severity::Hint,
+    docs_url: None,
+    fixable: false,
+};
+
+impl ProjectRule for DuplicateComponentName {
+    fn meta(&self) -> &'static RuleMeta {
+        &DUPLICATE_COMPONENT_NAME
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let mut by_name: FxHashMap<&str, Vec<&ProjectFile>> = FxHashMap::default();
+        for file in project.files() {
+            if let Some(name) = component_name(file) {
+                by_name.entry(name).or_default().push(file);
+            }
+        }
+        let mut diagnostics = Vec::new();
+        for (name, files) in by_name {
+            if files.len() < 2 {
+                continue;
+            }
+            for file in &files {
+                let others: Vec<String> = files
+                    .iter()
+                    .filter(|other| other.path() != file.path())
+                    .map(|other| project.display_path(other.path()))
+                    .collect();
+                diagnostics.push(report(
+                    file,
+                    Diagnostic::new(
+                        DUPLICATE_COMPONENT_NAME.code,
+                        format!(
+                            "Component name '{name}' is also used by {}, so auto-imports may pick the wrong one",
+                            others.join(", ")
+                        ),
+                        file_span(),
+                    ),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// `unrendered-snippet`: snippets passed to a component that has no such
+/// prop or never uses it.
+///
+/// Only components whose `$props()` are destructured without a rest element
+/// are checked; otherwise any prop may be read or passed on.
+pub struct UnrenderedSnippet;
+
+static UNRENDERED_SNIPPET: RuleMeta = RuleMeta {
+    code: DiagnosticCode::Rule("unrendered-snippet"),
+    category: RuleCategory::Component,
+    default_severity: Severity::Warning,
+    docs_url: None,
+    fixable: false,
+};
+
+impl ProjectRule for UnrenderedSnippet {
+    fn meta(&self) -> &'static RuleMeta {
+        &UNRENDERED_SNIPPET
+    }
+
+    fn check(&self, project: &Project) -> Vec<ProjectDiagnostic> {
+        let graph = project.graph();
+        let files = project.files();
+        let mut diagnostics = Vec::new();
+        for (i, file) in files.iter().enumerate() {
+            for usage in file.usages() {
+                let Some(child) = graph.edges(i).iter().find_map(|edge| {
+                    let import = &file.imports()[edge.import];
+                    (import.default_local.as_ref() == Some(&usage.name))
+                        .then(|| &files[edge.target])
+                }) else {
+                    continue;
+                };
+                let Some(props) = child.props() else {
+                    continue;
+                };
+                for snippet in &usage.snippets {
+                    let message = match props.get(&snippet.prop) {
+                        Some(true) => continue,
+                        Some(false) => format!(
+                            "<{}> never renders its '{}' prop, so this snippet is never shown",
+                            usage.name, snippet.prop
+                        ),
+                        None => format!(
+                            "<{}> has no '{}' prop, so this snippet is never shown",
+                            usage.name, snippet.prop
+                        ),
+                    };
+                    diagnostics.push(report(
+                        file,
+                        Diagnostic::new(UNRENDERED_SNIPPET.code, message, snippet.span),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Returns the name of a component file, unless it is a SvelteKit route
+/// file.
+fn component_name(file: &ProjectFile) -> Option<&str> {
+    if file.kind() != FileKind::Component {
+        return None;
+    }
+    let name = file.path().file_name()?.to_str()?.strip_suffix(".svelte")?;
+    (!name.starts_with('+')).then_some(name)
+}
+
+fn report(file: &ProjectFile, diagnostic: Diagnostic) -> ProjectDiagnostic {
+    ProjectDiagnostic {
+        path: file.path().to_path_buf(),
+        diagnostic,
+    }
+}
+
+/// The span file-level diagnostics are reported at: the start of the file.
+fn file_span() -> Span {
+    Span::empty(0u32)
+}
@@ -0,0 +1,387 @@
+use super::*;
+use crate::DiagnosticOptions;
+use svelte_parser::parse;
+
+fn file(path: &str, source: &str) -> ProjectFile {
+    if path.ends_with(".svelte") {
+        ProjectFile::component(path, &parse(source).document, source)
+    } else {
+        ProjectFile::script(path, source)
+    }
+}
+
+fn project(files: &[(&str, &str)]) -> Project {
+    Project::new(
+        files
+            .iter()
+            .map(|(path, source)| file(path, source))
+            .collect(),
+    )
+    .with_root("/app")
+    .with_alias("$lib", "/app/src/lib")
+}
+
+/// Returns `path: code: message` for each diagnostic.
+fn check(files: &[(&str, &str)]) -> Vec<String> {
+    let project = project(files);
+    project
+        .check(&DiagnosticOptions::all())
+        .into_iter()
+        .map(|reported| {
+            format!(
+                "{}: {}: {}",
+                project.display_path(&reported.path),
+                reported.diagnostic.code,
+                reported.diagnostic.message
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_resolves_relative_alias_and_extensionless_imports() {
+    let project = project(&[
+        (
+            "/app/src/routes/+page.svelte",
+            r#"<script lang="ts">
+    import Card from '$lib/Card.svelte';
+    import { count } from '../lib/state.svelte';
+    import { format } from '$lib/utils';
+    import type { Item } from '$lib/types.js';
+    const lazy = () => import('./Lazy.svelte');
+</script>"#,
+        ),
+        ("/app/src/lib/Card.svelte", ""),
+        ("/app/src/lib/state.svelte.ts", ""),
+        ("/app/src/lib/utils/index.ts", ""),
+        ("/app/src/lib/types.ts", ""),
+        ("/app/src/routes/Lazy.svelte", ""),
+    ]);
+    let graph = project.graph();
+    let targets: Vec<String> = graph
+        .edges(0)
+        .iter()
+        .map(|edge| project.display_path(project.files()[edge.target].path()))
+        .collect();
+    assert_eq!(
+        targets,
+        [
+            "src/lib/Card.svelte",
+            "src/lib/state.svelte.ts",
+            "src/lib/utils/index.ts",
+            "src/lib/types.ts",
+            "src/routes/Lazy.svelte",
+        ]
+    );
+    assert!(project.files()[0].imports()[3].type_only);
+}
+
+#[test]
+fn test_dependents_are_transitive() {
+    let project = project(&[
+        (
+            "/app/src/App.svelte",
+            "<script>import List from './List.svelte';</script>",
+        ),
+        (
+            "/app/src/List.svelte",
+            "<script>import Item from './Item.svelte';</script>",
+        ),
+        ("/app/src/Item.svelte", ""),
+        ("/app/src/main.ts", "import App from './App.svelte';"),
+    ]);
+    let item = project
+        .file_index(Path::new("/app/src/Item.svelte"))
+        .unwrap();
+    assert_eq!(project.graph().dependents(item), [0, 1, 3]);
+}
+
+#[test]
+fn test_unused_components() {
+    let diagnostics = check(&[
+        ("/app/src/main.ts", "import App from './App.svelte';"),
+        (
+            "/app/src/App.svelte",
+            "<script>import Used from './Used.svelte';</script><Used />",
+        ),
+        ("/app/src/Used.svelte", ""),
+        ("/app/src/Orphan.svelte", ""),
+        ("/app/src/Button.stories.svelte", ""),
+        (
+            "/app/src/blocks.ts",
+            "export const blocks = import.meta.glob(['./blocks/*.svelte', '$lib/icons/**/*.svelte']);",
+        ),
+        ("/app/src/blocks/Hero.svelte", ""),
+        ("/app/src/blocks/nested/Unmatched.svelte", ""),
+        ("/app/src/lib/icons/solid/Star.svelte", ""),
+        ("/app/src/routes/+page.svelte", ""),
+    ]);
+    assert_eq!(
+        diagnostics,
+        [
+            "src/Orphan.svelte: unused-component: Component is never imported",
+            "src/blocks/nested/Unmatched.svelte: unused-component: Component is never imported",
+        ]
+    );
+}
+
+#[test]
+fn test_import_cycles_through_modules() {
+    let diagnostics = check(&[
+        (
+            "/app/src/Counter.svelte",
+            "<script>import { counter } from './counter.svelte.js';</script>",
+        ),
+        (
+            "/app/src/counter.svelte.ts",
+            "import Counter from './Counter.svelte';\nexport const counter = $state({ component: Counter });",
+        ),
+        // Mutually recursive components are fine.
+        (
+            "/app/src/Tree.svelte",
+            "<script>import Node from './Node.svelte';</script>",
+        ),
+        (
+            "/app/src/Node.svelte",
+            "<script>import Tree from './Tree.svelte';</script>",
+        ),
+        // So are type-only imports.
+        (
+            "/app/src/a.svelte.ts",
+            "import type { B } from './b.svelte';\nexport type A = {};",
+        ),
+        (
+            "/app/src/b.svelte.ts",
+            "import type { A } from './a.svelte';\nexport type B = {};",
+        ),
+        // A longer cycle is still reported once.
+        ("/app/src/x.svelte.ts", "import { y } from './y.svelte';\nexport const x = 1;"),
+        ("/app/src/y.svelte.ts", "import { z } from './z';\nexport const y = 1;"),
+        ("/app/src/z.ts", "import { x } from './x.svelte';\nexport const z = 1;"),
+    ]);
+    let cycles: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.contains("import-cycle"))
+        .collect();
+    assert_eq!(
+        cycles,
+        [
+            "src/counter.svelte.ts: import-cycle: Import cycle: src/counter.svelte.ts -> src/Counter.svelte -> src/counter.svelte.ts. A module in a cycle can run before its imports are initialized",
+            "src/x.svelte.ts: import-cycle: Import cycle: src/x.svelte.ts -> src/y.svelte.ts -> src/z.ts -> src/x.svelte.ts. A module in a cycle can run before its imports are initialized",
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_component_names() {
+    let diagnostics = check(&[
+        ("/app/src/lib/ui/Button.svelte", ""),
+        ("/app/src/lib/forms/Button.svelte", ""),
+        ("/app/src/routes/+page.svelte", ""),
+        ("/app/src/routes/about/+page.svelte", ""),
+    ]);
+    let duplicates: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.contains("duplicate-component-name"))
+        .collect();
+    assert_eq!(
+        duplicates,
+        [
+            "src/lib/forms/Button.svelte: duplicate-component-name: Component name 'Button' is also used by src/lib/ui/Button.svelte, so auto-imports may pick the wrong one",
+            "src/lib/ui/Button.svelte: duplicate-component-name: Component name 'Button' is also used by src/lib/forms/Button.svelte, so auto-imports may pick the wrong one",
+        ]
+    );
+}
+
+#[test]
+fn test_unrendered_snippets() {
+    let diagnostics = check(&[
+        (
+            "/app/src/routes/+page.svelte",
+            r#"<script>
+    import Card from '$lib/Card.svelte';
+    import Legacy from '$lib/Legacy.svelte';
+</script>
+{#snippet footer()}<p>footer</p>{/snippet}
+<Card {footer}>
+    {#snippet header()}<h1>Title</h1>{/snippet}
+    {#snippet actions()}<button>Go</button>{/snippet}
+    <p>Body</p>
+</Card>
+<Legacy>
+    {#snippet anything()}<p>slot</p>{/snippet}
+</Legacy>"#,
+        ),
+        (
+            "/app/src/lib/Card.svelte",
+            r#"<script>
+    let { header, footer, actions, children } = $props();
+</script>
+{@render header()}
+{@render children()}
+{#if actions}{@render actions()}{/if}"#,
+        ),
+        (
+            "/app/src/lib/Legacy.svelte",
+            "<script>export let title;</script><slot />",
+        ),
+    ]);
+    let unrendered: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.contains("unrendered-snippet"))
+        .collect();
+    assert_eq!(
+        unrendered,
+        ["src/routes/+page.svelte: unrendered-snippet: <Card> never renders its 'footer' prop, so this snippet is never shown"]
+    );
+
+    let diagnostics = check(&[
+        (
+            "/app/src/App.svelte",
+            "<script>import Card from './Card.svelte';</script><Card>Body</Card>",
+        ),
+        (
+            "/app/src/Card.svelte",
+            "<script>let { title } = $props();</script><h1>{title}</h1>",
+        ),
+    ]);
+    assert!(diagnostics.contains(
+        &"src/App.svelte: unrendered-snippet: <Card> has no 'children' prop, so this snippet is never shown".to_string()
+    ));
+}
+
+#[test]
+fn test_rule_levels_and_svelte_ignore() {
+    let files = [
+        (
+            "/app/src/App.svelte",
+            "<script>import Card from './Card.svelte';</script>\n<!-- svelte-ignore unrendered_snippet -->\n<Card>Body</Card>",
+        ),
+        ("/app/src/Card.svelte", "<script>let { title } = $props();</script>{title}"),
+    ];
+    assert!(check(&files)
+        .iter()
+        .all(|d| !d.contains("unrendered-snippet")));
+
+    assert!(check(&files).iter().any(|d| d.contains("unused-component")));
+    let options = DiagnosticOptions::all()
+        .with_rule("unused-component", crate::RuleLevel::Off)
+        .with_rule(
+            "unrendered-snippet",
+            crate::RuleLevel::On(crate::Severity::Error),
+        );
+    let diagnostics = project(&files).check(&options);
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.diagnostic.code.as_str() != "unused-component"));
+}
+
+fn codes(diagnostics: &[String], code: &str) -> Vec<String> {
+    diagnostics
+        .iter()
+        .filter(|d| d.contains(&format!(": {code}: ")))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_context_keys_never_set_or_read() {
+    let diagnostics = check(&[
+        (
+            "/app/src/lib/keys.ts",
+            "export const THEME = Symbol('theme');\nexport const USER = 'user';\nexport const LOCALE = Symbol('locale');",
+        ),
+        (
+            "/app/src/routes/+layout.svelte",
+            r#"<script>
+    import { setContext } from 'svelte';
+    import { THEME } from '$lib/keys';
+    setContext(THEME, { dark: true });
+    setContext('user', { name: 'Ada' });
+    setContext('unused', 1);
+</script>"#,
+        ),
+        (
+            "/app/src/lib/Child.svelte",
+            r#"<script>
+    import { getContext, hasContext } from 'svelte';
+    import { THEME, USER, LOCALE } from './keys';
+    const theme = getContext(THEME);
+    const user = getContext(USER);
+    const locale = getContext(LOCALE);
+    // Packages may set string keys.
+    const missing = getContext('missing');
+    const maybe = hasContext('optional');
+    function read(key) {
+        return getContext(key);
+    }
+</script>"#,
+        ),
+    ]);
+    assert_eq!(
+        codes(&diagnostics, "context-never-set"),
+        ["src/lib/Child.svelte: context-never-set: Context `LOCALE` is read here but never set, so it is always undefined"]
+    );
+    assert_eq!(
+        codes(&diagnostics, "context-never-read"),
+        ["src/routes/+layout.svelte: context-never-read: Context 'unused' is set here but never read"]
+    );
+}
+
+#[test]
+fn test_create_context_pairs() {
+    let diagnostics = check(&[
+        (
+            "/app/src/lib/context.ts",
+            r#"import { createContext } from 'svelte';
+export const [getTheme, setTheme] = createContext<string>();
+export const [getUser, setUser] = createContext<string>();"#,
+        ),
+        (
+            "/app/src/routes/+layout.svelte",
+            "<script>\n    import { setTheme } from '$lib/context';\n    setTheme('dark');\n</script>",
+        ),
+        (
+            "/app/src/lib/Child.svelte",
+            "<script>\n    import { getTheme, getUser } from './context';\n    const theme = getTheme();\n    const user = getUser();\n</script>",
+        ),
+    ]);
+    assert_eq!(
+        codes(&diagnostics, "context-never-set"),
+        ["src/lib/Child.svelte: context-never-set: Context from `createContext()` (getUser) is read here but never set, so it is always undefined"]
+    );
+    assert!(codes(&diagnostics, "context-never-read").is_empty());
+}
+
+#[test]
+fn test_context_type_mismatch() {
+    let diagnostics = check(&[
+        (
+            "/app/src/routes/+layout.svelte",
+            r#"<script lang="ts">
+    import { setContext } from 'svelte';
+    import type { Theme } from '$lib/types';
+    let theme: Theme = $state({ dark: true });
+    setContext('theme', theme);
+    setContext<number>('count', 1);
+</script>"#,
+        ),
+        (
+            "/app/src/lib/Child.svelte",
+            r#"<script lang="ts">
+    import { getContext } from 'svelte';
+    const theme: Theme = getContext('theme');
+    const wrong = getContext<User>('theme');
+    const count = getContext('count') as string;
+    const loose = getContext<any>('count');
+</script>"#,
+        ),
+    ]);
+    assert_eq!(
+        codes(&diagnostics, "context-type-mismatch"),
+        [
+            "src/lib/Child.svelte: context-type-mismatch: Context 'theme' is read as `User` but set as `Theme`",
+            "src/lib/Child.svelte: context-type-mismatch: Context 'count' is read as `string` but set as `number`",
+        ]
+    );
+}
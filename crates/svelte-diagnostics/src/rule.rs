@@ -0,0 +1,384 @@
+//! The lint rule framework.
+//!
+//! Every check is a [`Rule`] that describes itself with [`RuleMeta`] and is
+//! collected in a [`RuleRegistry`]. The registry decides which rules run for
+//! a given [`DiagnosticOptions`], runs them in parallel over one shared
+//! [`Traversal`], applies the configured severities and drops diagnostics
+//! silenced by `svelte-ignore` comments.
+
+use crate::ignore::Ignores;
+use crate::semantic::SemanticModel;
+use crate::traversal::Traversal;
+use crate::version::SvelteVersion;
+use crate::{Diagnostic, DiagnosticCode, DiagnosticOptions, Severity};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::sync::OnceLock;
+use svelte_parser::SvelteDocument;
+
+/// The group a rule belongs to. Each category can be switched off as a whole
+/// through [`DiagnosticOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuleCategory {
+    /// Accessibility checks.
+    A11y,
+    /// Component validation.
+    Component,
+    /// `<style>` checks.
+    Css,
+}
+
+impl RuleCategory {
+    /// Returns the category name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleCategory::A11y => "a11y",
+            RuleCategory::Component => "component",
+            RuleCategory::Css => "css",
+        }
+    }
+}
+
+impl std::fmt::Display for RuleCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Static information about a rule.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMeta {
+    /// The code the rule reports under.
+    pub code: DiagnosticCode,
+    /// The category the rule belongs to.
+    pub category: RuleCategory,
+    /// The severity used unless configured otherwise.
+    pub default_severity: Severity,
+    /// The documentation page for the rule, if there is one.
+    pub docs_url: Option<&'static str>,
+    /// Whether the rule attaches fix suggestions to its diagnostics.
+    pub fixable: bool,
+}
+
+/// A configured level for a single rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleLevel {
+    /// Don't run the rule.
+    Off,
+    /// Run the rule and report at this severity.
+    On(Severity),
+}
+
+/// What a rule sees of the document it checks.
+pub struct RuleContext<'a> {
+    doc: &'a SvelteDocument,
+    source: &'a str,
+    filename: Option<&'a str>,
+    svelte_version: Option<SvelteVersion>,
+    sveltekit_routes: Option<&'a str>,
+    traversal: Traversal<'a>,
+    semantic: OnceLock<SemanticModel>,
+    a11y: OnceLock<Vec<Diagnostic>>,
+    reactivity: OnceLock<Vec<Diagnostic>>,
+}
+
+impl<'a> RuleContext<'a> {
+    /// Creates a context, walking the template once. `source` must be the
+    /// text `doc` was parsed from.
+    pub fn new(doc: &'a SvelteDocument, source: &'a str, filename: Option<&'a str>) -> Self {
+        Self {
+            doc,
+            source,
+            filename,
+            svelte_version: None,
+            sveltekit_routes: None,
+            traversal: Traversal::new(&doc.fragment),
+            semantic: OnceLock::new(),
+            a11y: OnceLock::new(),
+            reactivity: OnceLock::new(),
+        }
+    }
+
+    /// Returns the document.
+    pub fn doc(&self) -> &'a SvelteDocument {
+        self.doc
+    }
+
+    /// Returns the component source.
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the component's filename, if known.
+    pub fn filename(&self) -> Option<&'a str> {
+        self.filename
+    }
+
+    /// Sets the installed Svelte version.
+    pub fn with_svelte_version(mut self, version: Option<SvelteVersion>) -> Self {
+        self.svelte_version = version;
+        self
+    }
+
+    /// Returns the installed Svelte version, if known.
+    pub fn svelte_version(&self) -> Option<SvelteVersion> {
+        self.svelte_version
+    }
+
+    /// Sets the SvelteKit routes directory.
+    pub fn with_sveltekit_routes(mut self, routes: Option<&'a str>) -> Self {
+        self.sveltekit_routes = routes;
+        self
+    }
+
+    /// Returns the SvelteKit routes directory, if the project uses SvelteKit.
+    pub fn sveltekit_routes(&self) -> Option<&'a str> {
+        self.sveltekit_routes
+    }
+
+    /// Returns the shared template traversal.
+    pub fn traversal(&self) -> &Traversal<'a> {
+        &self.traversal
+    }
+
+    /// Returns the component's scopes and bindings, built on first use and
+    /// shared by all rules.
+    pub fn semantic(&self) -> &SemanticModel {
+        self.semantic
+            .get_or_init(|| SemanticModel::build(self.doc, self.source))
+    }
+
+    /// Returns the warnings of the ported compiler a11y checks, computed on
+    /// first use and split up by the [`A11yRule`](crate::a11y::A11yRule)s.
+    pub(crate) fn a11y_warnings(&self) -> &[Diagnostic] {
+        self.a11y
+            .get_or_init(|| crate::a11y::compiler_warnings(self))
+    }
+
+    /// Returns the reactivity hazards found in the scripts, computed on first
+    /// use and split up by the hazard rules in [`component`](crate::component).
+    pub(crate) fn reactivity_hazards(&self) -> &[Diagnostic] {
+        self.reactivity
+            .get_or_init(|| crate::component::reactivity::hazards(self))
+    }
+}
+
+/// A single check.
+pub trait Rule: Send + Sync {
+    /// Returns the rule's metadata.
+    fn meta(&self) -> &'static RuleMeta;
+
+    /// Checks a document. Severities are overwritten by the registry, so
+    /// rules can build diagnostics with [`Diagnostic::new`].
+    fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic>;
+}
+
+/// A set of rules.
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleRegistry {
+    /// Returns an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a registry with all built-in rules.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(crate::a11y::HeadingStructure));
+        for &code in crate::a11y::A11yCode::ALL {
+            registry.register(Box::new(crate::a11y::A11yRule(code)));
+        }
+        registry.register(Box::new(crate::component::TemplateRuneUsage));
+        registry.register(Box::new(crate::component::MissingDeclaration));
+        registry.register(Box::new(crate::component::UnusedProp));
+        registry.register(Box::new(crate::component::UnusedSnippet));
+        registry.register(Box::new(crate::component::UnusedDerived));
+        registry.register(Box::new(crate::component::UnreassignedState));
+        registry.register(Box::new(crate::component::EffectUpdateLoop));
+        registry.register(Box::new(crate::component::DerivedSideEffect));
+        registry.register(Box::new(crate::component::PreferDerived));
+        registry.register(Box::new(crate::component::MissingStateSnapshot));
+        registry.register(Box::new(crate::component::ComponentNameCase));
+        registry.register(Box::new(crate::component::SvelteKitRouteFile));
+        registry.register(Box::new(crate::component::LayoutMissingChildren));
+        registry.register(Box::new(crate::component::SelfImport));
+        registry.register(Box::new(crate::component::UnavailableFeature));
+        registry.register(Box::new(crate::css::UnusedSelector));
+        registry
+    }
+
+    /// Adds a rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a rule with the same code is already registered.
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        let code = rule.meta().code;
+        assert!(
+            self.get(code).is_none(),
+            "rule {code} is already registered"
+        );
+        self.rules.push(rule);
+    }
+
+    /// Returns the registered rules in registration order.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Returns the rule reporting under `code`.
+    pub fn get(&self, code: DiagnosticCode) -> Option<&dyn Rule> {
+        self.rules().find(|rule| rule.meta().code == code)
+    }
+
+    /// Runs every rule enabled by `options` and returns the diagnostics that
+    /// are not ignored, sorted by position.
+    pub fn check(
+        &self,
+        doc: &SvelteDocument,
+        source: &str,
+        options: &DiagnosticOptions,
+    ) -> Vec<Diagnostic> {
+        let ctx = RuleContext::new(doc, source, options.filename.as_deref())
+            .with_svelte_version(options.svelte_version)
+            .with_sveltekit_routes(options.sveltekit_routes.as_deref());
+        let mut diagnostics: Vec<Diagnostic> = self
+            .rules
+            .par_iter()
+            .filter_map(|rule| Some((rule, options.severity_for(rule.meta())?)))
+            .flat_map_iter(|(rule, severity)| {
+                rule.check(&ctx).into_iter().map(move |mut diagnostic| {
+                    diagnostic.severity = severity;
+                    diagnostic
+                })
+            })
+            .collect();
+        let ignores = Ignores::collect(doc);
+        diagnostics.retain(|d| !ignores.is_ignored(d.code.as_str(), d.span.start));
+        diagnostics.sort_by_key(|d| d.span.start);
+        diagnostics
+    }
+}
+
+/// Returns the registry of built-in rules.
+pub fn registry() -> &'static RuleRegistry {
+    static REGISTRY: OnceLock<RuleRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(RuleRegistry::builtin)
+}
+
+/// Returns the metadata of the built-in document or project rule reporting
+/// under `code`, from an index built on first use.
+pub(crate) fn builtin_meta(code: DiagnosticCode) -> Option<&'static RuleMeta> {
+    static INDEX: OnceLock<FxHashMap<DiagnosticCode, &'static RuleMeta>> = OnceLock::new();
+    INDEX
+        .get_or_init(|| {
+            registry()
+                .rules()
+                .map(|rule| rule.meta())
+                .chain(crate::project::rules().map(|rule| rule.meta()))
+                .map(|meta| (meta.code, meta))
+                .collect()
+        })
+        .get(&code)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    struct NoDivs;
+
+    static NO_DIVS: RuleMeta = RuleMeta {
+        code: DiagnosticCode::Rule("no-divs"),
+        category: RuleCategory::Component,
+        default_severity: Severity::Hint,
+        docs_url: None,
+        fixable: false,
+    };
+
+    impl Rule for NoDivs {
+        fn meta(&self) -> &'static RuleMeta {
+            &NO_DIVS
+        }
+
+        fn check(&self, ctx: &RuleContext<'_>) -> Vec<Diagnostic> {
+            ctx.traversal()
+                .nodes()
+                .iter()
+                .filter_map(|visited| match visited.node {
+                    svelte_parser::TemplateNode::Element(el) if el.name == "div" => {
+                        Some(Diagnostic::new(NO_DIVS.code, "Avoid <div>", el.span))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_builtin_codes_are_unique() {
+        let metas = registry()
+            .rules()
+            .map(|rule| rule.meta())
+            .chain(crate::project::rules().map(|rule| rule.meta()));
+        for meta in metas {
+            assert!(std::ptr::eq(builtin_meta(meta.code).unwrap(), meta));
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_uses_default_severity() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(NoDivs));
+        let source = "<div></div><p></p>{#if x}<div></div>{/if}";
+        let doc = parse(source).document;
+
+        let diagnostics = registry.check(&doc, source, &DiagnosticOptions::all());
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity == Severity::Hint && d.code.as_str() == "no-divs"));
+    }
+
+    #[test]
+    fn test_rule_level_overrides_category() {
+        let source = "<h1>Title</h1><h3>Skipped</h3>";
+        let doc = parse(source).document;
+
+        let off = DiagnosticOptions::all().with_rule("a11y-structure", RuleLevel::Off);
+        assert!(registry().check(&doc, source, &off).is_empty());
+
+        let error = DiagnosticOptions::default()
+            .with_rule("a11y-structure", RuleLevel::On(Severity::Error));
+        let diagnostics = registry().check(&doc, source, &error);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_svelte_ignore_silences_rule() {
+        let source =
+            "<h1>Title</h1>\n<!-- svelte-ignore a11y_structure -->\n<h3>Skipped</h3><h5>Again</h5>";
+        let doc = parse(source).document;
+        let diagnostics = registry().check(&doc, source, &DiagnosticOptions::all());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            u32::from(diagnostics[0].span.start),
+            source.find("<h5>").unwrap() as u32
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered")]
+    fn test_duplicate_registration_panics() {
+        let mut registry = RuleRegistry::new();
+        registry.register(Box::new(NoDivs));
+        registry.register(Box::new(NoDivs));
+    }
+}
@@ -0,0 +1,501 @@
+//! Scope and symbol analysis for components.
+//!
+//! [`SemanticModel::build`] collects the bindings a component declares — in
+//! the module and instance scripts and in template scopes such as `{#each}`
+//! items, `{#await}` values, snippet parameters, `{@const}` and `let:`
+//! directives — and resolves every identifier reference to its binding.
+//!
+//! Scripts and template expressions are parsed with swc. Type positions in
+//! TypeScript are not tracked, so type-only imports have no references.
+//! Expressions that fail to parse contribute no references; reporting the
+//! syntax error is the parser's and the compiler's job.
+
+mod script;
+mod template;
+
+pub(crate) use script::{parse_module, rune_callee};
+pub(crate) use template::leading_identifier;
+
+use rustc_hash::FxHashMap;
+use smol_str::SmolStr;
+use source_map::{ByteOffset, Span};
+use svelte_parser::SvelteDocument;
+
+/// Identifies a [`Scope`] in a [`SemanticModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(u32);
+
+/// Identifies a [`Binding`] in a [`SemanticModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BindingId(u32);
+
+/// What introduced a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// `<script module>` / `<script context="module">`.
+    Module,
+    /// The instance `<script>`.
+    Instance,
+    /// The top level of the template.
+    Template,
+    /// A function, method or arrow function.
+    Function,
+    /// A block statement, `for` loop or `catch` clause.
+    Block,
+    /// The body of an `{#each}` block.
+    Each,
+    /// The `{:then}` branch of an `{#await}` block.
+    AwaitThen,
+    /// The `{:catch}` branch of an `{#await}` block.
+    AwaitCatch,
+    /// The body of a `{#snippet}`.
+    Snippet,
+    /// The children of an element or component with `let:` directives.
+    LetDirective,
+}
+
+/// How a binding was declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// An `import`.
+    Import,
+    /// A `var` declaration.
+    Var,
+    /// A plain `let` declaration.
+    Let,
+    /// A plain `const` declaration.
+    Const,
+    /// A function declaration.
+    Function,
+    /// A class declaration.
+    Class,
+    /// A TypeScript `enum`.
+    Enum,
+    /// A function or catch parameter.
+    Param,
+    /// A prop from `$props()` destructuring or `export let`.
+    Prop,
+    /// A prop declared with `$bindable()`.
+    BindableProp,
+    /// The rest element of `$props()` destructuring, or `let props = $props()`.
+    RestProp,
+    /// A `$state(...)` declaration.
+    State,
+    /// A `$state.raw(...)` declaration.
+    RawState,
+    /// A `$derived(...)` or `$derived.by(...)` declaration.
+    Derived,
+    /// An `{#each}` item.
+    EachItem,
+    /// An `{#each}` index.
+    EachIndex,
+    /// The value of an `{:then}` branch.
+    AwaitValue,
+    /// The error of a `{:catch}` branch.
+    AwaitError,
+    /// A `{#snippet}` name.
+    Snippet,
+    /// A `{#snippet}` parameter.
+    SnippetParam,
+    /// An `{@const}` tag.
+    ConstTag,
+    /// A `{let ...}` or `{const ...}` declaration tag.
+    DeclarationTag,
+    /// A `let:` directive.
+    LetDirective,
+    /// A name first assigned by a `$:` statement, which declares it.
+    Reactive,
+}
+
+/// A lexical scope.
+#[derive(Debug, Clone)]
+pub struct Scope {
+    /// What introduced the scope.
+    pub kind: ScopeKind,
+    /// The enclosing scope.
+    pub parent: Option<ScopeId>,
+    /// The span the scope covers.
+    pub span: Span,
+    bindings: FxHashMap<SmolStr, BindingId>,
+}
+
+impl Scope {
+    /// Returns the binding declared directly in this scope under `name`.
+    pub fn get(&self, name: &str) -> Option<BindingId> {
+        self.bindings.get(name).copied()
+    }
+
+    /// Returns the bindings declared directly in this scope.
+    pub fn bindings(&self) -> impl Iterator<Item = BindingId> + '_ {
+        self.bindings.values().copied()
+    }
+}
+
+/// A declared name.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// The declared name.
+    pub name: SmolStr,
+    /// How it was declared.
+    pub kind: BindingKind,
+    /// The span of the declaring identifier.
+    pub span: Span,
+    /// The scope it is declared in.
+    pub scope: ScopeId,
+    /// Whether it was declared with `const`.
+    pub is_const: bool,
+}
+
+/// An identifier that reads or writes a binding.
+#[derive(Debug, Clone)]
+pub struct Reference {
+    /// The identifier as written (including a `$` store prefix).
+    pub name: SmolStr,
+    /// The span of the identifier.
+    pub span: Span,
+    /// The innermost scope containing the reference.
+    pub scope: ScopeId,
+    /// The resolved binding; `None` for globals and undeclared names.
+    pub binding: Option<BindingId>,
+    /// Whether the reference is an assignment or update target, or a
+    /// `bind:` directive.
+    pub is_write: bool,
+    /// Whether the reference reads the value. Compound assignments
+    /// (`count += 1`) and updates (`count++`) both read and write.
+    pub is_read: bool,
+    /// Whether the reference is a `$name` store subscription resolved to
+    /// the store `name`.
+    pub is_store_subscription: bool,
+    /// Whether the reference is in the template rather than a script.
+    pub in_template: bool,
+}
+
+/// The scopes, bindings and references of a component.
+#[derive(Debug, Clone)]
+pub struct SemanticModel {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    references: Vec<Reference>,
+    module_scope: ScopeId,
+    instance_scope: ScopeId,
+    template_scope: ScopeId,
+    partial: bool,
+    props_rune: bool,
+}
+
+impl SemanticModel {
+    /// Builds the model for a parsed component. `source` must be the text
+    /// `doc` was parsed from.
+    pub fn build(doc: &SvelteDocument, source: &str) -> Self {
+        let mut builder = ModelBuilder::new(doc, source);
+        script::collect(&mut builder, doc);
+        template::collect(&mut builder, doc);
+        builder.finish()
+    }
+
+    /// Returns all scopes; the module, instance and template scopes come
+    /// first.
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// Returns a scope.
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0 as usize]
+    }
+
+    /// Returns the module script scope (the root scope).
+    pub fn module_scope(&self) -> ScopeId {
+        self.module_scope
+    }
+
+    /// Returns the instance script scope.
+    pub fn instance_scope(&self) -> ScopeId {
+        self.instance_scope
+    }
+
+    /// Returns the top-level template scope.
+    pub fn template_scope(&self) -> ScopeId {
+        self.template_scope
+    }
+
+    /// Returns whether a script failed to parse, in which case its bindings
+    /// are missing from the model.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    /// Returns whether the instance script declares its props with
+    /// `$props()`.
+    pub fn has_props_rune(&self) -> bool {
+        self.props_rune
+    }
+
+    /// Returns all bindings in declaration order.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Returns a binding.
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0 as usize]
+    }
+
+    /// Returns the ids of all bindings.
+    pub fn binding_ids(&self) -> impl Iterator<Item = BindingId> {
+        (0..self.bindings.len() as u32).map(BindingId)
+    }
+
+    /// Returns all references in source order.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Returns the references resolved to `binding`.
+    pub fn references_to(&self, binding: BindingId) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |reference| reference.binding == Some(binding))
+    }
+
+    /// Returns the references that resolve to no binding.
+    pub fn unresolved_references(&self) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(|reference| reference.binding.is_none())
+    }
+
+    /// Resolves `name` as seen from `scope`.
+    pub fn resolve(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        self.scope_chain(scope)
+            .find_map(|scope| self.scope(scope).get(name))
+    }
+
+    /// Returns `scope` and its enclosing scopes, innermost first.
+    pub fn scope_chain(&self, scope: ScopeId) -> impl Iterator<Item = ScopeId> + '_ {
+        std::iter::successors(Some(scope), |&scope| self.scope(scope).parent)
+    }
+
+    /// Returns the binding declared or referenced at `offset`.
+    pub fn binding_at(&self, offset: ByteOffset) -> Option<BindingId> {
+        let touches = |span: Span| span.start <= offset && offset <= span.end;
+        self.binding_ids()
+            .find(|&id| touches(self.binding(id).span))
+            .or_else(|| {
+                self.references
+                    .iter()
+                    .find(|reference| touches(reference.span))
+                    .and_then(|reference| reference.binding)
+            })
+    }
+}
+
+/// A reference before resolution.
+struct PendingReference {
+    name: SmolStr,
+    span: Span,
+    scope: ScopeId,
+    is_write: bool,
+    is_read: bool,
+    in_template: bool,
+}
+
+/// Accumulates scopes, bindings and references while walking a component.
+pub(crate) struct ModelBuilder<'a> {
+    source: &'a str,
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    references: Vec<PendingReference>,
+    /// Names declared by `$:` assignments unless declared otherwise.
+    implicit: Vec<(ScopeId, SmolStr, Span)>,
+    module_scope: ScopeId,
+    instance_scope: ScopeId,
+    template_scope: ScopeId,
+    partial: bool,
+    props_rune: bool,
+}
+
+impl<'a> ModelBuilder<'a> {
+    fn new(doc: &SvelteDocument, source: &'a str) -> Self {
+        let mut builder = Self {
+            source,
+            scopes: Vec::new(),
+            bindings: Vec::new(),
+            references: Vec::new(),
+            implicit: Vec::new(),
+            module_scope: ScopeId(0),
+            instance_scope: ScopeId(0),
+            template_scope: ScopeId(0),
+            partial: false,
+            props_rune: false,
+        };
+        builder.module_scope = builder.push_scope(ScopeKind::Module, None, doc.span);
+        builder.instance_scope =
+            builder.push_scope(ScopeKind::Instance, Some(builder.module_scope), doc.span);
+        builder.template_scope = builder.push_scope(
+            ScopeKind::Template,
+            Some(builder.instance_scope),
+            doc.fragment.span,
+        );
+        builder
+    }
+
+    pub(crate) fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub(crate) fn push_scope(
+        &mut self,
+        kind: ScopeKind,
+        parent: Option<ScopeId>,
+        span: Span,
+    ) -> ScopeId {
+        let id = ScopeId(self.scopes.len() as u32);
+        self.scopes.push(Scope {
+            kind,
+            parent,
+            span,
+            bindings: FxHashMap::default(),
+        });
+        id
+    }
+
+    pub(crate) fn scope_kind(&self, scope: ScopeId) -> ScopeKind {
+        self.scopes[scope.0 as usize].kind
+    }
+
+    pub(crate) fn scope_parent(&self, scope: ScopeId) -> Option<ScopeId> {
+        self.scopes[scope.0 as usize].parent
+    }
+
+    /// Declares `name` in `scope`. A repeated declaration in the same scope
+    /// (e.g. `var` redeclaration, TypeScript overloads) keeps the first.
+    pub(crate) fn declare(&mut self, scope: ScopeId, name: &str, kind: BindingKind, span: Span) {
+        self.declare_with(scope, name, kind, span, false);
+    }
+
+    /// Declares `name` in `scope`, recording whether it is `const`.
+    pub(crate) fn declare_with(
+        &mut self,
+        scope: ScopeId,
+        name: &str,
+        kind: BindingKind,
+        span: Span,
+        is_const: bool,
+    ) {
+        let bindings = &mut self.scopes[scope.0 as usize].bindings;
+        if bindings.contains_key(name) {
+            return;
+        }
+        let id = BindingId(self.bindings.len() as u32);
+        bindings.insert(SmolStr::new(name), id);
+        self.bindings.push(Binding {
+            name: SmolStr::new(name),
+            kind,
+            span,
+            scope,
+            is_const,
+        });
+    }
+
+    pub(crate) fn reference(
+        &mut self,
+        scope: ScopeId,
+        name: &str,
+        span: Span,
+        is_write: bool,
+        is_read: bool,
+        in_template: bool,
+    ) {
+        self.references.push(PendingReference {
+            name: SmolStr::new(name),
+            span,
+            scope,
+            is_write,
+            is_read,
+            in_template,
+        });
+    }
+
+    /// Declares `name` in `scope` when the whole component is collected,
+    /// unless something else declares it.
+    pub(crate) fn declare_implicit(&mut self, scope: ScopeId, name: &str, span: Span) {
+        self.implicit.push((scope, SmolStr::new(name), span));
+    }
+
+    /// Records that a script failed to parse.
+    pub(crate) fn mark_partial(&mut self) {
+        self.partial = true;
+    }
+
+    /// Records that the instance script calls `$props()`.
+    pub(crate) fn mark_props_rune(&mut self) {
+        self.props_rune = true;
+    }
+
+    fn resolve(&self, scope: ScopeId, name: &str) -> Option<BindingId> {
+        let mut current = Some(scope);
+        while let Some(scope) = current {
+            let scope = &self.scopes[scope.0 as usize];
+            if let Some(&binding) = scope.bindings.get(name) {
+                return Some(binding);
+            }
+            current = scope.parent;
+        }
+        None
+    }
+
+    fn finish(mut self) -> SemanticModel {
+        for (scope, name, span) in std::mem::take(&mut self.implicit) {
+            self.declare(scope, &name, BindingKind::Reactive, span);
+        }
+        let mut references: Vec<Reference> = self
+            .references
+            .iter()
+            .map(|pending| {
+                let mut binding = self.resolve(pending.scope, &pending.name);
+                // `<script module>` can export top-level snippets
+                // (`export { row }`), which live in the instance scope.
+                if binding.is_none() && pending.scope == self.module_scope {
+                    binding = self.scopes[self.instance_scope.0 as usize]
+                        .bindings
+                        .get(&pending.name)
+                        .copied()
+                        .filter(|&id| self.bindings[id.0 as usize].kind == BindingKind::Snippet);
+                }
+                let mut is_store_subscription = false;
+                if binding.is_none() {
+                    if let Some(store) = pending.name.strip_prefix('$').filter(|s| !s.is_empty()) {
+                        binding = self.resolve(pending.scope, store);
+                        is_store_subscription = binding.is_some();
+                    }
+                }
+                Reference {
+                    name: pending.name.clone(),
+                    span: pending.span,
+                    scope: pending.scope,
+                    binding,
+                    is_write: pending.is_write,
+                    is_read: pending.is_read,
+                    is_store_subscription,
+                    in_template: pending.in_template,
+                }
+            })
+            .collect();
+        references.sort_by_key(|reference| reference.span.start);
+
+        SemanticModel {
+            scopes: self.scopes,
+            bindings: self.bindings,
+            references,
+            module_scope: self.module_scope,
+            instance_scope: self.instance_scope,
+            template_scope: self.template_scope,
+            partial: self.partial,
+            props_rune: self.props_rune,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,663 @@
+//! Declaration and reference collection for JavaScript/TypeScript code.
+//!
+//! The same resolver walks the instance and module scripts and every
+//! expression and pattern taken from the template.
+
+use super::{BindingKind, ModelBuilder, ScopeId, ScopeKind};
+use source_map::Span;
+use std::sync::Arc;
+use svelte_parser::{ScriptLang, SvelteDocument};
+use swc_common::{BytePos, FileName, SourceMap as SwcSourceMap};
+use swc_ecma_ast::{
+    ArrowExpr, AssignExpr, AssignOp, AssignTarget, BlockStmt, BlockStmtOrExpr, BreakStmt, CallExpr,
+    Callee, CatchClause, ClassDecl, ClassExpr, Constructor, ContinueStmt, Decl, ExportDecl,
+    ExportSpecifier, Expr, FnDecl, FnExpr, ForInStmt, ForOfStmt, ForStmt, Function, Ident,
+    ImportDecl, ImportSpecifier, LabeledStmt, MemberProp, ModuleExportName, ModuleItem,
+    NamedExport, ObjectPatProp, ParamOrTsParamProp, Pat, SetterProp, SimpleAssignTarget, Stmt,
+    TsEnumDecl, TsExprWithTypeArgs, TsImportEqualsDecl, TsInterfaceDecl, TsModuleDecl,
+    TsParamPropParam, TsType, TsTypeAliasDecl, TsTypeAnn, TsTypeParamDecl,
+    TsTypeParamInstantiation, UpdateExpr, VarDecl, VarDeclKind,
+};
+use swc_ecma_parser::{EsSyntax, Parser, StringInput, Syntax, TsSyntax};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// Collects the declarations and references of both scripts.
+pub(super) fn collect(builder: &mut ModelBuilder<'_>, doc: &SvelteDocument) {
+    let scripts = [
+        (doc.module_script.as_ref(), builder.module_scope),
+        (doc.instance_script.as_ref(), builder.instance_scope),
+    ];
+    for (script, scope) in scripts {
+        let Some(script) = script else {
+            continue;
+        };
+        let typescript = script.lang == ScriptLang::TypeScript;
+        let Some((module, start_pos)) = parse_module(&script.content, typescript) else {
+            builder.mark_partial();
+            continue;
+        };
+        let mut resolver = Resolver {
+            builder: &mut *builder,
+            scope,
+            base: u32::from(script.content_span.start),
+            start_pos,
+            in_template: false,
+            const_scope: None,
+        };
+        for item in &module.body {
+            item.visit_with(&mut resolver);
+        }
+    }
+}
+
+/// Records the references of a template expression.
+pub(super) fn expression(
+    builder: &mut ModelBuilder<'_>,
+    scope: ScopeId,
+    text: &str,
+    base: u32,
+    typescript: bool,
+) {
+    let Some((expr, start_pos)) = parse_expr(text, typescript) else {
+        return;
+    };
+    let mut resolver = Resolver {
+        builder,
+        scope,
+        base,
+        start_pos,
+        in_template: true,
+        const_scope: None,
+    };
+    expr.visit_with(&mut resolver);
+}
+
+/// Declares the names bound by a comma-separated list of patterns (an
+/// `{#each}` context, snippet parameters) in `scope`.
+pub(super) fn patterns(
+    builder: &mut ModelBuilder<'_>,
+    scope: ScopeId,
+    text: &str,
+    base: u32,
+    kind: BindingKind,
+    typescript: bool,
+) {
+    let wrapped = format!("({text}) => 0");
+    let Some((expr, start_pos)) = parse_expr(&wrapped, typescript) else {
+        return;
+    };
+    let Expr::Arrow(arrow) = &*expr else {
+        return;
+    };
+    let mut resolver = Resolver {
+        builder,
+        scope,
+        // Account for the leading `(`.
+        base: base.wrapping_sub(1),
+        start_pos,
+        in_template: true,
+        const_scope: None,
+    };
+    for param in &arrow.params {
+        resolver.declare_pat(param, kind);
+    }
+}
+
+/// Declares the names of a `name = value` declaration (an `{@const}` or a
+/// declaration tag) in `scope` and records the references of its value.
+pub(super) fn declaration(
+    builder: &mut ModelBuilder<'_>,
+    scope: ScopeId,
+    text: &str,
+    base: u32,
+    kind: BindingKind,
+    typescript: bool,
+) {
+    let prefix = "let ";
+    let wrapped = format!("{prefix}{text};");
+    let Some((module, start_pos)) = parse_module(&wrapped, typescript) else {
+        return;
+    };
+    let mut resolver = Resolver {
+        builder,
+        scope,
+        base: base.wrapping_sub(prefix.len() as u32),
+        start_pos,
+        in_template: true,
+        const_scope: None,
+    };
+    for item in &module.body {
+        if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) = item {
+            for declarator in &var.decls {
+                resolver.declare_pat(&declarator.name, kind);
+                declarator.init.visit_with(&mut resolver);
+            }
+        }
+    }
+}
+
+fn syntax(typescript: bool) -> Syntax {
+    if typescript {
+        Syntax::Typescript(TsSyntax::default())
+    } else {
+        Syntax::Es(EsSyntax::default())
+    }
+}
+
+pub(crate) fn parse_module(
+    text: &str,
+    typescript: bool,
+) -> Option<(swc_ecma_ast::Module, BytePos)> {
+    let cm: Arc<SwcSourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Custom("svelte-script".into()).into(),
+        text.to_string(),
+    );
+    let mut parser = Parser::new(syntax(typescript), StringInput::from(&*fm), None);
+    let module = parser.parse_module().ok()?;
+    Some((module, fm.start_pos))
+}
+
+fn parse_expr(text: &str, typescript: bool) -> Option<(Box<Expr>, BytePos)> {
+    let cm: Arc<SwcSourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Custom("svelte-expression".into()).into(),
+        text.to_string(),
+    );
+    let mut parser = Parser::new(syntax(typescript), StringInput::from(&*fm), None);
+    let expr = parser.parse_expr().ok()?;
+    Some((expr, fm.start_pos))
+}
+
+/// Returns the binding kind a rune initializer gives a declaration.
+fn rune_kind(init: Option<&Expr>) -> Option<BindingKind> {
+    let callee = rune_callee(init?)?;
+    match callee.as_str() {
+        "$state" => Some(BindingKind::State),
+        "$state.raw" => Some(BindingKind::RawState),
+        "$derived" | "$derived.by" => Some(BindingKind::Derived),
+        "$props" => Some(BindingKind::Prop),
+        _ => None,
+    }
+}
+
+/// Returns the callee of a rune call such as `$state(...)` or
+/// `$derived.by(...)`, looking through TypeScript assertions.
+pub(crate) fn rune_callee(expr: &Expr) -> Option<String> {
+    let call: &CallExpr = match expr {
+        Expr::Call(call) => call,
+        Expr::TsAs(inner) => return rune_callee(&inner.expr),
+        Expr::TsSatisfies(inner) => return rune_callee(&inner.expr),
+        Expr::TsNonNull(inner) => return rune_callee(&inner.expr),
+        Expr::Paren(inner) => return rune_callee(&inner.expr),
+        _ => return None,
+    };
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    match &**callee {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => match (&*member.obj, &member.prop) {
+            (Expr::Ident(object), MemberProp::Ident(prop)) => {
+                Some(format!("{}.{}", object.sym, prop.sym))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_bindable(expr: &Expr) -> bool {
+    rune_callee(expr).is_some_and(|callee| callee == "$bindable")
+}
+
+/// Walks code, declaring bindings and recording references.
+struct Resolver<'b, 'a> {
+    builder: &'b mut ModelBuilder<'a>,
+    scope: ScopeId,
+    /// Document offset of the first byte of the parsed text.
+    base: u32,
+    /// swc position of the first byte of the parsed text.
+    start_pos: BytePos,
+    in_template: bool,
+    /// The scope a `const` declaration being walked declares its names in.
+    const_scope: Option<ScopeId>,
+}
+
+impl Resolver<'_, '_> {
+    fn span(&self, span: swc_common::Span) -> Span {
+        let offset = |pos: BytePos| self.base.wrapping_add(pos.0 - self.start_pos.0);
+        Span::new(offset(span.lo), offset(span.hi))
+    }
+
+    fn in_scope(&mut self, kind: ScopeKind, span: swc_common::Span, f: impl FnOnce(&mut Self)) {
+        let span = self.span(span);
+        let outer = self.scope;
+        self.scope = self.builder.push_scope(kind, Some(outer), span);
+        f(self);
+        self.scope = outer;
+    }
+
+    /// Returns the scope `var` declarations hoist to.
+    fn var_scope(&self) -> ScopeId {
+        let mut scope = self.scope;
+        loop {
+            match self.builder.scope_kind(scope) {
+                ScopeKind::Function | ScopeKind::Instance | ScopeKind::Module => return scope,
+                _ => match self.builder.scope_parent(scope) {
+                    Some(parent) => scope = parent,
+                    None => return self.scope,
+                },
+            }
+        }
+    }
+
+    /// Returns the span of an identifier's name. swc extends the span of a
+    /// binding identifier over its type annotation.
+    fn ident_span(&self, ident: &Ident) -> Span {
+        let start = self.span(ident.span).start;
+        Span::new(
+            start,
+            start + source_map::ByteOffset::from(ident.sym.len() as u32),
+        )
+    }
+
+    fn declare_ident(&mut self, scope: ScopeId, ident: &Ident, kind: BindingKind) {
+        let span = self.ident_span(ident);
+        let is_const = self.const_scope == Some(scope);
+        self.builder
+            .declare_with(scope, &ident.sym, kind, span, is_const);
+    }
+
+    fn reference(&mut self, ident: &Ident, is_write: bool) {
+        self.reference_with(ident, is_write, !is_write);
+    }
+
+    fn reference_with(&mut self, ident: &Ident, is_write: bool, is_read: bool) {
+        let span = self.ident_span(ident);
+        self.builder.reference(
+            self.scope,
+            &ident.sym,
+            span,
+            is_write,
+            is_read,
+            self.in_template,
+        );
+    }
+
+    /// Declares the names bound by `pat` in the current scope and records
+    /// the references in its default values and computed keys.
+    fn declare_pat(&mut self, pat: &Pat, kind: BindingKind) {
+        let scope = self.scope;
+        self.declare_pat_in(scope, pat, kind);
+    }
+
+    fn declare_pat_in(&mut self, scope: ScopeId, pat: &Pat, kind: BindingKind) {
+        match pat {
+            Pat::Ident(binding) => self.declare_ident(scope, &binding.id, kind),
+            Pat::Array(array) => {
+                for elem in array.elems.iter().flatten() {
+                    self.declare_pat_in(scope, elem, kind);
+                }
+            }
+            Pat::Rest(rest) => self.declare_pat_in(scope, &rest.arg, kind),
+            Pat::Object(object) => {
+                for prop in &object.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(kv) => {
+                            kv.key.visit_with(self);
+                            self.declare_pat_in(scope, &kv.value, kind);
+                        }
+                        ObjectPatProp::Assign(assign) => {
+                            self.declare_ident(scope, &assign.key.id, kind);
+                            assign.value.visit_with(self);
+                        }
+                        ObjectPatProp::Rest(rest) => self.declare_pat_in(scope, &rest.arg, kind),
+                    }
+                }
+            }
+            Pat::Assign(assign) => {
+                self.declare_pat_in(scope, &assign.left, kind);
+                assign.right.visit_with(self);
+            }
+            Pat::Expr(expr) => expr.visit_with(self),
+            Pat::Invalid(_) => {}
+        }
+    }
+
+    /// Declares the names of a `$props()` destructuring.
+    fn declare_props(&mut self, scope: ScopeId, pat: &Pat) {
+        self.builder.mark_props_rune();
+        let Pat::Object(object) = pat else {
+            self.declare_pat_in(scope, pat, BindingKind::RestProp);
+            return;
+        };
+        for prop in &object.props {
+            match prop {
+                ObjectPatProp::KeyValue(kv) => {
+                    kv.key.visit_with(self);
+                    let kind = match &*kv.value {
+                        Pat::Assign(assign) if is_bindable(&assign.right) => {
+                            BindingKind::BindableProp
+                        }
+                        _ => BindingKind::Prop,
+                    };
+                    self.declare_pat_in(scope, &kv.value, kind);
+                }
+                ObjectPatProp::Assign(assign) => {
+                    let kind = match &assign.value {
+                        Some(value) if is_bindable(value) => BindingKind::BindableProp,
+                        _ => BindingKind::Prop,
+                    };
+                    self.declare_ident(scope, &assign.key.id, kind);
+                    assign.value.visit_with(self);
+                }
+                ObjectPatProp::Rest(rest) => {
+                    self.declare_pat_in(scope, &rest.arg, BindingKind::RestProp)
+                }
+            }
+        }
+    }
+
+    fn declare_var_decl(&mut self, var: &VarDecl, exported: bool) {
+        let scope = if var.kind == VarDeclKind::Var {
+            self.var_scope()
+        } else {
+            self.scope
+        };
+        let instance = scope == self.builder.instance_scope;
+        let const_scope = std::mem::replace(
+            &mut self.const_scope,
+            (var.kind == VarDeclKind::Const).then_some(scope),
+        );
+        for declarator in &var.decls {
+            let init = declarator.init.as_deref();
+            match rune_kind(init) {
+                Some(BindingKind::Prop) if instance => self.declare_props(scope, &declarator.name),
+                Some(kind) => self.declare_pat_in(scope, &declarator.name, kind),
+                None => {
+                    let kind = match var.kind {
+                        _ if exported && instance && var.kind != VarDeclKind::Const => {
+                            BindingKind::Prop
+                        }
+                        VarDeclKind::Var => BindingKind::Var,
+                        VarDeclKind::Let => BindingKind::Let,
+                        VarDeclKind::Const => BindingKind::Const,
+                    };
+                    self.declare_pat_in(scope, &declarator.name, kind);
+                }
+            }
+            declarator.init.visit_with(self);
+        }
+        self.const_scope = const_scope;
+    }
+
+    fn visit_function_body(&mut self, body: &Option<BlockStmt>) {
+        if let Some(body) = body {
+            body.stmts.visit_with(self);
+        }
+    }
+}
+
+impl Visit for Resolver<'_, '_> {
+    fn visit_import_decl(&mut self, node: &ImportDecl) {
+        let scope = self.scope;
+        for specifier in &node.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(namespace) => &namespace.local,
+            };
+            self.declare_ident(scope, local, BindingKind::Import);
+        }
+    }
+
+    fn visit_var_decl(&mut self, node: &VarDecl) {
+        self.declare_var_decl(node, false);
+    }
+
+    fn visit_export_decl(&mut self, node: &ExportDecl) {
+        match &node.decl {
+            Decl::Var(var) => self.declare_var_decl(var, true),
+            decl => decl.visit_with(self),
+        }
+    }
+
+    fn visit_fn_decl(&mut self, node: &FnDecl) {
+        let scope = self.scope;
+        self.declare_ident(scope, &node.ident, BindingKind::Function);
+        node.function.visit_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, node: &FnExpr) {
+        match &node.ident {
+            Some(ident) => self.in_scope(ScopeKind::Function, node.function.span, |this| {
+                let scope = this.scope;
+                this.declare_ident(scope, ident, BindingKind::Function);
+                node.function.visit_with(this);
+            }),
+            None => node.function.visit_with(self),
+        }
+    }
+
+    fn visit_function(&mut self, node: &Function) {
+        node.decorators.visit_with(self);
+        self.in_scope(ScopeKind::Function, node.span, |this| {
+            for param in &node.params {
+                this.declare_pat(&param.pat, BindingKind::Param);
+            }
+            this.visit_function_body(&node.body);
+        });
+    }
+
+    fn visit_arrow_expr(&mut self, node: &ArrowExpr) {
+        self.in_scope(ScopeKind::Function, node.span, |this| {
+            for param in &node.params {
+                this.declare_pat(param, BindingKind::Param);
+            }
+            match &*node.body {
+                BlockStmtOrExpr::BlockStmt(block) => block.stmts.visit_with(this),
+                BlockStmtOrExpr::Expr(expr) => expr.visit_with(this),
+            }
+        });
+    }
+
+    fn visit_constructor(&mut self, node: &Constructor) {
+        node.key.visit_with(self);
+        self.in_scope(ScopeKind::Function, node.span, |this| {
+            for param in &node.params {
+                match param {
+                    ParamOrTsParamProp::Param(param) => {
+                        this.declare_pat(&param.pat, BindingKind::Param)
+                    }
+                    ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+                        TsParamPropParam::Ident(binding) => {
+                            let scope = this.scope;
+                            this.declare_ident(scope, &binding.id, BindingKind::Param);
+                        }
+                        TsParamPropParam::Assign(assign) => {
+                            this.declare_pat(&assign.left, BindingKind::Param);
+                            assign.right.visit_with(this);
+                        }
+                    },
+                }
+            }
+            this.visit_function_body(&node.body);
+        });
+    }
+
+    fn visit_setter_prop(&mut self, node: &SetterProp) {
+        node.key.visit_with(self);
+        self.in_scope(ScopeKind::Function, node.span, |this| {
+            this.declare_pat(&node.param, BindingKind::Param);
+            this.visit_function_body(&node.body);
+        });
+    }
+
+    fn visit_class_decl(&mut self, node: &ClassDecl) {
+        let scope = self.scope;
+        self.declare_ident(scope, &node.ident, BindingKind::Class);
+        node.class.visit_with(self);
+    }
+
+    fn visit_class_expr(&mut self, node: &ClassExpr) {
+        match &node.ident {
+            Some(ident) => self.in_scope(ScopeKind::Block, node.class.span, |this| {
+                let scope = this.scope;
+                this.declare_ident(scope, ident, BindingKind::Class);
+                node.class.visit_with(this);
+            }),
+            None => node.class.visit_with(self),
+        }
+    }
+
+    fn visit_block_stmt(&mut self, node: &BlockStmt) {
+        self.in_scope(ScopeKind::Block, node.span, |this| {
+            node.stmts.visit_with(this)
+        });
+    }
+
+    fn visit_for_stmt(&mut self, node: &ForStmt) {
+        self.in_scope(ScopeKind::Block, node.span, |this| {
+            node.visit_children_with(this)
+        });
+    }
+
+    fn visit_for_in_stmt(&mut self, node: &ForInStmt) {
+        self.in_scope(ScopeKind::Block, node.span, |this| {
+            node.visit_children_with(this)
+        });
+    }
+
+    fn visit_for_of_stmt(&mut self, node: &ForOfStmt) {
+        self.in_scope(ScopeKind::Block, node.span, |this| {
+            node.visit_children_with(this)
+        });
+    }
+
+    fn visit_catch_clause(&mut self, node: &CatchClause) {
+        self.in_scope(ScopeKind::Block, node.span, |this| {
+            if let Some(param) = &node.param {
+                this.declare_pat(param, BindingKind::Param);
+            }
+            node.body.stmts.visit_with(this);
+        });
+    }
+
+    fn visit_labeled_stmt(&mut self, node: &LabeledStmt) {
+        // `$: name = ...` at the top level of the instance script declares
+        // `name` if nothing else does, as does every name in a destructuring
+        // target (`$: ({ a, b } = obj)`).
+        if node.label.sym == "$" && self.scope == self.builder.instance_scope {
+            if let Stmt::Expr(stmt) = &*node.body {
+                let mut expr = &*stmt.expr;
+                while let Expr::Paren(paren) = expr {
+                    expr = &paren.expr;
+                }
+                if let Expr::Assign(assign) = expr {
+                    let mut targets = AssignedNames::default();
+                    match &assign.left {
+                        AssignTarget::Simple(SimpleAssignTarget::Ident(binding)) => {
+                            targets.0.push(binding.id.clone());
+                        }
+                        AssignTarget::Pat(pat) => pat.visit_with(&mut targets),
+                        AssignTarget::Simple(_) => {}
+                    }
+                    let scope = self.scope;
+                    for ident in &targets.0 {
+                        let span = self.ident_span(ident);
+                        self.builder.declare_implicit(scope, &ident.sym, span);
+                    }
+                }
+            }
+        }
+        node.body.visit_with(self);
+    }
+
+    fn visit_break_stmt(&mut self, _node: &BreakStmt) {}
+
+    fn visit_continue_stmt(&mut self, _node: &ContinueStmt) {}
+
+    fn visit_named_export(&mut self, node: &NamedExport) {
+        if node.src.is_some() {
+            return;
+        }
+        for specifier in &node.specifiers {
+            if let ExportSpecifier::Named(named) = specifier {
+                if let ModuleExportName::Ident(ident) = &named.orig {
+                    self.reference(ident, false);
+                }
+            }
+        }
+    }
+
+    fn visit_ident(&mut self, node: &Ident) {
+        self.reference(node, false);
+    }
+
+    fn visit_binding_ident(&mut self, node: &swc_ecma_ast::BindingIdent) {
+        // Declarations never reach here (see `declare_pat`), so this is an
+        // assignment target.
+        self.reference(&node.id, true);
+    }
+
+    fn visit_assign_expr(&mut self, node: &AssignExpr) {
+        match &node.left {
+            // `count += 1` reads `count` as well as writing it.
+            AssignTarget::Simple(SimpleAssignTarget::Ident(binding))
+                if node.op != AssignOp::Assign =>
+            {
+                self.reference_with(&binding.id, true, true);
+                node.right.visit_with(self);
+            }
+            _ => node.visit_children_with(self),
+        }
+    }
+
+    fn visit_update_expr(&mut self, node: &UpdateExpr) {
+        match &*node.arg {
+            Expr::Ident(ident) => self.reference_with(ident, true, true),
+            arg => arg.visit_with(self),
+        }
+    }
+
+    fn visit_ts_enum_decl(&mut self, node: &TsEnumDecl) {
+        let scope = self.scope;
+        self.declare_ident(scope, &node.id, BindingKind::Enum);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, node: &TsImportEqualsDecl) {
+        let scope = self.scope;
+        self.declare_ident(scope, &node.id, BindingKind::Import);
+    }
+
+    // Type positions are not tracked.
+    fn visit_ts_type(&mut self, _node: &TsType) {}
+
+    fn visit_ts_type_ann(&mut self, _node: &TsTypeAnn) {}
+
+    fn visit_ts_type_param_decl(&mut self, _node: &TsTypeParamDecl) {}
+
+    fn visit_ts_type_param_instantiation(&mut self, _node: &TsTypeParamInstantiation) {}
+
+    fn visit_ts_interface_decl(&mut self, _node: &TsInterfaceDecl) {}
+
+    fn visit_ts_type_alias_decl(&mut self, _node: &TsTypeAliasDecl) {}
+
+    fn visit_ts_module_decl(&mut self, _node: &TsModuleDecl) {}
+
+    fn visit_ts_expr_with_type_args(&mut self, _node: &TsExprWithTypeArgs) {}
+}
+
+/// The names a destructuring assignment target writes.
+#[derive(Default)]
+struct AssignedNames(Vec<Ident>);
+
+impl Visit for AssignedNames {
+    fn visit_binding_ident(&mut self, node: &swc_ecma_ast::BindingIdent) {
+        self.0.push(node.id.clone());
+    }
+
+    // Default values, computed keys and member targets are not names.
+    fn visit_expr(&mut self, _node: &Expr) {}
+}
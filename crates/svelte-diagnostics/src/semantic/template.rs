@@ -0,0 +1,432 @@
+//! Template scopes and references.
+//!
+//! Expression text in the AST is not always trimmed and not every piece of
+//! it carries a precise span, so the walker locates each text in the
+//! component source within the span the parser reports.
+
+use super::{script, BindingKind, ModelBuilder, ScopeId, ScopeKind};
+use source_map::Span;
+use svelte_parser::{
+    Attribute, AttributeValue, AttributeValuePart, Directive, DirectiveKind, EachBlock, ElseBranch,
+    ExpressionValue, ScriptLang, SvelteDocument, TemplateNode,
+};
+
+/// Collects the template's scopes, bindings and references.
+pub(super) fn collect(builder: &mut ModelBuilder<'_>, doc: &SvelteDocument) {
+    let typescript = [&doc.instance_script, &doc.module_script]
+        .into_iter()
+        .flatten()
+        .any(|script| script.lang == ScriptLang::TypeScript);
+    let template_scope = builder.template_scope;
+    let mut walker = Walker {
+        builder,
+        typescript,
+    };
+    walker.fragment(&doc.fragment.nodes, template_scope);
+}
+
+struct Walker<'b, 'a> {
+    builder: &'b mut ModelBuilder<'a>,
+    typescript: bool,
+}
+
+impl Walker<'_, '_> {
+    /// Finds `text` (trimmed) in the source within `span`, starting at
+    /// `from`, and returns its offset.
+    fn locate(&self, text: &str, span: Span, from: u32) -> Option<u32> {
+        let text = text.trim();
+        let start = from.max(u32::from(span.start)) as usize;
+        let end = (u32::from(span.end) as usize).min(self.builder.source().len());
+        let haystack = self.builder.source().get(start..end)?;
+        if text.is_empty() {
+            return None;
+        }
+        haystack.find(text).map(|index| (start + index) as u32)
+    }
+
+    /// Finds the identifier `name` in the source within `span`, starting at
+    /// `from`, skipping occurrences inside longer identifiers.
+    fn locate_ident(&self, name: &str, span: Span, from: u32) -> Option<u32> {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+        let source = self.builder.source();
+        let mut from = from;
+        loop {
+            let start = self.locate(name, span, from)? as usize;
+            let end = start + name.len();
+            let before = source[..start].chars().next_back();
+            let after = source[end..].chars().next();
+            if !before.is_some_and(is_ident) && !after.is_some_and(is_ident) {
+                return Some(start as u32);
+            }
+            from = end as u32;
+        }
+    }
+
+    fn expression(&mut self, scope: ScopeId, text: &str, span: Span) {
+        let from = u32::from(span.start);
+        if let Some(base) = self.locate(text, span, from) {
+            script::expression(self.builder, scope, text.trim(), base, self.typescript);
+        }
+    }
+
+    fn expression_value(&mut self, scope: ScopeId, value: &ExpressionValue) {
+        // A quoted value (`style:color="red"`) is a string, not an expression.
+        if value.is_quoted {
+            return;
+        }
+        self.expression(scope, &value.expression, value.expression_span);
+    }
+
+    fn reference(&mut self, scope: ScopeId, name: &str, start: u32, is_write: bool) {
+        let span = Span::new(start, start + name.len() as u32);
+        self.builder
+            .reference(scope, name, span, is_write, !is_write, true);
+    }
+
+    fn fragment(&mut self, nodes: &[TemplateNode], scope: ScopeId) {
+        for node in nodes {
+            self.node(node, scope);
+        }
+    }
+
+    fn node(&mut self, node: &TemplateNode, scope: ScopeId) {
+        match node {
+            TemplateNode::Element(el) => self.element(&el.attributes, &el.children, el.span, scope),
+            TemplateNode::Component(comp) => {
+                let name = leading_identifier(&comp.name);
+                self.reference(scope, name, u32::from(comp.span.start) + 1, false);
+                self.element(&comp.attributes, &comp.children, comp.span, scope);
+            }
+            TemplateNode::SvelteElement(el) => {
+                self.element(&el.attributes, &el.children, el.span, scope)
+            }
+            TemplateNode::Text(_) | TemplateNode::Comment(_) => {}
+            TemplateNode::Expression(tag) => {
+                self.expression(scope, &tag.expression, tag.expression_span)
+            }
+            TemplateNode::HtmlTag(tag) => {
+                self.expression(scope, &tag.expression, tag.expression_span)
+            }
+            TemplateNode::RenderTag(tag) => {
+                self.expression(scope, &tag.expression, tag.expression_span)
+            }
+            TemplateNode::ConstTag(tag) => self.declaration(
+                scope,
+                &tag.declaration,
+                tag.declaration_span,
+                BindingKind::ConstTag,
+            ),
+            TemplateNode::DeclarationTag(tag) => self.declaration(
+                scope,
+                &tag.declaration,
+                tag.declaration_span,
+                BindingKind::DeclarationTag,
+            ),
+            TemplateNode::DebugTag(tag) => {
+                let mut from = u32::from(tag.span.start) + "{@debug".len() as u32;
+                for name in &tag.identifiers {
+                    if let Some(start) = self.locate_ident(name, tag.span, from) {
+                        self.reference(scope, name, start, false);
+                        from = start + name.len() as u32;
+                    }
+                }
+            }
+            TemplateNode::IfBlock(block) => {
+                self.expression(scope, &block.condition, block.condition_span);
+                self.fragment(&block.consequent.nodes, scope);
+                let mut alternate = &block.alternate;
+                while let Some(branch) = alternate {
+                    match branch {
+                        ElseBranch::Else(fragment) => {
+                            self.fragment(&fragment.nodes, scope);
+                            break;
+                        }
+                        ElseBranch::ElseIf(nested) => {
+                            self.expression(scope, &nested.condition, nested.condition_span);
+                            self.fragment(&nested.consequent.nodes, scope);
+                            alternate = &nested.alternate;
+                        }
+                    }
+                }
+            }
+            TemplateNode::EachBlock(block) => self.each(block, scope),
+            TemplateNode::AwaitBlock(block) => {
+                self.expression(scope, &block.expression, block.expression_span);
+                if let Some(pending) = &block.pending {
+                    self.fragment(&pending.nodes, scope);
+                }
+                if let Some(then) = &block.then {
+                    let inner =
+                        self.builder
+                            .push_scope(ScopeKind::AwaitThen, Some(scope), then.span);
+                    if let (Some(value), Some(span)) = (&then.value, then.value_span) {
+                        self.patterns(inner, value, span, BindingKind::AwaitValue);
+                    }
+                    self.fragment(&then.body.nodes, inner);
+                }
+                if let Some(catch) = &block.catch {
+                    let inner =
+                        self.builder
+                            .push_scope(ScopeKind::AwaitCatch, Some(scope), catch.span);
+                    if let Some(error) = &catch.error {
+                        let from = self
+                            .locate_ident("catch", block.span, u32::from(catch.span.start))
+                            .map_or(u32::from(catch.span.start), |start| start + 5);
+                        let span = Span::new(from, u32::from(catch.span.end));
+                        self.patterns(inner, error, span, BindingKind::AwaitError);
+                    }
+                    self.fragment(&catch.body.nodes, inner);
+                }
+            }
+            TemplateNode::KeyBlock(block) => {
+                self.expression(scope, &block.expression, block.expression_span);
+                self.fragment(&block.body.nodes, scope);
+            }
+            TemplateNode::SnippetBlock(block) => {
+                // Top-level snippets are visible to the instance script.
+                let declare_in = if scope == self.builder.template_scope {
+                    self.builder.instance_scope
+                } else {
+                    scope
+                };
+                // The parser keeps generics (`name<T>`) in the name.
+                let name = leading_identifier(&block.name);
+                let from = u32::from(block.span.start) + "{#snippet".len() as u32;
+                if let Some(start) = self.locate_ident(name, block.span, from) {
+                    let span = Span::new(start, start + name.len() as u32);
+                    self.builder
+                        .declare(declare_in, name, BindingKind::Snippet, span);
+                }
+                let inner = self
+                    .builder
+                    .push_scope(ScopeKind::Snippet, Some(scope), block.span);
+                self.patterns(
+                    inner,
+                    &block.parameters,
+                    block.parameters_span,
+                    BindingKind::SnippetParam,
+                );
+                self.fragment(&block.body.nodes, inner);
+            }
+        }
+    }
+
+    fn each(&mut self, block: &EachBlock, scope: ScopeId) {
+        self.expression(scope, &block.expression, block.expression_span);
+        let inner = self
+            .builder
+            .push_scope(ScopeKind::Each, Some(scope), block.span);
+        // The context span is not always exact for multi-line patterns, so
+        // search from its start to the end of the block.
+        let search = Span::new(block.context_span.start, block.span.end);
+        self.patterns(inner, &block.context, search, BindingKind::EachItem);
+        if let Some(index) = &block.index {
+            let from = self
+                .locate(&block.context, search, u32::from(search.start))
+                .map_or(u32::from(block.context_span.end), |start| {
+                    start + block.context.trim().len() as u32
+                });
+            if let Some(start) = self.locate_ident(index, block.span, from) {
+                let span = Span::new(start, start + index.len() as u32);
+                self.builder
+                    .declare(inner, index, BindingKind::EachIndex, span);
+            }
+        }
+        if let Some(key) = &block.key {
+            self.expression(inner, &key.expression, key.span);
+        }
+        self.fragment(&block.body.nodes, inner);
+        if let Some(fallback) = &block.fallback {
+            self.fragment(&fallback.nodes, scope);
+        }
+    }
+
+    fn patterns(&mut self, scope: ScopeId, text: &str, span: Span, kind: BindingKind) {
+        if let Some(base) = self.locate(text, span, u32::from(span.start)) {
+            script::patterns(
+                self.builder,
+                scope,
+                text.trim(),
+                base,
+                kind,
+                self.typescript,
+            );
+        }
+    }
+
+    fn declaration(&mut self, scope: ScopeId, text: &str, span: Span, kind: BindingKind) {
+        if let Some(base) = self.locate(text, span, u32::from(span.start)) {
+            script::declaration(
+                self.builder,
+                scope,
+                text.trim(),
+                base,
+                kind,
+                self.typescript,
+            );
+        }
+    }
+
+    fn element(
+        &mut self,
+        attributes: &[Attribute],
+        children: &[TemplateNode],
+        span: Span,
+        scope: ScopeId,
+    ) {
+        for attribute in attributes {
+            self.attribute(attribute, scope);
+        }
+        let lets: Vec<&Directive> = attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                Attribute::Directive(directive) if directive.kind == DirectiveKind::Let => {
+                    Some(directive)
+                }
+                _ => None,
+            })
+            .collect();
+        if lets.is_empty() {
+            self.fragment(children, scope);
+            return;
+        }
+        let inner = self
+            .builder
+            .push_scope(ScopeKind::LetDirective, Some(scope), span);
+        for directive in lets {
+            match &directive.expression {
+                Some(alias) => self.patterns(
+                    inner,
+                    &alias.expression,
+                    alias.expression_span,
+                    BindingKind::LetDirective,
+                ),
+                None => {
+                    if let Some(start) = self.directive_name(directive) {
+                        let span = Span::new(start, start + directive.name.len() as u32);
+                        self.builder.declare(
+                            inner,
+                            &directive.name,
+                            BindingKind::LetDirective,
+                            span,
+                        );
+                    }
+                }
+            }
+        }
+        self.fragment(children, inner);
+    }
+
+    /// Returns the offset of a directive's name (after the `kind:` prefix).
+    fn directive_name(&self, directive: &Directive) -> Option<u32> {
+        let from = self.locate(":", directive.span, u32::from(directive.span.start))? + 1;
+        self.locate(&directive.name, directive.span, from)
+    }
+
+    fn attribute_value(&mut self, value: &AttributeValue, scope: ScopeId) {
+        match value {
+            AttributeValue::True | AttributeValue::Text(_) => {}
+            AttributeValue::Expression(expr) => self.expression_value(scope, expr),
+            AttributeValue::Concat(parts) => {
+                for part in parts {
+                    if let AttributeValuePart::Expression(expr) = part {
+                        self.expression_value(scope, expr);
+                    }
+                }
+            }
+        }
+    }
+
+    fn attribute(&mut self, attribute: &Attribute, scope: ScopeId) {
+        match attribute {
+            Attribute::Normal(attr) => self.attribute_value(&attr.value, scope),
+            Attribute::Spread(spread) => {
+                self.expression(scope, &spread.expression, spread.expression_span)
+            }
+            Attribute::Attach(attach) => {
+                self.expression(scope, &attach.expression, attach.expression_span)
+            }
+            Attribute::Shorthand(shorthand) => {
+                if let Some(start) = self.locate_ident(
+                    &shorthand.name,
+                    shorthand.span,
+                    u32::from(shorthand.span.start),
+                ) {
+                    self.reference(scope, &shorthand.name, start, false);
+                }
+            }
+            Attribute::CssCustomProperty { value, .. } => {
+                if let Some(value) = value {
+                    self.attribute_value(value, scope);
+                }
+            }
+            Attribute::Directive(directive) => self.directive(directive, scope),
+        }
+    }
+
+    fn directive(&mut self, directive: &Directive, scope: ScopeId) {
+        match directive.kind {
+            // `let:` declares names for the children; see `element`.
+            DirectiveKind::Let => {}
+            DirectiveKind::On => {
+                if let Some(expr) = &directive.expression {
+                    self.expression_value(scope, expr);
+                }
+            }
+            DirectiveKind::Bind => match &directive.expression {
+                Some(expr) if is_identifier(expr.expression.trim()) => {
+                    let name = expr.expression.trim();
+                    let from = u32::from(expr.expression_span.start);
+                    if let Some(start) = self.locate_ident(name, expr.expression_span, from) {
+                        self.reference(scope, name, start, true);
+                    }
+                }
+                Some(expr) => self.expression_value(scope, expr),
+                None => {
+                    if let Some(start) = self.directive_name(directive) {
+                        self.reference(scope, &directive.name, start, true);
+                    }
+                }
+            },
+            DirectiveKind::Class | DirectiveKind::StyleDirective => match &directive.expression {
+                Some(expr) => self.expression_value(scope, expr),
+                None => {
+                    if let Some(start) = self.directive_name(directive) {
+                        self.reference(scope, &directive.name, start, false);
+                    }
+                }
+            },
+            DirectiveKind::Use
+            | DirectiveKind::Transition
+            | DirectiveKind::In
+            | DirectiveKind::Out
+            | DirectiveKind::Animate => {
+                // `use:a.b` references `a`; `use:a(args)` is accepted by the
+                // parser as well.
+                let name = leading_identifier(&directive.name);
+                if let Some(start) = self.directive_name(directive).filter(|_| !name.is_empty()) {
+                    self.reference(scope, name, start, false);
+                }
+                if let Some(expr) = &directive.expression {
+                    self.expression_value(scope, expr);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the identifier `text` starts with.
+pub(crate) fn leading_identifier(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
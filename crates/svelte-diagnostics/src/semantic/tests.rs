@@ -0,0 +1,302 @@
+use super::*;
+use svelte_parser::parse;
+
+fn model(source: &str) -> SemanticModel {
+    SemanticModel::build(&parse(source).document, source)
+}
+
+fn kind_of(model: &SemanticModel, name: &str) -> Option<BindingKind> {
+    model
+        .bindings()
+        .iter()
+        .find(|binding| binding.name == name)
+        .map(|binding| binding.kind)
+}
+
+fn unresolved(model: &SemanticModel) -> Vec<&str> {
+    model
+        .unresolved_references()
+        .map(|reference| reference.name.as_str())
+        .collect()
+}
+
+/// Returns the source text of every reference resolved to `name`.
+fn references_to<'s>(model: &SemanticModel, source: &'s str, name: &str) -> Vec<&'s str> {
+    model
+        .references()
+        .iter()
+        .filter(|reference| {
+            reference
+                .binding
+                .is_some_and(|binding| model.binding(binding).name == name)
+        })
+        .map(|reference| &source[reference.span.start.into()..reference.span.end.into()])
+        .collect()
+}
+
+#[test]
+fn test_script_binding_kinds() {
+    let model = model(
+        r#"<script lang="ts">
+    import Card, { helper } from './lib';
+    import * as utils from './utils';
+    let { title, open = $bindable(false), ...rest }: Props = $props();
+    let count = $state(0);
+    let items = $state.raw([]);
+    const double = $derived(count * 2);
+    const total = $derived.by(() => count);
+    let plain = 1;
+    const fixed = 2;
+    function increment() {}
+    class Store {}
+    enum Mode { A }
+</script>"#,
+    );
+
+    for (name, kind) in [
+        ("Card", BindingKind::Import),
+        ("helper", BindingKind::Import),
+        ("utils", BindingKind::Import),
+        ("title", BindingKind::Prop),
+        ("open", BindingKind::BindableProp),
+        ("rest", BindingKind::RestProp),
+        ("count", BindingKind::State),
+        ("items", BindingKind::RawState),
+        ("double", BindingKind::Derived),
+        ("total", BindingKind::Derived),
+        ("plain", BindingKind::Let),
+        ("fixed", BindingKind::Const),
+        ("increment", BindingKind::Function),
+        ("Store", BindingKind::Class),
+        ("Mode", BindingKind::Enum),
+    ] {
+        assert_eq!(kind_of(&model, name), Some(kind), "{name}");
+    }
+    // Type annotations are not references.
+    assert!(!unresolved(&model).contains(&"Props"));
+}
+
+#[test]
+fn test_legacy_props_and_module_scope() {
+    let source = r#"<script context="module">
+    export const shared = 1;
+</script>
+<script>
+    export let name;
+    export const version = shared;
+    let props = $props();
+</script>"#;
+    let model = model(source);
+    assert_eq!(kind_of(&model, "name"), Some(BindingKind::Prop));
+    assert_eq!(kind_of(&model, "version"), Some(BindingKind::Const));
+    assert_eq!(kind_of(&model, "props"), Some(BindingKind::RestProp));
+
+    let shared = model.scope(model.module_scope()).get("shared").unwrap();
+    assert_eq!(model.binding(shared).scope, model.module_scope());
+    assert_eq!(references_to(&model, source, "shared"), vec!["shared"]);
+}
+
+#[test]
+fn test_function_scopes_and_hoisting() {
+    let model = model(
+        r#"<script>
+    function outer(a, { b }) {
+        if (a) {
+            var hoisted = b;
+            let inner = hoisted;
+        }
+        return inner;
+    }
+    const arrow = (x) => x + y;
+</script>"#,
+    );
+    let hoisted = model
+        .binding_ids()
+        .find(|&id| model.binding(id).name == "hoisted")
+        .unwrap();
+    assert_eq!(
+        model.scope(model.binding(hoisted).scope).kind,
+        ScopeKind::Function
+    );
+    assert_eq!(kind_of(&model, "a"), Some(BindingKind::Param));
+    assert_eq!(kind_of(&model, "b"), Some(BindingKind::Param));
+    assert_eq!(kind_of(&model, "x"), Some(BindingKind::Param));
+    // `inner` is block-scoped, so the `return` does not see it.
+    assert_eq!(unresolved(&model), vec!["inner", "y"]);
+}
+
+#[test]
+fn test_writes_and_store_subscriptions() {
+    let source = r#"<script>
+    import { writable } from 'svelte/store';
+    const store = writable(0);
+    let count = 0;
+    count += 1;
+    count++;
+    $store = 2;
+</script>
+<input bind:value={count} />
+<p>{$store} {count}</p>"#;
+    let model = model(source);
+
+    let count = model.scope(model.instance_scope()).get("count").unwrap();
+    let accesses: Vec<_> = model
+        .references_to(count)
+        .map(|reference| (reference.is_write, reference.is_read, reference.in_template))
+        .collect();
+    assert_eq!(
+        accesses,
+        vec![
+            (true, true, false),
+            (true, true, false),
+            (true, false, true),
+            (false, true, true),
+        ]
+    );
+
+    let store = model.scope(model.instance_scope()).get("store").unwrap();
+    let subscriptions: Vec<_> = model
+        .references_to(store)
+        .filter(|reference| reference.is_store_subscription)
+        .map(|reference| reference.name.as_str())
+        .collect();
+    assert_eq!(subscriptions, vec!["$store", "$store"]);
+    assert!(unresolved(&model).is_empty());
+}
+
+#[test]
+fn test_each_block_scope() {
+    let source = r#"<script>
+    let items = [];
+</script>
+{#each items as { id, label }, i (id)}
+    <p>{label} {i}</p>
+{:else}
+    <p>{label}</p>
+{/each}"#;
+    let model = model(source);
+    assert_eq!(kind_of(&model, "id"), Some(BindingKind::EachItem));
+    assert_eq!(kind_of(&model, "label"), Some(BindingKind::EachItem));
+    assert_eq!(kind_of(&model, "i"), Some(BindingKind::EachIndex));
+    assert_eq!(references_to(&model, source, "id"), vec!["id"]);
+    assert_eq!(references_to(&model, source, "i"), vec!["i"]);
+    // The fallback is outside the each scope.
+    assert_eq!(unresolved(&model), vec!["label"]);
+}
+
+#[test]
+fn test_await_snippet_and_const_scopes() {
+    let source = r#"<script>
+    let promise = fetch('/');
+</script>
+{#await promise then response}
+    {@const status = response.status}
+    <p>{status}</p>
+{:catch error}
+    <p>{error.message} {response}</p>
+{/await}
+{#snippet row(item, index = 0)}
+    <td>{item} {index}</td>
+{/snippet}
+{@render row(promise)}"#;
+    let model = model(source);
+    assert_eq!(kind_of(&model, "response"), Some(BindingKind::AwaitValue));
+    assert_eq!(kind_of(&model, "error"), Some(BindingKind::AwaitError));
+    assert_eq!(kind_of(&model, "status"), Some(BindingKind::ConstTag));
+    assert_eq!(kind_of(&model, "row"), Some(BindingKind::Snippet));
+    assert_eq!(kind_of(&model, "item"), Some(BindingKind::SnippetParam));
+    assert_eq!(kind_of(&model, "index"), Some(BindingKind::SnippetParam));
+
+    assert_eq!(references_to(&model, source, "status"), vec!["status"]);
+    assert_eq!(references_to(&model, source, "error"), vec!["error"]);
+    assert_eq!(references_to(&model, source, "row"), vec!["row"]);
+    // `response` is only in scope in the `{:then}` branch.
+    assert_eq!(unresolved(&model), vec!["fetch", "response"]);
+
+    // Top-level snippets are visible to the instance script.
+    let row = model.scope(model.instance_scope()).get("row").unwrap();
+    assert_eq!(model.binding(row).scope, model.instance_scope());
+}
+
+#[test]
+fn test_let_directives_and_attributes() {
+    let source = r#"<script>
+    import List from './List.svelte';
+    import { fade } from 'svelte/transition';
+    let active = false;
+    let color = 'red';
+</script>
+<List let:item let:index={position} {active} on:click={() => (active = !active)}>
+    <p class:active style:color transition:fade>{item} {position}</p>
+</List>
+<p>{item}</p>"#;
+    let model = model(source);
+    assert_eq!(kind_of(&model, "item"), Some(BindingKind::LetDirective));
+    assert_eq!(kind_of(&model, "position"), Some(BindingKind::LetDirective));
+    assert_eq!(references_to(&model, source, "List"), vec!["List"]);
+    assert_eq!(references_to(&model, source, "fade"), vec!["fade"]);
+    assert_eq!(references_to(&model, source, "color"), vec!["color"]);
+    assert_eq!(
+        references_to(&model, source, "active"),
+        vec!["active", "active", "active", "active"]
+    );
+    assert_eq!(unresolved(&model), vec!["item"]);
+}
+
+#[test]
+fn test_binding_at_offset() {
+    let source = "<script>\n    let count = 0;\n</script>\n<p>{count}</p>";
+    let model = model(source);
+    let use_site = source.rfind("count").unwrap() as u32 + 2;
+    let binding = model.binding_at(ByteOffset::from(use_site)).unwrap();
+    assert_eq!(model.binding(binding).name, "count");
+    assert_eq!(
+        model.binding(binding).span.start,
+        ByteOffset::from(source.find("count").unwrap() as u32)
+    );
+}
+
+#[test]
+fn test_const_declarations_and_props_rune() {
+    let source = r#"<script>
+    let { title } = $props();
+    const items = $state([]);
+    let count = $state(0);
+    const handler = (event) => {
+        let local = event;
+    };
+</script>"#;
+    let model = model(source);
+    assert!(model.has_props_rune());
+    let is_const = |name: &str| {
+        model
+            .bindings()
+            .iter()
+            .find(|binding| binding.name == name)
+            .unwrap()
+            .is_const
+    };
+    assert!(is_const("items"));
+    assert!(is_const("handler"));
+    assert!(!is_const("title"));
+    assert!(!is_const("count"));
+    assert!(!is_const("event"));
+    assert!(!is_const("local"));
+
+    let legacy = self::model("<script>\n    export let name;\n</script>");
+    assert!(!legacy.has_props_rune());
+}
+
+#[test]
+fn test_generic_snippet_name() {
+    let source = r#"<script lang="ts">
+    let items: string[] = [];
+</script>
+{#snippet list<T extends string>(values: T[])}
+    {values.length}
+{/snippet}
+{@render list(items)}"#;
+    let model = model(source);
+    assert_eq!(kind_of(&model, "list"), Some(BindingKind::Snippet));
+    assert_eq!(references_to(&model, source, "list"), vec!["list"]);
+}
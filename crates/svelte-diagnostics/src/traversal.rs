@@ -0,0 +1,177 @@
+//! A flattened, shared walk over a template.
+//!
+//! The template is walked once per document and every rule reads the same
+//! node list, instead of each rule recursing through every block kind itself.
+
+use svelte_parser::{ElseBranch, Fragment, TemplateNode};
+
+/// A node visited by the traversal.
+#[derive(Debug, Clone, Copy)]
+pub struct VisitedNode<'a> {
+    /// The node.
+    pub node: &'a TemplateNode,
+    /// Index of the enclosing node in [`Traversal::nodes`], if any.
+    pub parent: Option<usize>,
+    /// Nesting depth; top-level nodes are at depth 0.
+    pub depth: usize,
+}
+
+/// All template nodes of a document in source order (pre-order).
+///
+/// Every branch is visited: both arms of `{#if}`, the `{:else}` of
+/// `{#each}`, all `{#await}` states, component slot content and snippet
+/// bodies.
+#[derive(Debug, Clone, Default)]
+pub struct Traversal<'a> {
+    nodes: Vec<VisitedNode<'a>>,
+}
+
+impl<'a> Traversal<'a> {
+    /// Walks a fragment.
+    pub fn new(fragment: &'a Fragment) -> Self {
+        let mut traversal = Self { nodes: Vec::new() };
+        traversal.walk(&fragment.nodes, None, 0);
+        traversal
+    }
+
+    /// Returns the visited nodes in source order.
+    pub fn nodes(&self) -> &[VisitedNode<'a>] {
+        &self.nodes
+    }
+
+    /// Returns the enclosing nodes of the node at `index`, innermost first.
+    pub fn ancestors(&self, index: usize) -> impl Iterator<Item = &VisitedNode<'a>> {
+        std::iter::successors(self.nodes[index].parent, |&parent| {
+            self.nodes[parent].parent
+        })
+        .map(|parent| &self.nodes[parent])
+    }
+
+    fn walk(&mut self, nodes: &'a [TemplateNode], parent: Option<usize>, depth: usize) {
+        for node in nodes {
+            let index = self.nodes.len();
+            self.nodes.push(VisitedNode {
+                node,
+                parent,
+                depth,
+            });
+            for children in child_lists(node) {
+                self.walk(children, Some(index), depth + 1);
+            }
+        }
+    }
+}
+
+/// Returns the child node lists of a node, in source order.
+pub(crate) fn child_lists(node: &TemplateNode) -> Vec<&[TemplateNode]> {
+    match node {
+        TemplateNode::Element(el) => vec![&el.children],
+        TemplateNode::Component(comp) => vec![&comp.children],
+        TemplateNode::SvelteElement(el) => vec![&el.children],
+        TemplateNode::IfBlock(block) => {
+            let mut lists = vec![block.consequent.nodes.as_slice()];
+            let mut alternate = &block.alternate;
+            while let Some(branch) = alternate {
+                match branch {
+                    ElseBranch::Else(fragment) => {
+                        lists.push(&fragment.nodes);
+                        break;
+                    }
+                    ElseBranch::ElseIf(nested) => {
+                        lists.push(&nested.consequent.nodes);
+                        alternate = &nested.alternate;
+                    }
+                }
+            }
+            lists
+        }
+        TemplateNode::EachBlock(block) => {
+            let mut lists = vec![block.body.nodes.as_slice()];
+            if let Some(fallback) = &block.fallback {
+                lists.push(&fallback.nodes);
+            }
+            lists
+        }
+        TemplateNode::AwaitBlock(block) => [
+            block.pending.as_ref(),
+            block.then.as_ref().map(|then| &then.body),
+            block.catch.as_ref().map(|catch| &catch.body),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|fragment| fragment.nodes.as_slice())
+        .collect(),
+        TemplateNode::KeyBlock(block) => vec![&block.body.nodes],
+        TemplateNode::SnippetBlock(block) => vec![&block.body.nodes],
+        TemplateNode::Text(_)
+        | TemplateNode::Comment(_)
+        | TemplateNode::Expression(_)
+        | TemplateNode::HtmlTag(_)
+        | TemplateNode::ConstTag(_)
+        | TemplateNode::DeclarationTag(_)
+        | TemplateNode::DebugTag(_)
+        | TemplateNode::RenderTag(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use svelte_parser::parse;
+
+    fn names(traversal: &Traversal<'_>) -> Vec<(String, usize)> {
+        traversal
+            .nodes()
+            .iter()
+            .filter_map(|visited| match visited.node {
+                TemplateNode::Element(el) => Some((el.name.to_string(), visited.depth)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_visits_every_branch_in_order() {
+        let doc = parse(
+            "<a><b></b></a>{#if x}<c></c>{:else if y}<d></d>{:else}<e></e>{/if}\
+             {#each xs as x}<f></f>{:else}<g></g>{/each}{#snippet s()}<h></h>{/snippet}",
+        )
+        .document;
+        let traversal = Traversal::new(&doc.fragment);
+        assert_eq!(
+            names(&traversal),
+            vec![
+                ("a".to_string(), 0),
+                ("b".to_string(), 1),
+                ("c".to_string(), 1),
+                ("d".to_string(), 1),
+                ("e".to_string(), 1),
+                ("f".to_string(), 1),
+                ("g".to_string(), 1),
+                ("h".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ancestors_innermost_first() {
+        let doc = parse("<div><Card><span></span></Card></div>").document;
+        let traversal = Traversal::new(&doc.fragment);
+        let span = traversal
+            .nodes()
+            .iter()
+            .position(
+                |visited| matches!(visited.node, TemplateNode::Element(el) if el.name == "span"),
+            )
+            .unwrap();
+        let kinds: Vec<_> = traversal
+            .ancestors(span)
+            .map(|visited| match visited.node {
+                TemplateNode::Element(el) => el.name.to_string(),
+                TemplateNode::Component(comp) => comp.name.to_string(),
+                _ => String::new(),
+            })
+            .collect();
+        assert_eq!(kinds, vec!["Card", "div"]);
+    }
+}
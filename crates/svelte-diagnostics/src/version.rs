@@ -0,0 +1,168 @@
+//! Svelte versions and the features that depend on them.
+//!
+//! Checks assume the newest Svelte unless [`DiagnosticOptions`] carries the
+//! installed version, in which case syntax and runes added in later releases
+//! are reported by the `unavailable-feature` rule.
+//!
+//! [`DiagnosticOptions`]: crate::DiagnosticOptions
+
+/// A Svelte release version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SvelteVersion {
+    /// The major version.
+    pub major: u32,
+    /// The minor version.
+    pub minor: u32,
+    /// The patch version.
+    pub patch: u32,
+}
+
+impl SvelteVersion {
+    /// The oldest Svelte release svelte-check-rs supports.
+    pub const MIN_SUPPORTED: SvelteVersion = SvelteVersion::new(5, 0, 0);
+
+    /// Creates a version.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a `package.json` version such as `5.36.2`. Pre-release and
+    /// build suffixes (`5.0.0-next.1`) are ignored; missing parts count as 0.
+    pub fn parse(version: &str) -> Option<Self> {
+        let core = version
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// Returns whether this version has `feature`.
+    pub fn supports(self, feature: SvelteFeature) -> bool {
+        self >= feature.introduced_in()
+    }
+}
+
+impl std::fmt::Display for SvelteVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Syntax, runes and options added after Svelte 5.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvelteFeature {
+    /// `$inspect.trace()`
+    InspectTrace,
+    /// `$props.id()`
+    PropsId,
+    /// `{@attach ...}` attributes
+    AttachTag,
+    /// `compilerOptions.experimental.async`
+    ExperimentalAsync,
+    /// `$effect.pending()`
+    EffectPending,
+    /// `$state.eager()`
+    StateEager,
+    /// `{const x = ...}` / `{let x = ...}` declaration tags
+    DeclarationTag,
+}
+
+impl SvelteFeature {
+    /// All features, oldest first.
+    pub const ALL: &'static [SvelteFeature] = &[
+        SvelteFeature::InspectTrace,
+        SvelteFeature::PropsId,
+        SvelteFeature::AttachTag,
+        SvelteFeature::ExperimentalAsync,
+        SvelteFeature::EffectPending,
+        SvelteFeature::StateEager,
+        SvelteFeature::DeclarationTag,
+    ];
+
+    /// Returns the first release with the feature.
+    pub fn introduced_in(self) -> SvelteVersion {
+        match self {
+            SvelteFeature::InspectTrace => SvelteVersion::new(5, 14, 0),
+            SvelteFeature::PropsId => SvelteVersion::new(5, 20, 0),
+            SvelteFeature::AttachTag => SvelteVersion::new(5, 29, 0),
+            SvelteFeature::ExperimentalAsync | SvelteFeature::EffectPending => {
+                SvelteVersion::new(5, 36, 0)
+            }
+            SvelteFeature::StateEager => SvelteVersion::new(5, 41, 0),
+            SvelteFeature::DeclarationTag => SvelteVersion::new(5, 46, 0),
+        }
+    }
+
+    /// Returns how the feature is written, for messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            SvelteFeature::InspectTrace => "$inspect.trace",
+            SvelteFeature::PropsId => "$props.id",
+            SvelteFeature::AttachTag => "{@attach}",
+            SvelteFeature::ExperimentalAsync => "experimental.async",
+            SvelteFeature::EffectPending => "$effect.pending",
+            SvelteFeature::StateEager => "$state.eager",
+            SvelteFeature::DeclarationTag => "Declaration tags",
+        }
+    }
+
+    /// Returns the feature behind a rune such as `$effect.pending`, if the
+    /// rune is not available in every Svelte 5 release.
+    pub fn for_rune(rune: &str) -> Option<Self> {
+        match rune {
+            "$inspect.trace" => Some(SvelteFeature::InspectTrace),
+            "$props.id" => Some(SvelteFeature::PropsId),
+            "$effect.pending" => Some(SvelteFeature::EffectPending),
+            "$state.eager" => Some(SvelteFeature::StateEager),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(
+            SvelteVersion::parse("5.36.2"),
+            Some(SvelteVersion::new(5, 36, 2))
+        );
+        assert_eq!(
+            SvelteVersion::parse("5.0.0-next.260"),
+            Some(SvelteVersion::new(5, 0, 0))
+        );
+        assert_eq!(
+            SvelteVersion::parse("4.2"),
+            Some(SvelteVersion::new(4, 2, 0))
+        );
+        assert_eq!(SvelteVersion::parse("latest"), None);
+    }
+
+    #[test]
+    fn test_supports_compares_against_first_release() {
+        let version = SvelteVersion::new(5, 36, 0);
+        assert!(version.supports(SvelteFeature::EffectPending));
+        assert!(!version.supports(SvelteFeature::StateEager));
+        assert!(!SvelteVersion::new(5, 28, 9).supports(SvelteFeature::AttachTag));
+    }
+
+    #[test]
+    fn test_features_are_listed_oldest_first() {
+        let versions: Vec<_> = SvelteFeature::ALL
+            .iter()
+            .map(|feature| feature.introduced_in())
+            .collect();
+        assert!(versions.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::PathBuf;
+use svelte_diagnostics::{project, registry, RuleMeta, Severity};
 
 fn compiler_codes_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -9,12 +10,6 @@ fn compiler_codes_path() -> PathBuf {
         .join("compiler-warning-codes.txt")
 }
 
-fn diagnostics_rs_path() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("src")
-        .join("diagnostic.rs")
-}
-
 fn coverage_doc_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .parent()
@@ -40,56 +35,44 @@ fn normalize_code(code: &str) -> String {
     code.replace('-', "_")
 }
 
-fn load_internal_codes() -> Vec<String> {
-    let content =
-        fs::read_to_string(diagnostics_rs_path()).expect("Failed to read diagnostics source file");
-    let mut in_as_str = false;
-    let mut brace_depth: i32 = 0;
-    let mut codes = Vec::new();
-
-    for line in content.lines() {
-        if !in_as_str {
-            if line.contains("fn as_str") {
-                in_as_str = true;
-                brace_depth += count_braces(line);
-            }
-            continue;
-        }
-
-        if let Some(code) = extract_code_from_line(line) {
-            codes.push(code);
-        }
-
-        brace_depth += count_braces(line);
-        if in_as_str && brace_depth == 0 {
-            break;
-        }
-    }
-
-    codes
+/// Returns the metadata of the per-file and project rules.
+fn rule_metas() -> impl Iterator<Item = &'static RuleMeta> {
+    registry()
+        .rules()
+        .map(|rule| rule.meta())
+        .chain(project::rules().map(|rule| rule.meta()))
 }
 
-fn count_braces(line: &str) -> i32 {
-    let mut count = 0;
-    for ch in line.chars() {
-        match ch {
-            '{' => count += 1,
-            '}' => count -= 1,
-            _ => {}
-        }
-    }
-    count
+fn load_internal_codes() -> Vec<String> {
+    rule_metas()
+        .map(|meta| meta.code.as_str().to_string())
+        .collect()
 }
 
-fn extract_code_from_line(line: &str) -> Option<String> {
-    if !line.contains("DiagnosticCode::") || !line.contains("=>") {
-        return None;
+fn render_rules_table() -> String {
+    let mut out = String::new();
+    out.push_str("## Rules\n");
+    out.push_str("| Code | Category | Default severity | Fixable |\n");
+    out.push_str("| --- | --- | --- | --- |\n");
+    for meta in rule_metas() {
+        let code = match meta.docs_url {
+            Some(url) => format!("[{}]({})", meta.code, url),
+            None => meta.code.to_string(),
+        };
+        let severity = match meta.default_severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Hint => "hint",
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            code,
+            meta.category,
+            severity,
+            if meta.fixable { "yes" } else { "no" }
+        ));
     }
-
-    let quote_start = line.find('"')?;
-    let rest = &line[quote_start + 1..];
-    let quote_end = rest.find('"')?;
-    Some(rest[..quote_end].to_string())
+    out
 }
 
 fn render_section(title: &str, items: &[String]) -> String {
@@ -161,6 +144,8 @@ fn render_coverage_report(compiler_codes: &BTreeSet<String>, internal_codes: &[S
     out.push_str(&render_section("Compiler-only", &compiler_only));
     out.push('\n');
     out.push_str(&render_section("Internal-only", &internal_only));
+    out.push('\n');
+    out.push_str(&render_rules_table());
 
     out
 }
@@ -189,10 +174,20 @@ fn compiler_warning_coverage_is_up_to_date() {
     let end_marker = "<!-- COVERAGE:END -->";
     let actual = extract_between_markers(&doc, start_marker, end_marker)
         .expect("Coverage markers not found in diagnostics-coverage.md");
+    let expected = normalize_line_endings(expected.trim());
+
+    if std::env::var_os("UPDATE_DIAGNOSTICS_COVERAGE").is_some() {
+        let doc = normalize_line_endings(&doc);
+        let start = doc.find(start_marker).unwrap() + start_marker.len();
+        let end = doc.find(end_marker).unwrap();
+        let updated = format!("{}\n{}\n{}", &doc[..start], expected, &doc[end..]);
+        fs::write(coverage_doc_path(), updated).expect("Failed to write diagnostics coverage doc");
+        return;
+    }
 
     assert_eq!(
-        actual,
-        normalize_line_endings(expected.trim()),
-        "Diagnostics coverage report is out of date. Update docs/diagnostics-coverage.md."
+        actual, expected,
+        "Diagnostics coverage report is out of date. Rerun this test with \
+         UPDATE_DIAGNOSTICS_COVERAGE=1 to regenerate docs/diagnostics-coverage.md."
     );
 }
@@ -70,6 +70,9 @@ pub struct Style {
     pub global: bool,
     /// Attributes on the style tag.
     pub attributes: Vec<Attribute>,
+    /// The parsed content; `None` when `lang` names a preprocessor language
+    /// (e.g. `scss`), which the CSS parser does not understand.
+    pub stylesheet: Option<crate::css::StyleSheet>,
 }
 
 /// A template fragment containing child nodes.
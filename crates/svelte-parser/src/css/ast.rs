@@ -0,0 +1,289 @@
+//! AST types for `<style>` content.
+//!
+//! The shapes follow the Svelte compiler's CSS AST closely enough that checks
+//! ported from it (unused selectors, `:global` validation) map one to one.
+//! All spans are in document coordinates.
+
+use smol_str::SmolStr;
+use source_map::Span;
+
+/// A parsed `<style>` block.
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheet {
+    /// The span of the style content.
+    pub span: Span,
+    /// Top-level rules and at-rules.
+    pub children: Vec<CssNode>,
+}
+
+/// An entry in a stylesheet or block.
+#[derive(Debug, Clone)]
+pub enum CssNode {
+    /// A style rule (`selectors { ... }`).
+    Rule(CssRule),
+    /// An at-rule (`@media ... { ... }`, `@import ...;`).
+    Atrule(CssAtrule),
+    /// A declaration (`color: red`).
+    Declaration(CssDeclaration),
+}
+
+impl CssNode {
+    /// Returns the span of this node.
+    pub fn span(&self) -> Span {
+        match self {
+            CssNode::Rule(rule) => rule.span,
+            CssNode::Atrule(atrule) => atrule.span,
+            CssNode::Declaration(declaration) => declaration.span,
+        }
+    }
+}
+
+/// A style rule.
+#[derive(Debug, Clone)]
+pub struct CssRule {
+    /// The span of the whole rule, selectors through `}`.
+    pub span: Span,
+    /// The selector list.
+    pub prelude: SelectorList,
+    /// The rule body.
+    pub block: CssBlock,
+}
+
+impl CssRule {
+    /// Returns true for a `:global { ... }` block, whose contents are all
+    /// global.
+    pub fn is_global_block(&self) -> bool {
+        self.prelude.selectors.iter().any(|complex| {
+            complex.children.last().is_some_and(|relative| {
+                relative.selectors.last().is_some_and(|simple| {
+                    matches!(simple, SimpleSelector::PseudoClass(p) if p.name == "global" && p.args.is_none())
+                })
+            })
+        })
+    }
+}
+
+/// A `{ ... }` body.
+#[derive(Debug, Clone, Default)]
+pub struct CssBlock {
+    /// The span from `{` to `}`.
+    pub span: Span,
+    /// Declarations, nested rules and at-rules.
+    pub children: Vec<CssNode>,
+}
+
+/// An at-rule.
+#[derive(Debug, Clone)]
+pub struct CssAtrule {
+    /// The span of the whole at-rule.
+    pub span: Span,
+    /// The name without the `@` (e.g. `media`).
+    pub name: SmolStr,
+    /// The raw prelude (e.g. `(min-width: 600px)`), trimmed.
+    pub prelude: String,
+    /// The span of the prelude.
+    pub prelude_span: Span,
+    /// The body, if the at-rule has one.
+    pub block: Option<CssBlock>,
+}
+
+impl CssAtrule {
+    /// Returns true for `@keyframes` and its vendor-prefixed forms.
+    pub fn is_keyframes(&self) -> bool {
+        self.name.ends_with("keyframes")
+    }
+}
+
+/// A declaration.
+#[derive(Debug, Clone)]
+pub struct CssDeclaration {
+    /// The span from the property through the value.
+    pub span: Span,
+    /// The property name (e.g. `color`, `--accent`).
+    pub property: SmolStr,
+    /// The span of the property name.
+    pub property_span: Span,
+    /// The value, trimmed, without `!important`.
+    pub value: String,
+    /// The span of the value.
+    pub value_span: Span,
+    /// Whether the value ends in `!important`.
+    pub important: bool,
+}
+
+/// A comma-separated list of selectors.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorList {
+    /// The span of the list.
+    pub span: Span,
+    /// The selectors.
+    pub selectors: Vec<ComplexSelector>,
+}
+
+/// A selector such as `div > .a:hover`.
+#[derive(Debug, Clone)]
+pub struct ComplexSelector {
+    /// The span of the selector.
+    pub span: Span,
+    /// The compound selectors with the combinators before them.
+    pub children: Vec<RelativeSelector>,
+}
+
+impl ComplexSelector {
+    /// Returns true if the selector is wrapped in `:global(...)` as a whole,
+    /// or starts with a bare `:global` modifier.
+    pub fn is_global(&self) -> bool {
+        self.children.first().is_some_and(|relative| {
+            relative.selectors.first().is_some_and(
+                |simple| matches!(simple, SimpleSelector::PseudoClass(p) if p.name == "global"),
+            )
+        })
+    }
+}
+
+/// A compound selector (`a.b:hover`) and the combinator that precedes it.
+#[derive(Debug, Clone)]
+pub struct RelativeSelector {
+    /// The span of the compound selector, including its combinator.
+    pub span: Span,
+    /// The combinator; `None` for the first compound of a selector.
+    pub combinator: Option<Combinator>,
+    /// The simple selectors.
+    pub selectors: Vec<SimpleSelector>,
+}
+
+/// A combinator between compound selectors.
+#[derive(Debug, Clone, Copy)]
+pub struct Combinator {
+    /// The span of the combinator; empty-width runs of whitespace for the
+    /// descendant combinator.
+    pub span: Span,
+    /// The kind of combinator.
+    pub kind: CombinatorKind,
+}
+
+/// The kind of a [`Combinator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinatorKind {
+    /// Whitespace.
+    Descendant,
+    /// `>`
+    Child,
+    /// `+`
+    NextSibling,
+    /// `~`
+    SubsequentSibling,
+    /// `||`
+    Column,
+}
+
+/// A simple selector.
+#[derive(Debug, Clone)]
+pub enum SimpleSelector {
+    /// `div`, `svelte-foo`, or `from`/`to` in keyframes.
+    Type {
+        /// The span.
+        span: Span,
+        /// The element name.
+        name: SmolStr,
+    },
+    /// `*`
+    Universal {
+        /// The span.
+        span: Span,
+    },
+    /// `.name`
+    Class {
+        /// The span, including the dot.
+        span: Span,
+        /// The class name.
+        name: SmolStr,
+    },
+    /// `#name`
+    Id {
+        /// The span, including the hash.
+        span: Span,
+        /// The id.
+        name: SmolStr,
+    },
+    /// `[name]`, `[name="value" i]`
+    Attribute(AttributeSelector),
+    /// `:name` or `:name(...)`
+    PseudoClass(PseudoClassSelector),
+    /// `::name`
+    PseudoElement {
+        /// The span, including the colons.
+        span: Span,
+        /// The pseudo-element name.
+        name: SmolStr,
+    },
+    /// `&`
+    Nesting {
+        /// The span.
+        span: Span,
+    },
+    /// A keyframe selector such as `50%`.
+    Percentage {
+        /// The span.
+        span: Span,
+        /// The percentage, including `%`.
+        value: SmolStr,
+    },
+}
+
+impl SimpleSelector {
+    /// Returns the span of this selector.
+    pub fn span(&self) -> Span {
+        match self {
+            SimpleSelector::Type { span, .. }
+            | SimpleSelector::Universal { span }
+            | SimpleSelector::Class { span, .. }
+            | SimpleSelector::Id { span, .. }
+            | SimpleSelector::PseudoElement { span, .. }
+            | SimpleSelector::Nesting { span }
+            | SimpleSelector::Percentage { span, .. } => *span,
+            SimpleSelector::Attribute(attribute) => attribute.span,
+            SimpleSelector::PseudoClass(pseudo) => pseudo.span,
+        }
+    }
+}
+
+/// An attribute selector.
+#[derive(Debug, Clone)]
+pub struct AttributeSelector {
+    /// The span, including the brackets.
+    pub span: Span,
+    /// The attribute name.
+    pub name: SmolStr,
+    /// The matcher (`=`, `~=`, `^=`, ...), if any.
+    pub matcher: Option<SmolStr>,
+    /// The value, without quotes.
+    pub value: Option<String>,
+    /// The `i`/`s` flag, if any.
+    pub flags: Option<SmolStr>,
+}
+
+/// A pseudo-class selector.
+#[derive(Debug, Clone)]
+pub struct PseudoClassSelector {
+    /// The span, including the colon and any arguments.
+    pub span: Span,
+    /// The pseudo-class name (e.g. `hover`, `global`, `not`).
+    pub name: SmolStr,
+    /// The arguments, if the pseudo-class has parentheses.
+    pub args: Option<PseudoClassArgs>,
+}
+
+/// The arguments of a pseudo-class.
+#[derive(Debug, Clone)]
+pub enum PseudoClassArgs {
+    /// A selector list, for `:global`, `:is`, `:where`, `:not` and `:has`.
+    Selectors(SelectorList),
+    /// Anything else, kept raw (e.g. `2n + 1` for `:nth-child`).
+    Raw {
+        /// The span of the raw text.
+        span: Span,
+        /// The raw text, trimmed.
+        value: String,
+    },
+}
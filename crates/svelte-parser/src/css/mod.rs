@@ -0,0 +1,178 @@
+//! CSS AST and parser for `<style>` blocks.
+//!
+//! The parser is deliberately forgiving: it understands the structure that
+//! Svelte's scoping cares about (selectors, `:global`, nesting, at-rules and
+//! declarations) and keeps values and at-rule preludes as raw text. Spans are
+//! in document coordinates, so they can be reported without remapping.
+//!
+//! # Example
+//!
+//! ```
+//! use svelte_parser::css::{parse_stylesheet, CssNode};
+//! use text_size::TextSize;
+//!
+//! let (sheet, errors) = parse_stylesheet("p :global(.x) { color: red; }", TextSize::from(0));
+//! assert!(errors.is_empty());
+//! assert!(matches!(sheet.children[0], CssNode::Rule(_)));
+//! ```
+
+mod ast;
+mod parser;
+
+pub use ast::*;
+
+use crate::error::ParseError;
+use text_size::TextSize;
+
+/// Parses the content of a `<style>` block that starts at `offset` in the
+/// document.
+pub fn parse_stylesheet(source: &str, offset: TextSize) -> (StyleSheet, Vec<ParseError>) {
+    parser::CssParser::new(source, offset).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> StyleSheet {
+        let (sheet, errors) = parse_stylesheet(source, TextSize::from(0));
+        assert!(errors.is_empty(), "{errors:?}");
+        sheet
+    }
+
+    fn rule(node: &CssNode) -> &CssRule {
+        match node {
+            CssNode::Rule(rule) => rule,
+            other => panic!("expected a rule, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_spans_are_document_coordinates() {
+        let (sheet, _) = parse_stylesheet(".a { color: red }", TextSize::from(10));
+        let rule = rule(&sheet.children[0]);
+        assert_eq!(u32::from(rule.span.start), 10);
+        let CssNode::Declaration(decl) = &rule.block.children[0] else {
+            panic!("expected a declaration");
+        };
+        assert_eq!(decl.property, "color");
+        assert_eq!(u32::from(decl.value_span.start), 22);
+        assert_eq!(u32::from(decl.value_span.end), 25);
+    }
+
+    #[test]
+    fn test_selector_structure() {
+        let sheet = parse("div > p.a + .b ~ span:hover::before, #x {}");
+        let rule = rule(&sheet.children[0]);
+        assert_eq!(rule.prelude.selectors.len(), 2);
+        let kinds: Vec<_> = rule.prelude.selectors[0]
+            .children
+            .iter()
+            .map(|r| r.combinator.map(|c| c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                None,
+                Some(CombinatorKind::Child),
+                Some(CombinatorKind::NextSibling),
+                Some(CombinatorKind::SubsequentSibling),
+            ]
+        );
+        let last = rule.prelude.selectors[0].children.last().unwrap();
+        assert!(matches!(
+            last.selectors.as_slice(),
+            [
+                SimpleSelector::Type { .. },
+                SimpleSelector::PseudoClass(_),
+                SimpleSelector::PseudoElement { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_global_selectors_and_blocks() {
+        let sheet = parse(":global(.x) .y {} :global { .z { a: b } } .a :global(p) {}");
+        let global = rule(&sheet.children[0]);
+        assert!(global.prelude.selectors[0].is_global());
+        let SimpleSelector::PseudoClass(pseudo) =
+            &global.prelude.selectors[0].children[0].selectors[0]
+        else {
+            panic!("expected :global(...)");
+        };
+        assert!(matches!(pseudo.args, Some(PseudoClassArgs::Selectors(_))));
+
+        let block = rule(&sheet.children[1]);
+        assert!(block.is_global_block());
+        assert!(matches!(block.block.children[0], CssNode::Rule(_)));
+
+        assert!(!rule(&sheet.children[2]).prelude.selectors[0].is_global());
+    }
+
+    #[test]
+    fn test_nesting_and_at_rules() {
+        let sheet = parse(
+            "@import 'a.css'; @media (min-width: 1px) { .a { color: red; &:hover { color: blue } } } @keyframes k { from { opacity: 0 } 50% { opacity: 1 } }",
+        );
+        let CssNode::Atrule(import) = &sheet.children[0] else {
+            panic!("expected @import");
+        };
+        assert_eq!(import.name, "import");
+        assert!(import.block.is_none());
+
+        let CssNode::Atrule(media) = &sheet.children[1] else {
+            panic!("expected @media");
+        };
+        assert_eq!(media.prelude, "(min-width: 1px)");
+        let inner = rule(&media.block.as_ref().unwrap().children[0]);
+        assert!(matches!(inner.block.children[0], CssNode::Declaration(_)));
+        let nested = rule(&inner.block.children[1]);
+        assert!(matches!(
+            nested.prelude.selectors[0].children[0].selectors[0],
+            SimpleSelector::Nesting { .. }
+        ));
+
+        let CssNode::Atrule(keyframes) = &sheet.children[2] else {
+            panic!("expected @keyframes");
+        };
+        assert!(keyframes.is_keyframes());
+        let step = rule(&keyframes.block.as_ref().unwrap().children[1]);
+        assert!(matches!(
+            step.prelude.selectors[0].children[0].selectors[0],
+            SimpleSelector::Percentage { .. }
+        ));
+    }
+
+    #[test]
+    fn test_important_and_strings() {
+        let sheet = parse(".a { content: \"a;}\"; color: red ! important }");
+        let rule = rule(&sheet.children[0]);
+        let decls: Vec<_> = rule
+            .block
+            .children
+            .iter()
+            .filter_map(|n| match n {
+                CssNode::Declaration(d) => Some((d.value.as_str(), d.important)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(decls, [("\"a;}\"", false), ("red", true)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let codes = |source: &str| {
+            parse_stylesheet(source, TextSize::from(0))
+                .1
+                .iter()
+                .map(|e| e.code().as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(". {}"), ["css_expected_identifier"]);
+        assert_eq!(codes(".a { color: ; }"), ["css_empty_declaration"]);
+        assert_eq!(codes(".a { color: red"), ["unexpected_eof"]);
+        assert_eq!(codes("div > {}"), ["css_selector_invalid"]);
+        assert_eq!(codes(".a { color red }"), ["css_expected_identifier"]);
+        assert!(codes(".a { --empty:; }").is_empty());
+    }
+}
@@ -0,0 +1,694 @@
+//! Parser for `<style>` content.
+
+use super::ast::*;
+use crate::error::{ParseError, ParseErrorCode, ParseErrorKind};
+use smol_str::SmolStr;
+use source_map::Span;
+use text_size::TextSize;
+
+/// Pseudo-classes whose arguments are selector lists.
+const SELECTOR_PSEUDO_CLASSES: &[&str] = &["global", "is", "where", "not", "has"];
+
+/// Recursive descent parser over the text of a `<style>` block.
+pub(super) struct CssParser<'src> {
+    /// The style content.
+    source: &'src str,
+    /// Current byte offset into `source`.
+    pos: usize,
+    /// Document offset of `source[0]`.
+    base: u32,
+    /// Errors collected during parsing.
+    errors: Vec<ParseError>,
+}
+
+impl<'src> CssParser<'src> {
+    /// Creates a parser for `source`, which starts at `offset` in the
+    /// document.
+    pub(super) fn new(source: &'src str, offset: TextSize) -> Self {
+        Self {
+            source,
+            pos: 0,
+            base: u32::from(offset),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Parses the whole stylesheet.
+    pub(super) fn parse(mut self) -> (StyleSheet, Vec<ParseError>) {
+        let children = self.parse_block_contents(true, false);
+        let stylesheet = StyleSheet {
+            span: self.span(0, self.source.len()),
+            children,
+        };
+        (stylesheet, self.errors)
+    }
+
+    // === Helpers ===
+
+    /// Converts a range of `source` into a document span.
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(
+            TextSize::from(self.base + start as u32),
+            TextSize::from(self.base + end as u32),
+        )
+    }
+
+    fn rest(&self) -> &'src str {
+        &self.source[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn error(
+        &mut self,
+        code: ParseErrorCode,
+        message: impl Into<String>,
+        start: usize,
+        end: usize,
+    ) {
+        self.errors.push(
+            ParseError::new(
+                ParseErrorKind::SyntaxError {
+                    message: message.into(),
+                },
+                self.span(start, end),
+            )
+            .with_code(code),
+        );
+    }
+
+    /// Skips whitespace, comments and the legacy `<!--`/`-->` markers.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if self.rest().starts_with("/*") {
+                let start = self.pos;
+                match self.rest()[2..].find("*/") {
+                    Some(i) => self.pos += i + 4,
+                    None => {
+                        self.pos = self.source.len();
+                        self.error(
+                            ParseErrorCode::UnexpectedEof,
+                            "unterminated comment",
+                            start,
+                            start + 2,
+                        );
+                    }
+                }
+            } else if self.rest().starts_with("<!--") {
+                self.pos += 4;
+            } else if self.rest().starts_with("-->") {
+                self.pos += 3;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Skips whitespace and comments up to `end`, returning true if any were
+    /// skipped.
+    fn skip_trivia_until(&mut self, end: usize) -> bool {
+        let start = self.pos;
+        loop {
+            while self.pos < end && self.peek().is_some_and(char::is_whitespace) {
+                self.bump();
+            }
+            if self.pos < end && self.rest().starts_with("/*") {
+                self.pos = self.rest()[2..]
+                    .find("*/")
+                    .map_or(end, |i| (self.pos + i + 4).min(end));
+            } else {
+                break;
+            }
+        }
+        self.pos > start
+    }
+
+    /// Scans forward from the current position to the first of `stops` at
+    /// bracket depth zero, skipping strings and comments. Returns its offset,
+    /// or the end of the source.
+    fn scan_to(&self, stops: &[char]) -> usize {
+        let bytes = self.source.as_bytes();
+        let mut depth = 0usize;
+        let mut i = self.pos;
+        while i < bytes.len() {
+            let c = bytes[i] as char;
+            match c {
+                '"' | '\'' => {
+                    i += 1;
+                    while i < bytes.len() && bytes[i] as char != c {
+                        if bytes[i] == b'\\' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                '/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = self.source[i + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |j| i + 2 + j + 1);
+                }
+                '\\' => i += 1,
+                _ if depth == 0 && stops.contains(&c) => return i,
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            i += 1;
+        }
+        bytes.len()
+    }
+
+    /// Reads an identifier (letters, digits, `-`, `_`, non-ASCII and escapes).
+    fn read_ident(&mut self, end: usize) -> Option<&'src str> {
+        let start = self.pos;
+        while self.pos < end {
+            match self.peek() {
+                Some('\\') => {
+                    self.bump();
+                    self.bump();
+                }
+                Some(c) if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() => {
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        let ident = &self.source[start..self.pos.min(end)];
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    // === Rules ===
+
+    /// Parses rules, at-rules and (inside blocks) declarations until `}` or
+    /// the end of input.
+    fn parse_block_contents(&mut self, top_level: bool, in_keyframes: bool) -> Vec<CssNode> {
+        let mut children = Vec::new();
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => break,
+                Some('}') if top_level => {
+                    self.error(
+                        ParseErrorCode::CssSelectorInvalid,
+                        "unexpected `}`",
+                        self.pos,
+                        self.pos + 1,
+                    );
+                    self.bump();
+                }
+                Some('}') => break,
+                Some(';') => self.bump(),
+                Some('@') => children.push(CssNode::Atrule(self.parse_atrule())),
+                Some(_) => {
+                    let stop = self.scan_to(&['{', ';', '}']);
+                    let is_rule = self.source.as_bytes().get(stop) == Some(&b'{');
+                    if is_rule || top_level {
+                        if let Some(rule) = self.parse_rule(in_keyframes) {
+                            children.push(CssNode::Rule(rule));
+                        }
+                    } else if let Some(declaration) = self.parse_declaration() {
+                        children.push(CssNode::Declaration(declaration));
+                    }
+                }
+            }
+        }
+        children
+    }
+
+    /// Parses a style rule.
+    fn parse_rule(&mut self, in_keyframes: bool) -> Option<CssRule> {
+        let start = self.pos;
+        let prelude_end = self.scan_to(&['{', ';', '}']);
+        if self.source.as_bytes().get(prelude_end) != Some(&b'{') {
+            self.error(
+                ParseErrorCode::ExpectedToken,
+                "expected `{` after selector",
+                start,
+                prelude_end,
+            );
+            self.pos = prelude_end;
+            if self.peek() == Some(';') {
+                self.bump();
+            }
+            return None;
+        }
+
+        let prelude = self.parse_selector_list(prelude_end, in_keyframes);
+        self.pos = prelude_end;
+        let block = self.parse_block(false);
+        Some(CssRule {
+            span: self.span(start, self.pos),
+            prelude,
+            block,
+        })
+    }
+
+    /// Parses a `{ ... }` body. The current character must be `{`.
+    fn parse_block(&mut self, in_keyframes: bool) -> CssBlock {
+        let start = self.pos;
+        self.bump();
+        let children = self.parse_block_contents(false, in_keyframes);
+        if self.peek() == Some('}') {
+            self.bump();
+        } else {
+            self.error(
+                ParseErrorCode::UnexpectedEof,
+                "expected `}` to close the block",
+                start,
+                start + 1,
+            );
+        }
+        CssBlock {
+            span: self.span(start, self.pos),
+            children,
+        }
+    }
+
+    /// Parses an at-rule. The current character must be `@`.
+    fn parse_atrule(&mut self) -> CssAtrule {
+        let start = self.pos;
+        self.bump();
+        let name = self.read_ident(self.source.len()).unwrap_or_default();
+        if name.is_empty() {
+            self.error(
+                ParseErrorCode::CssExpectedIdentifier,
+                "expected an at-rule name after `@`",
+                start,
+                start + 1,
+            );
+        }
+
+        let prelude_start = self.pos;
+        let prelude_end = self.scan_to(&['{', ';', '}']);
+        let raw = &self.source[prelude_start..prelude_end];
+        let leading = raw.len() - raw.trim_start().len();
+        let prelude = raw.trim();
+        let prelude_span = self.span(
+            prelude_start + leading,
+            prelude_start + leading + prelude.len(),
+        );
+        self.pos = prelude_end;
+
+        let block = match self.peek() {
+            Some('{') => Some(self.parse_block(name.ends_with("keyframes"))),
+            Some(';') => {
+                self.bump();
+                None
+            }
+            _ => None,
+        };
+
+        CssAtrule {
+            span: self.span(start, self.pos),
+            name: SmolStr::new(name),
+            prelude: prelude.to_string(),
+            prelude_span,
+            block,
+        }
+    }
+
+    /// Parses a declaration and its trailing `;`.
+    fn parse_declaration(&mut self) -> Option<CssDeclaration> {
+        let start = self.pos;
+        let end = self.scan_to(&[';', '}']);
+        let property = self.read_ident(end).unwrap_or_default();
+        let property_end = self.pos;
+        self.skip_trivia_until(end);
+
+        if property.is_empty() || self.peek() != Some(':') {
+            self.error(
+                ParseErrorCode::CssExpectedIdentifier,
+                "expected a declaration (`property: value`)",
+                start,
+                end,
+            );
+            self.pos = end;
+            if self.peek() == Some(';') {
+                self.bump();
+            }
+            return None;
+        }
+        self.bump();
+
+        let raw = &self.source[self.pos..end];
+        let leading = raw.len() - raw.trim_start().len();
+        let mut value = raw.trim();
+        let value_start = self.pos + leading;
+        let mut important = false;
+        if let Some(bang) = value.rfind('!') {
+            if value[bang + 1..].trim().eq_ignore_ascii_case("important") {
+                important = true;
+                value = value[..bang].trim_end();
+            }
+        }
+        let value_span = self.span(value_start, value_start + value.len());
+
+        if value.is_empty() && !property.starts_with("--") {
+            self.error(
+                ParseErrorCode::CssEmptyDeclaration,
+                "declaration cannot be empty",
+                start,
+                end,
+            );
+        }
+
+        self.pos = end;
+        let span_end = start + (self.source[start..end].trim_end().len());
+        if self.peek() == Some(';') {
+            self.bump();
+        }
+
+        Some(CssDeclaration {
+            span: self.span(start, span_end),
+            property: SmolStr::new(property),
+            property_span: self.span(start, property_end),
+            value: value.to_string(),
+            value_span,
+            important,
+        })
+    }
+
+    // === Selectors ===
+
+    /// Parses a selector list ending at `end`.
+    fn parse_selector_list(&mut self, end: usize, in_keyframes: bool) -> SelectorList {
+        self.skip_trivia_until(end);
+        let start = self.pos;
+        let mut selectors = Vec::new();
+        let mut list_end = start;
+        loop {
+            self.skip_trivia_until(end);
+            if let Some(selector) = self.parse_complex_selector(end, in_keyframes) {
+                list_end = self.pos;
+                selectors.push(selector);
+            }
+            self.skip_trivia_until(end);
+            if self.pos < end && self.peek() == Some(',') {
+                self.bump();
+                continue;
+            }
+            if self.pos < end {
+                self.error(
+                    ParseErrorCode::CssSelectorInvalid,
+                    "invalid selector",
+                    self.pos,
+                    end,
+                );
+                self.pos = end;
+            }
+            break;
+        }
+        SelectorList {
+            span: self.span(start, list_end),
+            selectors,
+        }
+    }
+
+    /// Parses a complex selector ending at `end` or a top-level `,`.
+    fn parse_complex_selector(
+        &mut self,
+        end: usize,
+        in_keyframes: bool,
+    ) -> Option<ComplexSelector> {
+        let start = self.pos;
+        let mut children: Vec<RelativeSelector> = Vec::new();
+
+        loop {
+            let relative_start = self.pos;
+            let had_space = self.skip_trivia_until(end);
+            let combinator_start = self.pos;
+            let kind = if self.rest().starts_with("||") {
+                self.pos += 2;
+                Some(CombinatorKind::Column)
+            } else {
+                let kind = match self.peek() {
+                    Some('>') => Some(CombinatorKind::Child),
+                    Some('+') => Some(CombinatorKind::NextSibling),
+                    Some('~') => Some(CombinatorKind::SubsequentSibling),
+                    _ => None,
+                };
+                if kind.is_some() {
+                    self.bump();
+                }
+                kind
+            };
+            let combinator = match kind {
+                Some(kind) => Some(Combinator {
+                    span: self.span(combinator_start, self.pos),
+                    kind,
+                }),
+                None if had_space && !children.is_empty() => Some(Combinator {
+                    span: self.span(relative_start, combinator_start),
+                    kind: CombinatorKind::Descendant,
+                }),
+                None => None,
+            };
+            self.skip_trivia_until(end);
+
+            if self.pos >= end || self.peek() == Some(',') {
+                if kind.is_some() {
+                    self.error(
+                        ParseErrorCode::CssSelectorInvalid,
+                        "expected a selector after the combinator",
+                        combinator_start,
+                        self.pos,
+                    );
+                }
+                break;
+            }
+
+            let compound_start = self.pos;
+            let error_count = self.errors.len();
+            let mut selectors = Vec::new();
+            while self.pos < end {
+                match self.parse_simple_selector(end, in_keyframes) {
+                    Some(selector) => selectors.push(selector),
+                    None => break,
+                }
+            }
+            if selectors.is_empty() {
+                let stop = self.scan_to(&[',']).min(end);
+                if self.errors.len() == error_count {
+                    self.error(
+                        ParseErrorCode::CssSelectorInvalid,
+                        "invalid selector",
+                        compound_start,
+                        stop.max(compound_start + 1).min(end),
+                    );
+                }
+                self.pos = stop;
+                break;
+            }
+            children.push(RelativeSelector {
+                span: self.span(
+                    if combinator.is_some() {
+                        combinator_start
+                    } else {
+                        compound_start
+                    },
+                    self.pos,
+                ),
+                combinator,
+                selectors,
+            });
+        }
+
+        (!children.is_empty()).then(|| ComplexSelector {
+            span: self.span(start, self.source[..self.pos].trim_end().len().max(start)),
+            children,
+        })
+    }
+
+    /// Parses one simple selector, or returns `None` at a combinator,
+    /// whitespace or anything that cannot start one.
+    fn parse_simple_selector(&mut self, end: usize, in_keyframes: bool) -> Option<SimpleSelector> {
+        let start = self.pos;
+        let c = self.peek()?;
+        match c {
+            '*' => {
+                self.bump();
+                Some(SimpleSelector::Universal {
+                    span: self.span(start, self.pos),
+                })
+            }
+            '&' => {
+                self.bump();
+                Some(SimpleSelector::Nesting {
+                    span: self.span(start, self.pos),
+                })
+            }
+            '.' | '#' => {
+                self.bump();
+                let Some(name) = self.read_ident(end) else {
+                    self.error(
+                        ParseErrorCode::CssExpectedIdentifier,
+                        format!("expected a name after `{c}`"),
+                        start,
+                        self.pos,
+                    );
+                    return None;
+                };
+                let span = self.span(start, self.pos);
+                let name = SmolStr::new(name);
+                Some(if c == '.' {
+                    SimpleSelector::Class { span, name }
+                } else {
+                    SimpleSelector::Id { span, name }
+                })
+            }
+            '[' => self.parse_attribute_selector(end),
+            ':' => self.parse_pseudo_selector(end),
+            c if in_keyframes && (c.is_ascii_digit() || c == '.') => {
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_digit() || c == '.' || c == '%')
+                {
+                    self.bump();
+                }
+                Some(SimpleSelector::Percentage {
+                    span: self.span(start, self.pos),
+                    value: SmolStr::new(&self.source[start..self.pos]),
+                })
+            }
+            c if c.is_alphabetic() || c == '-' || c == '_' || c == '\\' || !c.is_ascii() => {
+                let name = self.read_ident(end)?;
+                Some(SimpleSelector::Type {
+                    span: self.span(start, self.pos),
+                    name: SmolStr::new(name),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses `[name]`, `[name=value]` or `[name="value" i]`.
+    fn parse_attribute_selector(&mut self, end: usize) -> Option<SimpleSelector> {
+        let start = self.pos;
+        self.bump();
+        let close = self.scan_to(&[']']);
+        if close >= end {
+            self.error(
+                ParseErrorCode::ExpectedToken,
+                "expected `]` to close the attribute selector",
+                start,
+                end,
+            );
+            self.pos = end;
+            return None;
+        }
+        self.skip_trivia_until(close);
+        let Some(name) = self.read_ident(close) else {
+            self.error(
+                ParseErrorCode::CssExpectedIdentifier,
+                "expected an attribute name",
+                start,
+                close + 1,
+            );
+            self.pos = close + 1;
+            return None;
+        };
+        self.skip_trivia_until(close);
+
+        let mut matcher = None;
+        let mut value = None;
+        let mut flags = None;
+        let matcher_start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| matches!(c, '=' | '~' | '|' | '^' | '$' | '*'))
+            && self.pos < close
+        {
+            self.bump();
+        }
+        if self.pos > matcher_start {
+            matcher = Some(SmolStr::new(&self.source[matcher_start..self.pos]));
+            self.skip_trivia_until(close);
+            value = match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value_start = self.pos + 1;
+                    let value_end = self.source[value_start..close]
+                        .find(quote)
+                        .map_or(close, |i| value_start + i);
+                    self.pos = (value_end + 1).min(close);
+                    Some(self.source[value_start..value_end].to_string())
+                }
+                _ => self.read_ident(close).map(str::to_string),
+            };
+            self.skip_trivia_until(close);
+            flags = self.read_ident(close).map(SmolStr::new);
+        }
+        self.pos = close + 1;
+
+        Some(SimpleSelector::Attribute(AttributeSelector {
+            span: self.span(start, self.pos),
+            name: SmolStr::new(name),
+            matcher,
+            value,
+            flags,
+        }))
+    }
+
+    /// Parses `:name`, `:name(...)` or `::name`.
+    fn parse_pseudo_selector(&mut self, end: usize) -> Option<SimpleSelector> {
+        let start = self.pos;
+        self.bump();
+        let element = self.peek() == Some(':');
+        if element {
+            self.bump();
+        }
+        let Some(name) = self.read_ident(end) else {
+            self.error(
+                ParseErrorCode::CssExpectedIdentifier,
+                "expected a pseudo-class or pseudo-element name",
+                start,
+                self.pos,
+            );
+            return None;
+        };
+
+        let mut args = None;
+        if self.pos < end && self.peek() == Some('(') {
+            let open = self.pos;
+            self.bump();
+            let close = self.scan_to(&[')']);
+            if close >= end {
+                self.error(ParseErrorCode::ExpectedToken, "expected `)`", open, end);
+                self.pos = end;
+                return None;
+            }
+            if !element && SELECTOR_PSEUDO_CLASSES.contains(&name) {
+                args = Some(PseudoClassArgs::Selectors(
+                    self.parse_selector_list(close, false),
+                ));
+            } else {
+                let raw = &self.source[open + 1..close];
+                let leading = raw.len() - raw.trim_start().len();
+                let value = raw.trim();
+                args = Some(PseudoClassArgs::Raw {
+                    span: self.span(open + 1 + leading, open + 1 + leading + value.len()),
+                    value: value.to_string(),
+                });
+            }
+            self.pos = close + 1;
+        }
+
+        let span = self.span(start, self.pos);
+        let name = SmolStr::new(name);
+        Some(if element {
+            SimpleSelector::PseudoElement { span, name }
+        } else {
+            SimpleSelector::PseudoClass(PseudoClassSelector { span, name, args })
+        })
+    }
+}
@@ -11,12 +11,129 @@ pub struct ParseError {
     pub kind: ParseErrorKind,
     /// The location in the source where the error occurred.
     pub span: Span,
+    /// A more specific code than the kind implies, if any.
+    code: Option<ParseErrorCode>,
 }
 
 impl ParseError {
     /// Creates a new parse error.
     pub fn new(kind: ParseErrorKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            code: None,
+        }
+    }
+
+    /// Reports this error under a more specific code than its kind implies.
+    pub fn with_code(mut self, code: ParseErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Returns the Svelte-compatible code for this error.
+    pub fn code(&self) -> ParseErrorCode {
+        self.code.unwrap_or_else(|| self.kind.code())
+    }
+}
+
+/// A stable parse error code.
+///
+/// Each code matches the identifier the Svelte compiler uses for the same
+/// problem, so native parse errors can be deduplicated against compiler
+/// errors and linked to the Svelte documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseErrorCode {
+    /// `expected_token`
+    ExpectedToken,
+    /// `unexpected_eof`
+    UnexpectedEof,
+    /// `element_unclosed`
+    ElementUnclosed,
+    /// `element_invalid_closing_tag`
+    ElementInvalidClosingTag,
+    /// `block_unclosed`
+    BlockUnclosed,
+    /// `block_unexpected_close`
+    BlockUnexpectedClose,
+    /// `block_invalid_continuation_placement`
+    BlockInvalidContinuationPlacement,
+    /// `block_duplicate_clause`
+    BlockDuplicateClause,
+    /// `expected_block_type`
+    ExpectedBlockType,
+    /// `attribute_duplicate`
+    AttributeDuplicate,
+    /// `attribute_invalid_name`
+    AttributeInvalidName,
+    /// `directive_missing_name`
+    DirectiveMissingName,
+    /// `tag_invalid_name`
+    TagInvalidName,
+    /// `expected_identifier`
+    ExpectedIdentifier,
+    /// `expected_pattern`
+    ExpectedPattern,
+    /// `js_parse_error`
+    JsParseError,
+    /// `css_expected_identifier`
+    CssExpectedIdentifier,
+    /// `css_selector_invalid`
+    CssSelectorInvalid,
+    /// `css_empty_declaration`
+    CssEmptyDeclaration,
+}
+
+impl ParseErrorCode {
+    /// Returns the code as it appears in Svelte compiler output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorCode::ExpectedToken => "expected_token",
+            ParseErrorCode::UnexpectedEof => "unexpected_eof",
+            ParseErrorCode::ElementUnclosed => "element_unclosed",
+            ParseErrorCode::ElementInvalidClosingTag => "element_invalid_closing_tag",
+            ParseErrorCode::BlockUnclosed => "block_unclosed",
+            ParseErrorCode::BlockUnexpectedClose => "block_unexpected_close",
+            ParseErrorCode::BlockInvalidContinuationPlacement => {
+                "block_invalid_continuation_placement"
+            }
+            ParseErrorCode::BlockDuplicateClause => "block_duplicate_clause",
+            ParseErrorCode::ExpectedBlockType => "expected_block_type",
+            ParseErrorCode::AttributeDuplicate => "attribute_duplicate",
+            ParseErrorCode::AttributeInvalidName => "attribute_invalid_name",
+            ParseErrorCode::DirectiveMissingName => "directive_missing_name",
+            ParseErrorCode::TagInvalidName => "tag_invalid_name",
+            ParseErrorCode::ExpectedIdentifier => "expected_identifier",
+            ParseErrorCode::ExpectedPattern => "expected_pattern",
+            ParseErrorCode::JsParseError => "js_parse_error",
+            ParseErrorCode::CssExpectedIdentifier => "css_expected_identifier",
+            ParseErrorCode::CssSelectorInvalid => "css_selector_invalid",
+            ParseErrorCode::CssEmptyDeclaration => "css_empty_declaration",
+        }
+    }
+
+    /// Returns whether this error comes from the `<style>` parser.
+    pub fn is_css(&self) -> bool {
+        matches!(
+            self,
+            ParseErrorCode::CssExpectedIdentifier
+                | ParseErrorCode::CssSelectorInvalid
+                | ParseErrorCode::CssEmptyDeclaration
+        )
+    }
+
+    /// Returns the Svelte documentation page describing this error.
+    pub fn docs_url(&self) -> String {
+        format!(
+            "https://svelte.dev/docs/svelte/compiler-errors#{}",
+            self.as_str()
+        )
+    }
+}
+
+impl std::fmt::Display for ParseErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -112,6 +229,27 @@ pub enum ParseErrorKind {
     },
 }
 
+impl ParseErrorKind {
+    /// Returns the Svelte-compatible code for this error kind. Errors built
+    /// with [`ParseError::with_code`] may report a more specific one.
+    pub fn code(&self) -> ParseErrorCode {
+        match self {
+            ParseErrorKind::UnexpectedToken { .. } => ParseErrorCode::ExpectedToken,
+            ParseErrorKind::UnexpectedEof { .. } => ParseErrorCode::UnexpectedEof,
+            ParseErrorKind::UnclosedTag { .. } => ParseErrorCode::ElementUnclosed,
+            ParseErrorKind::MismatchedClosingTag { .. } => ParseErrorCode::ElementInvalidClosingTag,
+            ParseErrorKind::UnclosedBlock { .. } => ParseErrorCode::BlockUnclosed,
+            ParseErrorKind::InvalidAttribute { .. } => ParseErrorCode::AttributeInvalidName,
+            ParseErrorKind::InvalidExpression { .. } => ParseErrorCode::JsParseError,
+            ParseErrorKind::DuplicateAttribute { .. } => ParseErrorCode::AttributeDuplicate,
+            ParseErrorKind::InvalidTagName { .. } => ParseErrorCode::TagInvalidName,
+            ParseErrorKind::InvalidDirective { .. } => ParseErrorCode::DirectiveMissingName,
+            ParseErrorKind::InvalidBlockSyntax { .. } => ParseErrorCode::ExpectedToken,
+            ParseErrorKind::SyntaxError { .. } => ParseErrorCode::JsParseError,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,5 +268,34 @@ mod tests {
             error.to_string(),
             "unexpected token: expected tag name, found }"
         );
+        assert_eq!(error.code(), ParseErrorCode::ExpectedToken);
+    }
+
+    #[test]
+    fn test_error_codes_match_svelte() {
+        let kind = ParseErrorKind::MismatchedClosingTag {
+            expected: "div".to_string(),
+            found: "span".to_string(),
+        };
+        assert_eq!(kind.code().as_str(), "element_invalid_closing_tag");
+
+        let kind = ParseErrorKind::UnclosedBlock {
+            block_type: "if".to_string(),
+        };
+        assert_eq!(kind.code().as_str(), "block_unclosed");
+        assert_eq!(
+            kind.code().docs_url(),
+            "https://svelte.dev/docs/svelte/compiler-errors#block_unclosed"
+        );
+
+        let error = ParseError::new(
+            ParseErrorKind::InvalidBlockSyntax {
+                message: "unknown block type: iff".to_string(),
+            },
+            Span::new(TextSize::from(0), TextSize::from(5)),
+        );
+        assert_eq!(error.kind.code(), ParseErrorCode::ExpectedToken);
+        let error = error.with_code(ParseErrorCode::ExpectedBlockType);
+        assert_eq!(error.code().to_string(), "expected_block_type");
     }
 }
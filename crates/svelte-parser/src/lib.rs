@@ -5,6 +5,8 @@
 //! - Recursive descent parser
 //! - AST types for all Svelte constructs
 //! - Error recovery for partial parsing
+//! - A lossless concrete syntax tree built on `rowan` (see [`syntax`])
+//! - A CSS AST for `<style>` blocks (see [`css`])
 //!
 //! # Example
 //!
@@ -28,12 +30,14 @@
 //! ```
 
 mod ast;
+pub mod css;
 mod error;
 mod lexer;
 mod parser;
+pub mod syntax;
 
 pub use ast::*;
-pub use error::{ParseError, ParseErrorKind};
+pub use error::{ParseError, ParseErrorCode, ParseErrorKind};
 pub use lexer::{Lexer, Token};
 pub use source_map::Span;
 
@@ -326,3 +330,21 @@ fn test_style_directive_missing_value() {
     // The important thing is it doesn't crash
     let _ = result;
 }
+
+#[test]
+fn test_style_block_css_errors_are_parse_errors() {
+    let source = "<style>\n  .a { color: ; }\n</style>";
+    let result = parse(source);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].code(), ParseErrorCode::CssEmptyDeclaration);
+    assert_eq!(u32::from(result.errors[0].span.start), 15);
+    assert!(result.document.style.unwrap().stylesheet.is_some());
+
+    let result = parse("<style lang=\"scss\">\n  .a { .b { color: $x; } }\n</style>");
+    assert!(result.errors.is_empty());
+    assert!(result.document.style.unwrap().stylesheet.is_none());
+
+    let result = parse("<style lang=\"less\">\n  .a { .mixin(); color: ; }\n</style>");
+    assert!(result.errors.is_empty());
+    assert!(result.document.style.unwrap().stylesheet.is_none());
+}
@@ -1,7 +1,7 @@
 //! Recursive descent parser for Svelte 5.
 
 use crate::ast::*;
-use crate::error::{ParseError, ParseErrorKind};
+use crate::error::{ParseError, ParseErrorCode, ParseErrorKind};
 use crate::lexer::{Lexer, Token, TokenKind};
 use crate::{ParseOptions, ParseResult};
 use smol_str::SmolStr;
@@ -78,6 +78,14 @@ fn kind_for_slice(slice: &str) -> Option<TokenKind> {
     })
 }
 
+/// A block whose closing tag has not been reached yet.
+struct OpenBlock {
+    /// The block keyword (`if`, `each`, `await`, `key` or `snippet`).
+    keyword: &'static str,
+    /// Offset of the opening `{#`.
+    start: TextSize,
+}
+
 /// The Svelte parser.
 pub struct Parser<'src> {
     /// The source being parsed.
@@ -92,6 +100,8 @@ pub struct Parser<'src> {
     options: ParseOptions,
     /// EOF token for when we're past the end
     eof_token: Token,
+    /// Blocks currently being parsed, innermost last.
+    open_blocks: Vec<OpenBlock>,
 }
 
 impl<'src> Parser<'src> {
@@ -109,12 +119,16 @@ impl<'src> Parser<'src> {
             errors: Vec::new(),
             options,
             eof_token,
+            open_blocks: Vec::new(),
         }
     }
 
     /// Parses the source into a Svelte document.
     pub fn parse(mut self) -> ParseResult {
         let document = self.parse_document();
+        // Unclosed blocks are reported as they unwind, after the errors
+        // inside them; keep the list in source order.
+        self.errors.sort_by_key(|e| e.span.start);
         ParseResult {
             document,
             errors: self.errors,
@@ -201,6 +215,22 @@ impl<'src> Parser<'src> {
         self.errors.push(ParseError::new(kind, span));
     }
 
+    /// Reports an error at the current position under a specific Svelte code.
+    fn error_with_code(&mut self, code: ParseErrorCode, kind: ParseErrorKind) {
+        let span = self.current().span;
+        self.error_at_with_code(code, kind, span);
+    }
+
+    /// Reports an error at a specific span under a specific Svelte code.
+    /// Suppressed in loose mode.
+    fn error_at_with_code(&mut self, code: ParseErrorCode, kind: ParseErrorKind, span: Span) {
+        if self.options.loose {
+            return;
+        }
+        self.errors
+            .push(ParseError::new(kind, span).with_code(code));
+    }
+
     /// Skips whitespace and newlines.
     fn skip_whitespace(&mut self) {
         while self.check(TokenKind::Newline) || self.check(TokenKind::Text) {
@@ -972,10 +1002,15 @@ impl<'src> Parser<'src> {
                 }
             }
 
+            if self.skip_unexpected_block_close() {
+                continue;
+            }
+
             // Parse template nodes
+            let before = self.pos;
             if let Some(node) = self.parse_template_node() {
                 nodes.push(node);
-            } else {
+            } else if self.pos == before {
                 // Skip invalid token to avoid infinite loop
                 self.advance();
             }
@@ -1267,12 +1302,29 @@ impl<'src> Parser<'src> {
             .map(|t| t.span.end)
             .unwrap_or(content_start);
 
+        let is_css = attributes.iter().all(|attr| match attr {
+            Attribute::Normal(attr) if attr.name == "lang" || attr.name == "type" => {
+                matches!(&attr.value, AttributeValue::Text(t) if t.value == "css" || t.value == "text/css")
+            }
+            _ => true,
+        });
+        let stylesheet = if is_css {
+            let (stylesheet, errors) = crate::css::parse_stylesheet(&content, content_span.start);
+            for error in errors {
+                self.error_at_with_code(error.code(), error.kind, error.span);
+            }
+            Some(stylesheet)
+        } else {
+            None
+        };
+
         Some(Style {
             span: Span::new(start, end),
             content_span,
             content,
             global,
             attributes,
+            stylesheet,
         })
     }
 
@@ -1294,7 +1346,7 @@ impl<'src> Parser<'src> {
                 // {/ could be a closing block tag ({/if}, {/each}, etc.) or a regex literal ({/pattern/})
                 // If followed by a closing block keyword, return None to let parent block handle it
                 // Otherwise, parse as expression containing regex
-                if self.is_closing_block_keyword() {
+                if self.closing_block_keyword().is_some() {
                     None
                 } else {
                     self.parse_expression_tag_from_lbrace_slash()
@@ -1896,7 +1948,8 @@ impl<'src> Parser<'src> {
             // Error if directive name is empty (e.g., style:, on:, bind:)
             if remaining.is_empty() {
                 let span = Span::new(start, self.current().span.end);
-                self.error_at(
+                self.error_at_with_code(
+                    ParseErrorCode::DirectiveMissingName,
                     ParseErrorKind::InvalidDirective {
                         message: format!("`{}:` name cannot be empty", directive_name),
                     },
@@ -2301,9 +2354,12 @@ impl<'src> Parser<'src> {
         // Check for 'attach' keyword (will be an Ident token)
         let tag_text = self.current_text();
         if tag_text != "attach" {
-            self.error(ParseErrorKind::InvalidBlockSyntax {
-                message: format!("expected 'attach' after '{{@', found '{}'", tag_text),
-            });
+            self.error_with_code(
+                ParseErrorCode::ExpectedToken,
+                ParseErrorKind::InvalidBlockSyntax {
+                    message: format!("expected 'attach' after '{{@', found '{}'", tag_text),
+                },
+            );
             return None;
         }
         self.advance();
@@ -2442,6 +2498,10 @@ impl<'src> Parser<'src> {
                 }
             }
 
+            if self.skip_unexpected_block_close() {
+                continue;
+            }
+
             if let Some(node) = self.parse_template_node() {
                 children.push(node);
             } else if !self.check(TokenKind::Eof)
@@ -2552,26 +2612,216 @@ impl<'src> Parser<'src> {
         let block_type = self.current_text().to_string();
         self.advance();
 
-        match block_type.as_str() {
+        let keyword = match block_type.as_str() {
+            "if" => "if",
+            "each" => "each",
+            "await" => "await",
+            "key" => "key",
+            "snippet" => "snippet",
+            _ => {
+                self.error_with_code(
+                    ParseErrorCode::ExpectedBlockType,
+                    ParseErrorKind::InvalidBlockSyntax {
+                        message: format!("unknown block type: {}", block_type),
+                    },
+                );
+                return None;
+            }
+        };
+
+        self.open_blocks.push(OpenBlock { keyword, start });
+        match keyword {
             "if" => self.parse_if_block(start),
             "each" => self.parse_each_block(start),
             "await" => self.parse_await_block(start),
             "key" => self.parse_key_block(start),
-            "snippet" => self.parse_snippet_block(start),
-            _ => {
-                self.error(ParseErrorKind::InvalidBlockSyntax {
-                    message: format!("unknown block type: {}", block_type),
-                });
-                None
+            _ => self.parse_snippet_block(start),
+        }
+    }
+
+    /// Reads a block header expression up to its closing `}` and consumes
+    /// the brace.
+    ///
+    /// An unbalanced `(` or `[` would otherwise let the expression reader run
+    /// on into the block body, so in that case the header is cut at the first
+    /// `}` and an error is reported.
+    fn read_block_header(&mut self) -> (String, Span) {
+        let saved = self.pos;
+        let (text, span) = self.read_expression_until('}');
+        if self.check(TokenKind::RBrace) && !Self::has_mismatched_brackets(&text) {
+            self.advance();
+            return (text, span);
+        }
+
+        self.pos = saved;
+        let (text, span) = self.read_until(&["}"]);
+        let found = if self.check(TokenKind::RBrace) {
+            "`}`"
+        } else {
+            "end of file"
+        };
+        self.error_at_with_code(
+            ParseErrorCode::ExpectedToken,
+            ParseErrorKind::SyntaxError {
+                message: format!("unbalanced brackets in block header before {found}"),
+            },
+            span,
+        );
+        self.eat(TokenKind::RBrace);
+        (text, span)
+    }
+
+    /// Returns true if `expr` closes a bracket with the wrong kind of bracket
+    /// (e.g. `(}`), ignoring brackets inside string literals.
+    fn has_mismatched_brackets(expr: &str) -> bool {
+        let mut stack = Vec::new();
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for c in expr.chars() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
             }
+            match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '(' | '[' | '{' => stack.push(c),
+                ')' | ']' | '}' => {
+                    let open = match c {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    if stack.pop() != Some(open) {
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Reports an empty block expression such as `{#if}`.
+    fn check_block_expression(&mut self, expression: &str, start: TextSize) {
+        if !expression.trim().is_empty() {
+            return;
+        }
+        let keyword = self.open_blocks.last().map_or("", |b| b.keyword);
+        let end = self
+            .tokens
+            .get(self.pos.saturating_sub(1))
+            .map(|t| t.span.end)
+            .unwrap_or(start);
+        self.error_at(
+            ParseErrorKind::InvalidExpression {
+                message: format!("{{#{keyword}}} requires an expression"),
+            },
+            Span::new(start, end),
+        );
+    }
+
+    /// Returns the keyword of the closing block tag (`{/if}`, `{/each}`, ...)
+    /// at the current position, if any. Unknown words count only when the
+    /// `}` follows directly, so `{/unknown}` is a close while `{/re/.test(s)}`
+    /// stays a regex expression.
+    fn closing_block_keyword(&self) -> Option<&'src str> {
+        if !self.check(TokenKind::LBraceSlash) {
+            return None;
+        }
+        if self.is_closing_block_keyword() {
+            let span = self.tokens[self.pos + 1].span;
+            return Some(
+                &self.source[u32::from(span.start) as usize..u32::from(span.end) as usize],
+            );
+        }
+        let after = &self.source[u32::from(self.current().span.end) as usize..];
+        let len = after
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(after.len());
+        (len > 0 && after[len..].trim_start().starts_with('}')).then(|| &after[..len])
+    }
+
+    /// Consumes a closing block tag, returning its span.
+    fn consume_block_close(&mut self) -> Span {
+        let start = self.current().span.start;
+        let keyword_len = self.closing_block_keyword().map_or(0, str::len);
+        let keyword_end = u32::from(self.current().span.end) as usize + keyword_len;
+        let rest = &self.source[keyword_end..];
+        let trimmed = rest.trim_start();
+        let mut end = keyword_end + (rest.len() - trimmed.len());
+        if trimmed.starts_with('}') {
+            end += 1;
+        }
+        let end = TextSize::from(end as u32);
+        while self.current().span.start < end && !self.check(TokenKind::Eof) {
+            self.advance();
+        }
+        Span::new(start, end)
+    }
+
+    /// Reports and skips a closing block tag that matches no open block,
+    /// such as the `{/each}` in `{#if x}...{/each}`. Returns true if one was
+    /// skipped.
+    fn skip_unexpected_block_close(&mut self) -> bool {
+        let Some(keyword) = self.closing_block_keyword() else {
+            return false;
+        };
+        if self.open_blocks.iter().any(|b| b.keyword == keyword) {
+            return false;
+        }
+        let span = self.consume_block_close();
+        let message = match self.open_blocks.last() {
+            Some(open) => format!("unexpected {{/{keyword}}}; expected {{/{}}}", open.keyword),
+            None => format!("unexpected {{/{keyword}}} with no matching {{#{keyword}}}"),
+        };
+        self.error_at_with_code(
+            ParseErrorCode::BlockUnexpectedClose,
+            ParseErrorKind::InvalidBlockSyntax { message },
+            span,
+        );
+        true
+    }
+
+    /// Consumes the closing tag of the innermost open block, or reports the
+    /// block as unclosed at its opening tag.
+    fn finish_block(&mut self) {
+        let Some(open) = self.open_blocks.pop() else {
+            return;
+        };
+        if self.closing_block_keyword() == Some(open.keyword) {
+            let span = self.consume_block_close();
+            if !self.source[..u32::from(span.end) as usize].ends_with('}') {
+                self.error_at(
+                    ParseErrorKind::UnexpectedToken {
+                        expected: "'}'".to_string(),
+                        found: self.current_kind().name().to_string(),
+                    },
+                    Span::empty(span.end),
+                );
+            }
+            return;
         }
+        let opening_len = ("{#".len() + open.keyword.len()) as u32;
+        self.error_at(
+            ParseErrorKind::UnclosedBlock {
+                block_type: open.keyword.to_string(),
+            },
+            Span::new(open.start, open.start + TextSize::from(opening_len)),
+        );
     }
 
-    /// Parses an if block.
+    /// Parses an if block. An `{:else if}` branch is parsed as a nested if
+    /// block that consumes the shared `{/if}`.
     fn parse_if_block(&mut self, start: TextSize) -> Option<TemplateNode> {
         // Parse condition
-        let (condition, condition_span) = self.read_expression_until('}');
-        self.eat(TokenKind::RBrace);
+        let (condition, condition_span) = self.read_block_header();
+        self.check_block_expression(&condition, start);
 
         // Parse consequent
         let consequent = self.parse_block_children(&["{:else", "{/if"]);
@@ -2600,10 +2850,9 @@ impl<'src> Parser<'src> {
             None
         };
 
-        // Expect {/if}
-        self.eat(TokenKind::LBraceSlash);
-        self.eat(TokenKind::If);
-        self.eat(TokenKind::RBrace);
+        if !matches!(alternate, Some(ElseBranch::ElseIf(_))) {
+            self.finish_block();
+        }
 
         let end = self
             .tokens
@@ -2624,8 +2873,7 @@ impl<'src> Parser<'src> {
     fn parse_each_block(&mut self, start: TextSize) -> Option<TemplateNode> {
         // Parse expression and pattern: {#each items as item, index (key)}
         let expr_start = self.current().span.start;
-        let (full_expr, full_span) = self.read_expression_until('}');
-        self.eat(TokenKind::RBrace);
+        let (full_expr, full_span) = self.read_block_header();
 
         // Use brace-aware parsing to find " as " - we need to find the LAST " as "
         // that separates the expression from the pattern, since the expression itself
@@ -2673,6 +2921,23 @@ impl<'src> Parser<'src> {
 
         let rest = rest.trim();
 
+        self.check_block_expression(&expression, start);
+        let dangling_as = rest_offset == 0
+            && expression
+                .strip_suffix("as")
+                .is_some_and(|e| e.is_empty() || e.ends_with(char::is_whitespace));
+        if !expression.trim().is_empty()
+            && (dangling_as || (!item_less && rest_offset > 0 && rest.is_empty()))
+        {
+            self.error_at_with_code(
+                ParseErrorCode::ExpectedPattern,
+                ParseErrorKind::InvalidBlockSyntax {
+                    message: "expected a binding pattern after `as` in {#each}".to_string(),
+                },
+                full_span,
+            );
+        }
+
         // Parse context and index using brace-aware parsing.
         //
         // For the item-less form `{#each EXPR, INDEX (key)?}`, `rest` is just
@@ -2801,10 +3066,7 @@ impl<'src> Parser<'src> {
             None
         };
 
-        // Expect {/each}
-        self.eat(TokenKind::LBraceSlash);
-        self.eat(TokenKind::Each);
-        self.eat(TokenKind::RBrace);
+        self.finish_block();
 
         let end = self
             .tokens
@@ -2828,8 +3090,7 @@ impl<'src> Parser<'src> {
     /// Parses an await block.
     fn parse_await_block(&mut self, start: TextSize) -> Option<TemplateNode> {
         let expr_start = self.current().span.start;
-        let (full_expr, full_span) = self.read_expression_until('}');
-        self.eat(TokenKind::RBrace);
+        let (full_expr, full_span) = self.read_block_header();
 
         // Check for shorthand: {#await promise then value}
         // Use brace-aware parsing to find " then "
@@ -2851,6 +3112,7 @@ impl<'src> Parser<'src> {
             } else {
                 (full_expr.trim().to_string(), full_span, None)
             };
+        self.check_block_expression(&expression, start);
 
         // Parse pending content or body
         let (pending, then, catch) =
@@ -2952,10 +3214,7 @@ impl<'src> Parser<'src> {
                 (Some(pending), then_block, catch_block)
             };
 
-        // Expect {/await}
-        self.eat(TokenKind::LBraceSlash);
-        self.eat(TokenKind::Await);
-        self.eat(TokenKind::RBrace);
+        self.finish_block();
 
         let end = self
             .tokens
@@ -2975,15 +3234,12 @@ impl<'src> Parser<'src> {
 
     /// Parses a key block.
     fn parse_key_block(&mut self, start: TextSize) -> Option<TemplateNode> {
-        let (expression, expression_span) = self.read_expression_until('}');
-        self.eat(TokenKind::RBrace);
+        let (expression, expression_span) = self.read_block_header();
+        self.check_block_expression(&expression, start);
 
         let body = self.parse_block_children(&["{/key"]);
 
-        // Expect {/key}
-        self.eat(TokenKind::LBraceSlash);
-        self.eat(TokenKind::Key);
-        self.eat(TokenKind::RBrace);
+        self.finish_block();
 
         let end = self
             .tokens
@@ -3002,8 +3258,7 @@ impl<'src> Parser<'src> {
     /// Parses a snippet block.
     fn parse_snippet_block(&mut self, start: TextSize) -> Option<TemplateNode> {
         let sig_start = self.current().span.start;
-        let (full_signature, _) = self.read_expression_until('}');
-        self.eat(TokenKind::RBrace);
+        let (full_signature, signature_span) = self.read_block_header();
 
         // Parse name and parameters: name(params)
         // Use brace-aware parsing for the parenthesis
@@ -3037,12 +3292,33 @@ impl<'src> Parser<'src> {
                 )
             };
 
+        // Generic snippets (`{#snippet row<T>(item: T)}`) carry their type
+        // parameters in the name slice.
+        let bare_name = name.split('<').next().unwrap_or("").trim();
+        let is_identifier = bare_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && bare_name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        if !is_identifier {
+            self.error_at_with_code(
+                ParseErrorCode::ExpectedIdentifier,
+                ParseErrorKind::InvalidBlockSyntax {
+                    message: "{#snippet} requires a name".to_string(),
+                },
+                if signature_span.is_empty() {
+                    Span::new(start, signature_span.end)
+                } else {
+                    signature_span
+                },
+            );
+        }
+
         let body = self.parse_block_children(&["{/snippet"]);
 
-        // Expect {/snippet}
-        self.eat(TokenKind::LBraceSlash);
-        self.eat(TokenKind::Snippet);
-        self.eat(TokenKind::RBrace);
+        self.finish_block();
 
         let end = self
             .tokens
@@ -3069,17 +3345,30 @@ impl<'src> Parser<'src> {
             let current_offset = u32::from(self.current().span.start) as usize;
             let remaining = &self.source[current_offset..];
 
-            let at_delimiter = delimiters.iter().any(|d| remaining.starts_with(d));
+            // Delimiters match whole keywords only, so `{:elseif}` is not
+            // taken for `{:else`.
+            let at_delimiter = delimiters.iter().any(|d| {
+                remaining.strip_prefix(d).is_some_and(|after| {
+                    !after.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$')
+                })
+            });
             if at_delimiter {
                 break;
             }
 
+            // A close for an enclosing block ends this one too; the caller
+            // reports the missing close.
+            if self.closing_block_keyword().is_some() && !self.skip_unexpected_block_close() {
+                break;
+            }
+
+            let before = self.pos;
             if let Some(node) = self.parse_template_node() {
                 nodes.push(node);
-            } else if !self.check(TokenKind::Eof) {
-                self.advance();
-            } else {
+            } else if self.check(TokenKind::Eof) {
                 break;
+            } else if self.pos == before {
+                self.advance();
             }
         }
 
@@ -3155,6 +3444,21 @@ impl<'src> Parser<'src> {
         } else {
             String::new()
         };
+        let clause = match self.current_kind() {
+            TokenKind::Else => {
+                let next = self.tokens[self.pos + 1..]
+                    .iter()
+                    .find(|t| !matches!(t.kind, TokenKind::Text | TokenKind::Newline));
+                if next.is_some_and(|t| t.kind == TokenKind::If) {
+                    Some("else if")
+                } else {
+                    Some("else")
+                }
+            }
+            TokenKind::Then => Some("then"),
+            TokenKind::Catch => Some("catch"),
+            _ => None,
+        };
 
         // Find the end of the tag (look for })
         while !self.check(TokenKind::RBrace) && !self.check(TokenKind::Eof) {
@@ -3170,15 +3474,24 @@ impl<'src> Parser<'src> {
         };
 
         // Emit appropriate error message
-        let message = if keyword.is_empty() {
-            "expected block continuation keyword (else, then, catch)".to_string()
+        let (code, message) = if let Some(clause) = clause {
+            self.continuation_placement_error(clause)
+        } else if keyword.is_empty() {
+            (
+                ParseErrorCode::ExpectedToken,
+                "expected block continuation keyword (else, then, catch)".to_string(),
+            )
         } else {
-            format!(
-                "'{keyword}' is not a valid block continuation. Expected {{:else}}, {{:else if}}, {{:then}}, or {{:catch}}"
+            (
+                ParseErrorCode::ExpectedToken,
+                format!(
+                    "'{keyword}' is not a valid block continuation. Expected {{:else}}, {{:else if}}, {{:then}}, or {{:catch}}"
+                ),
             )
         };
 
-        self.error_at(
+        self.error_at_with_code(
+            code,
             ParseErrorKind::InvalidBlockSyntax { message },
             Span::new(start, end),
         );
@@ -3186,6 +3499,37 @@ impl<'src> Parser<'src> {
         None
     }
 
+    /// Describes a well-spelled continuation (`{:else}`, `{:then}`, ...)
+    /// that the enclosing block did not accept at this point.
+    fn continuation_placement_error(&self, clause: &str) -> (ParseErrorCode, String) {
+        let parents: &[&str] = match clause {
+            "else" => &["if", "each"],
+            "else if" => &["if"],
+            _ => &["await"],
+        };
+        match self.open_blocks.last() {
+            // The block took this clause already, or a later one.
+            Some(open) if parents.contains(&open.keyword) => (
+                ParseErrorCode::BlockDuplicateClause,
+                format!(
+                    "unexpected {{:{clause}}} in {{#{}}} block; each clause may appear only once, in order",
+                    open.keyword
+                ),
+            ),
+            _ => {
+                let expected = parents
+                    .iter()
+                    .map(|p| format!("{{#{p}}}"))
+                    .collect::<Vec<_>>()
+                    .join(" or ");
+                (
+                    ParseErrorCode::BlockInvalidContinuationPlacement,
+                    format!("{{:{clause}}} can only appear directly inside {expected}"),
+                )
+            }
+        }
+    }
+
     /// Parses a special tag ({@html}, {@const}, {@debug}, {@render}).
     fn parse_special_tag(&mut self) -> Option<TemplateNode> {
         let start = self.current().span.start;
@@ -3268,9 +3612,12 @@ impl<'src> Parser<'src> {
                 }))
             }
             _ => {
-                self.error(ParseErrorKind::InvalidBlockSyntax {
-                    message: format!("unknown special tag: @{}", tag_type),
-                });
+                self.error_with_code(
+                    ParseErrorCode::ExpectedToken,
+                    ParseErrorKind::InvalidBlockSyntax {
+                        message: format!("unknown special tag: @{}", tag_type),
+                    },
+                );
                 None
             }
         }
@@ -3391,9 +3738,12 @@ impl<'src> Parser<'src> {
         // compiler / language-tools rule). Non-fatal: still build the node so
         // downstream stages keep working.
         if declaration.contains(';') {
-            self.error(ParseErrorKind::SyntaxError {
-                message: "declaration tags may not contain a semicolon".to_string(),
-            });
+            self.error_with_code(
+                ParseErrorCode::JsParseError,
+                ParseErrorKind::SyntaxError {
+                    message: "declaration tags may not contain a semicolon".to_string(),
+                },
+            );
         }
 
         Some(TemplateNode::DeclarationTag(DeclarationTag {
@@ -3989,6 +4339,154 @@ mod tests {
         assert!(result.errors.is_empty());
     }
 
+    // ==========================================
+    // Tests for block structure
+    // ==========================================
+
+    fn error_codes(source: &str) -> Vec<&'static str> {
+        Parser::new(source, ParseOptions::default())
+            .parse()
+            .errors
+            .iter()
+            .map(|e| e.code().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_unclosed_block_points_at_opening_tag() {
+        let source = "<p>a</p>\n{#each items as item}\n  <li>{item}</li>\n";
+        let result = Parser::new(source, ParseOptions::default()).parse();
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.code(), ParseErrorCode::BlockUnclosed);
+        let start = u32::from(error.span.start) as usize;
+        let end = u32::from(error.span.end) as usize;
+        assert_eq!(&source[start..end], "{#each");
+    }
+
+    #[test]
+    fn test_unclosed_blocks_all_kinds() {
+        for source in [
+            "{#if a}x",
+            "{#if a}x{:else}y",
+            "{#if a}x{:else if b}y",
+            "{#each a as b}x",
+            "{#await p}x{:then v}y",
+            "{#key k}x",
+            "{#snippet s()}x",
+        ] {
+            assert_eq!(error_codes(source), ["block_unclosed"], "{source}");
+        }
+    }
+
+    #[test]
+    fn test_inner_block_unclosed_by_outer_close() {
+        assert_eq!(
+            error_codes("{#if a}{#each b as c}x{/if}"),
+            ["block_unclosed"]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_block_close() {
+        let source = "{#if a}x{/each}";
+        let result = Parser::new(source, ParseOptions::default()).parse();
+        let codes: Vec<_> = result.errors.iter().map(|e| e.code().as_str()).collect();
+        assert_eq!(codes, ["block_unclosed", "block_unexpected_close"]);
+        let span = result.errors[1].span;
+        assert_eq!(
+            &source[u32::from(span.start) as usize..u32::from(span.end) as usize],
+            "{/each}"
+        );
+    }
+
+    #[test]
+    fn test_stray_block_close() {
+        assert_eq!(error_codes("<div>{/if}</div>"), ["block_unexpected_close"]);
+        assert_eq!(error_codes("{/unknown}"), ["block_unexpected_close"]);
+        // A regex expression is not a close.
+        assert!(error_codes("{/a+/.test(s)}").is_empty());
+    }
+
+    #[test]
+    fn test_continuation_outside_block() {
+        for source in [
+            "{:else}",
+            "{:then value}",
+            "{:catch error}",
+            "{#key k}{:else}{/key}",
+        ] {
+            assert_eq!(
+                error_codes(source),
+                ["block_invalid_continuation_placement"],
+                "{source}"
+            );
+        }
+        assert_eq!(
+            error_codes("{#each a as b}x{:then v}{/each}"),
+            ["block_invalid_continuation_placement"]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_else_clause() {
+        assert_eq!(
+            error_codes("{#if a}x{:else}y{:else}z{/if}"),
+            ["block_duplicate_clause"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_block_headers() {
+        assert_eq!(error_codes("{#if}x{/if}"), ["js_parse_error"]);
+        assert_eq!(error_codes("{#each}x{/each}"), ["js_parse_error"]);
+        assert_eq!(error_codes("{#await}x{/await}"), ["js_parse_error"]);
+        assert_eq!(error_codes("{#key}x{/key}"), ["js_parse_error"]);
+        assert_eq!(
+            error_codes("{#each items as}x{/each}"),
+            ["expected_pattern"]
+        );
+        assert_eq!(
+            error_codes("{#snippet}x{/snippet}"),
+            ["expected_identifier"]
+        );
+        assert_eq!(error_codes("{#snippet s(}x{/snippet}"), ["expected_token"]);
+        assert_eq!(
+            error_codes("{#each items as item (}x{/each}"),
+            ["expected_token"]
+        );
+    }
+
+    #[test]
+    fn test_valid_block_forms_not_flagged() {
+        for source in [
+            "{#await p then}x{:catch}y{/await}",
+            "{#await p}x{:then}y{/await}",
+            "{#each items}x{/each}",
+            "{#each items, i}{i}{/each}",
+            "{#each items as { id, name } (id)}{name}{/each}",
+            "{#snippet row<T>(item: T)}{item}{/snippet}",
+            "{#if fn(() => { return 1; })}x{/if}",
+            "{#if a}<p>x</p>{:else if b}<p>y</p>{:else}<p>z</p>{/if}",
+            "{#if a}x{/if }",
+        ] {
+            assert!(error_codes(source).is_empty(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_loose_mode_suppresses_block_errors() {
+        let result = Parser::new(
+            "{#if a}x{/each}{:else}",
+            ParseOptions {
+                loose: true,
+                ..Default::default()
+            },
+        )
+        .parse();
+        assert!(result.errors.is_empty());
+    }
+
     // ==========================================
     // Tests for keywords as attribute names
     // Issue #5: https://github.com/pheuter/svelte-check-rs/issues/5
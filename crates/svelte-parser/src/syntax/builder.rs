@@ -0,0 +1,505 @@
+//! Lays out a lossless green tree from a parsed document.
+//!
+//! The builder walks the AST in source order and opens a node for every
+//! construct whose span fits inside its parent. The text between child spans
+//! is split into trivia and punctuation tokens by [`lex_gap`], so every byte of
+//! the source ends up in exactly one token regardless of how well the AST
+//! covers it.
+
+use super::SyntaxKind;
+use crate::ast::*;
+use rowan::{GreenNode, GreenNodeBuilder};
+use source_map::Span;
+use text_size::TextSize;
+
+/// Builds the green tree for `source`.
+pub(super) fn build(source: &str, document: &SvelteDocument) -> GreenNode {
+    let mut builder = Builder {
+        source,
+        inner: GreenNodeBuilder::new(),
+        cursor: 0,
+        limits: vec![source.len()],
+    };
+
+    // Scripts and styles are stored apart from the fragment, but they
+    // interleave with template nodes in the source.
+    let mut items: Vec<TopLevel<'_>> = Vec::new();
+    items.extend(document.module_script.iter().map(TopLevel::Script));
+    items.extend(document.instance_script.iter().map(TopLevel::Script));
+    items.extend(document.style.iter().map(TopLevel::Style));
+    items.extend(document.fragment.nodes.iter().map(TopLevel::Node));
+    items.sort_by_key(|item| item.span().start);
+
+    builder.inner.start_node(SyntaxKind::Document.into());
+    for item in items {
+        match item {
+            TopLevel::Script(script) => builder.script(script),
+            TopLevel::Style(style) => builder.style(style),
+            TopLevel::Node(node) => builder.template_node(node),
+        }
+    }
+    builder.gap(source.len());
+    builder.inner.finish_node();
+    builder.inner.finish()
+}
+
+enum TopLevel<'a> {
+    Script(&'a Script),
+    Style(&'a Style),
+    Node(&'a TemplateNode),
+}
+
+impl TopLevel<'_> {
+    fn span(&self) -> Span {
+        match self {
+            TopLevel::Script(script) => script.span,
+            TopLevel::Style(style) => style.span,
+            TopLevel::Node(node) => node.span(),
+        }
+    }
+}
+
+struct Builder<'src> {
+    source: &'src str,
+    inner: GreenNodeBuilder<'static>,
+    /// Byte offset up to which source text has been emitted.
+    cursor: usize,
+    /// End offsets of the currently open nodes; children must fit inside.
+    limits: Vec<usize>,
+}
+
+impl<'src> Builder<'src> {
+    /// Returns the byte range of `span` if it can be placed at the cursor.
+    fn range(&self, span: Span) -> Option<(usize, usize)> {
+        let start = u32::from(span.start) as usize;
+        let end = u32::from(span.end) as usize;
+        let limit = self.limits.last().copied().unwrap_or(self.source.len());
+        let fits = self.cursor <= start
+            && start <= end
+            && end <= limit
+            && self.source.is_char_boundary(start)
+            && self.source.is_char_boundary(end);
+        fits.then_some((start, end))
+    }
+
+    /// Opens a node of `kind` covering `span`, runs `children`, and closes it.
+    /// Does nothing if the span cannot be placed.
+    fn node(&mut self, kind: SyntaxKind, span: Span, children: impl FnOnce(&mut Self)) {
+        let Some((start, end)) = self.range(span) else {
+            return;
+        };
+        self.gap(start);
+        self.inner.start_node(kind.into());
+        self.limits.push(end);
+        children(self);
+        self.gap(end);
+        self.limits.pop();
+        self.inner.finish_node();
+    }
+
+    /// Emits a single token of `kind` covering `span`.
+    fn token(&mut self, kind: SyntaxKind, span: Span) {
+        let Some((start, end)) = self.range(span) else {
+            return;
+        };
+        if start == end {
+            return;
+        }
+        self.gap(start);
+        self.inner.token(kind.into(), &self.source[start..end]);
+        self.cursor = end;
+    }
+
+    /// Emits the unclaimed source text up to `to` as gap tokens.
+    fn gap(&mut self, to: usize) {
+        while self.cursor < to {
+            let text = &self.source[self.cursor..to];
+            let (kind, len) = lex_gap(text);
+            self.inner.token(kind.into(), &text[..len]);
+            self.cursor += len;
+        }
+    }
+
+    fn script(&mut self, script: &Script) {
+        self.node(SyntaxKind::Script, script.span, |b| {
+            let open = Span::new(script.span.start, script.content_span.start);
+            b.node(SyntaxKind::StartTag, open, |b| {
+                b.attributes(&script.attributes)
+            });
+            b.token(SyntaxKind::ScriptContent, script.content_span);
+            b.end_tag(script.span.end);
+        });
+    }
+
+    fn style(&mut self, style: &Style) {
+        self.node(SyntaxKind::Style, style.span, |b| {
+            let open = Span::new(style.span.start, style.content_span.start);
+            b.node(SyntaxKind::StartTag, open, |b| {
+                b.attributes(&style.attributes)
+            });
+            b.token(SyntaxKind::StyleContent, style.content_span);
+            b.end_tag(style.span.end);
+        });
+    }
+
+    fn template_node(&mut self, node: &TemplateNode) {
+        match node {
+            TemplateNode::Element(n) => {
+                self.element(SyntaxKind::Element, n.span, &n.attributes, &n.children)
+            }
+            TemplateNode::Component(n) => {
+                self.element(SyntaxKind::Component, n.span, &n.attributes, &n.children)
+            }
+            TemplateNode::SvelteElement(n) => self.element(
+                SyntaxKind::SvelteElement,
+                n.span,
+                &n.attributes,
+                &n.children,
+            ),
+            TemplateNode::Text(n) => self.node(SyntaxKind::TextNode, n.span, |b| {
+                b.token(SyntaxKind::Text, n.span)
+            }),
+            TemplateNode::Comment(n) => self.node(SyntaxKind::Comment, n.span, |b| {
+                let start = u32::from(n.span.start) as usize;
+                let end = u32::from(n.span.end) as usize;
+                let body_start = (start + "<!--".len()).min(end);
+                let body_end = if b.source[start..end].ends_with("-->") {
+                    end - "-->".len()
+                } else {
+                    end
+                };
+                b.gap(body_start);
+                b.token(SyntaxKind::Text, byte_span(body_start, body_end));
+            }),
+            TemplateNode::Expression(n) => self.node(SyntaxKind::ExpressionTag, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span)
+            }),
+            TemplateNode::HtmlTag(n) => self.node(SyntaxKind::HtmlTag, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span)
+            }),
+            TemplateNode::ConstTag(n) => self.node(SyntaxKind::ConstTag, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.declaration_span)
+            }),
+            TemplateNode::DeclarationTag(n) => self.node(SyntaxKind::DeclarationTag, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.declaration_span)
+            }),
+            TemplateNode::DebugTag(n) => self.node(SyntaxKind::DebugTag, n.span, |_| {}),
+            TemplateNode::RenderTag(n) => self.node(SyntaxKind::RenderTag, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span)
+            }),
+            TemplateNode::IfBlock(n) => self.if_block(n),
+            TemplateNode::EachBlock(n) => self.node(SyntaxKind::EachBlock, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span);
+                b.token(SyntaxKind::Pattern, n.context_span);
+                if let Some(key) = &n.key {
+                    b.node(SyntaxKind::EachKey, key.span, |b| {
+                        b.token(SyntaxKind::Expression, key.span)
+                    });
+                }
+                b.fragment(&n.body);
+                if let Some(fallback) = &n.fallback {
+                    b.fragment(fallback);
+                }
+            }),
+            TemplateNode::AwaitBlock(n) => self.node(SyntaxKind::AwaitBlock, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span);
+                // In the `{#await p then v}` shorthand the value binding sits in
+                // the opening tag, before the `AwaitThen` span starts.
+                let shorthand_then = n.pending.is_none();
+                if let Some(then) = n.then.as_ref().filter(|_| shorthand_then) {
+                    if let Some(value_span) = then.value_span {
+                        b.token(SyntaxKind::Pattern, value_span);
+                    }
+                }
+                if let Some(pending) = &n.pending {
+                    b.fragment(pending);
+                }
+                if let Some(then) = &n.then {
+                    b.node(SyntaxKind::AwaitThen, then.span, |b| {
+                        if !shorthand_then {
+                            if let Some(value_span) = then.value_span {
+                                b.token(SyntaxKind::Pattern, value_span);
+                            }
+                        }
+                        b.fragment(&then.body);
+                    });
+                }
+                if let Some(catch) = &n.catch {
+                    b.node(SyntaxKind::AwaitCatch, catch.span, |b| {
+                        b.fragment(&catch.body)
+                    });
+                }
+            }),
+            TemplateNode::KeyBlock(n) => self.node(SyntaxKind::KeyBlock, n.span, |b| {
+                b.token(SyntaxKind::Expression, n.expression_span);
+                b.fragment(&n.body);
+            }),
+            TemplateNode::SnippetBlock(n) => self.node(SyntaxKind::SnippetBlock, n.span, |b| {
+                b.token(SyntaxKind::Pattern, n.parameters_span);
+                b.fragment(&n.body);
+            }),
+        }
+    }
+
+    fn if_block(&mut self, block: &IfBlock) {
+        self.node(SyntaxKind::IfBlock, block.span, |b| {
+            b.token(SyntaxKind::Expression, block.condition_span);
+            b.fragment(&block.consequent);
+            match &block.alternate {
+                Some(ElseBranch::Else(fragment)) => b.fragment(fragment),
+                Some(ElseBranch::ElseIf(nested)) => b.if_block(nested),
+                None => {}
+            }
+        });
+    }
+
+    fn fragment(&mut self, fragment: &Fragment) {
+        self.node(SyntaxKind::Fragment, fragment.span, |b| {
+            for node in &fragment.nodes {
+                b.template_node(node);
+            }
+        });
+    }
+
+    fn element(
+        &mut self,
+        kind: SyntaxKind,
+        span: Span,
+        attributes: &[Attribute],
+        children: &[TemplateNode],
+    ) {
+        self.node(kind, span, |b| {
+            let start = u32::from(span.start) as usize;
+            let end = u32::from(span.end) as usize;
+
+            // The start tag runs past the last attribute (or in-tag comment) up
+            // to the first `>`, but never into the children.
+            let attrs_end = attributes
+                .iter()
+                .flat_map(|a| {
+                    std::iter::once(a.span())
+                        .chain(a.leading_comments().iter().map(|c| c.span))
+                        .chain(a.trailing_comments().iter().map(|c| c.span))
+                })
+                .map(|s| u32::from(s.end) as usize)
+                .max()
+                .unwrap_or(start)
+                .clamp(start, end);
+            let children_start = children
+                .first()
+                .map(|c| u32::from(c.span().start) as usize)
+                .unwrap_or(end)
+                .clamp(attrs_end, end);
+            let tag_end = b.source[attrs_end..children_start]
+                .find('>')
+                .map(|pos| attrs_end + pos + 1)
+                .unwrap_or(children_start);
+            b.node(SyntaxKind::StartTag, byte_span(start, tag_end), |b| {
+                b.attributes(attributes)
+            });
+
+            for child in children {
+                b.template_node(child);
+            }
+
+            b.end_tag(span.end);
+        });
+    }
+
+    /// Emits an `EndTag` node from the last `</` before `end` up to `end`.
+    fn end_tag(&mut self, end: TextSize) {
+        let end = u32::from(end) as usize;
+        if end < self.cursor || !self.source.is_char_boundary(end) {
+            return;
+        }
+        if let Some(pos) = self.source[self.cursor..end].rfind("</") {
+            let start = self.cursor + pos;
+            self.node(SyntaxKind::EndTag, byte_span(start, end), |_| {});
+        }
+    }
+
+    fn attributes(&mut self, attributes: &[Attribute]) {
+        for attribute in attributes {
+            for comment in attribute.leading_comments() {
+                self.token(SyntaxKind::TagComment, comment.span);
+            }
+            self.attribute(attribute);
+            for comment in attribute.trailing_comments() {
+                self.token(SyntaxKind::TagComment, comment.span);
+            }
+        }
+    }
+
+    fn attribute(&mut self, attribute: &Attribute) {
+        match attribute {
+            Attribute::Normal(a) => self.node(SyntaxKind::Attribute, a.span, |b| {
+                b.attribute_value(&a.value)
+            }),
+            Attribute::Spread(a) => self.node(SyntaxKind::SpreadAttribute, a.span, |b| {
+                b.token(SyntaxKind::Expression, a.expression_span)
+            }),
+            Attribute::Directive(a) => self.node(SyntaxKind::Directive, a.span, |b| {
+                if let Some(expression) = &a.expression {
+                    b.expression_value(expression);
+                }
+            }),
+            Attribute::Shorthand(a) => self.node(SyntaxKind::ShorthandAttribute, a.span, |_| {}),
+            Attribute::Attach(a) => self.node(SyntaxKind::AttachAttribute, a.span, |b| {
+                b.token(SyntaxKind::Expression, a.expression_span)
+            }),
+            Attribute::CssCustomProperty { value, span, .. } => {
+                self.node(SyntaxKind::CssCustomProperty, *span, |b| {
+                    if let Some(value) = value {
+                        b.attribute_value(value);
+                    }
+                })
+            }
+        }
+    }
+
+    fn attribute_value(&mut self, value: &AttributeValue) {
+        match value {
+            AttributeValue::True => {}
+            AttributeValue::Text(text) => self.token(SyntaxKind::Text, text.span),
+            AttributeValue::Expression(expression) => self.expression_value(expression),
+            AttributeValue::Concat(parts) => {
+                for part in parts {
+                    match part {
+                        AttributeValuePart::Text(text) => self.token(SyntaxKind::Text, text.span),
+                        AttributeValuePart::Expression(expression) => {
+                            self.expression_value(expression)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn expression_value(&mut self, value: &ExpressionValue) {
+        // Directive values record the whole directive as their span; narrow it
+        // to the opening brace or quote so the name stays outside the node.
+        let start = u32::from(value.span.start) as usize;
+        let expr_start = u32::from(value.expression_span.start) as usize;
+        let open = self
+            .source
+            .get(start..expr_start)
+            .and_then(|prefix| prefix.rfind(['{', '"', '\'']))
+            .map_or(start, |pos| start + pos);
+        let span = Span::new(TextSize::from(open as u32), value.span.end);
+        self.node(SyntaxKind::ExpressionValue, span, |b| {
+            b.token(SyntaxKind::Expression, value.expression_span)
+        });
+    }
+}
+
+fn byte_span(start: usize, end: usize) -> Span {
+    Span::new(TextSize::from(start as u32), TextSize::from(end as u32))
+}
+
+/// Punctuation recognized in gap text, longest first.
+const PUNCTUATION: &[(&str, SyntaxKind)] = &[
+    ("<!--", SyntaxKind::CommentOpen),
+    ("-->", SyntaxKind::CommentClose),
+    ("...", SyntaxKind::Ellipsis),
+    ("</", SyntaxKind::LAngleSlash),
+    ("/>", SyntaxKind::SlashRAngle),
+    ("{#", SyntaxKind::LBraceHash),
+    ("{/", SyntaxKind::LBraceSlash),
+    ("{:", SyntaxKind::LBraceColon),
+    ("{@", SyntaxKind::LBraceAt),
+    ("<", SyntaxKind::LAngle),
+    (">", SyntaxKind::RAngle),
+    ("/", SyntaxKind::Slash),
+    ("=", SyntaxKind::Eq),
+    ("\"", SyntaxKind::Quote),
+    ("'", SyntaxKind::Quote),
+    ("{", SyntaxKind::LBrace),
+    ("}", SyntaxKind::RBrace),
+    (":", SyntaxKind::Colon),
+    ("|", SyntaxKind::Pipe),
+    (",", SyntaxKind::Comma),
+    ("(", SyntaxKind::LParen),
+    (")", SyntaxKind::RParen),
+];
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '-' | '.')
+}
+
+/// Lexes the first token of `text`, returning its kind and byte length.
+///
+/// `text` must be non-empty.
+fn lex_gap(text: &str) -> (SyntaxKind, usize) {
+    let run = |pred: fn(char) -> bool| -> usize {
+        text.char_indices()
+            .find(|(_, c)| !pred(*c))
+            .map_or(text.len(), |(i, _)| i)
+    };
+
+    let whitespace = run(char::is_whitespace);
+    if whitespace > 0 {
+        return (SyntaxKind::Whitespace, whitespace);
+    }
+    if let Some((punct, kind)) = PUNCTUATION.iter().find(|(p, _)| text.starts_with(p)) {
+        return (*kind, punct.len());
+    }
+    let ident = run(is_ident_char);
+    if ident > 0 {
+        return (SyntaxKind::Ident, ident);
+    }
+    let other = run(|c| {
+        !c.is_whitespace()
+            && !is_ident_char(c)
+            && !PUNCTUATION.iter().any(|(p, _)| p.starts_with(c))
+    });
+    // A lone character that only starts a punctuation prefix (e.g. `-` not
+    // followed by `->`) still needs to be consumed.
+    let len = if other > 0 {
+        other
+    } else {
+        text.chars().next().map_or(1, char::len_utf8)
+    };
+    (SyntaxKind::Text, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_all(mut text: &str) -> Vec<(SyntaxKind, &str)> {
+        let mut tokens = Vec::new();
+        while !text.is_empty() {
+            let (kind, len) = lex_gap(text);
+            tokens.push((kind, &text[..len]));
+            text = &text[len..];
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_lex_gap_tag_punctuation() {
+        assert_eq!(
+            lex_all("<svelte:head  on:click|once="),
+            vec![
+                (SyntaxKind::LAngle, "<"),
+                (SyntaxKind::Ident, "svelte"),
+                (SyntaxKind::Colon, ":"),
+                (SyntaxKind::Ident, "head"),
+                (SyntaxKind::Whitespace, "  "),
+                (SyntaxKind::Ident, "on"),
+                (SyntaxKind::Colon, ":"),
+                (SyntaxKind::Ident, "click"),
+                (SyntaxKind::Pipe, "|"),
+                (SyntaxKind::Ident, "once"),
+                (SyntaxKind::Eq, "="),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lex_gap_consumes_everything() {
+        let text = "{#each}{:else}{/each}{@html}{...x}<!-- -->#!? \u{e9}\u{1f600}";
+        let joined: String = lex_all(text).iter().map(|(_, t)| *t).collect();
+        assert_eq!(joined, text);
+    }
+}
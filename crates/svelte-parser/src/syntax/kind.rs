@@ -0,0 +1,253 @@
+//! Node and token kinds for the Svelte CST.
+
+/// The kind of a CST node or token.
+///
+/// Token kinds come first, node kinds second; [`SyntaxKind::is_token`] relies
+/// on that ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u16)]
+pub enum SyntaxKind {
+    // === Tokens ===
+    /// A run of whitespace (spaces, tabs, newlines).
+    Whitespace,
+    /// Text content, or source text with no more specific kind.
+    Text,
+    /// A tag name, attribute name or block keyword.
+    Ident,
+    /// `<`
+    LAngle,
+    /// `>`
+    RAngle,
+    /// `/>`
+    SlashRAngle,
+    /// `</`
+    LAngleSlash,
+    /// `/`
+    Slash,
+    /// `=`
+    Eq,
+    /// `"` or `'`
+    Quote,
+    /// `{`
+    LBrace,
+    /// `}`
+    RBrace,
+    /// `{#`
+    LBraceHash,
+    /// `{/`
+    LBraceSlash,
+    /// `{:`
+    LBraceColon,
+    /// `{@`
+    LBraceAt,
+    /// `:`
+    Colon,
+    /// `|`
+    Pipe,
+    /// `,`
+    Comma,
+    /// `(`
+    LParen,
+    /// `)`
+    RParen,
+    /// `...`
+    Ellipsis,
+    /// `<!--`
+    CommentOpen,
+    /// `-->`
+    CommentClose,
+    /// An in-tag `// line` or `/* block */` comment.
+    TagComment,
+    /// A raw JavaScript/TypeScript expression.
+    Expression,
+    /// A binding pattern or parameter list (`{#each}` context, `{:then}`
+    /// value, snippet parameters).
+    Pattern,
+    /// The raw content of a `<script>` block.
+    ScriptContent,
+    /// The raw content of a `<style>` block.
+    StyleContent,
+
+    // === Nodes ===
+    /// The root of the tree.
+    Document,
+    /// A top-level `<script>` block.
+    Script,
+    /// A top-level `<style>` block.
+    Style,
+    /// The children of a block branch.
+    Fragment,
+    /// An HTML element.
+    Element,
+    /// A component.
+    Component,
+    /// A `svelte:*` element.
+    SvelteElement,
+    /// An opening tag, from `<` to `>` or `/>`.
+    StartTag,
+    /// A closing tag, from `</` to `>`.
+    EndTag,
+    /// A text node.
+    TextNode,
+    /// An HTML comment.
+    Comment,
+    /// `{expr}`
+    ExpressionTag,
+    /// `{@html expr}`
+    HtmlTag,
+    /// `{@const ...}`
+    ConstTag,
+    /// `{const ...}` / `{let ...}`
+    DeclarationTag,
+    /// `{@debug ...}`
+    DebugTag,
+    /// `{@render ...}`
+    RenderTag,
+    /// `{#if}` (also used for `{:else if}` branches).
+    IfBlock,
+    /// `{#each}`
+    EachBlock,
+    /// The `(key)` of an `{#each}` block.
+    EachKey,
+    /// `{#await}`
+    AwaitBlock,
+    /// The `{:then}` branch of an await block.
+    AwaitThen,
+    /// The `{:catch}` branch of an await block.
+    AwaitCatch,
+    /// `{#key}`
+    KeyBlock,
+    /// `{#snippet}`
+    SnippetBlock,
+    /// `name`, `name="value"` or `name={expr}`.
+    Attribute,
+    /// `{...expr}`
+    SpreadAttribute,
+    /// `kind:name|modifiers={expr}`
+    Directive,
+    /// `{name}`
+    ShorthandAttribute,
+    /// `{@attach expr}`
+    AttachAttribute,
+    /// `--name="value"`
+    CssCustomProperty,
+    /// An expression in an attribute value, with its braces or quotes.
+    ExpressionValue,
+}
+
+impl SyntaxKind {
+    const ALL: &'static [SyntaxKind] = &[
+        SyntaxKind::Whitespace,
+        SyntaxKind::Text,
+        SyntaxKind::Ident,
+        SyntaxKind::LAngle,
+        SyntaxKind::RAngle,
+        SyntaxKind::SlashRAngle,
+        SyntaxKind::LAngleSlash,
+        SyntaxKind::Slash,
+        SyntaxKind::Eq,
+        SyntaxKind::Quote,
+        SyntaxKind::LBrace,
+        SyntaxKind::RBrace,
+        SyntaxKind::LBraceHash,
+        SyntaxKind::LBraceSlash,
+        SyntaxKind::LBraceColon,
+        SyntaxKind::LBraceAt,
+        SyntaxKind::Colon,
+        SyntaxKind::Pipe,
+        SyntaxKind::Comma,
+        SyntaxKind::LParen,
+        SyntaxKind::RParen,
+        SyntaxKind::Ellipsis,
+        SyntaxKind::CommentOpen,
+        SyntaxKind::CommentClose,
+        SyntaxKind::TagComment,
+        SyntaxKind::Expression,
+        SyntaxKind::Pattern,
+        SyntaxKind::ScriptContent,
+        SyntaxKind::StyleContent,
+        SyntaxKind::Document,
+        SyntaxKind::Script,
+        SyntaxKind::Style,
+        SyntaxKind::Fragment,
+        SyntaxKind::Element,
+        SyntaxKind::Component,
+        SyntaxKind::SvelteElement,
+        SyntaxKind::StartTag,
+        SyntaxKind::EndTag,
+        SyntaxKind::TextNode,
+        SyntaxKind::Comment,
+        SyntaxKind::ExpressionTag,
+        SyntaxKind::HtmlTag,
+        SyntaxKind::ConstTag,
+        SyntaxKind::DeclarationTag,
+        SyntaxKind::DebugTag,
+        SyntaxKind::RenderTag,
+        SyntaxKind::IfBlock,
+        SyntaxKind::EachBlock,
+        SyntaxKind::EachKey,
+        SyntaxKind::AwaitBlock,
+        SyntaxKind::AwaitThen,
+        SyntaxKind::AwaitCatch,
+        SyntaxKind::KeyBlock,
+        SyntaxKind::SnippetBlock,
+        SyntaxKind::Attribute,
+        SyntaxKind::SpreadAttribute,
+        SyntaxKind::Directive,
+        SyntaxKind::ShorthandAttribute,
+        SyntaxKind::AttachAttribute,
+        SyntaxKind::CssCustomProperty,
+        SyntaxKind::ExpressionValue,
+    ];
+
+    /// Converts a raw rowan kind back into a `SyntaxKind`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `raw` was not produced from a `SyntaxKind`.
+    pub fn from_raw(raw: u16) -> Self {
+        Self::ALL[raw as usize]
+    }
+
+    /// Returns true if this kind is a token kind.
+    pub fn is_token(self) -> bool {
+        self < SyntaxKind::Document
+    }
+
+    /// Returns true if this kind is trivia (whitespace or an in-tag comment).
+    pub fn is_trivia(self) -> bool {
+        matches!(self, SyntaxKind::Whitespace | SyntaxKind::TagComment)
+    }
+
+    /// Returns true if this kind is an attribute-like node.
+    pub fn is_attribute(self) -> bool {
+        matches!(
+            self,
+            SyntaxKind::Attribute
+                | SyntaxKind::SpreadAttribute
+                | SyntaxKind::Directive
+                | SyntaxKind::ShorthandAttribute
+                | SyntaxKind::AttachAttribute
+                | SyntaxKind::CssCustomProperty
+        )
+    }
+}
+
+impl From<SyntaxKind> for rowan::SyntaxKind {
+    fn from(kind: SyntaxKind) -> Self {
+        rowan::SyntaxKind(kind as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_round_trip() {
+        for (index, kind) in SyntaxKind::ALL.iter().enumerate() {
+            assert_eq!(*kind as usize, index);
+            assert_eq!(SyntaxKind::from_raw(*kind as u16), *kind);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Lossless concrete syntax tree (CST) for Svelte components.
+//!
+//! The typed AST in [`crate::ast`] is convenient for analysis but drops
+//! whitespace, quote styles and other trivia. The CST defined here keeps every
+//! byte of the source: concatenating the text of all tokens reproduces the
+//! input exactly, which is what formatters, refactorings and `--fix` edits
+//! need.
+//!
+//! The tree is built on [`rowan`] green/red nodes. It is laid out from the
+//! spans of a parsed [`SvelteDocument`], so node boundaries always agree with
+//! the AST. Source text that no AST node claims (tag punctuation, attribute
+//! names, block keywords, whitespace) is split into small trivia and
+//! punctuation tokens.
+//!
+//! Going the other way, [`SyntaxTree::to_document`] derives the typed AST from
+//! the tree. Because the tree is lossless, an edited tree lowers to exactly the
+//! document the parser would produce for the edited source.
+//!
+//! # Example
+//!
+//! ```
+//! use svelte_parser::syntax::{parse, SyntaxKind};
+//! use svelte_parser::ParseOptions;
+//!
+//! let source = "<Button  label='Save' />";
+//! let parsed = parse(source, ParseOptions::default());
+//! assert_eq!(parsed.tree.text(), source);
+//!
+//! let label = parsed
+//!     .tree
+//!     .syntax()
+//!     .descendants_with_tokens()
+//!     .filter_map(|e| e.into_token())
+//!     .find(|t| t.kind() == SyntaxKind::Ident && t.text() == "label")
+//!     .unwrap();
+//! let renamed = parsed.tree.replace_token(&label, "title");
+//! assert_eq!(renamed.text(), "<Button  title='Save' />");
+//! ```
+
+mod builder;
+mod kind;
+mod nodes;
+
+pub use kind::SyntaxKind;
+pub use nodes::{AnyAttribute, AnyElement, CstNode, Tag};
+
+use crate::ast::SvelteDocument;
+use crate::error::ParseError;
+use crate::{ParseOptions, ParseResult};
+use rowan::{GreenNode, GreenToken, NodeOrToken};
+
+/// The rowan language tag for Svelte syntax trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SvelteLanguage {}
+
+impl rowan::Language for SvelteLanguage {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> SyntaxKind {
+        SyntaxKind::from_raw(raw.0)
+    }
+
+    fn kind_to_raw(kind: SyntaxKind) -> rowan::SyntaxKind {
+        kind.into()
+    }
+}
+
+/// A red (cursor) node in the Svelte CST.
+pub type SyntaxNode = rowan::SyntaxNode<SvelteLanguage>;
+/// A token in the Svelte CST.
+pub type SyntaxToken = rowan::SyntaxToken<SvelteLanguage>;
+/// Either a node or a token in the Svelte CST.
+pub type SyntaxElement = rowan::SyntaxElement<SvelteLanguage>;
+
+/// An immutable, lossless syntax tree for a Svelte component.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxTree {
+    green: GreenNode,
+}
+
+impl SyntaxTree {
+    /// Builds the CST for `source` from an already parsed document.
+    ///
+    /// `document` must be the result of parsing `source`; spans that fall
+    /// outside the source or overlap earlier nodes are ignored and their text
+    /// is kept as plain tokens, so the tree stays lossless either way.
+    pub fn from_document(source: &str, document: &SvelteDocument) -> Self {
+        Self {
+            green: builder::build(source, document),
+        }
+    }
+
+    /// Returns the root `Document` node.
+    pub fn syntax(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green.clone())
+    }
+
+    /// Returns the underlying green node.
+    pub fn green(&self) -> &GreenNode {
+        &self.green
+    }
+
+    /// Returns the full source text of the tree.
+    pub fn text(&self) -> String {
+        self.syntax().text().to_string()
+    }
+
+    /// Derives the typed AST from this tree.
+    ///
+    /// The parser runs over the tree's text, which is exact because the tree
+    /// is lossless; spans in the result refer to offsets in [`Self::text`].
+    pub fn to_document(&self, options: ParseOptions) -> ParseResult {
+        crate::parse_with_options(&self.text(), options)
+    }
+
+    /// Returns a new tree with `token`'s text replaced by `text`.
+    ///
+    /// The token keeps its kind. `token` must belong to this tree. Replacing
+    /// tokens leaves the rest of the source byte-for-byte intact, which is what
+    /// renames and `--fix` edits rely on.
+    pub fn replace_token(&self, token: &SyntaxToken, text: &str) -> Self {
+        let green = token.replace_with(GreenToken::new(token.kind().into(), text));
+        Self { green }
+    }
+
+    /// Returns a new tree with `node` replaced by a single token of `kind`
+    /// holding `text`. `node` must belong to this tree.
+    pub fn replace_node(&self, node: &SyntaxNode, kind: SyntaxKind, text: &str) -> Self {
+        let token = GreenToken::new(kind.into(), text);
+        let Some(parent) = node.parent() else {
+            let mut builder = rowan::GreenNodeBuilder::new();
+            builder.start_node(SyntaxKind::Document.into());
+            builder.token(kind.into(), text);
+            builder.finish_node();
+            return Self {
+                green: builder.finish(),
+            };
+        };
+        let index = node.index();
+        let new_parent = parent
+            .green()
+            .replace_child(index, NodeOrToken::Token(token));
+        Self {
+            green: parent.replace_with(new_parent),
+        }
+    }
+}
+
+/// The result of parsing a Svelte file into both a CST and a typed AST.
+#[derive(Debug)]
+pub struct SyntaxParse {
+    /// The lossless syntax tree.
+    pub tree: SyntaxTree,
+    /// The typed document.
+    pub document: SvelteDocument,
+    /// Any errors encountered during parsing.
+    pub errors: Vec<ParseError>,
+}
+
+/// Parses a Svelte source file into a lossless syntax tree alongside its AST.
+pub fn parse(source: &str, options: ParseOptions) -> SyntaxParse {
+    let ParseResult { document, errors } = crate::parse_with_options(source, options);
+    let tree = SyntaxTree::from_document(source, &document);
+    SyntaxParse {
+        tree,
+        document,
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(source: &str) -> SyntaxTree {
+        let parsed = parse(source, ParseOptions::default());
+        assert_eq!(parsed.tree.text(), source);
+        parsed.tree
+    }
+
+    fn kinds(node: &SyntaxNode) -> Vec<SyntaxKind> {
+        node.descendants().map(|n| n.kind()).collect()
+    }
+
+    #[test]
+    fn test_round_trip_preserves_trivia() {
+        round_trip("");
+        round_trip("  \n\t<div   class = 'a'  id=\"b\" >\n  hi  </div  >\n");
+        round_trip("<!-- note --><input\n  // @ts-ignore\n  value={x}\n/>");
+        round_trip("<script lang=\"ts\">\n  let x = 1;\n</script>\n\n<style>\n p { color: red; }\n</style>");
+    }
+
+    #[test]
+    fn test_round_trip_blocks() {
+        let tree = round_trip(
+            "{#if a}\n  x\n{:else if b}\n  y\n{:else}\n  z\n{/if}\n{#each items as item, i (item.id)}{item}{:else}none{/each}\n{#await p}\n  ...\n{:then v}\n  {v}\n{:catch e}\n  {e}\n{/await}\n{#key k}{k}{/key}\n{#snippet row(a: string)}{a}{/snippet}\n{@render row('x')}\n{@html raw}\n{@const y = 1}",
+        );
+        let kinds = kinds(&tree.syntax());
+        for expected in [
+            SyntaxKind::IfBlock,
+            SyntaxKind::EachBlock,
+            SyntaxKind::EachKey,
+            SyntaxKind::AwaitBlock,
+            SyntaxKind::AwaitThen,
+            SyntaxKind::AwaitCatch,
+            SyntaxKind::KeyBlock,
+            SyntaxKind::SnippetBlock,
+            SyntaxKind::RenderTag,
+            SyntaxKind::HtmlTag,
+            SyntaxKind::ConstTag,
+        ] {
+            assert!(kinds.contains(&expected), "missing {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip_invalid_source() {
+        round_trip("<div><span></div>{#if x}");
+        round_trip("<div class=\"unterminated>\n{#each}\n</");
+        round_trip("<p>caf\u{e9} {\u{1f600}}</p>");
+    }
+
+    #[test]
+    fn test_element_structure() {
+        let tree = round_trip("<Button label=\"Save\" {disabled} on:click|once={go}>Hi</Button>");
+        let element = tree
+            .syntax()
+            .descendants()
+            .find_map(AnyElement::cast)
+            .unwrap();
+        assert_eq!(element.syntax().kind(), SyntaxKind::Component);
+        assert_eq!(element.name().as_deref(), Some("Button"));
+        assert_eq!(
+            element.end_tag().and_then(|t| t.name()).as_deref(),
+            Some("Button")
+        );
+
+        let names: Vec<_> = element.attributes().filter_map(|a| a.name()).collect();
+        assert_eq!(names, ["label", "disabled", "on:click|once"]);
+    }
+
+    #[test]
+    fn test_rename_prop_round_trip() {
+        let source = "<Child  value = {count} />\n<Child value='static'/>";
+        let mut tree = round_trip(source);
+        loop {
+            let Some(token) = tree
+                .syntax()
+                .descendants()
+                .filter_map(AnyAttribute::cast)
+                .filter(|a| a.name().as_deref() == Some("value"))
+                .find_map(|a| a.name_token())
+            else {
+                break;
+            };
+            tree = tree.replace_token(&token, "amount");
+        }
+        assert_eq!(
+            tree.text(),
+            "<Child  amount = {count} />\n<Child amount='static'/>"
+        );
+        let result = tree.to_document(ParseOptions::default());
+        assert!(result.errors.is_empty());
+        let renamed = result
+            .document
+            .fragment
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                crate::TemplateNode::Component(c) => c.attributes.first(),
+                _ => None,
+            })
+            .filter(|a| matches!(a, crate::Attribute::Normal(n) if n.name == "amount"))
+            .count();
+        assert_eq!(renamed, 2);
+    }
+
+    #[test]
+    fn test_replace_node() {
+        let tree = round_trip("<p>{a}</p>");
+        let tag = tree
+            .syntax()
+            .descendants()
+            .find(|n| n.kind() == SyntaxKind::ExpressionTag)
+            .unwrap();
+        let edited = tree.replace_node(&tag, SyntaxKind::Text, "b");
+        assert_eq!(edited.text(), "<p>b</p>");
+    }
+}
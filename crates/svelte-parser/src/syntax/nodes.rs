@@ -0,0 +1,174 @@
+//! Typed views over CST nodes.
+//!
+//! These wrappers are thin: they hold a [`SyntaxNode`] and expose accessors
+//! that locate the interesting tokens within it. They never copy text, so
+//! tokens obtained through them can be handed to [`super::SyntaxTree`] edit
+//! methods.
+
+use super::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// A typed view over a CST node.
+pub trait CstNode: Sized {
+    /// Returns true if nodes of `kind` can be viewed as `Self`.
+    fn can_cast(kind: SyntaxKind) -> bool;
+
+    /// Wraps `node` if it has a compatible kind.
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    /// Returns the underlying node.
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! cst_node {
+    ($(#[$meta:meta])* $name:ident, $($kind:ident)|+) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(SyntaxNode);
+
+        impl CstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                matches!(kind, $(SyntaxKind::$kind)|+)
+            }
+
+            fn cast(node: SyntaxNode) -> Option<Self> {
+                Self::can_cast(node.kind()).then(|| Self(node))
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                &self.0
+            }
+        }
+    };
+}
+
+cst_node!(
+    /// An element, component or `svelte:*` element.
+    AnyElement,
+    Element | Component | SvelteElement
+);
+
+cst_node!(
+    /// A start or end tag.
+    Tag,
+    StartTag | EndTag
+);
+
+cst_node!(
+    /// Any attribute-like node inside a start tag.
+    AnyAttribute,
+    Attribute
+        | SpreadAttribute
+        | Directive
+        | ShorthandAttribute
+        | AttachAttribute
+        | CssCustomProperty
+);
+
+impl AnyElement {
+    /// Returns the opening tag.
+    pub fn start_tag(&self) -> Option<Tag> {
+        self.0.children().find_map(|n| {
+            (n.kind() == SyntaxKind::StartTag)
+                .then_some(n)
+                .and_then(Tag::cast)
+        })
+    }
+
+    /// Returns the closing tag, if the element has one.
+    pub fn end_tag(&self) -> Option<Tag> {
+        self.0.children().find_map(|n| {
+            (n.kind() == SyntaxKind::EndTag)
+                .then_some(n)
+                .and_then(Tag::cast)
+        })
+    }
+
+    /// Returns the tag name as written in the opening tag.
+    pub fn name(&self) -> Option<String> {
+        self.start_tag().and_then(|tag| tag.name())
+    }
+
+    /// Returns the attributes in source order.
+    pub fn attributes(&self) -> impl Iterator<Item = AnyAttribute> {
+        self.start_tag()
+            .into_iter()
+            .flat_map(|tag| tag.0.children().filter_map(AnyAttribute::cast))
+    }
+
+    /// Returns the child template nodes (everything between the tags).
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> {
+        self.0
+            .children()
+            .filter(|n| !matches!(n.kind(), SyntaxKind::StartTag | SyntaxKind::EndTag))
+    }
+}
+
+impl Tag {
+    /// Returns true for a closing tag.
+    pub fn is_end(&self) -> bool {
+        self.0.kind() == SyntaxKind::EndTag
+    }
+
+    /// Returns the tokens that make up the tag name (e.g. `svelte`, `:`,
+    /// `head`).
+    pub fn name_tokens(&self) -> Vec<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|e| e.into_token())
+            .skip_while(|t| matches!(t.kind(), SyntaxKind::LAngle | SyntaxKind::LAngleSlash))
+            .take_while(|t| matches!(t.kind(), SyntaxKind::Ident | SyntaxKind::Colon))
+            .collect()
+    }
+
+    /// Returns the tag name.
+    pub fn name(&self) -> Option<String> {
+        let name: String = self.name_tokens().iter().map(|t| t.text()).collect();
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+impl AnyAttribute {
+    /// Returns the tokens that make up the attribute name, i.e. everything
+    /// before `=` or the value. Empty for spreads and attachments.
+    pub fn name_tokens(&self) -> Vec<SyntaxToken> {
+        if matches!(
+            self.0.kind(),
+            SyntaxKind::SpreadAttribute | SyntaxKind::AttachAttribute
+        ) {
+            return Vec::new();
+        }
+        self.0
+            .children_with_tokens()
+            .take_while(|e| e.kind() != SyntaxKind::Eq && e.as_node().is_none())
+            .filter_map(|e| e.into_token())
+            .filter(|t| {
+                !t.kind().is_trivia()
+                    && !matches!(t.kind(), SyntaxKind::LBrace | SyntaxKind::RBrace)
+            })
+            .collect()
+    }
+
+    /// Returns the attribute name as written, including any directive prefix
+    /// and modifiers (e.g. `on:click|once`).
+    pub fn name(&self) -> Option<String> {
+        let name: String = self.name_tokens().iter().map(|t| t.text()).collect();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Returns the token naming the attribute when the name is a single
+    /// identifier, which is the token to replace when renaming a prop.
+    pub fn name_token(&self) -> Option<SyntaxToken> {
+        match self.name_tokens().as_slice() {
+            [token] if token.kind() == SyntaxKind::Ident => Some(token.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the expression tokens in the attribute value, in source order.
+    pub fn expressions(&self) -> impl Iterator<Item = SyntaxToken> {
+        self.0
+            .descendants_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Expression)
+    }
+}
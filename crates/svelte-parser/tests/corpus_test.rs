@@ -318,3 +318,107 @@ fn test_stress_long_expression() {
     // Should parse without crashing
     assert!(result.errors.is_empty());
 }
+
+/// Counts template nodes in the AST, including `{:else if}` branches which the
+/// CST also represents as `IfBlock` nodes.
+fn count_template_nodes(nodes: &[svelte_parser::TemplateNode]) -> usize {
+    use svelte_parser::{ElseBranch, TemplateNode};
+
+    fn count_if(block: &svelte_parser::IfBlock) -> usize {
+        1 + count_template_nodes(&block.consequent.nodes)
+            + match &block.alternate {
+                Some(ElseBranch::Else(fragment)) => count_template_nodes(&fragment.nodes),
+                Some(ElseBranch::ElseIf(nested)) => count_if(nested),
+                None => 0,
+            }
+    }
+
+    nodes
+        .iter()
+        .map(|node| match node {
+            TemplateNode::Element(n) => 1 + count_template_nodes(&n.children),
+            TemplateNode::Component(n) => 1 + count_template_nodes(&n.children),
+            TemplateNode::SvelteElement(n) => 1 + count_template_nodes(&n.children),
+            TemplateNode::IfBlock(n) => count_if(n),
+            TemplateNode::EachBlock(n) => {
+                1 + count_template_nodes(&n.body.nodes)
+                    + n.fallback
+                        .as_ref()
+                        .map_or(0, |f| count_template_nodes(&f.nodes))
+            }
+            TemplateNode::AwaitBlock(n) => {
+                1 + n
+                    .pending
+                    .as_ref()
+                    .map_or(0, |f| count_template_nodes(&f.nodes))
+                    + n.then
+                        .as_ref()
+                        .map_or(0, |t| count_template_nodes(&t.body.nodes))
+                    + n.catch
+                        .as_ref()
+                        .map_or(0, |c| count_template_nodes(&c.body.nodes))
+            }
+            TemplateNode::KeyBlock(n) => 1 + count_template_nodes(&n.body.nodes),
+            TemplateNode::SnippetBlock(n) => 1 + count_template_nodes(&n.body.nodes),
+            _ => 1,
+        })
+        .sum()
+}
+
+#[test]
+fn test_syntax_tree_round_trips_all_fixtures() {
+    use svelte_parser::syntax::{self, SyntaxKind};
+    use svelte_parser::ParseOptions;
+
+    let files = collect_svelte_files(&get_fixtures_dir());
+    assert!(!files.is_empty(), "No fixtures found");
+
+    for path in &files {
+        let source = fs::read_to_string(path).expect("Failed to read file");
+        let filename = path.file_name().unwrap().to_string_lossy();
+
+        let parsed = syntax::parse(&source, ParseOptions::default());
+        assert_eq!(
+            parsed.tree.text(),
+            source,
+            "CST for {} is not lossless",
+            filename
+        );
+
+        // Every template node of a valid component gets its own CST node.
+        if parsed.errors.is_empty() {
+            let cst_nodes = parsed
+                .tree
+                .syntax()
+                .descendants()
+                .filter(|n| {
+                    matches!(
+                        n.kind(),
+                        SyntaxKind::Element
+                            | SyntaxKind::Component
+                            | SyntaxKind::SvelteElement
+                            | SyntaxKind::TextNode
+                            | SyntaxKind::Comment
+                            | SyntaxKind::ExpressionTag
+                            | SyntaxKind::HtmlTag
+                            | SyntaxKind::ConstTag
+                            | SyntaxKind::DeclarationTag
+                            | SyntaxKind::DebugTag
+                            | SyntaxKind::RenderTag
+                            | SyntaxKind::IfBlock
+                            | SyntaxKind::EachBlock
+                            | SyntaxKind::AwaitBlock
+                            | SyntaxKind::KeyBlock
+                            | SyntaxKind::SnippetBlock
+                    )
+                })
+                .count();
+            assert_eq!(
+                cst_nodes,
+                count_template_nodes(&parsed.document.fragment.nodes),
+                "CST for {} is missing template nodes",
+                filename
+            );
+        }
+    }
+}
@@ -274,6 +274,19 @@ fn test_snapshot_style_directive_with_style_attr() {
     );
 }
 
+#[test]
+fn test_snapshot_style_block_css_ast() {
+    parse_snapshot(
+        "style_block_css_ast",
+        r#"<p class="a">x</p>
+<style>
+  .a > span:hover, :global(.b) { color: red !important; }
+  :global { .c { margin: 0 } }
+  @media (min-width: 600px) { p { &.a { padding: 1px } } }
+</style>"#,
+    );
+}
+
 // === Use Directive Snapshots ===
 
 #[test]
@@ -38,7 +38,7 @@ mod kit;
 mod parser;
 mod runner;
 
-pub use parser::{DiagnosticSeverity, TsgoDiagnostic, TsgoOutput};
+pub use parser::{DiagnosticPosition, DiagnosticSeverity, TsgoDiagnostic, TsgoOutput};
 pub use runner::{
     TransformedFile, TransformedFiles, TsgoCacheStats, TsgoCheckOutput, TsgoCheckStats, TsgoError,
     TsgoRunner, TsgoTimingStats,
@@ -743,7 +743,7 @@ impl TsgoRunner {
 
     /// Checks if this is a SvelteKit project by searching for @sveltejs/kit
     /// in node_modules, walking up the directory tree for monorepo support.
-    fn is_sveltekit_project(project_root: &Utf8Path) -> bool {
+    pub fn is_sveltekit_project(project_root: &Utf8Path) -> bool {
         let mut current = Some(project_root);
 
         while let Some(dir) = current {
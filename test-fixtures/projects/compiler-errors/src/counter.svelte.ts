@@ -0,0 +1,5 @@
+export let count = $state<number>(0);
+
+export function increment(step: number = 1): void {
+  count += step;
+}